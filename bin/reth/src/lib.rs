@@ -119,6 +119,11 @@ pub mod chainspec {
     pub use reth_ethereum_cli::chainspec::*;
 }
 
+/// Re-exported from `reth_exex_remote`.
+pub mod exex_remote {
+    pub use reth_exex_remote::*;
+}
+
 /// Re-exported from `reth_provider`.
 pub mod providers {
     pub use reth_provider::*;
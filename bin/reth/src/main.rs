@@ -8,7 +8,10 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 static MALLOC_CONF: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
 
 use clap::Parser;
-use reth::cli::Cli;
+use reth::{
+    cli::Cli,
+    exex_remote::{RemoteExExArgs, RemoteExExServer},
+};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_ethereum::EthereumNode;
 use tracing::info;
@@ -21,12 +24,28 @@ fn main() {
         unsafe { std::env::set_var("RUST_BACKTRACE", "1") };
     }
 
-    if let Err(err) = Cli::<EthereumChainSpecParser>::parse().run(async move |builder, _| {
-        info!(target: "reth::cli", "Launching node");
-        let handle = builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
+    if let Err(err) = Cli::<EthereumChainSpecParser, RemoteExExArgs>::parse().run(
+        async move |builder, remote_exex| {
+            info!(target: "reth::cli", "Launching node");
+            let mut builder = builder.node(EthereumNode::default());
+            let server = RemoteExExServer::new(remote_exex.ipcpath);
+            for id in remote_exex.ids {
+                let server = server.clone();
+                builder = builder.install_exex(id.clone(), move |ctx| async move {
+                    server.install(id, ctx).await
+                });
+            }
+            if remote_exex.dynamic {
+                builder = builder.enable_exex_registry();
+            }
+            let handle = builder.launch_with_debug_capabilities().await?;
+            if let Some(registry) = handle.node.exex_registry.clone() {
+                server.serve_registry(registry).await?;
+            }
 
-        handle.wait_for_node_exit().await
-    }) {
+            handle.wait_for_node_exit().await
+        },
+    ) {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -5,7 +5,7 @@ use alloy_eips::{calc_next_block_base_fee, eip1559::BaseFeeParams, eip7840::Blob
 use alloy_genesis::Genesis;
 use alloy_primitives::{B256, U256};
 use core::fmt::{Debug, Display};
-use reth_ethereum_forks::EthereumHardforks;
+use reth_ethereum_forks::{EthereumDevnetHardfork, EthereumHardforks, ForkCondition, Hardforks};
 use reth_network_peers::NodeRecord;
 use reth_primitives_traits::{AlloyBlockHeader, BlockHeader};
 
@@ -72,6 +72,19 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
             self.base_fee_params_at_timestamp(target_timestamp),
         ))
     }
+
+    /// Returns the activation of fork-choice enforced inclusion lists, see
+    /// [`EthereumDevnetHardfork::Focil`].
+    ///
+    /// The fork is only scheduled on devnets, chains that don't configure it never activate it.
+    fn focil_activation(&self) -> ForkCondition {
+        ForkCondition::Never
+    }
+
+    /// Returns `true` if fork-choice enforced inclusion lists are active at the given timestamp.
+    fn is_focil_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.focil_activation().active_at_timestamp(timestamp)
+    }
 }
 
 impl<H: BlockHeader> EthChainSpec for ChainSpec<H> {
@@ -134,4 +147,8 @@ impl<H: BlockHeader> EthChainSpec for ChainSpec<H> {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.paris_block_and_final_difficulty.map(|(_, final_difficulty)| final_difficulty)
     }
+
+    fn focil_activation(&self) -> ForkCondition {
+        self.fork(EthereumDevnetHardfork::Focil)
+    }
 }
@@ -36,8 +36,9 @@ use alloy_trie::root::state_root_ref_unhashed;
 use core::fmt::Debug;
 use derive_more::From;
 use reth_ethereum_forks::{
-    ChainHardforks, DisplayHardforks, EthereumHardfork, EthereumHardforks, ForkCondition,
-    ForkFilter, ForkFilterKey, ForkHash, ForkId, Hardfork, Hardforks, Head, DEV_HARDFORKS,
+    ChainHardforks, DisplayHardforks, EthereumDevnetHardfork, EthereumHardfork, EthereumHardforks,
+    ForkCondition, ForkFilter, ForkFilterKey, ForkHash, ForkId, Hardfork, Hardforks, Head,
+    DEV_HARDFORKS,
 };
 use reth_network_peers::{holesky_nodes, hoodi_nodes, mainnet_nodes, sepolia_nodes, NodeRecord};
 use reth_primitives_traits::{sync::LazyLock, BlockHeader, SealedHeader};
@@ -891,6 +892,10 @@ impl From<Genesis> for ChainSpec {
             (EthereumHardfork::Bpo3.boxed(), genesis.config.bpo3_time),
             (EthereumHardfork::Bpo4.boxed(), genesis.config.bpo4_time),
             (EthereumHardfork::Bpo5.boxed(), genesis.config.bpo5_time),
+            (
+                EthereumDevnetHardfork::Focil.boxed(),
+                genesis.config.extra_fields.get_deserialized("focilTime").and_then(Result::ok),
+            ),
         ];
 
         let mut time_hardforks = time_hardfork_opts
@@ -1197,6 +1202,13 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Enable the FOCIL devnet fork at genesis.
+    pub fn focil_activated(mut self) -> Self {
+        self = self.osaka_activated();
+        self.hardforks.insert(EthereumDevnetHardfork::Focil, ForkCondition::Timestamp(0));
+        self
+    }
+
     /// Build the resulting [`ChainSpec`].
     ///
     /// # Panics
@@ -1283,7 +1295,7 @@ mod tests {
     use alloy_consensus::constants::ETH_TO_WEI;
     use alloy_eips::{eip4844::BLOB_TX_MIN_BLOB_GASPRICE, eip7840::BlobParams};
     use alloy_evm::block::calc::{base_block_reward, block_reward};
-    use alloy_genesis::{ChainConfig, GenesisAccount};
+    use alloy_genesis::{ChainConfig, CliqueConfig, GenesisAccount};
     use alloy_primitives::{b256, hex};
     use alloy_trie::{TrieAccount, EMPTY_ROOT_HASH};
     use core::ops::Deref;
@@ -2591,6 +2603,31 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.cancun_time, Some(4661));
     }
 
+    #[test]
+    fn test_parse_focil_genesis_json() {
+        let s = r#"{"config":{"chainId":1337,"terminalTotalDifficulty":0,"shanghaiTime":0,"cancunTime":0,"pragueTime":0,"osakaTime":0,"focilTime":4661},"gasLimit":"0x4c4b40","difficulty":"0x0","alloc":{}}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::from_genesis(genesis);
+
+        assert_eq!(chainspec.focil_activation(), ForkCondition::Timestamp(4661));
+        assert!(!chainspec.is_focil_active_at_timestamp(4660));
+        assert!(chainspec.is_focil_active_at_timestamp(4661));
+
+        let chainspec = ChainSpec::from_genesis(Genesis::default());
+        assert_eq!(chainspec.focil_activation(), ForkCondition::Never);
+    }
+
+    #[test]
+    fn clique_genesis_stays_pre_merge() {
+        let mut genesis = Genesis::default();
+        genesis.config.london_block = Some(0);
+        genesis.config.clique = Some(CliqueConfig { period: Some(5), epoch: Some(30000) });
+        let chainspec = ChainSpec::from_genesis(genesis);
+        assert!(chainspec.is_london_active_at_block(0));
+        assert!(!chainspec.is_paris_active_at_block(0));
+        assert_eq!(chainspec.paris_block_and_final_difficulty, None);
+    }
+
     #[test]
     fn test_paris_block_and_total_difficulty() {
         let genesis = Genesis { gas_limit: 0x2fefd8u64, ..Default::default() };
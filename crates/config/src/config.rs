@@ -1,9 +1,12 @@
 //! Configuration files.
+use alloy_primitives::{Address, Bytes, Selector};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use reth_static_file_types::{StaticFileMap, StaticFileSegment};
 use std::{
+    collections::BTreeMap,
+    net::{IpAddr, SocketAddr},
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -32,6 +35,18 @@ pub struct Config {
     /// Configuration for static files.
     #[cfg_attr(feature = "serde", serde(default))]
     pub static_files: StaticFilesConfig,
+    /// Runtime-adjustable payload builder settings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub builder: BuilderConfig,
+    /// Local transaction inclusion policy.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inclusion_policy: InclusionPolicyConfig,
+    /// Configuration for execution extensions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exex: ExExConfig,
+    /// Configuration for the RPC servers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rpc: RpcConfig,
 }
 
 impl Config {
@@ -69,6 +84,15 @@ impl Config {
         }
     }
 
+    /// Load configuration from an existing file.
+    ///
+    /// Unlike [`Config::from_path`], this fails if the file does not exist.
+    pub fn read_from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let cfg_string = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to load configuration: {e}"))?;
+        toml::from_str(&cfg_string).map_err(|e| eyre::eyre!("Failed to parse TOML: {e}"))
+    }
+
     /// Returns the [`PeersConfig`] for the node.
     ///
     /// If a peers file is provided, the basic nodes from the file are added to the configuration.
@@ -130,6 +154,8 @@ pub struct StageConfig {
     pub index_storage_history: IndexHistoryConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
+    /// Pipeline configuration.
+    pub pipeline: PipelineConfig,
 }
 
 impl StageConfig {
@@ -379,6 +405,19 @@ impl Default for TransactionLookupConfig {
     }
 }
 
+/// Pipeline configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PipelineConfig {
+    /// The number of blocks in a window, if the stages after the header stages run in block
+    /// windows.
+    ///
+    /// In block windows, the bodies of the next window are downloaded while the blocks of the
+    /// current window are executed, instead of downloading all bodies before executing.
+    pub window: Option<u64>,
+}
+
 /// Common ETL related configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -526,6 +565,237 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Payload builder configuration.
+///
+/// Unlike the `--builder.*` arguments, these settings are re-applied whenever the configuration
+/// file is reloaded while the node is running. Values that are also set with a `--builder.*`
+/// argument are ignored, unset values fall back to the CLI arguments.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BuilderConfig {
+    /// Target gas limit for built blocks.
+    pub gas_limit: Option<u64>,
+    /// Extra data for built blocks.
+    pub extra_data: Option<Bytes>,
+    /// Maximum number of blobs to include per block.
+    pub max_blobs_per_block: Option<u64>,
+    /// Minimum priority fee per gas, in wei, for transactions included in built blocks.
+    pub min_priority_fee: Option<u128>,
+}
+
+/// Transaction inclusion policy configuration.
+///
+/// Transactions matching any of the lists are rejected by the transaction pool and excluded from
+/// locally built blocks. The lists are replaced whenever the configuration file is reloaded while
+/// the node is running.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InclusionPolicyConfig {
+    /// Excluded transaction senders.
+    pub senders: Vec<Address>,
+    /// Excluded call targets.
+    pub recipients: Vec<Address>,
+    /// Excluded addresses in the access list or EIP-7702 authorization list of a transaction.
+    pub touched: Vec<Address>,
+    /// Excluded function selectors.
+    pub selectors: Vec<Selector>,
+}
+
+/// Execution extensions (`ExEx`) configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExExConfig {
+    /// Configuration of the `ExEx` write-ahead log.
+    pub wal: ExExWalConfig,
+}
+
+/// `ExEx` write-ahead log configuration.
+///
+/// The WAL keeps the notifications sent to `ExEx`es until the blocks they contain are finalized
+/// and processed by all `ExEx`es, so a stalled `ExEx` makes it grow without bound unless a size
+/// or age limit is set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExExWalConfig {
+    /// Whether to compress new WAL entries with zstd. Existing uncompressed entries stay
+    /// readable.
+    pub compression: bool,
+    /// The maximum total size of the WAL entries in bytes.
+    pub max_size: Option<u64>,
+    /// The maximum age of the oldest WAL entry.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "humantime_serde::serialize",
+            deserialize_with = "deserialize_duration"
+        )
+    )]
+    pub max_age: Option<Duration>,
+    /// What to do when the WAL exceeds `max_size` or `max_age`.
+    pub retention_policy: WalRetentionPolicy,
+}
+
+/// What to do when the `ExEx` WAL exceeds its retention limits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WalRetentionPolicy {
+    /// Fail the commit of the notification that exceeded the limits, which stops the node.
+    #[default]
+    Fail,
+    /// Evict the oldest entries until the WAL is within the limits, logging an error for every
+    /// eviction. An `ExEx` that needs an evicted entry to recover from a reorg fails instead.
+    Evict,
+}
+
+/// RPC server configuration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcConfig {
+    /// Per-client quotas of the HTTP and WS servers. Requests are not limited if unset.
+    pub quota: Option<RpcQuotaConfig>,
+    /// Additional HTTP and WS servers, by name.
+    pub listeners: BTreeMap<String, RpcListenerConfig>,
+    /// Cache of the responses to requests for finalized blocks. Responses are not cached if
+    /// unset.
+    pub response_cache: Option<RpcResponseCacheConfig>,
+}
+
+/// Configuration of the RPC response cache.
+///
+/// Responses of deterministic methods are cached if the blocks of the request are finalized, so
+/// that repeated requests for the same data are answered without executing the method again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcResponseCacheConfig {
+    /// Maximum total size of the cached responses in bytes.
+    pub max_bytes: u64,
+    /// Maximum size of a cached response in bytes, larger responses are not cached.
+    pub max_response_bytes: u64,
+    /// Cached methods, all supported methods if unset.
+    pub methods: Option<Vec<String>>,
+}
+
+impl Default for RpcResponseCacheConfig {
+    fn default() -> Self {
+        Self { max_bytes: 256 * 1024 * 1024, max_response_bytes: 16 * 1024 * 1024, methods: None }
+    }
+}
+
+/// Configuration of an additional RPC server.
+///
+/// The server serves the methods of the selected namespaces that pass the `allow` and `deny` lists,
+/// using the same handlers as the servers configured with the `--http` and `--ws` arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RpcListenerConfig {
+    /// Transport of the server.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transport: RpcListenerTransport,
+    /// Address of the server.
+    pub addr: SocketAddr,
+    /// Allowed CORS domains, comma separated.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cors: Option<String>,
+    /// Path to the hex-encoded JWT secret that requests have to be authenticated with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub jwt_secret: Option<PathBuf>,
+    /// Served namespaces, all namespaces if unset.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modules: Option<Vec<String>>,
+    /// Allowed methods, all methods if empty. Names ending with `*` match all methods with that
+    /// prefix.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allow: Vec<String>,
+    /// Denied methods, takes precedence over `allow`. Names ending with `*` match all methods
+    /// with that prefix.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deny: Vec<String>,
+}
+
+/// Transport of an additional RPC server.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RpcListenerTransport {
+    /// JSON-RPC over HTTP.
+    #[default]
+    Http,
+    /// JSON-RPC over websockets.
+    Ws,
+}
+
+/// Per-client RPC quota configuration.
+///
+/// Every client has a token bucket that refills at `rate` tokens per second up to `burst` tokens,
+/// and every request takes the cost of its method from the bucket. Clients are identified as
+/// one of the configured `tenants` by their API key, JWT subject or IP address, in that order.
+/// Other clients get the default quota, either per forwarded IP address or shared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcQuotaConfig {
+    /// Tokens added to the bucket of a client per second.
+    pub rate: u64,
+    /// Capacity of the bucket of a client.
+    pub burst: u64,
+    /// Header carrying the API key of a client.
+    pub api_key_header: String,
+    /// Whether to identify clients by the `X-Forwarded-For` or `X-Real-IP` header.
+    ///
+    /// Only enable this behind a reverse proxy that sets these headers, clients can set them to
+    /// arbitrary values otherwise.
+    pub forwarded_ip: bool,
+    /// Cost of methods without a configured cost.
+    pub default_cost: u64,
+    /// Costs of methods by name. Names ending with `*` match all methods with that prefix.
+    pub costs: BTreeMap<String, u64>,
+    /// Number of blocks of the range of an `eth_getLogs` request that are charged the cost of
+    /// `eth_getLogs` once.
+    pub logs_blocks_per_cost: u64,
+    /// Clients with their own quotas, by name.
+    pub tenants: BTreeMap<String, RpcTenantConfig>,
+}
+
+impl Default for RpcQuotaConfig {
+    fn default() -> Self {
+        Self {
+            rate: 100,
+            burst: 1000,
+            api_key_header: "x-api-key".to_string(),
+            forwarded_ip: false,
+            default_cost: 1,
+            costs: BTreeMap::new(),
+            logs_blocks_per_cost: 100,
+            tenants: BTreeMap::new(),
+        }
+    }
+}
+
+/// Identification and quota of an RPC client.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcTenantConfig {
+    /// API keys of the tenant.
+    pub api_keys: Vec<String>,
+    /// Subjects of the JWTs of the tenant. Only used if the servers require JWT authentication.
+    pub jwt_subjects: Vec<String>,
+    /// IP addresses of the tenant. Only used if clients are identified by their forwarded IP
+    /// address.
+    pub ips: Vec<IpAddr>,
+    /// Tokens added to the bucket of the tenant per second, overrides the default rate.
+    pub rate: Option<u64>,
+    /// Capacity of the bucket of the tenant, overrides the default burst.
+    pub burst: Option<u64>,
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -615,12 +885,14 @@ where
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{
+        Config, ExExWalConfig, RpcListenerTransport, RpcQuotaConfig, WalRetentionPolicy, EXTENSION,
+    };
     use crate::PruneConfig;
-    use alloy_primitives::Address;
+    use alloy_primitives::{Address, Bytes, Selector};
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
-    use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+    use std::{collections::BTreeMap, net::IpAddr, path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -651,6 +923,14 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_read_from_path_requires_existing_file() {
+        with_config_path(|path| {
+            assert!(Config::read_from_path(path).is_err());
+            assert!(!path.exists());
+        })
+    }
+
     #[test]
     fn test_load_path_reads_existing_config() {
         with_config_path(|path| {
@@ -1133,6 +1413,163 @@ receipts = { distance = 16384 }
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
+    #[test]
+    fn test_load_pipeline_config() {
+        let reth_toml = r#"
+[stages.pipeline]
+window = 10000
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        assert_eq!(conf.stages.pipeline.window, Some(10_000));
+        assert_eq!(Config::default().stages.pipeline.window, None);
+    }
+
+    #[test]
+    fn test_load_builder_config() {
+        let reth_toml = r#"
+[builder]
+gas_limit = 60000000
+extra_data = "0x7265746a"
+min_priority_fee = 1000000000
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        assert_eq!(conf.builder.gas_limit, Some(60_000_000));
+        assert_eq!(conf.builder.extra_data, Some(Bytes::from_static(b"retj")));
+        assert_eq!(conf.builder.max_blobs_per_block, None);
+        assert_eq!(conf.builder.min_priority_fee, Some(1_000_000_000));
+
+        let reloaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(reloaded.builder, conf.builder);
+    }
+
+    #[test]
+    fn test_load_inclusion_policy_config() {
+        let reth_toml = r#"
+[inclusion_policy]
+senders = ["0x00000000000000000000000000000000000000aa"]
+selectors = ["0xa9059cbb"]
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        assert_eq!(
+            conf.inclusion_policy.senders,
+            vec![Address::from_str("0x00000000000000000000000000000000000000aa").unwrap()]
+        );
+        assert!(conf.inclusion_policy.recipients.is_empty());
+        assert_eq!(conf.inclusion_policy.selectors, vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])]);
+    }
+
+    #[test]
+    fn test_load_exex_wal_config() {
+        let reth_toml = r#"
+[exex.wal]
+compression = true
+max_size = 10737418240
+max_age = "1day"
+retention_policy = "evict"
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        assert_eq!(
+            conf.exex.wal,
+            ExExWalConfig {
+                compression: true,
+                max_size: Some(10 * 1024 * 1024 * 1024),
+                max_age: Some(Duration::from_secs(24 * 60 * 60)),
+                retention_policy: WalRetentionPolicy::Evict,
+            }
+        );
+
+        let reloaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(reloaded.exex, conf.exex);
+        assert_eq!(Config::default().exex.wal.retention_policy, WalRetentionPolicy::Fail);
+    }
+
+    #[test]
+    fn test_load_rpc_quota_config() {
+        let reth_toml = r#"
+[rpc.quota]
+rate = 50
+forwarded_ip = true
+
+[rpc.quota.costs]
+eth_getLogs = 2
+"debug_trace*" = 500
+
+[rpc.quota.tenants.indexer]
+api_keys = ["secret"]
+ips = ["10.0.0.1"]
+rate = 1000
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let quota = conf.rpc.quota.as_ref().unwrap();
+        assert_eq!(quota.rate, 50);
+        assert_eq!(quota.burst, RpcQuotaConfig::default().burst);
+        assert!(quota.forwarded_ip);
+        assert_eq!(quota.costs["debug_trace*"], 500);
+        let indexer = &quota.tenants["indexer"];
+        assert_eq!(indexer.api_keys, vec!["secret".to_string()]);
+        assert_eq!(indexer.ips, vec![IpAddr::from([10, 0, 0, 1])]);
+        assert_eq!(indexer.rate, Some(1000));
+        assert_eq!(indexer.burst, None);
+
+        let reloaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(reloaded.rpc, conf.rpc);
+        assert_eq!(Config::default().rpc.quota, None);
+    }
+
+    #[test]
+    fn test_load_rpc_listeners_config() {
+        let reth_toml = r#"
+[rpc.listeners.public]
+addr = "0.0.0.0:8545"
+cors = "*"
+modules = ["eth", "net", "web3"]
+deny = ["eth_sendTransaction", "eth_sign*"]
+
+[rpc.listeners.internal]
+transport = "ws"
+addr = "10.0.0.1:8546"
+jwt_secret = "/etc/reth/internal.hex"
+allow = ["debug_*", "admin_*"]
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let public = &conf.rpc.listeners["public"];
+        assert_eq!(public.transport, RpcListenerTransport::Http);
+        assert_eq!(public.addr, "0.0.0.0:8545".parse().unwrap());
+        assert_eq!(public.cors.as_deref(), Some("*"));
+        assert_eq!(public.jwt_secret, None);
+        assert_eq!(public.deny, vec!["eth_sendTransaction".to_string(), "eth_sign*".to_string()]);
+        let internal = &conf.rpc.listeners["internal"];
+        assert_eq!(internal.transport, RpcListenerTransport::Ws);
+        assert_eq!(internal.jwt_secret, Some("/etc/reth/internal.hex".into()));
+        assert_eq!(internal.modules, None);
+
+        let reloaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(reloaded.rpc, conf.rpc);
+    }
+
+    #[test]
+    fn test_load_rpc_response_cache_config() {
+        let conf: Config = toml::from_str("").unwrap();
+        assert_eq!(conf.rpc.response_cache, None);
+
+        let reth_toml = r#"
+[rpc.response_cache]
+max_bytes = 1073741824
+methods = ["eth_getBlockReceipts", "eth_getLogs"]
+"#;
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let cache = conf.rpc.response_cache.clone().unwrap();
+        assert_eq!(cache.max_bytes, 1 << 30);
+        assert_eq!(cache.max_response_bytes, RpcResponseCacheConfig::default().max_response_bytes);
+        assert_eq!(
+            cache.methods,
+            Some(vec!["eth_getBlockReceipts".to_string(), "eth_getLogs".to_string()])
+        );
+
+        let reloaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(reloaded.rpc, conf.rpc);
+    }
+
     #[test]
     fn test_conf_trust_nodes_only() {
         let trusted_nodes_only = r"#
@@ -9,4 +9,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig};
+pub use config::{
+    BodiesConfig, BuilderConfig, Config, ExExConfig, ExExWalConfig, InclusionPolicyConfig,
+    PipelineConfig, PruneConfig, RpcConfig, RpcListenerConfig, RpcListenerTransport,
+    RpcQuotaConfig, RpcResponseCacheConfig, RpcTenantConfig, WalRetentionPolicy,
+};
@@ -0,0 +1,64 @@
+//! Clique engine configuration.
+
+use alloy_genesis::Genesis;
+
+/// Default number of blocks after which to checkpoint and reset the pending votes.
+pub const DEFAULT_EPOCH_LENGTH: u64 = 30_000;
+
+/// Clique proof-of-authority parameters, as configured in the genesis `config.clique` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliqueConfig {
+    /// Minimum number of seconds between two consecutive blocks.
+    ///
+    /// A period of `0` means blocks are only sealed when there are pending transactions.
+    pub period: u64,
+    /// Number of blocks after which to checkpoint and reset the pending votes.
+    pub epoch: u64,
+}
+
+impl CliqueConfig {
+    /// Creates a new config with the given block period and epoch length.
+    ///
+    /// An epoch length of `0` is replaced with [`DEFAULT_EPOCH_LENGTH`].
+    pub const fn new(period: u64, epoch: u64) -> Self {
+        let epoch = if epoch == 0 { DEFAULT_EPOCH_LENGTH } else { epoch };
+        Self { period, epoch }
+    }
+
+    /// Returns the Clique config of the given genesis, if it configures a Clique network.
+    pub fn from_genesis(genesis: &Genesis) -> Option<Self> {
+        let clique = genesis.config.clique.as_ref()?;
+        Some(Self::new(clique.period.unwrap_or_default(), clique.epoch.unwrap_or_default()))
+    }
+
+    /// Returns `true` if the block with the given number is an epoch checkpoint.
+    pub const fn is_checkpoint(&self, number: u64) -> bool {
+        number.is_multiple_of(self.epoch)
+    }
+}
+
+impl Default for CliqueConfig {
+    fn default() -> Self {
+        Self::new(0, DEFAULT_EPOCH_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_from_genesis() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":1337,"clique":{"period":5,"epoch":0}},"alloc":{}}"#,
+        )
+        .unwrap();
+        let config = CliqueConfig::from_genesis(&genesis).unwrap();
+        assert_eq!(config, CliqueConfig { period: 5, epoch: DEFAULT_EPOCH_LENGTH });
+        assert!(config.is_checkpoint(0));
+        assert!(config.is_checkpoint(DEFAULT_EPOCH_LENGTH));
+        assert!(!config.is_checkpoint(1));
+
+        assert!(CliqueConfig::from_genesis(&Genesis::default()).is_none());
+    }
+}
@@ -0,0 +1,83 @@
+//! Clique consensus errors.
+
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_consensus::ConsensusError;
+use reth_storage_api::errors::ProviderError;
+use std::sync::Arc;
+
+/// Errors returned when a header violates the Clique consensus rules.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CliqueError {
+    /// The extra-data section is shorter than the 32 byte vanity prefix.
+    #[error("extra-data 32 byte vanity prefix missing")]
+    MissingVanity,
+    /// The extra-data section doesn't contain a 65 byte secp256k1 signature.
+    #[error("extra-data 65 byte signature suffix missing")]
+    MissingSignature,
+    /// A non-checkpoint block contains signer data in its extra-data section.
+    #[error("non-checkpoint block contains extra signer list")]
+    ExtraSigners,
+    /// A checkpoint block's signer list is malformed or doesn't match the local state.
+    #[error("invalid signer list on checkpoint block")]
+    InvalidCheckpointSigners,
+    /// A checkpoint block has a non-zero beneficiary.
+    #[error("beneficiary in checkpoint block non-zero")]
+    InvalidCheckpointBeneficiary,
+    /// A checkpoint block has a vote nonce set.
+    #[error("vote nonce in checkpoint block non-zero")]
+    InvalidCheckpointVote,
+    /// The vote nonce is neither the authorize nor the drop marker.
+    #[error("vote nonce not 0x00..0 or 0xff..f")]
+    InvalidVote,
+    /// The mix digest is non-zero.
+    #[error("non-zero mix digest")]
+    InvalidMixDigest,
+    /// The ommers hash is not the hash of an empty list.
+    #[error("non empty uncle hash")]
+    InvalidUncleHash,
+    /// The difficulty is neither the in-turn nor the out-of-turn value.
+    #[error("invalid difficulty")]
+    InvalidDifficulty,
+    /// The difficulty doesn't match the turn-ness of the signer.
+    #[error("wrong difficulty: expected {expected}, got {got}")]
+    WrongDifficulty {
+        /// The difficulty required by the signer's turn-ness.
+        expected: u64,
+        /// The difficulty in the header.
+        got: u64,
+    },
+    /// The block was sealed earlier than the parent timestamp plus the block period.
+    #[error("invalid timestamp: {timestamp} is earlier than {earliest}")]
+    InvalidTimestamp {
+        /// The timestamp of the header.
+        timestamp: u64,
+        /// The earliest allowed timestamp.
+        earliest: u64,
+    },
+    /// The seal signature could not be recovered.
+    #[error("invalid seal signature")]
+    InvalidSignature,
+    /// The block was signed by an address outside of the signer set.
+    #[error("unauthorized signer {0}")]
+    UnauthorizedSigner(Address),
+    /// The signer signed one of the most recent blocks.
+    #[error("signer {0} recently signed")]
+    RecentlySigned(Address),
+    /// An ancestor required to compute the signer snapshot is unknown.
+    #[error("unknown ancestor {hash} at block {number}")]
+    UnknownAncestor {
+        /// The number of the missing block.
+        number: BlockNumber,
+        /// The hash of the missing block.
+        hash: B256,
+    },
+    /// Error while reading headers from the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<CliqueError> for ConsensusError {
+    fn from(err: CliqueError) -> Self {
+        Self::Custom(Arc::new(err))
+    }
+}
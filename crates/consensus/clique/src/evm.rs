@@ -0,0 +1,298 @@
+//! EVM configuration for Clique networks.
+
+use crate::header::{DIFF_IN_TURN, DIFF_NO_TURN};
+use alloy_consensus::Header;
+use alloy_eips::Decodable2718;
+use alloy_evm::{
+    block::BlockExecutorFactory,
+    eth::{spec::EthExecutorSpec, EthBlockExecutionCtx, EthBlockExecutorFactory},
+    EthEvmFactory,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_rpc_types_engine::ExecutionData;
+use reth_chainspec::{
+    ChainSpec, EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition, Hardforks,
+};
+use reth_ethereum_primitives::{Block, EthPrimitives};
+use reth_evm::{
+    execute::{BlockAssembler, BlockAssemblerInput, BlockExecutionError},
+    ConfigureEngineEvm, ConfigureEvm, EvmEnv, EvmEnvFor, ExecutableTxIterator, ExecutionCtxFor,
+    NextBlockEnvAttributes,
+};
+use reth_evm_ethereum::{EthBlockAssembler, EthEvmConfig, RethReceiptBuilder};
+use reth_primitives_traits::{SealedBlock, SealedHeader, SignedTransaction, TxTy};
+use reth_storage_api::errors::any::AnyError;
+use std::{convert::Infallible, sync::Arc};
+
+/// The chain spec as seen by the block executor of a Clique network.
+///
+/// Clique chains never transition to proof-of-stake and don't pay out block rewards. Block rewards
+/// are only paid out before Paris, so the executor sees Paris as active at genesis. All other forks
+/// are taken from the wrapped chain spec.
+#[derive(Debug, Clone)]
+pub struct CliqueExecutorSpec<ChainSpec>(Arc<ChainSpec>);
+
+impl<ChainSpec: EthereumHardforks> EthereumHardforks for CliqueExecutorSpec<ChainSpec> {
+    fn ethereum_fork_activation(&self, fork: EthereumHardfork) -> ForkCondition {
+        match fork {
+            EthereumHardfork::Paris => ForkCondition::Block(0),
+            fork => self.0.ethereum_fork_activation(fork),
+        }
+    }
+}
+
+impl<ChainSpec: EthExecutorSpec> EthExecutorSpec for CliqueExecutorSpec<ChainSpec> {
+    fn deposit_contract_address(&self) -> Option<Address> {
+        self.0.deposit_contract_address()
+    }
+}
+
+/// Block assembler for Clique networks.
+///
+/// Assembles blocks like the [`EthBlockAssembler`], but leaves out the base fee before London. The
+/// seal related header fields are set by the [`CliqueSealer`](crate::CliqueSealer).
+#[derive(Debug, Clone)]
+pub struct CliqueBlockAssembler<ChainSpec>(EthBlockAssembler<ChainSpec>);
+
+impl<F, ChainSpec> BlockAssembler<F> for CliqueBlockAssembler<ChainSpec>
+where
+    F: for<'a> BlockExecutorFactory<
+        ExecutionCtx<'a> = EthBlockExecutionCtx<'a>,
+        Transaction: SignedTransaction,
+        Receipt: reth_primitives_traits::Receipt,
+    >,
+    ChainSpec: EthChainSpec + EthereumHardforks,
+{
+    type Block = alloy_consensus::Block<F::Transaction>;
+
+    fn assemble_block(
+        &self,
+        input: BlockAssemblerInput<'_, '_, F>,
+    ) -> Result<Self::Block, BlockExecutionError> {
+        let mut block = self.0.assemble_block(input)?;
+        if !self.0.chain_spec.is_london_active_at_block(block.header.number) {
+            block.header.base_fee_per_gas = None;
+        }
+        Ok(block)
+    }
+}
+
+/// EVM configuration for Clique networks.
+///
+/// The EVM spec is picked from the block forks of the chain spec, like for any pre-merge chain, so
+/// the `DIFFICULTY` opcode returns the difficulty of the block. Blocks are executed without block
+/// rewards, see [`CliqueExecutorSpec`].
+///
+/// When building blocks, the difficulty of the next block is passed as
+/// [`NextBlockEnvAttributes::prev_randao`], which is unused before the merge: it has to be known
+/// before execution, as transactions can observe it.
+#[derive(Debug, Clone)]
+pub struct CliqueEvmConfig<C = ChainSpec> {
+    /// Ethereum EVM configuration, used for the EVM environment.
+    inner: EthEvmConfig<C>,
+    /// Executor factory that doesn't pay out block rewards.
+    executor_factory: EthBlockExecutorFactory<RethReceiptBuilder, CliqueExecutorSpec<C>>,
+    /// Clique block assembler.
+    block_assembler: CliqueBlockAssembler<C>,
+}
+
+impl<C> CliqueEvmConfig<C> {
+    /// Creates a new Clique EVM configuration with the given chain spec.
+    pub fn new(chain_spec: Arc<C>) -> Self {
+        Self {
+            inner: EthEvmConfig::new(chain_spec.clone()),
+            executor_factory: EthBlockExecutorFactory::new(
+                RethReceiptBuilder::default(),
+                CliqueExecutorSpec(chain_spec.clone()),
+                EthEvmFactory::default(),
+            ),
+            block_assembler: CliqueBlockAssembler(EthBlockAssembler::new(chain_spec)),
+        }
+    }
+
+    /// Returns the chain spec associated with this configuration.
+    pub const fn chain_spec(&self) -> &Arc<C> {
+        self.inner.chain_spec()
+    }
+}
+
+impl<C> ConfigureEvm for CliqueEvmConfig<C>
+where
+    C: EthExecutorSpec + EthChainSpec<Header = Header> + Hardforks + 'static,
+{
+    type Primitives = EthPrimitives;
+    type Error = Infallible;
+    type NextBlockEnvCtx = NextBlockEnvAttributes;
+    type BlockExecutorFactory = EthBlockExecutorFactory<RethReceiptBuilder, CliqueExecutorSpec<C>>;
+    type BlockAssembler = CliqueBlockAssembler<C>;
+
+    fn block_executor_factory(&self) -> &Self::BlockExecutorFactory {
+        &self.executor_factory
+    }
+
+    fn block_assembler(&self) -> &Self::BlockAssembler {
+        &self.block_assembler
+    }
+
+    fn evm_env(&self, header: &Header) -> Result<EvmEnv, Self::Error> {
+        self.inner.evm_env(header)
+    }
+
+    fn next_evm_env(
+        &self,
+        parent: &Header,
+        attributes: &NextBlockEnvAttributes,
+    ) -> Result<EvmEnv, Self::Error> {
+        let mut evm_env = self.inner.next_evm_env(parent, attributes)?;
+        if !self.chain_spec().is_paris_active_at_block(parent.number + 1) {
+            let difficulty = U256::from_be_bytes(attributes.prev_randao.0);
+            evm_env.block_env.difficulty = if difficulty == U256::from(DIFF_IN_TURN) {
+                difficulty
+            } else {
+                U256::from(DIFF_NO_TURN)
+            };
+        }
+        Ok(evm_env)
+    }
+
+    fn context_for_block<'a>(
+        &self,
+        block: &'a SealedBlock<Block>,
+    ) -> Result<EthBlockExecutionCtx<'a>, Self::Error> {
+        self.inner.context_for_block(block)
+    }
+
+    fn context_for_next_block(
+        &self,
+        parent: &SealedHeader,
+        attributes: Self::NextBlockEnvCtx,
+    ) -> Result<EthBlockExecutionCtx<'_>, Self::Error> {
+        self.inner.context_for_next_block(parent, attributes)
+    }
+}
+
+impl<C> ConfigureEngineEvm<ExecutionData> for CliqueEvmConfig<C>
+where
+    C: EthExecutorSpec + EthChainSpec<Header = Header> + Hardforks + 'static,
+{
+    fn evm_env_for_payload(&self, payload: &ExecutionData) -> Result<EvmEnvFor<Self>, Self::Error> {
+        self.inner.evm_env_for_payload(payload)
+    }
+
+    fn context_for_payload<'a>(
+        &self,
+        payload: &'a ExecutionData,
+    ) -> Result<ExecutionCtxFor<'a, Self>, Self::Error> {
+        self.inner.context_for_payload(payload)
+    }
+
+    fn tx_iterator_for_payload(
+        &self,
+        payload: &ExecutionData,
+    ) -> Result<impl ExecutableTxIterator<Self>, Self::Error> {
+        let txs = payload.payload.transactions().clone();
+        let convert = |tx: Bytes| {
+            let tx =
+                TxTy::<Self::Primitives>::decode_2718_exact(tx.as_ref()).map_err(AnyError::new)?;
+            let signer = tx.try_recover().map_err(AnyError::new)?;
+            Ok::<_, AnyError>(tx.with_signer(signer))
+        };
+
+        Ok((txs, convert))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_genesis::{CliqueConfig as GenesisCliqueConfig, Genesis};
+    use alloy_primitives::{address, bytes, keccak256, TxKind};
+    use reth_ethereum_primitives::{BlockBody, Transaction};
+    use reth_evm::execute::Executor;
+    use reth_primitives_traits::{Block as _, SignerRecoverable};
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::{AccountInfo, Bytecode},
+    };
+
+    #[test]
+    fn executes_pre_london_block_with_difficulty() {
+        let mut genesis = Genesis::default();
+        genesis.config.chain_id = 1337;
+        genesis.config.homestead_block = Some(0);
+        genesis.config.eip150_block = Some(0);
+        genesis.config.eip155_block = Some(0);
+        genesis.config.eip158_block = Some(0);
+        genesis.config.byzantium_block = Some(0);
+        genesis.config.constantinople_block = Some(0);
+        genesis.config.petersburg_block = Some(0);
+        genesis.config.istanbul_block = Some(0);
+        genesis.config.berlin_block = Some(0);
+        genesis.config.london_block = Some(10);
+        genesis.config.clique = Some(GenesisCliqueConfig { period: Some(1), epoch: Some(30000) });
+        let chain_spec = Arc::new(ChainSpec::from_genesis(genesis));
+        assert!(!chain_spec.is_paris_active_at_block(1));
+
+        // stores the difficulty of the block in slot 0: DIFFICULTY PUSH1 0 SSTORE
+        let contract = address!("0x1000000000000000000000000000000000000000");
+        let code = bytes!("0x44600055");
+        let beneficiary = address!("0x2000000000000000000000000000000000000000");
+
+        let tx = sign_tx_with_random_key_pair(
+            &mut generators::rng(),
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1337),
+                nonce: 0,
+                gas_price: 1,
+                gas_limit: 100_000,
+                to: TxKind::Call(contract),
+                ..Default::default()
+            }),
+        );
+        let sender = tx.recover_signer().unwrap();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: keccak256(&code),
+                code: Some(Bytecode::new_raw(code)),
+                ..Default::default()
+            },
+        );
+        db.insert_account_info(
+            sender,
+            AccountInfo { balance: U256::from(1_000_000_000u64), ..Default::default() },
+        );
+
+        let header = Header {
+            number: 1,
+            timestamp: 1,
+            gas_limit: 1_000_000,
+            beneficiary,
+            difficulty: U256::from(DIFF_IN_TURN),
+            ..Default::default()
+        };
+        let block =
+            Block { header, body: BlockBody { transactions: vec![tx], ..Default::default() } }
+                .try_into_recovered()
+                .unwrap();
+
+        let evm_config = CliqueEvmConfig::new(chain_spec);
+        let output = evm_config.batch_executor(db).execute(&block).unwrap();
+
+        assert_eq!(
+            output.state.account(&contract).unwrap().storage_slot(U256::ZERO),
+            Some(U256::from(DIFF_IN_TURN))
+        );
+
+        // the beneficiary only receives the transaction fees, no block reward
+        let fees = U256::from(output.gas_used);
+        assert_eq!(
+            output.state.account(&beneficiary).unwrap().info.as_ref().unwrap().balance,
+            fees
+        );
+    }
+}
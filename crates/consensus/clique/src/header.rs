@@ -0,0 +1,131 @@
+//! Clique header layout and seal helpers.
+
+use crate::CliqueError;
+use alloy_consensus::Header;
+use alloy_primitives::{Address, Bytes, Signature, B256, B64};
+use reth_primitives_traits::crypto::secp256k1::recover_signer_unchecked;
+
+/// Fixed number of extra-data prefix bytes reserved for signer vanity.
+pub const EXTRA_VANITY: usize = 32;
+
+/// Fixed number of extra-data suffix bytes reserved for the signer seal.
+pub const EXTRA_SEAL: usize = 65;
+
+/// Magic nonce number to vote on adding a new signer.
+pub const NONCE_AUTH: B64 = B64::new([0xff; 8]);
+
+/// Magic nonce number to vote on removing a signer.
+pub const NONCE_DROP: B64 = B64::ZERO;
+
+/// Block difficulty for in-turn signatures.
+pub const DIFF_IN_TURN: u64 = 2;
+
+/// Block difficulty for out-of-turn signatures.
+pub const DIFF_NO_TURN: u64 = 1;
+
+/// Returns the hash that is signed by the sealer, i.e. the header hash without the seal.
+///
+/// The extra-data must be at least [`EXTRA_SEAL`] bytes long.
+pub fn seal_hash(header: &Header) -> B256 {
+    let mut header = header.clone();
+    header.extra_data = unsealed_extra_data(&header.extra_data);
+    header.hash_slow()
+}
+
+/// Recovers the address that sealed the given header.
+pub fn recover_signer(header: &Header) -> Result<Address, CliqueError> {
+    let extra = &header.extra_data;
+    if extra.len() < EXTRA_SEAL {
+        return Err(CliqueError::MissingSignature)
+    }
+    let signature = Signature::from_raw(&extra[extra.len() - EXTRA_SEAL..])
+        .map_err(|_| CliqueError::InvalidSignature)?;
+    recover_signer_unchecked(&signature, seal_hash(header))
+        .map_err(|_| CliqueError::InvalidSignature)
+}
+
+/// Returns the signer list embedded in the extra-data of a checkpoint header.
+pub fn checkpoint_signers(header: &Header) -> Result<Vec<Address>, CliqueError> {
+    let extra = &header.extra_data;
+    if extra.len() < EXTRA_VANITY {
+        return Err(CliqueError::MissingVanity)
+    }
+    if extra.len() < EXTRA_VANITY + EXTRA_SEAL {
+        return Err(CliqueError::MissingSignature)
+    }
+    let signers = &extra[EXTRA_VANITY..extra.len() - EXTRA_SEAL];
+    if !signers.len().is_multiple_of(Address::len_bytes()) {
+        return Err(CliqueError::InvalidCheckpointSigners)
+    }
+    Ok(signers.chunks_exact(Address::len_bytes()).map(Address::from_slice).collect())
+}
+
+/// Builds the extra-data of a block that is about to be sealed: the vanity, padded or truncated to
+/// [`EXTRA_VANITY`] bytes, followed by the signer list on checkpoint blocks and room for the seal.
+pub fn unsealed_extra_data_with_signers(
+    vanity: &[u8],
+    signers: Option<impl IntoIterator<Item = Address>>,
+) -> Bytes {
+    let mut extra = vanity[..vanity.len().min(EXTRA_VANITY)].to_vec();
+    extra.resize(EXTRA_VANITY, 0);
+    for signer in signers.into_iter().flatten() {
+        extra.extend_from_slice(signer.as_slice());
+    }
+    extra.resize(extra.len() + EXTRA_SEAL, 0);
+    extra.into()
+}
+
+/// Writes the seal into the last [`EXTRA_SEAL`] bytes of the header's extra-data.
+///
+/// The extra-data must already reserve room for the seal, see
+/// [`unsealed_extra_data_with_signers`].
+pub fn apply_seal(header: &mut Header, signature: &Signature) {
+    let mut extra = header.extra_data.to_vec();
+    let len = extra.len();
+    extra[len - EXTRA_SEAL..].copy_from_slice(&signature.as_rsy());
+    header.extra_data = extra.into();
+}
+
+/// Returns the extra-data without the seal suffix.
+fn unsealed_extra_data(extra: &Bytes) -> Bytes {
+    extra.slice(..extra.len().saturating_sub(EXTRA_SEAL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn seal_and_recover() {
+        let signer = PrivateKeySigner::random();
+        let mut header = Header {
+            number: 1,
+            extra_data: unsealed_extra_data_with_signers(b"reth", None::<Vec<Address>>),
+            ..Default::default()
+        };
+        assert_eq!(header.extra_data.len(), EXTRA_VANITY + EXTRA_SEAL);
+
+        let unsealed = seal_hash(&header);
+        let signature = signer.sign_hash_sync(&unsealed).unwrap();
+        apply_seal(&mut header, &signature);
+
+        // the seal hash doesn't commit to the seal itself
+        assert_eq!(seal_hash(&header), unsealed);
+        assert_eq!(recover_signer(&header).unwrap(), signer.address());
+    }
+
+    #[test]
+    fn parse_checkpoint_signers() {
+        let signers = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        let header = Header {
+            extra_data: unsealed_extra_data_with_signers(&[], Some(signers.clone())),
+            ..Default::default()
+        };
+        assert_eq!(checkpoint_signers(&header).unwrap(), signers);
+
+        let header = Header { extra_data: vec![0; EXTRA_VANITY + 1].into(), ..Default::default() };
+        assert!(matches!(checkpoint_signers(&header), Err(CliqueError::MissingSignature)));
+    }
+}
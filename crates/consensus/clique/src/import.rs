@@ -0,0 +1,339 @@
+//! Import of blocks propagated over devp2p.
+
+use crate::CliqueConsensus;
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatusEnum};
+use futures::{stream::FuturesUnordered, StreamExt};
+use reth_chainspec::EthChainSpec;
+use reth_engine_primitives::{BuiltPayloadBlock, ConsensusEngineHandle};
+use reth_eth_wire_types::NewBlock;
+use reth_network::{
+    import::{
+        BlockImport, BlockImportError, BlockImportEvent, BlockImportOutcome, BlockValidation,
+        NewBlockEvent,
+    },
+    message::NewBlockMessage,
+};
+use reth_network_peers::PeerId;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
+use reth_primitives_traits::{Block, SealedBlock};
+use reth_storage_api::{BlockNumReader, HeaderProvider};
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, trace};
+
+/// Block announcement received from a peer.
+type IncomingBlock<B> = (NewBlockEvent<NewBlock<B>>, PeerId);
+
+/// [`BlockImport`] for Clique networks, where sealed blocks are propagated over devp2p.
+///
+/// Incoming blocks are forwarded to the [`CliqueImportService`], which inserts them into the
+/// engine once the node is launched.
+pub struct CliqueBlockImport<B> {
+    /// Sends incoming blocks to the import service.
+    to_service: UnboundedSender<IncomingBlock<B>>,
+    /// Receives import outcomes from the import service.
+    from_service: UnboundedReceiver<BlockImportEvent<NewBlock<B>>>,
+}
+
+impl<B: Block> CliqueBlockImport<B> {
+    /// Creates a new block import and the service that processes its blocks.
+    pub fn new() -> (Self, CliqueImportService<B>) {
+        let (to_service, from_network) = mpsc::unbounded_channel();
+        let (to_network, from_service) = mpsc::unbounded_channel();
+        (Self { to_service, from_service }, CliqueImportService { from_network, to_network })
+    }
+}
+
+impl<B: Block> BlockImport<NewBlock<B>> for CliqueBlockImport<B> {
+    fn on_new_block(&mut self, peer_id: PeerId, incoming_block: NewBlockEvent<NewBlock<B>>) {
+        let _ = self.to_service.send((incoming_block, peer_id));
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<BlockImportEvent<NewBlock<B>>> {
+        match self.from_service.poll_recv(cx) {
+            Poll::Ready(Some(event)) => Poll::Ready(event),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+impl<B> fmt::Debug for CliqueBlockImport<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CliqueBlockImport").finish_non_exhaustive()
+    }
+}
+
+/// Inserts blocks received by a [`CliqueBlockImport`] into the engine.
+///
+/// Blocks whose chain has a higher total difficulty than the local head are made canonical.
+/// Announced hashes of unknown blocks are passed to the engine as the new head, which downloads
+/// them from the network.
+pub struct CliqueImportService<B> {
+    /// Receives incoming blocks from the network.
+    from_network: UnboundedReceiver<IncomingBlock<B>>,
+    /// Sends import outcomes to the network.
+    to_network: UnboundedSender<BlockImportEvent<NewBlock<B>>>,
+}
+
+impl<B: Block<Header = Header>> CliqueImportService<B> {
+    /// Runs the service until the network shuts down.
+    pub async fn run<T, ChainSpec, Provider>(
+        mut self,
+        engine: ConsensusEngineHandle<T>,
+        consensus: CliqueConsensus<ChainSpec, Provider>,
+    ) where
+        T: PayloadTypes,
+        BuiltPayloadBlock<T>: Block<Header = Header>,
+        B: Into<BuiltPayloadBlock<T>>,
+        ChainSpec: EthChainSpec,
+        Provider: HeaderProvider<Header = Header> + BlockNumReader,
+    {
+        let mut pending = FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                incoming = self.from_network.recv() => {
+                    let Some((event, peer)) = incoming else { return };
+                    match event {
+                        NewBlockEvent::Block(block) => {
+                            if is_new_head(&consensus, &block.block) {
+                                pending.push(import_block(engine.clone(), block, peer));
+                            }
+                        }
+                        NewBlockEvent::Hashes(hashes) => {
+                            let best = consensus.provider().best_block_number().unwrap_or_default();
+                            if let Some(head) =
+                                hashes.0.iter().filter(|h| h.number > best).max_by_key(|h| h.number)
+                            {
+                                debug!(target: "consensus::clique", ?head, %peer, "Syncing to announced block");
+                                let _ = engine
+                                    .fork_choice_updated(
+                                        head_state(head.hash),
+                                        None,
+                                        EngineApiMessageVersion::default(),
+                                    )
+                                    .await;
+                            }
+                        }
+                    }
+                }
+                Some(outcome) = pending.next(), if !pending.is_empty() => {
+                    if self.to_network.send(BlockImportEvent::Outcome(outcome)).is_err() {
+                        return
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<B> fmt::Debug for CliqueImportService<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CliqueImportService").finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if the announced block should replace the local head.
+///
+/// The chain with the highest total difficulty wins, ties are broken in favor of the shorter chain
+/// like in geth. If the parent of the block is unknown, the total difficulty announced by the peer
+/// is used.
+fn is_new_head<B, ChainSpec, Provider>(
+    consensus: &CliqueConsensus<ChainSpec, Provider>,
+    block: &NewBlock<B>,
+) -> bool
+where
+    B: Block<Header = Header>,
+    ChainSpec: EthChainSpec,
+    Provider: HeaderProvider<Header = Header> + BlockNumReader,
+{
+    let provider = consensus.provider();
+    let Ok(Some(head)) =
+        provider.best_block_number().and_then(|number| provider.sealed_header(number))
+    else {
+        return false
+    };
+    let Ok(head_td) = consensus.total_difficulty(head.number, head.hash()) else { return false };
+
+    let header = block.block.header();
+    let td = consensus
+        .total_difficulty(header.number.saturating_sub(1), header.parent_hash)
+        .map(|parent_td| parent_td + header.difficulty)
+        .unwrap_or_else(|_| U256::from(block.td));
+
+    td > head_td || (td == head_td && header.number < head.number)
+}
+
+/// Forkchoice state for a Clique head, which is never final.
+const fn head_state(head_block_hash: B256) -> ForkchoiceState {
+    ForkchoiceState {
+        head_block_hash,
+        safe_block_hash: B256::ZERO,
+        finalized_block_hash: B256::ZERO,
+    }
+}
+
+/// Inserts the block into the engine and makes it canonical.
+async fn import_block<T, B>(
+    engine: ConsensusEngineHandle<T>,
+    block: NewBlockMessage<NewBlock<B>>,
+    peer: PeerId,
+) -> BlockImportOutcome<NewBlock<B>>
+where
+    T: PayloadTypes,
+    B: Block + Into<BuiltPayloadBlock<T>>,
+{
+    let sealed = SealedBlock::seal_slow(block.block.block.clone().into());
+    let hash = sealed.hash();
+
+    let result = match engine.new_block(sealed).await {
+        Ok(status) => match status.status {
+            PayloadStatusEnum::Valid => {
+                match engine
+                    .fork_choice_updated(head_state(hash), None, EngineApiMessageVersion::default())
+                    .await
+                {
+                    Ok(_) => Ok(BlockValidation::ValidBlock { block }),
+                    Err(err) => Err(BlockImportError::Other(err.into())),
+                }
+            }
+            PayloadStatusEnum::Invalid { validation_error } => {
+                Err(BlockImportError::Other(validation_error.into()))
+            }
+            status => {
+                // the parent is unknown, sync to the block instead
+                trace!(target: "consensus::clique", %hash, ?status, "Block not yet importable");
+                let _ = engine
+                    .fork_choice_updated(head_state(hash), None, EngineApiMessageVersion::default())
+                    .await;
+                Ok(BlockValidation::ValidHeader { block })
+            }
+        },
+        Err(err) => Err(BlockImportError::Other(err.into())),
+    };
+
+    BlockImportOutcome { peer, result }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::{DIFF_IN_TURN, DIFF_NO_TURN},
+        tests::{chain_spec, child, seal},
+    };
+    use alloy_primitives::U128;
+    use alloy_rpc_types_engine::PayloadStatus;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::ChainSpec;
+    use reth_engine_primitives::{BeaconEngineMessage, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_ethereum_primitives::{Block as EthBlock, EthPrimitives};
+    use reth_primitives_traits::SealedHeader;
+    use reth_provider::test_utils::MockEthProvider;
+    use std::{future::poll_fn, sync::Arc};
+
+    type TestConsensus = CliqueConsensus<ChainSpec, MockEthProvider<EthPrimitives, ChainSpec>>;
+
+    /// Returns two sorted signers and a consensus engine whose canonical chain is the genesis and
+    /// an in-turn block 1.
+    fn setup() -> ([PrivateKeySigner; 2], TestConsensus, SealedHeader) {
+        let mut keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        keys.sort_by_key(|k| k.address());
+        let chain_spec = chain_spec(&keys.iter().map(|k| k.address()).collect::<Vec<_>>());
+        let provider = MockEthProvider::default().with_chain_spec((*chain_spec).clone());
+        let consensus = CliqueConsensus::new(chain_spec.clone(), provider.clone()).unwrap();
+
+        let genesis = chain_spec.sealed_genesis_header();
+        provider.add_header(genesis.hash(), genesis.header().clone());
+
+        let mut header = child(&genesis, None);
+        header.difficulty = U256::from(DIFF_IN_TURN);
+        let head = seal(&mut header, &keys[1]);
+        provider.add_header(head.hash(), head.header().clone());
+
+        (keys, consensus, head)
+    }
+
+    fn new_block(header: &SealedHeader, td: u64) -> NewBlock<EthBlock> {
+        NewBlock {
+            block: EthBlock { header: header.header().clone(), body: Default::default() },
+            td: U128::from(td),
+        }
+    }
+
+    #[test]
+    fn fork_choice_by_total_difficulty() {
+        let (keys, consensus, head) = setup();
+        let genesis = consensus.chain_spec().sealed_genesis_header();
+        let head_td = consensus.total_difficulty(head.number, head.hash()).unwrap();
+        assert_eq!(head_td, genesis.difficulty + U256::from(DIFF_IN_TURN));
+
+        // out-of-turn sibling of the head
+        let sibling = seal(&mut child(&genesis, None), &keys[0]);
+        assert!(!is_new_head(&consensus, &new_block(&sibling, 0)));
+
+        // out-of-turn child of the head
+        let next = seal(&mut child(&head, None), &keys[0]);
+        assert!(is_new_head(&consensus, &new_block(&next, 0)));
+
+        // with an unknown parent, the announced total difficulty is used
+        let mut orphan = child(&next, None);
+        orphan.number += 1;
+        orphan.difficulty = U256::from(DIFF_NO_TURN);
+        let orphan = seal(&mut orphan, &keys[1]);
+        assert!(!is_new_head(&consensus, &new_block(&orphan, head_td.to())));
+        assert!(is_new_head(&consensus, &new_block(&orphan, head_td.to::<u64>() + 1)));
+    }
+
+    #[tokio::test]
+    async fn imports_block_with_higher_total_difficulty() {
+        let (keys, consensus, head) = setup();
+        let genesis = consensus.chain_spec().sealed_genesis_header();
+
+        let (to_engine, mut from_import) = mpsc::unbounded_channel();
+        let (mut import, service) = CliqueBlockImport::<EthBlock>::new();
+        tokio::spawn(
+            service.run(ConsensusEngineHandle::<EthEngineTypes>::new(to_engine), consensus),
+        );
+
+        let peer = PeerId::random();
+        let sibling = seal(&mut child(&genesis, None), &keys[0]);
+        let next = seal(&mut child(&head, None), &keys[0]);
+        for header in [&sibling, &next] {
+            let block =
+                NewBlockMessage { hash: header.hash(), block: Arc::new(new_block(header, 0)) };
+            import.on_new_block(peer, NewBlockEvent::Block(block));
+        }
+
+        // the sibling has a lower total difficulty and is ignored
+        let Some(BeaconEngineMessage::NewBlock { block, tx }) = from_import.recv().await else {
+            panic!("expected new block")
+        };
+        assert_eq!(block.hash(), next.hash());
+        let status = PayloadStatus::new(PayloadStatusEnum::Valid, Some(next.hash()));
+        tx.send(Ok(status.clone())).unwrap();
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { state, tx, .. }) =
+            from_import.recv().await
+        else {
+            panic!("expected forkchoice update")
+        };
+        assert_eq!(state.head_block_hash, next.hash());
+        tx.send(Ok(OnForkChoiceUpdated::valid(status))).unwrap();
+
+        let BlockImportEvent::Outcome(outcome) = poll_fn(|cx| import.poll(cx)).await else {
+            panic!("expected import outcome")
+        };
+        assert_eq!(outcome.peer, peer);
+        assert!(matches!(
+            outcome.result,
+            Ok(BlockValidation::ValidBlock { block }) if block.hash == next.hash()
+        ));
+        assert!(from_import.try_recv().is_err());
+    }
+}
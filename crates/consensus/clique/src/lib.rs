@@ -0,0 +1,657 @@
+//! Clique proof-of-authority consensus ([EIP-225](https://eips.ethereum.org/EIPS/eip-225)).
+//!
+//! Clique networks are configured via the `clique` section of the genesis config. Blocks are
+//! sealed by a set of authorized signers that can vote other signers in and out, without a
+//! separate consensus layer client.
+//!
+//! This crate provides:
+//!  - [`CliqueConsensus`]: validates headers and blocks against the Clique rules.
+//!  - [`CliqueSealer`]: produces blocks with the payload builder and seals them with a signer key.
+//!  - [`CliqueBlockImport`]: imports sealed blocks that are propagated over devp2p.
+//!  - [`CliqueEvmConfig`]: executes blocks without proof-of-work block rewards.
+//!
+//! Clique chains never transition to proof-of-stake: the chain with the highest total difficulty
+//! is the canonical one.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::{eip7840::BlobParams, merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use parking_lot::Mutex;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{
+    Consensus, ConsensusError, FullConsensus, HeaderValidator, ReceiptRootBloom, TransactionRoot,
+};
+use reth_consensus_common::validation::{
+    validate_4844_header_standalone, validate_against_parent_4844,
+    validate_against_parent_eip1559_base_fee, validate_against_parent_gas_limit,
+    validate_against_parent_hash_number, validate_block_pre_execution,
+    validate_block_pre_execution_with_tx_root, validate_body_against_header,
+    validate_header_base_fee, validate_header_gas,
+};
+use reth_ethereum_consensus::validate_block_post_execution;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_storage_api::HeaderProvider;
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod config;
+pub use config::{CliqueConfig, DEFAULT_EPOCH_LENGTH};
+
+mod error;
+pub use error::CliqueError;
+
+pub mod header;
+use header::{
+    checkpoint_signers, recover_signer, DIFF_IN_TURN, DIFF_NO_TURN, EXTRA_SEAL, EXTRA_VANITY,
+    NONCE_AUTH, NONCE_DROP,
+};
+
+mod evm;
+pub use evm::{CliqueBlockAssembler, CliqueEvmConfig, CliqueExecutorSpec};
+
+mod import;
+pub use import::{CliqueBlockImport, CliqueImportService};
+
+mod sealer;
+pub use sealer::{CliqueSealer, CliqueSealerHandle};
+
+pub mod snapshot;
+use snapshot::Snapshot;
+
+/// Number of recent snapshots to keep in memory.
+const SNAPSHOT_CACHE_SIZE: u32 = 128;
+
+/// Number of recent total difficulties to keep in memory.
+const TD_CACHE_SIZE: u32 = 1024;
+
+/// Clique proof-of-authority consensus engine.
+///
+/// Structural header checks are done in [`HeaderValidator::validate_header`]. The seal is
+/// verified against the signer snapshot of the parent in
+/// [`FullConsensus::validate_block_post_execution`], because headers are downloaded in reverse
+/// during sync and the ancestors are only guaranteed to be known once the block is executed.
+#[derive(Debug, Clone)]
+pub struct CliqueConsensus<ChainSpec, Provider> {
+    /// Configuration
+    chain_spec: Arc<ChainSpec>,
+    /// Clique parameters from the genesis.
+    config: CliqueConfig,
+    /// Header provider used to compute signer snapshots.
+    provider: Provider,
+    /// Recent snapshots, keyed by block hash.
+    snapshots: Arc<Mutex<LruMap<B256, Snapshot, ByLength>>>,
+    /// Recent total difficulties, keyed by block hash.
+    total_difficulties: Arc<Mutex<LruMap<B256, U256, ByLength>>>,
+}
+
+impl<ChainSpec, Provider> CliqueConsensus<ChainSpec, Provider>
+where
+    ChainSpec: EthChainSpec,
+    Provider: HeaderProvider<Header = Header>,
+{
+    /// Creates a new instance of [`CliqueConsensus`].
+    ///
+    /// Returns `None` if the genesis of the chain spec doesn't configure Clique.
+    pub fn new(chain_spec: Arc<ChainSpec>, provider: Provider) -> Option<Self> {
+        let config = CliqueConfig::from_genesis(chain_spec.genesis())?;
+        Some(Self::with_config(chain_spec, config, provider))
+    }
+
+    /// Creates a new instance of [`CliqueConsensus`] with the given Clique parameters.
+    pub fn with_config(
+        chain_spec: Arc<ChainSpec>,
+        config: CliqueConfig,
+        provider: Provider,
+    ) -> Self {
+        Self {
+            chain_spec,
+            config,
+            provider,
+            snapshots: Arc::new(Mutex::new(LruMap::new(ByLength::new(SNAPSHOT_CACHE_SIZE)))),
+            total_difficulties: Arc::new(Mutex::new(LruMap::new(ByLength::new(TD_CACHE_SIZE)))),
+        }
+    }
+
+    /// Returns the chain spec associated with this consensus engine.
+    pub const fn chain_spec(&self) -> &Arc<ChainSpec> {
+        &self.chain_spec
+    }
+
+    /// Returns the Clique parameters.
+    pub const fn config(&self) -> &CliqueConfig {
+        &self.config
+    }
+
+    /// Returns the provider used to look up ancestor headers.
+    pub const fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    /// Returns the signer snapshot after the block with the given number and hash.
+    ///
+    /// Walks back to the closest cached snapshot or epoch checkpoint and applies the headers in
+    /// between.
+    pub fn snapshot(&self, number: BlockNumber, hash: B256) -> Result<Snapshot, CliqueError> {
+        let (mut number, mut hash) = (number, hash);
+        let mut headers = Vec::new();
+        let base = loop {
+            if let Some(snap) = self.snapshots.lock().get(&hash) {
+                break snap.clone()
+            }
+            let header =
+                self.provider.header(hash)?.ok_or(CliqueError::UnknownAncestor { number, hash })?;
+            if self.config.is_checkpoint(number) {
+                break self.checkpoint_snapshot(&header, hash)?
+            }
+            let parent_hash = header.parent_hash;
+            headers.push((header, hash));
+            number -= 1;
+            hash = parent_hash;
+        };
+
+        let snap = base.apply(headers.iter().rev().map(|(h, hash)| (h, *hash)), &self.config)?;
+        self.snapshots.lock().insert(snap.hash, snap.clone());
+        Ok(snap)
+    }
+
+    /// Returns the total difficulty of the chain up to and including the block with the given
+    /// number and hash.
+    ///
+    /// The total difficulty isn't stored, it's computed by walking back to the closest cached
+    /// block or the genesis and summing up the difficulties.
+    pub fn total_difficulty(&self, number: BlockNumber, hash: B256) -> Result<U256, CliqueError> {
+        let (mut number, mut hash) = (number, hash);
+        let mut headers = Vec::new();
+        let mut td = loop {
+            if let Some(td) = self.total_difficulties.lock().get(&hash) {
+                break *td
+            }
+            let header =
+                self.provider.header(hash)?.ok_or(CliqueError::UnknownAncestor { number, hash })?;
+            if number == 0 {
+                break header.difficulty
+            }
+            let parent_hash = header.parent_hash;
+            headers.push((header.difficulty, hash));
+            number -= 1;
+            hash = parent_hash;
+        };
+
+        let mut cache = self.total_difficulties.lock();
+        for (difficulty, hash) in headers.into_iter().rev() {
+            td += difficulty;
+            cache.insert(hash, td);
+        }
+        Ok(td)
+    }
+
+    /// Creates the snapshot at an epoch checkpoint from the signer list in its extra-data.
+    ///
+    /// The recent signers are recovered from the headers leading up to the checkpoint, so that
+    /// the signing limit keeps being enforced across the epoch transition.
+    fn checkpoint_snapshot(&self, header: &Header, hash: B256) -> Result<Snapshot, CliqueError> {
+        let mut snap = Snapshot::new(header.number, hash, checkpoint_signers(header)?);
+
+        let mut recents = BTreeMap::new();
+        let mut current = header.clone();
+        while current.number > 0 {
+            recents.insert(current.number, recover_signer(&current)?);
+            if recents.len() as u64 == snap.signer_limit() {
+                break
+            }
+            let number = current.number - 1;
+            let hash = current.parent_hash;
+            current =
+                self.provider.header(hash)?.ok_or(CliqueError::UnknownAncestor { number, hash })?;
+        }
+        snap.recents = recents;
+
+        Ok(snap)
+    }
+
+    /// Verifies the seal of the given header against the snapshot of its parent and returns the
+    /// signer.
+    pub fn verify_seal(&self, header: &SealedHeader<Header>) -> Result<Address, CliqueError> {
+        let number = header.number;
+        let snap = self.snapshot(number.saturating_sub(1), header.parent_hash)?;
+
+        if self.config.is_checkpoint(number) &&
+            !checkpoint_signers(header)?.iter().eq(snap.signers.iter())
+        {
+            return Err(CliqueError::InvalidCheckpointSigners)
+        }
+
+        // checks that the signer is authorized and didn't sign recently
+        let next = snap.apply([(header.header(), header.hash())], &self.config)?;
+        let signer = next.recents[&number];
+
+        let expected = if snap.is_in_turn(number, &signer) { DIFF_IN_TURN } else { DIFF_NO_TURN };
+        if header.difficulty != U256::from(expected) {
+            return Err(CliqueError::WrongDifficulty {
+                expected,
+                got: header.difficulty.saturating_to(),
+            })
+        }
+
+        self.snapshots.lock().insert(next.hash, next);
+        Ok(signer)
+    }
+
+    /// Checks the Clique specific header fields that don't depend on the chain state.
+    fn validate_clique_fields(&self, header: &Header) -> Result<(), CliqueError> {
+        let checkpoint = self.config.is_checkpoint(header.number);
+
+        if checkpoint && header.beneficiary != Address::ZERO {
+            return Err(CliqueError::InvalidCheckpointBeneficiary)
+        }
+        if header.nonce != NONCE_AUTH && header.nonce != NONCE_DROP {
+            return Err(CliqueError::InvalidVote)
+        }
+        if checkpoint && header.nonce != NONCE_DROP {
+            return Err(CliqueError::InvalidCheckpointVote)
+        }
+
+        let extra = header.extra_data.len();
+        if extra < EXTRA_VANITY {
+            return Err(CliqueError::MissingVanity)
+        }
+        if extra < EXTRA_VANITY + EXTRA_SEAL {
+            return Err(CliqueError::MissingSignature)
+        }
+        let signers = extra - EXTRA_VANITY - EXTRA_SEAL;
+        if !checkpoint && signers != 0 {
+            return Err(CliqueError::ExtraSigners)
+        }
+        if checkpoint && !signers.is_multiple_of(Address::len_bytes()) {
+            return Err(CliqueError::InvalidCheckpointSigners)
+        }
+
+        if header.mix_hash != B256::ZERO {
+            return Err(CliqueError::InvalidMixDigest)
+        }
+        if header.ommers_hash != EMPTY_OMMER_ROOT_HASH {
+            return Err(CliqueError::InvalidUncleHash)
+        }
+        if header.number > 0 &&
+            header.difficulty != U256::from(DIFF_IN_TURN) &&
+            header.difficulty != U256::from(DIFF_NO_TURN)
+        {
+            return Err(CliqueError::InvalidDifficulty)
+        }
+
+        Ok(())
+    }
+}
+
+impl<ChainSpec, Provider, N> FullConsensus<N> for CliqueConsensus<ChainSpec, Provider>
+where
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
+    Provider: HeaderProvider<Header = Header> + Debug + Sync,
+    N: NodePrimitives<BlockHeader = Header>,
+{
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        receipt_root_bloom: Option<ReceiptRootBloom>,
+    ) -> Result<(), ConsensusError> {
+        validate_block_post_execution(
+            block,
+            &self.chain_spec,
+            &result.receipts,
+            &result.requests,
+            receipt_root_bloom,
+        )?;
+        self.verify_seal(block.sealed_header())?;
+        Ok(())
+    }
+}
+
+impl<B, ChainSpec, Provider> Consensus<B> for CliqueConsensus<ChainSpec, Provider>
+where
+    B: Block<Header = Header>,
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
+    Provider: HeaderProvider<Header = Header> + Debug + Sync,
+{
+    fn validate_body_against_header(
+        &self,
+        body: &B::Body,
+        header: &SealedHeader<B::Header>,
+    ) -> Result<(), ConsensusError> {
+        validate_body_against_header(body, header.header())
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), ConsensusError> {
+        validate_block_pre_execution(block, &self.chain_spec)
+    }
+
+    fn validate_block_pre_execution_with_tx_root(
+        &self,
+        block: &SealedBlock<B>,
+        transaction_root: Option<TransactionRoot>,
+    ) -> Result<(), ConsensusError> {
+        validate_block_pre_execution_with_tx_root(block, &self.chain_spec, transaction_root)
+    }
+}
+
+impl<ChainSpec, Provider> HeaderValidator<Header> for CliqueConsensus<ChainSpec, Provider>
+where
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
+    Provider: HeaderProvider<Header = Header> + Debug + Sync,
+{
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
+        let header = header.header();
+
+        let present_timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if header.timestamp > present_timestamp + ALLOWED_FUTURE_BLOCK_TIME_SECONDS {
+            return Err(ConsensusError::TimestampIsInFuture {
+                timestamp: header.timestamp,
+                present_timestamp,
+            })
+        }
+
+        self.validate_clique_fields(header)?;
+        validate_header_gas(header)?;
+        validate_header_base_fee(header, &self.chain_spec)?;
+
+        // EIP-4895: Beacon chain push withdrawals as operations
+        if self.chain_spec.is_shanghai_active_at_timestamp(header.timestamp) !=
+            header.withdrawals_root.is_some()
+        {
+            return Err(if header.withdrawals_root.is_some() {
+                ConsensusError::WithdrawalsRootUnexpected
+            } else {
+                ConsensusError::WithdrawalsRootMissing
+            })
+        }
+
+        // Ensures that EIP-4844 fields are valid once cancun is active.
+        if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp) {
+            validate_4844_header_standalone(
+                header,
+                self.chain_spec
+                    .blob_params_at_timestamp(header.timestamp)
+                    .unwrap_or_else(BlobParams::cancun),
+            )?;
+        } else if header.blob_gas_used.is_some() {
+            return Err(ConsensusError::BlobGasUsedUnexpected)
+        } else if header.excess_blob_gas.is_some() {
+            return Err(ConsensusError::ExcessBlobGasUnexpected)
+        } else if header.parent_beacon_block_root.is_some() {
+            return Err(ConsensusError::ParentBeaconBlockRootUnexpected)
+        }
+
+        if self.chain_spec.is_prague_active_at_timestamp(header.timestamp) {
+            if header.requests_hash.is_none() {
+                return Err(ConsensusError::RequestsHashMissing)
+            }
+        } else if header.requests_hash.is_some() {
+            return Err(ConsensusError::RequestsHashUnexpected)
+        }
+
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        validate_against_parent_hash_number(header.header(), parent)?;
+
+        let earliest = parent.timestamp + self.config.period;
+        if header.timestamp < earliest {
+            return Err(
+                CliqueError::InvalidTimestamp { timestamp: header.timestamp, earliest }.into()
+            )
+        }
+
+        validate_against_parent_gas_limit(header, parent, &self.chain_spec)?;
+
+        validate_against_parent_eip1559_base_fee(
+            header.header(),
+            parent.header(),
+            &self.chain_spec,
+        )?;
+
+        // ensure that the blob gas fields for this block
+        if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp) {
+            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::header::{apply_seal, seal_hash, unsealed_extra_data_with_signers};
+    use alloy_genesis::{CliqueConfig as GenesisCliqueConfig, Genesis};
+    use alloy_primitives::B64;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::ChainSpec;
+    use reth_storage_api::{errors::ProviderResult, noop::NoopProvider};
+    use std::{collections::HashMap, ops::RangeBounds};
+
+    /// In-memory header provider for tests.
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct Headers(pub(crate) Arc<Mutex<HashMap<B256, Header>>>);
+
+    impl HeaderProvider for Headers {
+        type Header = Header;
+
+        fn header(&self, block_hash: B256) -> ProviderResult<Option<Header>> {
+            Ok(self.0.lock().get(&block_hash).cloned())
+        }
+
+        fn header_by_number(&self, num: u64) -> ProviderResult<Option<Header>> {
+            Ok(self.0.lock().values().find(|h| h.number == num).cloned())
+        }
+
+        fn headers_range(
+            &self,
+            _range: impl RangeBounds<BlockNumber>,
+        ) -> ProviderResult<Vec<Header>> {
+            Ok(vec![])
+        }
+
+        fn sealed_header(
+            &self,
+            number: BlockNumber,
+        ) -> ProviderResult<Option<SealedHeader<Header>>> {
+            Ok(self.header_by_number(number)?.map(SealedHeader::seal_slow))
+        }
+
+        fn sealed_headers_while(
+            &self,
+            _range: impl RangeBounds<BlockNumber>,
+            _predicate: impl FnMut(&SealedHeader<Header>) -> bool,
+        ) -> ProviderResult<Vec<SealedHeader<Header>>> {
+            Ok(vec![])
+        }
+    }
+
+    pub(crate) fn chain_spec(signers: &[Address]) -> Arc<ChainSpec> {
+        let mut genesis = Genesis::default()
+            .with_gas_limit(30_000_000)
+            .with_extra_data(unsealed_extra_data_with_signers(&[], Some(signers.to_vec())));
+        genesis.config.clique = Some(GenesisCliqueConfig { period: Some(1), epoch: Some(4) });
+        Arc::new(genesis.into())
+    }
+
+    pub(crate) fn seal(header: &mut Header, signer: &PrivateKeySigner) -> SealedHeader {
+        let signature = signer.sign_hash_sync(&seal_hash(header)).unwrap();
+        apply_seal(header, &signature);
+        SealedHeader::seal_slow(header.clone())
+    }
+
+    pub(crate) fn child(parent: &SealedHeader, signers: Option<&[Address]>) -> Header {
+        Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            timestamp: parent.timestamp + 1,
+            gas_limit: parent.gas_limit,
+            extra_data: unsealed_extra_data_with_signers(&[], signers.map(|s| s.to_vec())),
+            difficulty: U256::from(DIFF_NO_TURN),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn requires_clique_genesis() {
+        let chain_spec = Arc::new(ChainSpec::<Header>::default());
+        assert!(CliqueConsensus::new(chain_spec, NoopProvider::default()).is_none());
+    }
+
+    #[test]
+    fn validate_header_fields() {
+        let keys = [PrivateKeySigner::random()];
+        let chain_spec = chain_spec(&[keys[0].address()]);
+        let consensus = CliqueConsensus::new(chain_spec.clone(), Headers::default()).unwrap();
+        let genesis = chain_spec.sealed_genesis_header();
+
+        let mut header = child(&genesis, None);
+        header.difficulty = U256::from(DIFF_IN_TURN);
+        let sealed = seal(&mut header.clone(), &keys[0]);
+        assert!(HeaderValidator::validate_header(&consensus, &sealed).is_ok());
+        assert!(consensus.validate_header_against_parent(&sealed, &genesis).is_ok());
+
+        let mut invalid = header.clone();
+        invalid.difficulty = U256::from(3);
+        assert!(
+            HeaderValidator::validate_header(&consensus, &seal(&mut invalid, &keys[0])).is_err()
+        );
+
+        let mut invalid = header.clone();
+        invalid.nonce = B64::with_last_byte(1);
+        assert!(
+            HeaderValidator::validate_header(&consensus, &seal(&mut invalid, &keys[0])).is_err()
+        );
+
+        let mut invalid = header;
+        invalid.timestamp = genesis.timestamp;
+        assert!(consensus
+            .validate_header_against_parent(&seal(&mut invalid, &keys[0]), &genesis)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_seal_across_checkpoint() {
+        let mut keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        keys.sort_by_key(|k| k.address());
+        let addresses = keys.iter().map(|k| k.address()).collect::<Vec<_>>();
+        let chain_spec = chain_spec(&addresses);
+        let headers = Headers::default();
+        let consensus = CliqueConsensus::new(chain_spec.clone(), headers.clone()).unwrap();
+
+        let genesis = chain_spec.sealed_genesis_header();
+        headers.0.lock().insert(genesis.hash(), genesis.header().clone());
+
+        // blocks 1..=5 signed in turn, block 4 is an epoch checkpoint
+        let mut parent = genesis;
+        for number in 1..=5u64 {
+            let signer = &keys[(number % 2) as usize];
+            let mut header = child(
+                &parent,
+                consensus.config().is_checkpoint(number).then_some(addresses.as_slice()),
+            );
+            header.difficulty = U256::from(DIFF_IN_TURN);
+            let sealed = seal(&mut header, signer);
+
+            assert_eq!(consensus.verify_seal(&sealed).unwrap(), signer.address());
+            headers.0.lock().insert(sealed.hash(), sealed.header().clone());
+            parent = sealed;
+        }
+
+        // the signer of block 5 can't sign block 6
+        let mut header = child(&parent, None);
+        let sealed = seal(&mut header, &keys[1]);
+        assert!(matches!(consensus.verify_seal(&sealed), Err(CliqueError::RecentlySigned(_))));
+
+        // the other signer is in-turn and must use the in-turn difficulty
+        let mut header = child(&parent, None);
+        let sealed = seal(&mut header, &keys[0]);
+        assert!(matches!(
+            consensus.verify_seal(&sealed),
+            Err(CliqueError::WrongDifficulty { expected: DIFF_IN_TURN, .. })
+        ));
+
+        // a cold cache rebuilds the snapshot from the checkpoint and the signers before it
+        let cold = CliqueConsensus::new(chain_spec, headers).unwrap();
+        let mut header = child(&parent, None);
+        let sealed = seal(&mut header, &keys[1]);
+        assert!(matches!(cold.verify_seal(&sealed), Err(CliqueError::RecentlySigned(_))));
+    }
+
+    #[test]
+    fn verify_seal_after_execution() {
+        let keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        let chain_spec = chain_spec(&[keys[0].address()]);
+        let headers = Headers::default();
+        let consensus = CliqueConsensus::new(chain_spec.clone(), headers.clone()).unwrap();
+        let genesis = chain_spec.sealed_genesis_header();
+        headers.0.lock().insert(genesis.hash(), genesis.header().clone());
+
+        let validate = |signer: &PrivateKeySigner| {
+            let mut header = child(&genesis, None);
+            header.difficulty = U256::from(DIFF_IN_TURN);
+            let header = seal(&mut header, signer).unseal();
+            let block = reth_ethereum_primitives::Block { header, body: Default::default() };
+            let block = RecoveredBlock::new_sealed(SealedBlock::seal_slow(block), vec![]);
+            FullConsensus::<reth_ethereum_primitives::EthPrimitives>::validate_block_post_execution(
+                &consensus,
+                &block,
+                &BlockExecutionResult::default(),
+                None,
+            )
+        };
+
+        assert!(validate(&keys[0]).is_ok());
+        assert!(matches!(validate(&keys[1]), Err(ConsensusError::Custom(_))));
+    }
+
+    #[test]
+    fn total_difficulty() {
+        let keys = [PrivateKeySigner::random()];
+        let chain_spec = chain_spec(&[keys[0].address()]);
+        let headers = Headers::default();
+        let consensus = CliqueConsensus::new(chain_spec.clone(), headers.clone()).unwrap();
+        let genesis = chain_spec.sealed_genesis_header();
+        headers.0.lock().insert(genesis.hash(), genesis.header().clone());
+
+        let mut parent = genesis.clone();
+        for difficulty in [DIFF_IN_TURN, DIFF_NO_TURN, DIFF_IN_TURN] {
+            let mut header = child(&parent, None);
+            header.difficulty = U256::from(difficulty);
+            parent = seal(&mut header, &keys[0]);
+            headers.0.lock().insert(parent.hash(), parent.header().clone());
+        }
+
+        let expected = genesis.difficulty + U256::from(5);
+        assert_eq!(consensus.total_difficulty(parent.number, parent.hash()).unwrap(), expected);
+        // served from the cache once the headers are gone
+        headers.0.lock().clear();
+        assert_eq!(consensus.total_difficulty(parent.number, parent.hash()).unwrap(), expected);
+        assert!(matches!(
+            consensus.total_difficulty(parent.number + 1, B256::ZERO),
+            Err(CliqueError::UnknownAncestor { .. })
+        ));
+    }
+}
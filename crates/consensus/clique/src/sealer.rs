@@ -0,0 +1,376 @@
+//! Block sealing for authorized signers.
+
+use crate::{
+    header::{
+        apply_seal, seal_hash, unsealed_extra_data_with_signers, DIFF_IN_TURN, DIFF_NO_TURN,
+        NONCE_AUTH, NONCE_DROP,
+    },
+    snapshot::Snapshot,
+    CliqueConsensus,
+};
+use alloy_consensus::Header;
+use alloy_primitives::{map::AddressMap, Address, B256, U256};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadAttributes};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use eyre::OptionExt;
+use futures::StreamExt;
+use parking_lot::RwLock;
+use rand::{seq::IteratorRandom, Rng};
+use reth_chain_state::CanonStateSubscriptions;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::{BuiltPayloadBlock, ConsensusEngineHandle};
+use reth_eth_wire_types::{NetworkPrimitives, NewBlock};
+use reth_network::NetworkHandle;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{BuiltPayload, EngineApiMessageVersion, PayloadKind, PayloadTypes};
+use reth_primitives_traits::{Block, SealedBlock, SealedHeader};
+use reth_storage_api::{BlockNumReader, HeaderProvider};
+use reth_transaction_pool::TransactionPool;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, error, info};
+
+/// Delay per signer that out-of-turn signers wait at most before sealing, to reduce the chance of
+/// competing blocks.
+const WIGGLE_TIME: Duration = Duration::from_millis(500);
+
+/// Shared handle to the pending signer proposals of a [`CliqueSealer`].
+///
+/// Proposals are cast as votes in the blocks sealed by the local signer until they pass.
+#[derive(Debug, Clone, Default)]
+pub struct CliqueSealerHandle {
+    proposals: Arc<RwLock<AddressMap<bool>>>,
+}
+
+impl CliqueSealerHandle {
+    /// Proposes to authorize or deauthorize the given address.
+    pub fn propose(&self, address: Address, authorize: bool) {
+        self.proposals.write().insert(address, authorize);
+    }
+
+    /// Drops a pending proposal.
+    pub fn discard(&self, address: &Address) {
+        self.proposals.write().remove(address);
+    }
+
+    /// Returns the pending proposals.
+    pub fn proposals(&self) -> AddressMap<bool> {
+        self.proposals.read().clone()
+    }
+
+    /// Picks a random proposal that still makes sense with the given snapshot.
+    fn pick(&self, snap: &Snapshot) -> Option<(Address, bool)> {
+        self.proposals
+            .read()
+            .iter()
+            .filter(|(address, authorize)| snap.is_valid_vote(address, **authorize))
+            .map(|(address, authorize)| (*address, *authorize))
+            .choose(&mut rand::rng())
+    }
+}
+
+/// Seals new blocks on top of the canonical head with the configured signer key.
+///
+/// Blocks are built by the payload builder via forkchoice updates with payload attributes, then
+/// sealed and inserted with [`ConsensusEngineHandle::new_block`] because the seal lives in header
+/// fields that the execution payload format can't carry. Sealed blocks are announced to peers.
+#[derive(Debug)]
+pub struct CliqueSealer<T, ChainSpec, Provider, Pool, Net>
+where
+    T: PayloadTypes,
+    Net: NetworkPrimitives,
+{
+    /// Consensus engine used to compute signer snapshots.
+    consensus: CliqueConsensus<ChainSpec, Provider>,
+    /// Provider of the canonical head.
+    provider: Provider,
+    /// The signer key.
+    signer: PrivateKeySigner,
+    /// Sender for events to engine.
+    to_engine: ConsensusEngineHandle<T>,
+    /// The payload builder for the engine.
+    payload_builder: PayloadBuilderHandle<T>,
+    /// Transaction pool, used to seal on demand if the block period is `0`.
+    pool: Pool,
+    /// Network used to announce sealed blocks.
+    network: NetworkHandle<Net>,
+    /// Pending signer proposals.
+    handle: CliqueSealerHandle,
+}
+
+impl<T, ChainSpec, Provider, Pool, Net> CliqueSealer<T, ChainSpec, Provider, Pool, Net>
+where
+    T: PayloadTypes<PayloadAttributes: From<PayloadAttributes>>,
+    BuiltPayloadBlock<T>: Block<Header = Header>,
+    ChainSpec: EthChainSpec + EthereumHardforks,
+    Provider: HeaderProvider<Header = Header> + BlockNumReader + CanonStateSubscriptions + Clone,
+    Pool: TransactionPool,
+    Net: NetworkPrimitives<NewBlockPayload = NewBlock<BuiltPayloadBlock<T>>>,
+{
+    /// Creates a new [`CliqueSealer`].
+    pub fn new(
+        consensus: CliqueConsensus<ChainSpec, Provider>,
+        signer: PrivateKeySigner,
+        to_engine: ConsensusEngineHandle<T>,
+        payload_builder: PayloadBuilderHandle<T>,
+        pool: Pool,
+        network: NetworkHandle<Net>,
+    ) -> Self {
+        Self {
+            provider: consensus.provider.clone(),
+            consensus,
+            signer,
+            to_engine,
+            payload_builder,
+            pool,
+            network,
+            handle: Default::default(),
+        }
+    }
+
+    /// Sets the handle to the signer proposals, e.g. to share it with an RPC server that was
+    /// set up before the sealer.
+    pub fn with_handle(mut self, handle: CliqueSealerHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Returns a handle to manage the signer proposals of this sealer.
+    pub fn handle(&self) -> CliqueSealerHandle {
+        self.handle.clone()
+    }
+
+    /// Runs the sealer until the engine shuts down.
+    ///
+    /// Whenever the canonical head changes, the next block is scheduled according to the block
+    /// period and the turn-ness of the signer.
+    pub async fn run(self) {
+        let mut canonical = self.provider.canonical_state_stream();
+        let mut pending = self.pool.pending_transactions_listener();
+        info!(target: "consensus::clique", signer=%self.signer.address(), "Starting clique sealer");
+
+        loop {
+            let head = match self.head() {
+                Ok(head) => head,
+                Err(err) => {
+                    error!(target: "consensus::clique", %err, "Failed to read canonical head");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue
+                }
+            };
+
+            let delay = match self.schedule(&head) {
+                Ok(delay) => delay,
+                Err(err) => {
+                    error!(target: "consensus::clique", %err, "Failed to compute signer snapshot");
+                    None
+                }
+            };
+            let Some(delay) = delay else {
+                // not allowed to seal on top of this head, wait for the next one
+                if canonical.next().await.is_none() {
+                    return
+                }
+                continue
+            };
+
+            // with a block period of 0, blocks are only sealed if there are transactions
+            if self.consensus.config().period == 0 &&
+                self.pool.pending_and_queued_txn_count().0 == 0
+            {
+                tokio::select! {
+                    _ = pending.recv() => {}
+                    notification = canonical.next() => if notification.is_none() { return },
+                }
+                continue
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {
+                    if let Err(err) = self.seal(&head).await {
+                        error!(target: "consensus::clique", %err, parent=?head.num_hash(), "Failed to seal block");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+                notification = canonical.next() => if notification.is_none() { return },
+            }
+        }
+    }
+
+    /// Returns the canonical head.
+    fn head(&self) -> eyre::Result<SealedHeader<Header>> {
+        let number = self.provider.best_block_number()?;
+        self.provider.sealed_header(number)?.ok_or_eyre("canonical head not found")
+    }
+
+    /// Returns the delay until the next block should be sealed on top of the given head, or
+    /// `None` if the local signer isn't allowed to seal it.
+    fn schedule(&self, head: &SealedHeader<Header>) -> eyre::Result<Option<Duration>> {
+        let snap = self.consensus.snapshot(head.number, head.hash())?;
+        let signer = self.signer.address();
+        if !snap.is_authorized(&signer) {
+            debug!(target: "consensus::clique", %signer, "Not an authorized signer");
+            return Ok(None)
+        }
+        if snap.is_recently_signed(&signer) {
+            debug!(target: "consensus::clique", %signer, "Signed recently, waiting for others");
+            return Ok(None)
+        }
+
+        let mut delay = Duration::from_secs(self.timestamp(head)).saturating_sub(unix_now());
+        if !snap.is_in_turn(head.number + 1, &signer) {
+            let wiggle = WIGGLE_TIME * snap.signer_limit() as u32;
+            delay += rand::rng().random_range(Duration::ZERO..=wiggle);
+        }
+        Ok(Some(delay))
+    }
+
+    /// Returns the timestamp of the block following the given head.
+    fn timestamp(&self, head: &SealedHeader<Header>) -> u64 {
+        let period = self.consensus.config().period.max(1);
+        (head.timestamp + period).max(unix_now().as_secs())
+    }
+
+    /// Builds a block on top of the given head, seals it and makes it canonical.
+    async fn seal(&self, head: &SealedHeader<Header>) -> eyre::Result<()> {
+        let snap = self.consensus.snapshot(head.number, head.hash())?;
+        let number = head.number + 1;
+        let checkpoint = self.consensus.config().is_checkpoint(number);
+        let (beneficiary, authorize) =
+            if checkpoint { None } else { self.handle.pick(&snap) }.unwrap_or_default();
+
+        let signer = self.signer.address();
+        let difficulty =
+            U256::from(if snap.is_in_turn(number, &signer) { DIFF_IN_TURN } else { DIFF_NO_TURN });
+
+        let timestamp = self.timestamp(head);
+        let chain_spec = self.consensus.chain_spec();
+        let attributes = PayloadAttributes {
+            timestamp,
+            // the difficulty must be known before execution, see `CliqueEvmConfig`
+            prev_randao: difficulty.into(),
+            suggested_fee_recipient: beneficiary,
+            withdrawals: chain_spec.is_shanghai_active_at_timestamp(timestamp).then(Vec::new),
+            parent_beacon_block_root: chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+        };
+
+        // clique blocks are never final
+        let state = ForkchoiceState {
+            head_block_hash: head.hash(),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let res = self
+            .to_engine
+            .fork_choice_updated(state, Some(attributes.into()), EngineApiMessageVersion::default())
+            .await?;
+        let payload_id = res.payload_id.ok_or_eyre("No payload id")?;
+
+        let Some(Ok(payload)) =
+            self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
+        else {
+            eyre::bail!("No payload")
+        };
+
+        let (header, body) = payload.block().clone().split_header_body();
+        // the difficulty is only set by `CliqueEvmConfig`
+        if header.difficulty != difficulty {
+            eyre::bail!("Built block has difficulty {}, expected {difficulty}", header.difficulty)
+        }
+        let header = seal_header(
+            header,
+            &self.signer,
+            authorize,
+            checkpoint.then_some(snap.signers.iter().copied()),
+        )?;
+        let block = SealedBlock::seal_slow(BuiltPayloadBlock::<T>::new(header, body));
+        let hash = block.hash();
+
+        let status = self.to_engine.new_block(block.clone()).await?;
+        if !status.is_valid() {
+            eyre::bail!("Sealed block {hash} is invalid: {status:?}")
+        }
+
+        let state = ForkchoiceState { head_block_hash: hash, ..state };
+        let res = self
+            .to_engine
+            .fork_choice_updated(state, None, EngineApiMessageVersion::default())
+            .await?;
+        if !res.is_valid() {
+            eyre::bail!("Invalid fork choice update {state:?}: {res:?}")
+        }
+
+        info!(target: "consensus::clique", number, %hash, txs=block.transaction_count(), "Sealed new block");
+
+        let td = self.consensus.total_difficulty(number, hash)?;
+        self.network
+            .announce_block(NewBlock { block: block.into_block(), td: td.saturating_to() }, hash);
+
+        Ok(())
+    }
+}
+
+/// Sets the vote and the signer list of a built block and signs it.
+///
+/// The signer list is only included in epoch checkpoints.
+fn seal_header(
+    mut header: Header,
+    signer: &PrivateKeySigner,
+    authorize: bool,
+    signers: Option<impl IntoIterator<Item = Address>>,
+) -> alloy_signer::Result<Header> {
+    header.nonce = if authorize { NONCE_AUTH } else { NONCE_DROP };
+    header.extra_data = unsealed_extra_data_with_signers(&header.extra_data, signers);
+    let signature = signer.sign_hash_sync(&seal_hash(&header))?;
+    apply_seal(&mut header, &signature);
+    Ok(header)
+}
+
+/// Returns the duration since the unix epoch.
+fn unix_now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::{checkpoint_signers, EXTRA_SEAL, EXTRA_VANITY},
+        tests::{chain_spec, child, Headers},
+    };
+
+    #[test]
+    fn sealed_header_passes_seal_verification() {
+        let mut keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        keys.sort_by_key(|k| k.address());
+        let addresses = keys.iter().map(|k| k.address()).collect::<Vec<_>>();
+        let chain_spec = chain_spec(&addresses);
+        let headers = Headers::default();
+        let consensus = CliqueConsensus::new(chain_spec.clone(), headers.clone()).unwrap();
+        let genesis = chain_spec.sealed_genesis_header();
+        headers.0.lock().insert(genesis.hash(), genesis.header().clone());
+
+        // a block as returned by the payload builder, with vanity in the extra-data
+        let mut built = child(&genesis, None);
+        built.extra_data = b"reth/v1".to_vec().into();
+        built.difficulty = U256::from(DIFF_IN_TURN);
+        built.beneficiary = Address::repeat_byte(1);
+
+        let header = seal_header(built.clone(), &keys[1], true, None::<Vec<Address>>).unwrap();
+        assert_eq!(header.nonce, NONCE_AUTH);
+        assert_eq!(header.extra_data.len(), EXTRA_VANITY + EXTRA_SEAL);
+        assert!(header.extra_data.starts_with(b"reth/v1"));
+        let sealed = SealedHeader::seal_slow(header);
+        assert_eq!(consensus.verify_seal(&sealed).unwrap(), keys[1].address());
+
+        // checkpoints carry the signer list
+        let header = seal_header(built, &keys[1], false, Some(addresses.clone())).unwrap();
+        assert_eq!(header.nonce, NONCE_DROP);
+        assert_eq!(checkpoint_signers(&header).unwrap(), addresses);
+    }
+}
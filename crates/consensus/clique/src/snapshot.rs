@@ -0,0 +1,331 @@
+//! Signer set and voting state at a given block.
+
+use crate::{
+    header::{recover_signer, NONCE_AUTH, NONCE_DROP},
+    CliqueConfig, CliqueError,
+};
+use alloy_consensus::Header;
+use alloy_primitives::{map::AddressMap, Address, BlockNumber, B256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A single vote that an authorized signer made to modify the list of authorizations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vote {
+    /// Authorized signer that cast this vote.
+    pub signer: Address,
+    /// Block number the vote was cast in.
+    pub block: BlockNumber,
+    /// Account being voted on to change its authorization.
+    pub address: Address,
+    /// Whether to authorize or deauthorize the voted account.
+    pub authorize: bool,
+}
+
+/// A simple vote tally to keep the current score of votes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    /// Whether the vote is about authorizing or kicking someone.
+    pub authorize: bool,
+    /// Number of votes until now wanting to pass the proposal.
+    pub votes: usize,
+}
+
+/// The state of the authorization voting at a given point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Block number where the snapshot was created.
+    pub number: BlockNumber,
+    /// Block hash where the snapshot was created.
+    pub hash: B256,
+    /// Set of authorized signers at this moment, in ascending order.
+    pub signers: BTreeSet<Address>,
+    /// Set of recent signers for spam protections, keyed by block number.
+    pub recents: BTreeMap<BlockNumber, Address>,
+    /// List of votes cast in chronological order.
+    pub votes: Vec<Vote>,
+    /// Current vote tally to avoid recalculating.
+    pub tally: AddressMap<Tally>,
+}
+
+impl Snapshot {
+    /// Creates a new snapshot with the specified startup parameters.
+    ///
+    /// This is used for the genesis block and for epoch checkpoints, which carry the full signer
+    /// list and reset any pending votes.
+    pub fn new(
+        number: BlockNumber,
+        hash: B256,
+        signers: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            number,
+            hash,
+            signers: signers.into_iter().collect(),
+            recents: Default::default(),
+            votes: Default::default(),
+            tally: Default::default(),
+        }
+    }
+
+    /// Returns the number of consecutive blocks after which a signer may sign again.
+    pub fn signer_limit(&self) -> u64 {
+        (self.signers.len() / 2 + 1) as u64
+    }
+
+    /// Returns `true` if the signer is authorized at this snapshot.
+    pub fn is_authorized(&self, signer: &Address) -> bool {
+        self.signers.contains(signer)
+    }
+
+    /// Returns `true` if the signer signed one of the recent blocks and is not allowed to sign the
+    /// block following this snapshot.
+    pub fn is_recently_signed(&self, signer: &Address) -> bool {
+        let next = self.number + 1;
+        let limit = self.signer_limit();
+        self.recents.iter().any(|(seen, recent)| recent == signer && *seen + limit > next)
+    }
+
+    /// Returns `true` if the given signer is in-turn for the given block number.
+    pub fn is_in_turn(&self, number: BlockNumber, signer: &Address) -> bool {
+        let Some(offset) = self.signers.iter().position(|s| s == signer) else { return false };
+        (number % self.signers.len() as u64) as usize == offset
+    }
+
+    /// Returns `true` if a proposal to change the authorization of the address is meaningful.
+    pub fn is_valid_vote(&self, address: &Address, authorize: bool) -> bool {
+        self.signers.contains(address) != authorize
+    }
+
+    /// Applies the given headers on top of this snapshot and returns the resulting snapshot.
+    ///
+    /// The headers must be contiguous and start at the block following this snapshot.
+    pub fn apply<'a>(
+        &self,
+        headers: impl IntoIterator<Item = (&'a Header, B256)>,
+        config: &CliqueConfig,
+    ) -> Result<Self, CliqueError> {
+        let mut snap = self.clone();
+        for (header, hash) in headers {
+            let number = header.number;
+            debug_assert_eq!(number, snap.number + 1, "headers must be contiguous");
+
+            // remove any votes on checkpoint blocks
+            if config.is_checkpoint(number) {
+                snap.votes.clear();
+                snap.tally.clear();
+            }
+
+            // delete the oldest signer from the recent list to allow it signing again
+            let limit = snap.signer_limit();
+            if number >= limit {
+                snap.recents.remove(&(number - limit));
+            }
+
+            let signer = recover_signer(header)?;
+            if !snap.signers.contains(&signer) {
+                return Err(CliqueError::UnauthorizedSigner(signer))
+            }
+            if snap.recents.values().any(|recent| *recent == signer) {
+                return Err(CliqueError::RecentlySigned(signer))
+            }
+            snap.recents.insert(number, signer);
+
+            // discard any previous votes from the signer on the same account
+            let beneficiary = header.beneficiary;
+            if let Some(pos) =
+                snap.votes.iter().position(|v| v.signer == signer && v.address == beneficiary)
+            {
+                let vote = snap.votes.remove(pos);
+                snap.uncast(vote.address, vote.authorize);
+            }
+
+            let authorize = match header.nonce {
+                NONCE_AUTH => true,
+                NONCE_DROP => false,
+                _ => return Err(CliqueError::InvalidVote),
+            };
+            if snap.cast(beneficiary, authorize) {
+                snap.votes.push(Vote { signer, block: number, address: beneficiary, authorize });
+            }
+
+            // if the vote passed, update the list of signers
+            if let Some(tally) = snap.tally.get(&beneficiary).copied() &&
+                tally.votes > snap.signers.len() / 2
+            {
+                if tally.authorize {
+                    snap.signers.insert(beneficiary);
+                } else {
+                    snap.signers.remove(&beneficiary);
+
+                    // the signer list shrunk, delete any leftover recent caches
+                    let limit = snap.signer_limit();
+                    if number >= limit {
+                        snap.recents.remove(&(number - limit));
+                    }
+
+                    // discard any previous votes the deauthorized signer cast
+                    let (dropped, kept) =
+                        snap.votes.drain(..).partition::<Vec<_>, _>(|v| v.signer == beneficiary);
+                    snap.votes = kept;
+                    for vote in dropped {
+                        snap.uncast(vote.address, vote.authorize);
+                    }
+                }
+
+                // discard any previous votes around the just changed account
+                snap.votes.retain(|v| v.address != beneficiary);
+                snap.tally.remove(&beneficiary);
+            }
+
+            snap.number = number;
+            snap.hash = hash;
+        }
+        Ok(snap)
+    }
+
+    /// Adds a new vote into the tally, returns `false` if the vote is meaningless.
+    fn cast(&mut self, address: Address, authorize: bool) -> bool {
+        if !self.is_valid_vote(&address, authorize) {
+            return false
+        }
+        let tally = self.tally.entry(address).or_insert(Tally { authorize, votes: 0 });
+        if tally.authorize != authorize {
+            return false
+        }
+        tally.votes += 1;
+        true
+    }
+
+    /// Removes a previously cast vote from the tally.
+    fn uncast(&mut self, address: Address, authorize: bool) {
+        let Some(tally) = self.tally.get_mut(&address) else { return };
+        if tally.authorize != authorize {
+            return
+        }
+        if tally.votes > 1 {
+            tally.votes -= 1;
+        } else {
+            self.tally.remove(&address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{apply_seal, seal_hash, unsealed_extra_data_with_signers};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    /// Signers sorted by address, so that the in-turn order is predictable.
+    fn signers(n: usize) -> Vec<PrivateKeySigner> {
+        let mut signers = (0..n).map(|_| PrivateKeySigner::random()).collect::<Vec<_>>();
+        signers.sort_by_key(|s| s.address());
+        signers
+    }
+
+    fn sealed(number: u64, signer: &PrivateKeySigner, vote: Option<(Address, bool)>) -> Header {
+        let (beneficiary, nonce) = match vote {
+            Some((address, true)) => (address, NONCE_AUTH),
+            Some((address, false)) => (address, NONCE_DROP),
+            None => (Address::ZERO, NONCE_DROP),
+        };
+        let mut header = Header {
+            number,
+            beneficiary,
+            nonce,
+            extra_data: unsealed_extra_data_with_signers(&[], None::<Vec<Address>>),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&seal_hash(&header)).unwrap();
+        apply_seal(&mut header, &signature);
+        header
+    }
+
+    fn apply(snap: &Snapshot, headers: &[Header]) -> Result<Snapshot, CliqueError> {
+        snap.apply(headers.iter().map(|h| (h, h.hash_slow())), &CliqueConfig::default())
+    }
+
+    #[test]
+    fn single_signer_votes_in_new_signer() {
+        let keys = signers(1);
+        let new = Address::with_last_byte(0xaa);
+        let snap = Snapshot::new(0, B256::ZERO, [keys[0].address()]);
+
+        let snap = apply(&snap, &[sealed(1, &keys[0], Some((new, true)))]).unwrap();
+        assert_eq!(snap.signers.len(), 2);
+        assert!(snap.is_authorized(&new));
+        assert!(snap.votes.is_empty());
+        assert!(snap.tally.is_empty());
+        assert_eq!(snap.signer_limit(), 2);
+    }
+
+    #[test]
+    fn vote_requires_majority() {
+        let keys = signers(3);
+        let new = Address::with_last_byte(0xaa);
+        let snap = Snapshot::new(0, B256::ZERO, keys.iter().map(|k| k.address()));
+
+        let snap = apply(&snap, &[sealed(1, &keys[1], Some((new, true)))]).unwrap();
+        assert!(!snap.is_authorized(&new));
+        assert_eq!(snap.tally[&new], Tally { authorize: true, votes: 1 });
+
+        let snap = apply(&snap, &[sealed(2, &keys[2], Some((new, true)))]).unwrap();
+        assert!(snap.is_authorized(&new));
+        assert!(snap.votes.is_empty());
+    }
+
+    #[test]
+    fn deauthorized_signer_votes_are_discarded() {
+        let keys = signers(3);
+        let [a, b, c] = [&keys[0], &keys[1], &keys[2]];
+        let new = Address::with_last_byte(0xaa);
+        let snap = Snapshot::new(0, B256::ZERO, keys.iter().map(|k| k.address()));
+
+        let snap = apply(
+            &snap,
+            &[
+                // c votes in a new signer
+                sealed(1, c, Some((new, true))),
+                // a and b vote out c
+                sealed(2, a, Some((c.address(), false))),
+                sealed(3, b, Some((c.address(), false))),
+            ],
+        )
+        .unwrap();
+        assert!(!snap.is_authorized(&c.address()));
+        assert!(!snap.tally.contains_key(&new));
+        assert!(snap.votes.is_empty());
+    }
+
+    #[test]
+    fn rejects_unauthorized_and_recent_signers() {
+        let keys = signers(3);
+        let outsider = PrivateKeySigner::random();
+        let snap = Snapshot::new(0, B256::ZERO, keys.iter().map(|k| k.address()));
+
+        assert!(matches!(
+            apply(&snap, &[sealed(1, &outsider, None)]),
+            Err(CliqueError::UnauthorizedSigner(_))
+        ));
+
+        // with three signers, a signer may sign at most one of two consecutive blocks
+        assert!(matches!(
+            apply(&snap, &[sealed(1, &keys[0], None), sealed(2, &keys[0], None)]),
+            Err(CliqueError::RecentlySigned(_))
+        ));
+        let snap = apply(&snap, &[sealed(1, &keys[0], None), sealed(2, &keys[1], None)]).unwrap();
+        assert!(!snap.is_recently_signed(&keys[0].address()));
+        assert!(snap.is_recently_signed(&keys[1].address()));
+        assert!(apply(&snap, &[sealed(3, &keys[0], None)]).is_ok());
+    }
+
+    #[test]
+    fn in_turn_follows_sorted_signers() {
+        let keys = signers(3);
+        let snap = Snapshot::new(0, B256::ZERO, keys.iter().map(|k| k.address()));
+        assert!(snap.is_in_turn(3, &keys[0].address()));
+        assert!(snap.is_in_turn(4, &keys[1].address()));
+        assert!(!snap.is_in_turn(4, &keys[2].address()));
+    }
+}
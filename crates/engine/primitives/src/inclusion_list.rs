@@ -0,0 +1,326 @@
+//! Fork-choice enforced inclusion lists, see [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{map::B256Set, Address, Bytes, TxHash, B256, U256};
+use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
+use reth_primitives_traits::{Account, Recovered, SignedTransaction};
+
+/// The maximum size of all transactions of an inclusion list, in bytes.
+pub const MAX_BYTES_PER_INCLUSION_LIST: usize = 8192;
+
+/// The EIP-2718 encoded transactions of an inclusion list.
+pub type InclusionList = Vec<Bytes>;
+
+/// Returns the total size of the transactions of an inclusion list, in bytes.
+pub fn inclusion_list_size(inclusion_list: &[Bytes]) -> usize {
+    inclusion_list.iter().map(|tx| tx.len()).sum()
+}
+
+/// Decodes and recovers the transactions of an inclusion list.
+///
+/// Transactions that can't be decoded or recovered, and blob transactions, are skipped: they can
+/// never be included by a block, so they never constrain it.
+pub fn decode_inclusion_list<T: SignedTransaction>(inclusion_list: &[Bytes]) -> Vec<Recovered<T>> {
+    inclusion_list
+        .iter()
+        .filter_map(|raw| {
+            SignedTransaction::try_into_recovered(T::decode_2718_exact(raw).ok()?).ok()
+        })
+        .filter(|tx| !tx.is_eip4844())
+        .collect()
+}
+
+/// Checks whether a block satisfies an inclusion list.
+///
+/// Every transaction of the list must either be included in the block, or it must be impossible
+/// to append it to the block, because:
+///  - it doesn't fit into the gas left in the block
+///  - its fee cap is below the base fee of the block
+///  - the nonce or the balance of its sender in the post-state of the block don't allow it
+///
+/// `account` returns the account of an address in the post-state of the block.
+///
+/// Returns the hash of the first transaction that could have been appended to the block, or
+/// `None` if the block satisfies the inclusion list.
+pub fn find_unsatisfied_transaction<T, E>(
+    inclusion_list: &[Recovered<T>],
+    block_transactions: impl IntoIterator<Item = TxHash>,
+    gas_left: u64,
+    base_fee: Option<u64>,
+    mut account: impl FnMut(Address) -> Result<Option<Account>, E>,
+) -> Result<Option<TxHash>, E>
+where
+    T: SignedTransaction,
+{
+    let included: B256Set = block_transactions.into_iter().collect();
+
+    for tx in inclusion_list {
+        if included.contains(tx.tx_hash()) || tx.gas_limit() > gas_left {
+            continue
+        }
+        if let Some(base_fee) = base_fee &&
+            tx.max_fee_per_gas() < base_fee as u128
+        {
+            continue
+        }
+
+        let sender = account(tx.signer())?.unwrap_or_default();
+        let cost = U256::from(tx.gas_limit()) * U256::from(tx.max_fee_per_gas()) + tx.value();
+        if sender.nonce == tx.nonce() && sender.balance >= cost {
+            return Ok(Some(*tx.tx_hash()))
+        }
+    }
+
+    Ok(None)
+}
+
+/// The status of a payload that was received with an inclusion list.
+///
+/// Extends the [`PayloadStatus`] with [`InclusionListPayloadStatusKind::InclusionListUnsatisfied`]:
+/// a block that doesn't satisfy its inclusion list is still valid, the consensus layer decides
+/// whether to attest to it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionListPayloadStatus {
+    /// The status of the payload.
+    pub status: InclusionListPayloadStatusKind,
+    /// The hash of the most recent valid block in the branch defined by the payload and its
+    /// ancestors.
+    pub latest_valid_hash: Option<B256>,
+    /// The reason the payload is invalid, if it is.
+    pub validation_error: Option<String>,
+}
+
+impl InclusionListPayloadStatus {
+    /// Returns the status of a valid payload that doesn't satisfy its inclusion list.
+    pub const fn unsatisfied(block_hash: B256) -> Self {
+        Self {
+            status: InclusionListPayloadStatusKind::InclusionListUnsatisfied,
+            latest_valid_hash: Some(block_hash),
+            validation_error: None,
+        }
+    }
+}
+
+impl From<PayloadStatus> for InclusionListPayloadStatus {
+    fn from(status: PayloadStatus) -> Self {
+        let PayloadStatus { status, latest_valid_hash } = status;
+        let (kind, validation_error) = match status {
+            PayloadStatusEnum::Valid => (InclusionListPayloadStatusKind::Valid, None),
+            PayloadStatusEnum::Invalid { validation_error } => {
+                (InclusionListPayloadStatusKind::Invalid, Some(validation_error))
+            }
+            PayloadStatusEnum::Syncing => (InclusionListPayloadStatusKind::Syncing, None),
+            PayloadStatusEnum::Accepted => (InclusionListPayloadStatusKind::Accepted, None),
+        };
+        Self { status: kind, latest_valid_hash, validation_error }
+    }
+}
+
+/// The possible values of [`InclusionListPayloadStatus::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InclusionListPayloadStatusKind {
+    /// The payload is valid and satisfies its inclusion list.
+    Valid,
+    /// The payload is invalid.
+    Invalid,
+    /// The payload couldn't be validated yet, because the node is syncing.
+    Syncing,
+    /// The payload was accepted, but not validated yet.
+    Accepted,
+    /// The payload is valid, but a transaction of its inclusion list could have been included.
+    InclusionListUnsatisfied,
+}
+
+#[cfg(feature = "std")]
+pub use store::InclusionListStore;
+
+#[cfg(feature = "std")]
+mod store {
+    use super::InclusionList;
+    use alloy_primitives::{TxHash, B256};
+    use alloy_rpc_types_engine::PayloadId;
+    use parking_lot::Mutex;
+    use schnellru::{ByLength, LruMap};
+    use std::sync::Arc;
+
+    /// The inclusion lists the consensus layer supplied over the engine API.
+    ///
+    /// Keeps two kinds of lists:
+    ///  - the lists new payloads have to satisfy, by block hash. The engine API inserts them, and
+    ///    the engine tree checks a block against its list when it's executed and records whether
+    ///    the block satisfied it.
+    ///  - the lists payloads that are being built have to include, by payload id. The engine API
+    ///    inserts them, and the payload builder includes their transactions.
+    ///
+    /// All clones share the same lists.
+    #[derive(Clone)]
+    pub struct InclusionListStore {
+        inner: Arc<Mutex<Lists>>,
+    }
+
+    struct Lists {
+        blocks: LruMap<B256, BlockInclusionList>,
+        payloads: LruMap<PayloadId, Arc<InclusionList>>,
+    }
+
+    struct BlockInclusionList {
+        list: Arc<InclusionList>,
+        unsatisfied: Option<TxHash>,
+    }
+
+    impl InclusionListStore {
+        /// The default number of inclusion lists that are kept, for blocks and payloads each.
+        pub const DEFAULT_CAPACITY: u32 = 64;
+
+        /// Creates a new store that keeps the inclusion lists of the `capacity` most recent
+        /// blocks and payloads.
+        pub fn new(capacity: u32) -> Self {
+            Self {
+                inner: Arc::new(Mutex::new(Lists {
+                    blocks: LruMap::new(ByLength::new(capacity)),
+                    payloads: LruMap::new(ByLength::new(capacity)),
+                })),
+            }
+        }
+
+        /// Sets the inclusion list the block with the given hash must satisfy.
+        pub fn insert(&self, block_hash: B256, inclusion_list: InclusionList) {
+            self.inner.lock().blocks.insert(
+                block_hash,
+                BlockInclusionList { list: Arc::new(inclusion_list), unsatisfied: None },
+            );
+        }
+
+        /// Returns the inclusion list the block with the given hash must satisfy, if any.
+        pub fn get(&self, block_hash: &B256) -> Option<Arc<InclusionList>> {
+            self.inner.lock().blocks.get(block_hash).map(|entry| entry.list.clone())
+        }
+
+        /// Records that the block with the given hash doesn't satisfy its inclusion list, because
+        /// the transaction with the given hash could have been included.
+        pub fn set_unsatisfied(&self, block_hash: &B256, tx_hash: TxHash) {
+            if let Some(entry) = self.inner.lock().blocks.get(block_hash) {
+                entry.unsatisfied = Some(tx_hash);
+            }
+        }
+
+        /// Returns the transaction of its inclusion list the block with the given hash could
+        /// have included, if the block was found to not satisfy the list.
+        pub fn unsatisfied(&self, block_hash: &B256) -> Option<TxHash> {
+            self.inner.lock().blocks.get(block_hash).and_then(|entry| entry.unsatisfied)
+        }
+
+        /// Sets the inclusion list the payload with the given id has to include.
+        pub fn insert_for_payload(&self, payload_id: PayloadId, inclusion_list: InclusionList) {
+            self.inner.lock().payloads.insert(payload_id, Arc::new(inclusion_list));
+        }
+
+        /// Returns the inclusion list the payload with the given id has to include, if any.
+        pub fn get_for_payload(&self, payload_id: &PayloadId) -> Option<Arc<InclusionList>> {
+            self.inner.lock().payloads.get(payload_id).cloned()
+        }
+    }
+
+    impl core::fmt::Debug for InclusionListStore {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let inner = self.inner.lock();
+            f.debug_struct("InclusionListStore")
+                .field("blocks", &inner.blocks.len())
+                .field("payloads", &inner.payloads.len())
+                .finish()
+        }
+    }
+
+    impl PartialEq for InclusionListStore {
+        fn eq(&self, other: &Self) -> bool {
+            Arc::ptr_eq(&self.inner, &other.inner)
+        }
+    }
+
+    impl Eq for InclusionListStore {}
+
+    impl Default for InclusionListStore {
+        fn default() -> Self {
+            Self::new(Self::DEFAULT_CAPACITY)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{Signature, TxKind};
+    use core::convert::Infallible;
+    use reth_ethereum_primitives::TransactionSigned;
+
+    fn tx(nonce: u64, gas_limit: u64) -> Recovered<TransactionSigned> {
+        let tx = TxEip1559 {
+            nonce,
+            gas_limit,
+            max_fee_per_gas: 10,
+            to: TxKind::Call(Address::ZERO),
+            ..Default::default()
+        };
+        Recovered::new_unchecked(
+            TransactionSigned::new_unhashed(tx.into(), Signature::test_signature()),
+            Address::with_last_byte(1),
+        )
+    }
+
+    #[test]
+    fn checks_transactions_that_could_be_appended() {
+        let account = |_| {
+            Ok::<_, Infallible>(Some(Account {
+                balance: U256::from(1_000_000),
+                ..Default::default()
+            }))
+        };
+        let list = vec![tx(0, 21_000)];
+        let hash = *list[0].tx_hash();
+
+        // included
+        assert_eq!(find_unsatisfied_transaction(&list, [hash], 21_000, Some(1), account), Ok(None));
+        // not enough gas left
+        assert_eq!(find_unsatisfied_transaction(&list, [], 20_999, Some(1), account), Ok(None));
+        // fee cap below base fee
+        assert_eq!(find_unsatisfied_transaction(&list, [], 21_000, Some(11), account), Ok(None));
+        // could have been appended
+        assert_eq!(
+            find_unsatisfied_transaction(&list, [], 21_000, Some(1), account),
+            Ok(Some(hash))
+        );
+
+        // nonce already used
+        let list = vec![tx(1, 21_000)];
+        assert_eq!(
+            find_unsatisfied_transaction(&list, [], 21_000, Some(1), |_| {
+                Ok::<_, Infallible>(Some(Account {
+                    nonce: 2,
+                    balance: U256::MAX,
+                    ..Default::default()
+                }))
+            }),
+            Ok(None)
+        );
+        // insufficient balance
+        assert_eq!(
+            find_unsatisfied_transaction(&list, [], 21_000, Some(1), |_| Ok::<_, Infallible>(None)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn payload_status_serde() {
+        let status = InclusionListPayloadStatus::unsatisfied(B256::with_last_byte(1));
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["status"], "INCLUSION_LIST_UNSATISFIED");
+        assert_eq!(serde_json::from_value::<InclusionListPayloadStatus>(json).unwrap(), status);
+
+        let status: InclusionListPayloadStatus =
+            PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::ZERO)).into();
+        assert_eq!(serde_json::to_value(&status).unwrap()["status"], "VALID");
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
 };
+use alloy_consensus::BlockHeader as _;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -14,7 +15,8 @@ use core::{
 use futures::{future::Either, FutureExt, TryFutureExt};
 use reth_errors::RethResult;
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
+use reth_payload_primitives::{BuiltPayload, EngineApiMessageVersion, PayloadTypes};
+use reth_primitives_traits::{NodePrimitives, SealedBlock};
 use std::time::Duration;
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
@@ -22,6 +24,10 @@ use tokio::sync::{mpsc::UnboundedSender, oneshot};
 #[deprecated(note = "Use ConsensusEngineHandle instead")]
 pub type BeaconConsensusEngineHandle<Payload> = ConsensusEngineHandle<Payload>;
 
+/// The block type of the payloads built for the given [`PayloadTypes`].
+pub type BuiltPayloadBlock<Payload> =
+    <<<Payload as PayloadTypes>::BuiltPayload as BuiltPayload>::Primitives as NodePrimitives>::Block;
+
 /// Represents the outcome of forkchoice update.
 ///
 /// This is a future that resolves to [`ForkChoiceUpdateResult`]
@@ -93,6 +99,12 @@ impl OnForkChoiceUpdated {
         }
     }
 
+    /// Creates a new instance of `OnForkChoiceUpdated` from the already resolved outcome of a
+    /// forkchoice update, e.g. after it has been awaited to be inspected.
+    pub fn resolved(forkchoice_status: ForkchoiceStatus, result: ForkChoiceUpdateResult) -> Self {
+        Self { forkchoice_status, fut: Either::Left(futures::future::ready(result)) }
+    }
+
     /// If the forkchoice update was successful and no payload attributes were provided, this method
     pub const fn updated_with_pending_payload_id(
         payload_status: PayloadStatus,
@@ -178,6 +190,16 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning payload status result and timing breakdown.
         tx: oneshot::Sender<Result<(PayloadStatus, NewPayloadTimings), BeaconOnNewPayloadError>>,
     },
+    /// Message with a new sealed block.
+    ///
+    /// Used by in-process block producers, e.g. proof-of-authority sealers, whose blocks carry
+    /// header fields that can't be represented by [`PayloadTypes::ExecutionData`].
+    NewBlock {
+        /// The sealed block to validate and insert.
+        block: SealedBlock<BuiltPayloadBlock<Payload>>,
+        /// The sender for returning payload status result.
+        tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+    },
     /// Message with updated forkchoice state.
     ForkchoiceUpdated {
         /// The updated forkchoice state.
@@ -212,6 +234,15 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload.block_hash()
                 )
             }
+            Self::NewBlock { block, .. } => {
+                write!(
+                    f,
+                    "NewBlock(parent: {}, number: {}, hash: {})",
+                    block.parent_hash(),
+                    block.number(),
+                    block.hash()
+                )
+            }
             Self::ForkchoiceUpdated { state, payload_attrs, .. } => {
                 // we don't want to print the entire payload attributes, because for OP this
                 // includes all txs
@@ -270,6 +301,19 @@ where
         rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
+    /// Sends a sealed block to the beacon consensus engine and waits for a response.
+    ///
+    /// Unlike [`Self::new_payload`], the block is inserted as is, without a round trip through the
+    /// execution payload format.
+    pub async fn new_block(
+        &self,
+        block: SealedBlock<BuiltPayloadBlock<Payload>>,
+    ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::NewBlock { block, tx });
+        rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
+    }
+
     /// Sends a forkchoice update message to the beacon consensus engine and waits for a response.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_forkchoiceupdatedv2>
@@ -0,0 +1,288 @@
+//! Snapshots of the hottest [`ExecutionCache`](super::cached_state::ExecutionCache) entries that
+//! survive restarts.
+//!
+//! Cached values are only valid for the state of the block the cache was used for, so a snapshot
+//! records the hash of that block and is only loaded while the block is still the persisted tip.
+//!
+//! The snapshot is a flat binary file:
+//!
+//! ```text
+//! magic (8) | version (1) | block hash (32) | #accounts (8) | #slots (8) | #bytecodes (8)
+//! accounts | slots | bytecodes | keccak256 of all previous bytes (32)
+//! ```
+
+use super::cached_state::HotEntries;
+use alloy_primitives::{keccak256, Address, Bytes, StorageKey, StorageValue, B256, U256};
+use reth_primitives_traits::{Account, Bytecode};
+use std::{fs, io, path::Path};
+
+/// Identifies execution cache snapshot files.
+const MAGIC: [u8; 8] = *b"RETHECS\0";
+
+/// Version of the snapshot format.
+const VERSION: u8 = 1;
+
+/// Length of the header, up to and including the entry counts.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 32 + 3 * 8;
+
+/// Writes a snapshot of `entries`, which are valid for the state of `block_hash`, to `path`.
+///
+/// The snapshot is written to a temporary file first and then moved into place, so an interrupted
+/// write never leaves a partial snapshot behind.
+pub(crate) fn write(path: &Path, block_hash: B256, entries: &HotEntries) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(
+        HEADER_LEN +
+            entries.accounts.len() * 94 +
+            entries.storage.len() * 84 +
+            entries.bytecodes.iter().map(|(_, code)| 37 + code_len(code)).sum::<usize>() +
+            32,
+    );
+    buf.extend_from_slice(&MAGIC);
+    buf.push(VERSION);
+    buf.extend_from_slice(block_hash.as_slice());
+    buf.extend_from_slice(&(entries.accounts.len() as u64).to_be_bytes());
+    buf.extend_from_slice(&(entries.storage.len() as u64).to_be_bytes());
+    buf.extend_from_slice(&(entries.bytecodes.len() as u64).to_be_bytes());
+
+    for (address, account) in &entries.accounts {
+        buf.extend_from_slice(address.as_slice());
+        match account {
+            Some(account) => {
+                buf.push(1);
+                buf.extend_from_slice(&account.nonce.to_be_bytes());
+                buf.extend_from_slice(&account.balance.to_be_bytes::<32>());
+                match account.bytecode_hash {
+                    Some(hash) => {
+                        buf.push(1);
+                        buf.extend_from_slice(hash.as_slice());
+                    }
+                    None => buf.push(0),
+                }
+            }
+            None => buf.push(0),
+        }
+    }
+
+    for (address, key, value) in &entries.storage {
+        buf.extend_from_slice(address.as_slice());
+        buf.extend_from_slice(key.as_slice());
+        buf.extend_from_slice(&value.to_be_bytes::<32>());
+    }
+
+    for (hash, code) in &entries.bytecodes {
+        buf.extend_from_slice(hash.as_slice());
+        match code {
+            Some(code) => {
+                let bytes = code.original_byte_slice();
+                buf.push(1);
+                buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+                buf.extend_from_slice(bytes);
+            }
+            None => buf.push(0),
+        }
+    }
+
+    let checksum = keccak256(&buf);
+    buf.extend_from_slice(checksum.as_slice());
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, &buf)?;
+    fs::rename(&tmp, path)
+}
+
+/// Reads the snapshot at `path`.
+///
+/// Returns `None` if there is no snapshot, or if it wasn't taken at `block_hash` and its entries
+/// may therefore be stale.
+pub(crate) fn read(path: &Path, block_hash: B256) -> io::Result<Option<HotEntries>> {
+    let buf = match fs::read(path) {
+        Ok(buf) => buf,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    if buf.len() < HEADER_LEN + 32 {
+        return Err(invalid_data("snapshot is truncated"))
+    }
+    let (body, checksum) = buf.split_at(buf.len() - 32);
+    if keccak256(body).as_slice() != checksum {
+        return Err(invalid_data("snapshot checksum mismatch"))
+    }
+
+    let mut reader = Reader(body);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(invalid_data("not an execution cache snapshot"))
+    }
+    let version = reader.u8()?;
+    if version != VERSION {
+        return Err(invalid_data(format!("unsupported snapshot version {version}")))
+    }
+    if reader.b256()? != block_hash {
+        return Ok(None)
+    }
+
+    let num_accounts = reader.u64()? as usize;
+    let num_slots = reader.u64()? as usize;
+    let num_bytecodes = reader.u64()? as usize;
+
+    let mut entries = HotEntries::default();
+    for _ in 0..num_accounts {
+        let address = reader.address()?;
+        let account = reader
+            .flag()?
+            .then(|| -> io::Result<_> {
+                let nonce = reader.u64()?;
+                let balance = U256::from_be_bytes(reader.b256()?.0);
+                let bytecode_hash = reader.flag()?.then(|| reader.b256()).transpose()?;
+                Ok(Account { nonce, balance, bytecode_hash })
+            })
+            .transpose()?;
+        entries.accounts.push((address, account));
+    }
+
+    for _ in 0..num_slots {
+        let address = reader.address()?;
+        let key = StorageKey::from(reader.b256()?);
+        let value = StorageValue::from_be_bytes(reader.b256()?.0);
+        entries.storage.push((address, key, value));
+    }
+
+    for _ in 0..num_bytecodes {
+        let hash = reader.b256()?;
+        let code = reader
+            .flag()?
+            .then(|| -> io::Result<_> {
+                let len = reader.u32()? as usize;
+                let bytes = Bytes::copy_from_slice(reader.take(len)?);
+                Bytecode::new_raw_checked(bytes).map_err(|err| invalid_data(err.to_string()))
+            })
+            .transpose()?;
+        entries.bytecodes.push((hash, code));
+    }
+
+    if !reader.0.is_empty() {
+        return Err(invalid_data("trailing bytes in snapshot"))
+    }
+
+    Ok(Some(entries))
+}
+
+/// Returns the number of bytes the bytecode takes up in the snapshot.
+fn code_len(code: &Option<Bytecode>) -> usize {
+    code.as_ref().map_or(0, |code| code.original_byte_slice().len())
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Reads the fields of a snapshot.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(invalid_data("snapshot is truncated"))
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn flag(&mut self) -> io::Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            flag => Err(invalid_data(format!("invalid flag {flag}"))),
+        }
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes")))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().expect("8 bytes")))
+    }
+
+    fn address(&mut self) -> io::Result<Address> {
+        Ok(Address::from_slice(self.take(20)?))
+    }
+
+    fn b256(&mut self) -> io::Result<B256> {
+        Ok(B256::from_slice(self.take(32)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> HotEntries {
+        HotEntries {
+            accounts: vec![
+                (
+                    Address::random(),
+                    Some(Account {
+                        nonce: 7,
+                        balance: U256::from(1_000),
+                        bytecode_hash: Some(B256::random()),
+                    }),
+                ),
+                (Address::random(), Some(Account::default())),
+                (Address::random(), None),
+            ],
+            storage: vec![
+                (Address::random(), StorageKey::random(), StorageValue::from(42)),
+                (Address::random(), StorageKey::random(), StorageValue::ZERO),
+            ],
+            bytecodes: vec![
+                (B256::random(), Some(Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0xf3])))),
+                (B256::random(), None),
+            ],
+        }
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution_cache.snapshot");
+        let block_hash = B256::random();
+        let entries = entries();
+
+        write(&path, block_hash, &entries).unwrap();
+        assert_eq!(read(&path, block_hash).unwrap(), Some(entries));
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn stale_or_missing_snapshot_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution_cache.snapshot");
+        assert_eq!(read(&path, B256::random()).unwrap(), None);
+
+        write(&path, B256::random(), &entries()).unwrap();
+        assert_eq!(read(&path, B256::random()).unwrap(), None);
+    }
+
+    #[test]
+    fn corrupted_snapshot_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution_cache.snapshot");
+        let block_hash = B256::random();
+        write(&path, block_hash, &entries()).unwrap();
+
+        let mut buf = fs::read(&path).unwrap();
+        buf[HEADER_LEN + 10] ^= 1;
+        fs::write(&path, &buf).unwrap();
+        assert_eq!(read(&path, block_hash).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        buf.truncate(HEADER_LEN);
+        fs::write(&path, &buf).unwrap();
+        assert_eq!(read(&path, block_hash).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -5,7 +5,7 @@ use alloy_primitives::{
 };
 use fixed_cache::{AnyRef, CacheConfig, Stats, StatsHandler};
 use metrics::{Counter, Gauge, Histogram};
-use parking_lot::Once;
+use parking_lot::{Mutex, Once};
 use reth_errors::ProviderResult;
 use reth_metrics::Metrics;
 use reth_primitives_traits::{Account, Bytecode};
@@ -19,7 +19,9 @@ use reth_trie::{
     MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 use revm_primitives::eip7907::MAX_CODE_SIZE;
+use schnellru::{ByLength, LruMap};
 use std::{
+    hash::Hash,
     mem::size_of,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -300,6 +302,118 @@ impl<K: PartialEq, V> StatsHandler<K, V> for CacheStatsHandler {
     }
 }
 
+/// Limits on the number of hot keys tracked per cache of an [`ExecutionCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotKeyLimits {
+    /// Maximum number of tracked accounts.
+    pub accounts: u32,
+    /// Maximum number of tracked storage slots.
+    pub storage: u32,
+    /// Maximum number of tracked bytecodes.
+    pub bytecodes: u32,
+}
+
+impl Default for HotKeyLimits {
+    fn default() -> Self {
+        Self { accounts: 100_000, storage: 500_000, bytecodes: 10_000 }
+    }
+}
+
+/// Tracks the most recently used keys of a cache.
+///
+/// [`FixedCache`] can't be iterated, so this keeps a bounded LRU of the keys that were hit or
+/// inserted, which is used to find the hottest entries of the cache.
+#[derive(Debug)]
+struct HotKeys<K: Hash + PartialEq> {
+    keys: Mutex<LruMap<K, (), ByLength>>,
+}
+
+impl<K: Hash + PartialEq + Clone> HotKeys<K> {
+    /// Creates a tracker for at most `limit` keys.
+    fn new(limit: u32) -> Self {
+        Self { keys: Mutex::new(LruMap::new(ByLength::new(limit))) }
+    }
+
+    /// Marks the key as most recently used.
+    ///
+    /// Tracking is best effort: the update is skipped if another thread holds the tracker, so
+    /// cache reads never block on it.
+    fn touch(&self, key: &K) {
+        if let Some(mut keys) = self.keys.try_lock() {
+            keys.insert(key.clone(), ());
+        }
+    }
+
+    /// Returns the tracked keys, most recently used first.
+    fn keys(&self) -> Vec<K> {
+        self.keys.lock().iter().map(|(key, _)| key.clone()).collect()
+    }
+}
+
+/// A [`StatsHandler`] that records hit and inserted keys in [`HotKeys`] and forwards all events to
+/// a [`CacheStatsHandler`].
+#[derive(Debug)]
+struct HotKeysStatsHandler<K: Hash + PartialEq> {
+    stats: Arc<CacheStatsHandler>,
+    hot_keys: Arc<HotKeys<K>>,
+}
+
+impl<K: Hash + PartialEq + Clone + Send + Sync, V> StatsHandler<K, V> for HotKeysStatsHandler<K> {
+    fn on_hit(&self, key: &K, value: &V) {
+        self.hot_keys.touch(key);
+        StatsHandler::<K, V>::on_hit(&*self.stats, key, value);
+    }
+
+    fn on_miss(&self, key: AnyRef<'_>) {
+        StatsHandler::<K, V>::on_miss(&*self.stats, key);
+    }
+
+    fn on_insert(&self, key: &K, value: &V, evicted: Option<(&K, &V)>) {
+        self.hot_keys.touch(key);
+        self.stats.on_insert(key, value, evicted);
+    }
+
+    fn on_remove(&self, key: &K, value: &V) {
+        self.stats.on_remove(key, value);
+    }
+}
+
+/// Creates the [`Stats`] of a cache, recording its hot keys if they are tracked.
+fn cache_stats<K, V>(
+    stats: &Arc<CacheStatsHandler>,
+    hot_keys: Option<&Arc<HotKeys<K>>>,
+) -> Stats<K, V>
+where
+    K: Hash + PartialEq + Clone + Send + Sync + 'static,
+    V: 'static,
+{
+    match hot_keys {
+        Some(hot_keys) => {
+            Stats::new(HotKeysStatsHandler { stats: stats.clone(), hot_keys: hot_keys.clone() })
+        }
+        None => Stats::new(stats.clone()),
+    }
+}
+
+/// The hot key trackers of an [`ExecutionCache`].
+#[derive(Debug)]
+struct ExecutionCacheHotKeys {
+    code: Arc<HotKeys<B256>>,
+    storage: Arc<HotKeys<(Address, StorageKey)>>,
+    accounts: Arc<HotKeys<Address>>,
+}
+
+/// The hottest entries of an [`ExecutionCache`], most recently used first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct HotEntries {
+    /// Cached accounts.
+    pub(crate) accounts: Vec<(Address, Option<Account>)>,
+    /// Cached storage slots.
+    pub(crate) storage: Vec<(Address, StorageKey, StorageValue)>,
+    /// Cached bytecodes, keyed by code hash.
+    pub(crate) bytecodes: Vec<(B256, Option<Bytecode>)>,
+}
+
 impl<S: AccountReader, const PREWARM: bool> AccountReader for CachedStateProvider<S, PREWARM> {
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         if PREWARM {
@@ -512,6 +626,9 @@ struct ExecutionCacheInner {
     /// Stats handler for the account cache (shared with the cache via [`Stats`]).
     account_stats: Arc<CacheStatsHandler>,
 
+    /// Trackers of the hottest keys, if enabled.
+    hot_keys: Option<ExecutionCacheHotKeys>,
+
     /// One-time notification when SELFDESTRUCT is encountered
     selfdestruct_encountered: Once,
 }
@@ -539,6 +656,16 @@ impl ExecutionCache {
 
     /// Build an [`ExecutionCache`] struct, so that execution caches can be easily cloned.
     pub fn new(total_cache_size: usize) -> Self {
+        Self::build(total_cache_size, None)
+    }
+
+    /// Build an [`ExecutionCache`] that tracks its hottest keys, so that its hottest entries can
+    /// be snapshotted.
+    pub fn with_hot_keys(total_cache_size: usize, limits: HotKeyLimits) -> Self {
+        Self::build(total_cache_size, Some(limits))
+    }
+
+    fn build(total_cache_size: usize, hot_key_limits: Option<HotKeyLimits>) -> Self {
         let code_cache_size = (total_cache_size * 556) / 10000; // 5.56% of total
         let storage_cache_size = (total_cache_size * 8888) / 10000; // 88.88% of total
         let account_cache_size = (total_cache_size * 556) / 10000; // 5.56% of total
@@ -551,16 +678,29 @@ impl ExecutionCache {
         let storage_stats = Arc::new(CacheStatsHandler::new(storage_capacity));
         let account_stats = Arc::new(CacheStatsHandler::new(account_capacity));
 
+        let hot_keys = hot_key_limits.map(|limits| ExecutionCacheHotKeys {
+            code: Arc::new(HotKeys::new(limits.bytecodes)),
+            storage: Arc::new(HotKeys::new(limits.storage)),
+            accounts: Arc::new(HotKeys::new(limits.accounts)),
+        });
+
         Self(Arc::new(ExecutionCacheInner {
             code_cache: FixedCache::new(code_capacity, FbBuildHasher::<32>::default())
-                .with_stats(Some(Stats::new(code_stats.clone()))),
+                .with_stats(Some(cache_stats(&code_stats, hot_keys.as_ref().map(|k| &k.code)))),
             storage_cache: FixedCache::new(storage_capacity, DefaultHashBuilder::default())
-                .with_stats(Some(Stats::new(storage_stats.clone()))),
+                .with_stats(Some(cache_stats(
+                    &storage_stats,
+                    hot_keys.as_ref().map(|k| &k.storage),
+                ))),
             account_cache: FixedCache::new(account_capacity, FbBuildHasher::<20>::default())
-                .with_stats(Some(Stats::new(account_stats.clone()))),
+                .with_stats(Some(cache_stats(
+                    &account_stats,
+                    hot_keys.as_ref().map(|k| &k.accounts),
+                ))),
             code_stats,
             storage_stats,
             account_stats,
+            hot_keys,
             selfdestruct_encountered: Once::new(),
         }))
     }
@@ -731,6 +871,52 @@ impl ExecutionCache {
         Ok(())
     }
 
+    /// Returns the hottest entries that are still cached, or `None` if hot keys aren't tracked.
+    pub(crate) fn hot_entries(&self) -> Option<HotEntries> {
+        let hot_keys = self.0.hot_keys.as_ref()?;
+        Some(HotEntries {
+            accounts: hot_keys
+                .accounts
+                .keys()
+                .into_iter()
+                .filter_map(|address| {
+                    self.0.account_cache.get(&address).map(|account| (address, account))
+                })
+                .collect(),
+            storage: hot_keys
+                .storage
+                .keys()
+                .into_iter()
+                .filter_map(|(address, key)| {
+                    self.0.storage_cache.get(&(address, key)).map(|value| (address, key, value))
+                })
+                .collect(),
+            bytecodes: hot_keys
+                .code
+                .keys()
+                .into_iter()
+                .filter_map(|hash| self.0.code_cache.get(&hash).map(|code| (hash, code)))
+                .collect(),
+        })
+    }
+
+    /// Inserts the given entries into the cache.
+    ///
+    /// Entries are inserted in reverse, so that the hottest entries are also the most recently
+    /// used ones if hot keys are tracked.
+    pub(crate) fn insert_hot_entries(&self, entries: HotEntries) {
+        let HotEntries { accounts, storage, bytecodes } = entries;
+        for (hash, code) in bytecodes.into_iter().rev() {
+            self.insert_code(hash, code);
+        }
+        for (address, key, value) in storage.into_iter().rev() {
+            self.insert_storage(address, key, Some(value));
+        }
+        for (address, account) in accounts.into_iter().rev() {
+            self.insert_account(address, account);
+        }
+    }
+
     /// Clears storage and account caches, resetting them to empty state.
     ///
     /// We do not clear the bytecodes cache, because its mapping can never change, as it's
@@ -1085,4 +1271,43 @@ mod tests {
         assert!(caches.0.account_cache.get(&addr1).is_none());
         assert!(caches.0.account_cache.get(&addr2).is_some());
     }
+
+    #[test]
+    fn test_hot_entries() {
+        assert!(ExecutionCache::new(1000).hot_entries().is_none());
+
+        let limits = HotKeyLimits { accounts: 2, storage: 2, bytecodes: 2 };
+        let caches = ExecutionCache::with_hot_keys(1000, limits);
+        let (addr1, addr2, addr3) = (Address::random(), Address::random(), Address::random());
+        let account = Some(Account { nonce: 1, ..Default::default() });
+        caches.insert_account(addr1, account);
+        caches.insert_account(addr2, None);
+        caches.insert_account(addr3, account);
+        // a hit makes addr2 the hottest account, addr1 is no longer tracked
+        let _ = caches.get_or_try_insert_account_with(addr2, || Ok::<_, ()>(account));
+
+        let slot = StorageKey::random();
+        caches.insert_storage(addr1, slot, Some(U256::from(42)));
+        let code_hash = B256::random();
+        caches.insert_code(code_hash, Some(Bytecode::new_raw([0x00].into())));
+
+        let entries = caches.hot_entries().unwrap();
+        assert_eq!(
+            entries,
+            HotEntries {
+                accounts: vec![(addr2, None), (addr3, account)],
+                storage: vec![(addr1, slot, U256::from(42))],
+                bytecodes: vec![(code_hash, Some(Bytecode::new_raw([0x00].into())))],
+            }
+        );
+
+        // evicted entries are skipped
+        caches.0.account_cache.remove(&addr3);
+        assert_eq!(caches.hot_entries().unwrap().accounts, vec![(addr2, None)]);
+
+        // reinserted entries keep their order
+        let warmed = ExecutionCache::with_hot_keys(1000, limits);
+        warmed.insert_hot_entries(entries.clone());
+        assert_eq!(warmed.hot_entries(), Some(entries));
+    }
 }
@@ -2,7 +2,10 @@ use crate::tree::{error::InsertBlockFatalError, TreeOutcome};
 use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
 use reth_engine_primitives::{ForkchoiceStatus, OnForkChoiceUpdated};
 use reth_errors::ProviderError;
-use reth_evm::metrics::ExecutorMetrics;
+use reth_evm::{
+    metrics::ExecutorMetrics,
+    profiler::{ExecutionProfile, ExecutionStats},
+};
 use reth_execution_types::BlockExecutionOutput;
 use reth_metrics::{
     metrics::{Counter, Gauge, Histogram},
@@ -10,7 +13,7 @@ use reth_metrics::{
 };
 use reth_primitives_traits::{constants::gas_units::MEGAGAS, FastInstant as Instant};
 use reth_trie::updates::TrieUpdates;
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 /// Upper bounds for each gas bucket. The last bucket is a catch-all for
 /// everything above the final threshold: <5M, 5-10M, 10-20M, 20-30M, 30-40M, >40M.
@@ -349,6 +352,76 @@ impl Default for ExecutionGasBucketMetrics {
     }
 }
 
+/// Metrics of a contract, selector or precompile in the execution profile.
+#[derive(Clone, Metrics)]
+#[metrics(scope = "sync.execution.profile")]
+pub(crate) struct ExecutionProfileEntryMetrics {
+    /// Number of calls since the profiler was started.
+    pub(crate) calls: Gauge,
+    /// Gas spent since the profiler was started.
+    pub(crate) gas_used: Gauge,
+    /// Wall time spent since the profiler was started, in seconds.
+    pub(crate) time_seconds: Gauge,
+}
+
+impl ExecutionProfileEntryMetrics {
+    fn set(&self, stats: &ExecutionStats) {
+        self.calls.set(stats.calls as f64);
+        self.gas_used.set(stats.gas_used as f64);
+        self.time_seconds.set(stats.time.as_secs_f64());
+    }
+}
+
+/// Exports the top entries of the [`ExecutionProfile`], labeled by `kind` and `key`.
+///
+/// Entries that drop out of the top are reset to zero.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionProfileMetrics {
+    entries: HashMap<(&'static str, String), ExecutionProfileEntryMetrics>,
+}
+
+impl ExecutionProfileMetrics {
+    /// Records the entries of the profile.
+    pub(crate) fn record(&mut self, profile: &ExecutionProfile) {
+        let entries = profile
+            .contracts
+            .iter()
+            .map(|(address, stats)| (("contract", address.to_string()), stats))
+            .chain(
+                profile
+                    .selectors
+                    .iter()
+                    .map(|(selector, stats)| (("selector", selector.to_string()), stats)),
+            )
+            .chain(
+                profile
+                    .precompiles
+                    .iter()
+                    .map(|(address, stats)| (("precompile", address.to_string()), stats)),
+            )
+            .collect::<HashMap<_, _>>();
+
+        self.entries.retain(|key, metrics| {
+            let retained = entries.contains_key(key);
+            if !retained {
+                metrics.set(&ExecutionStats::default());
+            }
+            retained
+        });
+        for ((kind, key), stats) in entries {
+            self.entries
+                .entry((kind, key))
+                .or_insert_with_key(|(kind, key)| {
+                    ExecutionProfileEntryMetrics::new_with_labels(&[
+                        ("kind", kind.to_string()),
+                        ("key", key.clone()),
+                    ])
+                })
+                .set(stats);
+        }
+    }
+}
+
 /// Per-gas-bucket block validation metrics (state root).
 #[derive(Clone, Metrics)]
 #[metrics(scope = "sync.block_validation")]
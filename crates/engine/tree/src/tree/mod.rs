@@ -52,6 +52,7 @@ use tokio::sync::{
 use tracing::*;
 
 mod block_buffer;
+mod cache_snapshot;
 mod cached_state;
 pub mod error;
 pub mod instrumented_state;
@@ -67,7 +68,9 @@ mod trie_updates;
 
 use crate::tree::error::AdvancePersistenceError;
 pub use block_buffer::BlockBuffer;
-pub use cached_state::{CachedStateMetrics, CachedStateProvider, ExecutionCache, SavedCache};
+pub use cached_state::{
+    CachedStateMetrics, CachedStateProvider, ExecutionCache, HotKeyLimits, SavedCache,
+};
 pub use invalid_headers::InvalidHeaderCache;
 pub use metrics::EngineApiMetrics;
 pub use payload_processor::*;
@@ -638,6 +641,73 @@ where
         Ok(outcome)
     }
 
+    /// Invoked when we receive a new sealed block from an in-process block producer.
+    ///
+    /// This mirrors [`Self::on_new_payload`] for blocks that can't be represented as an execution
+    /// payload, e.g. proof-of-authority blocks that carry a seal in the header.
+    #[instrument(
+        level = "debug",
+        target = "engine::tree",
+        skip_all,
+        fields(block_hash = %block.hash(), block_num = %block.number()),
+    )]
+    fn on_new_block(
+        &mut self,
+        block: SealedBlock<N::Block>,
+    ) -> Result<TreeOutcome<PayloadStatus>, InsertBlockFatalError> {
+        trace!(target: "engine::tree", "invoked new block");
+
+        let num_hash = block.num_hash();
+        let parent_hash = block.parent_hash();
+        let engine_event = ConsensusEngineEvent::BlockReceived(num_hash);
+        self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
+
+        let lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(parent_hash);
+        if let Some(status) =
+            self.check_invalid_ancestor_with_head(lowest_buffered_ancestor, &block)?
+        {
+            return Ok(TreeOutcome::new(status))
+        }
+
+        if !self.backfill_sync_state.is_idle() {
+            let status = match self.buffer_block(block) {
+                Ok(()) => PayloadStatus::from_status(PayloadStatusEnum::Syncing),
+                Err(error) => self.on_insert_block_error(error)?,
+            };
+            return Ok(TreeOutcome::new(status))
+        }
+
+        let status = match self.insert_block(block) {
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Valid)) => {
+                self.try_connect_buffered_blocks(num_hash)?;
+                PayloadStatus::new(PayloadStatusEnum::Valid, Some(num_hash.hash))
+            }
+            Ok(InsertPayloadOk::AlreadySeen(BlockStatus::Valid)) => {
+                PayloadStatus::new(PayloadStatusEnum::Valid, Some(num_hash.hash))
+            }
+            Ok(
+                InsertPayloadOk::Inserted(BlockStatus::Disconnected { .. }) |
+                InsertPayloadOk::AlreadySeen(BlockStatus::Disconnected { .. }),
+            ) => PayloadStatus::from_status(PayloadStatusEnum::Syncing),
+            Err(InsertPayloadError::Block(error)) => self.on_insert_block_error(error)?,
+            Err(InsertPayloadError::Payload(error)) => {
+                self.on_new_payload_error(error, num_hash, parent_hash)?
+            }
+        };
+
+        let mut outcome = TreeOutcome::new(status);
+        if outcome.outcome.is_valid() &&
+            self.is_sync_target_head(num_hash.hash) &&
+            self.state.tree_state.canonical_block_hash() != num_hash.hash
+        {
+            outcome = outcome.with_event(TreeEvent::TreeAction(TreeAction::MakeCanonical {
+                sync_target_head: num_hash.hash,
+            }));
+        }
+
+        Ok(outcome)
+    }
+
     /// Processes a payload during normal sync operation.
     ///
     /// Returns:
@@ -1311,6 +1381,9 @@ where
     ) -> Result<(), AdvancePersistenceError> {
         trace!(target: "engine::tree", "finishing termination, persisting remaining blocks");
         let result = self.persist_until_complete();
+        if result.is_ok() {
+            self.payload_validator.on_shutdown(self.persistence_state.last_persisted_block);
+        }
         let _ = pending_termination.send(());
         result
     }
@@ -1641,6 +1714,24 @@ where
                                         .increment(1);
                                 }
 
+                                self.on_maybe_tree_event(maybe_event)?;
+                            }
+                            BeaconEngineMessage::NewBlock { block, tx } => {
+                                let start = Instant::now();
+                                let num_hash = block.num_hash();
+                                let mut output = self.on_new_block(block);
+
+                                let maybe_event =
+                                    output.as_mut().ok().and_then(|out| out.event.take());
+
+                                if let Err(err) =
+                                    tx.send(output.map(|o| o.outcome).map_err(|e| {
+                                        BeaconOnNewPayloadError::Internal(Box::new(e))
+                                    }))
+                                {
+                                    warn!(target: "engine::tree", block=?num_hash, elapsed=?start.elapsed(), "Failed to deliver new block response, receiver dropped (request cancelled): {err:?}");
+                                }
+
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
                         }
@@ -2,7 +2,8 @@
 
 use super::precompile_cache::PrecompileCacheMap;
 use crate::tree::{
-    cached_state::{CachedStateMetrics, ExecutionCache, SavedCache},
+    cache_snapshot,
+    cached_state::{CachedStateMetrics, ExecutionCache, HotKeyLimits, SavedCache},
     payload_processor::{
         prewarm::{PrewarmCacheTask, PrewarmContext, PrewarmMode, PrewarmTaskEvent},
         sparse_trie::StateRootComputeOutcome,
@@ -44,6 +45,7 @@ use reth_trie_sparse::{
 };
 use std::{
     ops::Not,
+    path::Path,
     sync::{
         atomic::AtomicBool,
         mpsc::{self, channel},
@@ -51,7 +53,7 @@ use std::{
     },
     time::Duration,
 };
-use tracing::{debug, debug_span, instrument, warn, Span};
+use tracing::{debug, debug_span, info, instrument, warn, Span};
 
 pub mod bal;
 pub mod multiproof;
@@ -118,6 +120,8 @@ where
     trie_metrics: MultiProofTaskMetrics,
     /// Cross-block cache size in bytes.
     cross_block_cache_size: usize,
+    /// Limits of the hot keys tracked by the execution cache, if it is snapshotted.
+    hot_key_limits: Option<HotKeyLimits>,
     /// Whether transactions should not be executed on prewarming task.
     disable_transaction_prewarming: bool,
     /// Whether state cache should be disable
@@ -164,6 +168,7 @@ where
             execution_cache: Default::default(),
             trie_metrics: Default::default(),
             cross_block_cache_size: config.cross_block_cache_size(),
+            hot_key_limits: None,
             disable_transaction_prewarming: config.disable_prewarming(),
             evm_config,
             disable_state_cache: config.disable_state_cache(),
@@ -534,7 +539,7 @@ where
         } else {
             debug!("creating new execution cache on cache miss");
             let start = Instant::now();
-            let cache = ExecutionCache::new(self.cross_block_cache_size);
+            let cache = self.new_execution_cache();
             let metrics = CachedStateMetrics::zeroed();
             metrics.record_cache_creation(start.elapsed());
             SavedCache::new(parent_hash, cache, metrics)
@@ -542,6 +547,92 @@ where
         }
     }
 
+    /// Creates an empty execution cache, tracking its hot keys if it is snapshotted.
+    fn new_execution_cache(&self) -> ExecutionCache {
+        match self.hot_key_limits {
+            Some(limits) => ExecutionCache::with_hot_keys(self.cross_block_cache_size, limits),
+            None => ExecutionCache::new(self.cross_block_cache_size),
+        }
+    }
+
+    /// Enables tracking of the hottest execution cache entries, so that they can be snapshotted
+    /// with [`Self::save_execution_cache_snapshot`].
+    pub const fn track_hot_keys(&mut self, limits: HotKeyLimits) {
+        self.hot_key_limits = Some(limits);
+    }
+
+    /// Writes the hottest entries of the execution cache to the snapshot at `path`.
+    ///
+    /// The snapshot is only written if the cache holds the state of `persisted_tip`, which it is
+    /// validated against when it's loaded again.
+    pub fn save_execution_cache_snapshot(&self, path: &Path, persisted_tip: B256) {
+        let Some(cache) = self.execution_cache.inner.read().clone() else {
+            debug!(target: "engine::caching", "No execution cache to snapshot");
+            return
+        };
+        if cache.executed_block_hash() != persisted_tip {
+            debug!(
+                target: "engine::caching",
+                cached_hash = %cache.executed_block_hash(),
+                %persisted_tip,
+                "Execution cache is not at the persisted tip, skipping snapshot"
+            );
+            return
+        }
+        let Some(entries) = cache.cache().hot_entries() else { return };
+
+        let start = Instant::now();
+        match cache_snapshot::write(path, persisted_tip, &entries) {
+            Ok(()) => info!(
+                target: "engine::caching",
+                ?path,
+                accounts = entries.accounts.len(),
+                storage = entries.storage.len(),
+                bytecodes = entries.bytecodes.len(),
+                elapsed = ?start.elapsed(),
+                "Wrote execution cache snapshot"
+            ),
+            Err(err) => {
+                warn!(target: "engine::caching", %err, ?path, "Failed to write execution cache snapshot")
+            }
+        }
+    }
+
+    /// Warms the execution cache with the snapshot at `path`, if it was taken at `persisted_tip`.
+    pub fn load_execution_cache_snapshot(&self, path: &Path, persisted_tip: B256) {
+        let start = Instant::now();
+        let entries = match cache_snapshot::read(path, persisted_tip) {
+            Ok(Some(entries)) => entries,
+            Ok(None) => {
+                debug!(target: "engine::caching", ?path, %persisted_tip, "No execution cache snapshot for the persisted tip");
+                return
+            }
+            Err(err) => {
+                warn!(target: "engine::caching", %err, ?path, "Failed to read execution cache snapshot");
+                return
+            }
+        };
+
+        let (accounts, storage, bytecodes) =
+            (entries.accounts.len(), entries.storage.len(), entries.bytecodes.len());
+        let cache = self.new_execution_cache();
+        cache.insert_hot_entries(entries);
+        let cache = SavedCache::new(persisted_tip, cache, CachedStateMetrics::zeroed())
+            .with_disable_cache_metrics(self.disable_cache_metrics);
+        cache.update_metrics();
+        self.execution_cache.update_with_guard(|cached| *cached = Some(cache));
+
+        info!(
+            target: "engine::caching",
+            ?path,
+            accounts,
+            storage,
+            bytecodes,
+            elapsed = ?start.elapsed(),
+            "Loaded execution cache snapshot"
+        );
+    }
+
     /// Spawns the [`SparseTrieCacheTask`] for this payload processor.
     ///
     /// The trie is preserved when the new payload is a child of the previous one.
@@ -695,7 +786,7 @@ where
             let (caches, cache_metrics, _) = match cached.take() {
                 Some(existing) => existing.split(),
                 None => (
-                    ExecutionCache::new(self.cross_block_cache_size),
+                    self.new_execution_cache(),
                     CachedStateMetrics::zeroed(),
                     false,
                 ),
@@ -1,9 +1,10 @@
 //! Types and traits for validating blocks and payloads.
 
 use crate::tree::{
-    cached_state::CachedStateProvider,
+    cached_state::{CachedStateProvider, HotKeyLimits},
     error::{InsertBlockError, InsertBlockErrorKind, InsertPayloadError},
     instrumented_state::InstrumentedStateProvider,
+    metrics::ExecutionProfileMetrics,
     payload_processor::PayloadProcessor,
     precompile_cache::{CachedPrecompile, CachedPrecompileMetrics, PrecompileCacheMap},
     sparse_trie::StateRootComputeOutcome,
@@ -22,12 +23,15 @@ use crate::tree::payload_processor::receipt_root_task::{IndexedReceipt, ReceiptR
 use reth_chain_state::{CanonicalInMemoryState, DeferredTrieData, ExecutedBlock, LazyOverlay};
 use reth_consensus::{ConsensusError, FullConsensus, ReceiptRootBloom};
 use reth_engine_primitives::{
+    inclusion_list::{decode_inclusion_list, find_unsatisfied_transaction, InclusionListStore},
     ConfigureEngineEvm, ExecutableTxIterator, ExecutionPayload, InvalidBlockHook, PayloadValidator,
 };
 use reth_errors::{BlockExecutionError, ProviderResult};
 use reth_evm::{
-    block::BlockExecutor, execute::ExecutableTxFor, ConfigureEvm, EvmEnvFor, ExecutionCtxFor,
-    SpecFor,
+    block::{BlockExecutor, BlockExecutorFor},
+    execute::ExecutableTxFor,
+    profiler::ExecutionProfiler,
+    ConfigureEvm, Database, EvmEnvFor, ExecutionCtxFor, InspectorFor, SpecFor,
 };
 use reth_payload_primitives::{
     BuiltPayload, InvalidPayloadAttributesError, NewPayloadError, PayloadTypes,
@@ -37,10 +41,10 @@ use reth_primitives_traits::{
     RecoveredBlock, SealedBlock, SealedHeader, SignerRecoverable,
 };
 use reth_provider::{
-    providers::OverlayStateProviderFactory, BlockExecutionOutput, BlockNumReader, BlockReader,
-    ChangeSetReader, DatabaseProviderFactory, DatabaseProviderROFactory, HashedPostStateProvider,
-    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProvider,
-    StateProviderFactory, StateReader, StorageChangeSetReader, StorageSettingsCache,
+    providers::OverlayStateProviderFactory, BlockExecutionOutput, BlockHashReader, BlockNumReader,
+    BlockReader, ChangeSetReader, DatabaseProviderFactory, DatabaseProviderROFactory,
+    HashedPostStateProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
+    StateProvider, StateProviderFactory, StateReader, StorageChangeSetReader, StorageSettingsCache,
 };
 use reth_revm::db::{states::bundle_state::BundleRetention, State};
 use reth_trie::{updates::TrieUpdates, HashedPostState, StateRoot};
@@ -50,6 +54,7 @@ use revm_primitives::Address;
 use std::{
     collections::HashMap,
     panic::{self, AssertUnwindSafe},
+    path::PathBuf,
     sync::{mpsc::RecvTimeoutError, Arc},
 };
 use tracing::{debug, debug_span, error, info, instrument, trace, warn, Span};
@@ -139,6 +144,14 @@ where
     changeset_cache: ChangesetCache,
     /// Task runtime for spawning parallel work.
     runtime: reth_tasks::Runtime,
+    /// Inclusion lists that new payloads have to satisfy.
+    inclusion_lists: InclusionListStore,
+    /// Profiler of the executed contracts and precompiles, if enabled.
+    execution_profiler: Option<ExecutionProfiler>,
+    /// Metrics of the top entries of the execution profile.
+    execution_profile_metrics: ExecutionProfileMetrics,
+    /// Path of the execution cache snapshot, if enabled.
+    execution_cache_snapshot: Option<PathBuf>,
 }
 
 impl<N, P, Evm, V> BasicEngineValidator<P, Evm, V>
@@ -194,9 +207,56 @@ where
             validator,
             changeset_cache,
             runtime,
+            inclusion_lists: InclusionListStore::default(),
+            execution_profiler: None,
+            execution_profile_metrics: ExecutionProfileMetrics::default(),
+            execution_cache_snapshot: None,
         }
     }
 
+    /// Sets the store of inclusion lists that new payloads have to satisfy.
+    pub fn with_inclusion_lists(mut self, inclusion_lists: InclusionListStore) -> Self {
+        self.inclusion_lists = inclusion_lists;
+        self
+    }
+
+    /// Sets the profiler to execute blocks with.
+    pub fn with_execution_profiler(
+        mut self,
+        execution_profiler: Option<ExecutionProfiler>,
+    ) -> Self {
+        self.execution_profiler = execution_profiler;
+        self
+    }
+
+    /// Sets the path of the execution cache snapshot.
+    ///
+    /// The hottest execution cache entries are written to the snapshot on shutdown, and the cache
+    /// is warmed with the snapshot here if it was taken at the persisted tip.
+    pub fn with_execution_cache_snapshot(mut self, path: Option<PathBuf>) -> Self {
+        if let Some(path) = &path {
+            self.payload_processor.track_hot_keys(HotKeyLimits::default());
+            match self.persisted_tip() {
+                Ok(tip) => self.payload_processor.load_execution_cache_snapshot(path, tip.hash),
+                Err(err) => {
+                    warn!(target: "engine::tree", %err, "Failed to read persisted tip, not loading execution cache snapshot")
+                }
+            }
+        }
+        self.execution_cache_snapshot = path;
+        self
+    }
+
+    /// Returns the last block that was persisted to disk.
+    fn persisted_tip(&self) -> ProviderResult<NumHash> {
+        let provider = self.provider.database_provider_ro()?;
+        let number = provider.last_block_number()?;
+        let hash = provider
+            .block_hash(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        Ok(NumHash::new(number, hash))
+    }
+
     /// Converts a [`BlockOrPayload`] to a recovered block.
     #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
     pub fn convert_to_block<T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>>(
@@ -787,11 +847,8 @@ where
 
     /// Executes a block with the given state provider.
     ///
-    /// This method orchestrates block execution:
-    /// 1. Sets up the EVM with state database and precompile caching
-    /// 2. Spawns a background task for incremental receipt root computation
-    /// 3. Executes transactions with metrics collection via state hooks
-    /// 4. Merges state transitions and records execution metrics
+    /// If execution profiling is enabled, the block is executed with the profiler's inspector and
+    /// the top entries of the profile are exported as metrics afterwards.
     #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
     #[expect(clippy::type_complexity)]
     fn execute_block<S, Err, T>(
@@ -825,17 +882,56 @@ where
                 .build()
         });
 
-        let (spec_id, mut executor) = {
-            let _span = debug_span!(target: "engine::tree", "create_evm").entered();
-            let spec_id = *env.evm_env.spec_id();
-            let evm = self.evm_config.evm_with_env(&mut db, env.evm_env);
-            let ctx = self
-                .execution_ctx_for(input)
-                .map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
-            let executor = self.evm_config.create_executor(evm, ctx);
-            (spec_id, executor)
+        let span = debug_span!(target: "engine::tree", "create_evm").entered();
+        let spec_id = *env.evm_env.spec_id();
+        let ctx =
+            self.execution_ctx_for(input).map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
+        // the executor borrows the config for the whole execution, which needs `&mut self`
+        let evm_config = self.evm_config.clone();
+
+        let Some(profiler) = self.execution_profiler.clone() else {
+            let evm = evm_config.evm_with_env(&mut db, env.evm_env);
+            let executor = evm_config.create_executor(evm, ctx);
+            drop(span);
+            return self.execute_block_with(executor, spec_id, input, handle)
         };
 
+        let evm = evm_config.evm_with_env_and_inspector(&mut db, env.evm_env, profiler.inspector());
+        let executor = evm_config.create_executor(evm, ctx);
+        drop(span);
+        let output = self.execute_block_with(executor, spec_id, input, handle)?;
+        self.execution_profile_metrics.record(&profiler.profile(profiler.top()));
+        Ok(output)
+    }
+
+    /// Executes a block with the given executor.
+    ///
+    /// This method orchestrates block execution:
+    /// 1. Sets up precompile caching
+    /// 2. Spawns a background task for incremental receipt root computation
+    /// 3. Executes transactions with metrics collection via state hooks
+    /// 4. Merges state transitions and records execution metrics
+    #[expect(clippy::type_complexity)]
+    fn execute_block_with<'a, DB, I, Err, T>(
+        &mut self,
+        mut executor: impl BlockExecutorFor<'a, Evm::BlockExecutorFactory, DB, I>,
+        spec_id: SpecFor<Evm>,
+        input: &BlockOrPayload<T>,
+        handle: &mut PayloadHandle<impl ExecutableTxFor<Evm>, Err, N::Receipt>,
+    ) -> Result<
+        (
+            BlockExecutionOutput<N::Receipt>,
+            Vec<Address>,
+            tokio::sync::oneshot::Receiver<(B256, alloy_primitives::Bloom)>,
+        ),
+        InsertBlockErrorKind,
+    >
+    where
+        DB: Database + 'a,
+        I: InspectorFor<Evm, &'a mut State<DB>> + 'a,
+        Err: core::error::Error + Send + Sync + 'static,
+        T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>,
+    {
         if !self.config.precompile_cache_disabled() {
             let _span = debug_span!(target: "engine::tree", "setup_precompile_cache").entered();
             executor.evm_mut().precompiles_mut().map_pure_precompiles(|address, precompile| {
@@ -879,7 +975,7 @@ where
 
         // Finish execution and get the result
         let post_exec_start = Instant::now();
-        let (_evm, result) = debug_span!(target: "engine::tree", "BlockExecutor::finish")
+        let (db, result) = debug_span!(target: "engine::tree", "BlockExecutor::finish")
             .in_scope(|| executor.finish())
             .map(|(evm, result)| (evm.into_db(), result))?;
         self.metrics.record_post_execution(post_exec_start.elapsed());
@@ -1301,6 +1397,10 @@ where
             self.on_invalid_block(parent_block, block, output, None, ctx.state_mut());
             return Err(err.into())
         }
+        drop(_enter);
+
+        // an unsatisfied inclusion list doesn't invalidate the block, it's only recorded
+        self.check_inclusion_list(block, parent_block, output, ctx)?;
 
         // record post-execution validation duration
         self.metrics
@@ -1311,6 +1411,56 @@ where
         Ok(hashed_state)
     }
 
+    /// Checks whether the block satisfies the inclusion list it was received with, if any.
+    ///
+    /// The block doesn't satisfy the list if a transaction of the list is missing that could have
+    /// been appended to the block, see [`find_unsatisfied_transaction`]. This is recorded in the
+    /// [`InclusionListStore`], so that the engine API can report it: the block stays valid.
+    pub(super) fn check_inclusion_list(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        parent_block: &SealedHeader<N::BlockHeader>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        ctx: &TreeCtx<'_, N>,
+    ) -> Result<(), InsertBlockErrorKind> {
+        let Some(inclusion_list) = self.inclusion_lists.get(&block.hash()) else { return Ok(()) };
+        let _enter = debug_span!(target: "engine::tree::payload_validator", "check_inclusion_list")
+            .entered();
+
+        let inclusion_list = decode_inclusion_list::<N::SignedTx>(&inclusion_list);
+        if inclusion_list.is_empty() {
+            return Ok(())
+        }
+
+        // accounts that weren't changed by the block are looked up in the parent state
+        let mut parent_state = None;
+        let unsatisfied = find_unsatisfied_transaction(
+            &inclusion_list,
+            block.body().transactions_iter().map(|tx| *tx.tx_hash()),
+            block.gas_limit().saturating_sub(block.gas_used()),
+            block.base_fee_per_gas(),
+            |address| {
+                if let Some(account) = output.state.account(&address) {
+                    return Ok(account.info.as_ref().map(Into::into))
+                }
+                if parent_state.is_none() {
+                    let builder = self
+                        .state_provider_builder(parent_block.hash(), ctx.state())?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(parent_block.hash().into()))?;
+                    parent_state = Some(builder.build()?);
+                }
+                parent_state.as_ref().expect("initialized above").basic_account(&address)
+            },
+        )?;
+
+        if let Some(tx_hash) = unsatisfied {
+            warn!(target: "engine::tree::payload_validator", block=?block.num_hash(), %tx_hash, "Block does not satisfy inclusion list");
+            self.inclusion_lists.set_unsatisfied(&block.hash(), tx_hash);
+        }
+
+        Ok(())
+    }
+
     /// Spawns a payload processor task based on the state root strategy.
     ///
     /// This method determines how to execute the block and compute its state root based on
@@ -1710,6 +1860,12 @@ pub trait EngineValidator<
     /// This is invoked when blocks are inserted via `InsertExecutedBlock` (e.g., locally built
     /// blocks by sequencers) to allow implementations to update internal state such as caches.
     fn on_inserted_executed_block(&self, block: ExecutedBlock<N>);
+
+    /// Hook called on shutdown, after all canonical blocks have been persisted.
+    ///
+    /// `persisted_tip` is the last block on disk, which implementations can use to save state that
+    /// is valid on top of it.
+    fn on_shutdown(&self, _persisted_tip: NumHash) {}
 }
 
 impl<N, Types, P, Evm, V> EngineValidator<Types> for BasicEngineValidator<P, Evm, V>
@@ -1773,6 +1929,12 @@ where
             &block.execution_output.state,
         );
     }
+
+    fn on_shutdown(&self, persisted_tip: NumHash) {
+        if let Some(path) = &self.execution_cache_snapshot {
+            self.payload_processor.save_execution_cache_snapshot(path, persisted_tip.hash);
+        }
+    }
 }
 
 impl<P, Evm, V> WaitForCaches for BasicEngineValidator<P, Evm, V>
@@ -21,13 +21,14 @@ use alloy_rpc_types_engine::{
 use assert_matches::assert_matches;
 use reth_chain_state::{test_utils::TestBlockBuilder, BlockState, ComputedTrieData};
 use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
+use reth_consensus::{ConsensusError, HeaderValidator};
 use reth_engine_primitives::{EngineApiValidator, ForkchoiceStatus, NoopInvalidBlockHook};
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_ethereum_primitives::{Block, EthPrimitives};
 use reth_evm_ethereum::MockEvmConfig;
 use reth_primitives_traits::Block as _;
-use reth_provider::test_utils::MockEthProvider;
+use reth_provider::{test_utils::MockEthProvider, ExecutionOutcome};
 use reth_tasks::spawn_os_thread;
 use std::{
     collections::BTreeMap,
@@ -158,26 +159,34 @@ struct TestHarness {
 
 impl TestHarness {
     fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        let consensus = Arc::new(EthBeaconConsensus::new(chain_spec.clone()));
+        Self::with_consensus(chain_spec, consensus)
+    }
+
+    fn with_consensus(
+        chain_spec: Arc<ChainSpec>,
+        consensus: Arc<dyn FullConsensus<EthPrimitives>>,
+    ) -> Self {
         use std::sync::mpsc::channel;
         let (action_tx, action_rx) = channel();
-        Self::with_persistence_channel(chain_spec, action_tx, action_rx)
+        Self::with_persistence_channel(chain_spec, consensus, action_tx, action_rx)
     }
 
     #[expect(dead_code)]
     fn with_test_channel(chain_spec: Arc<ChainSpec>) -> (Self, TestChannelHandle) {
         let (action_tx, action_rx, handle) = TestChannel::spawn_channel();
-        (Self::with_persistence_channel(chain_spec, action_tx, action_rx), handle)
+        let consensus = Arc::new(EthBeaconConsensus::new(chain_spec.clone()));
+        (Self::with_persistence_channel(chain_spec, consensus, action_tx, action_rx), handle)
     }
 
     fn with_persistence_channel(
         chain_spec: Arc<ChainSpec>,
+        consensus: Arc<dyn FullConsensus<EthPrimitives>>,
         action_tx: Sender<PersistenceAction>,
         action_rx: Receiver<PersistenceAction>,
     ) -> Self {
         let persistence_handle = PersistenceHandle::new(action_tx);
 
-        let consensus = Arc::new(EthBeaconConsensus::new(chain_spec.clone()));
-
         let provider = MockEthProvider::default();
 
         let payload_validator = MockEngineValidator;
@@ -661,6 +670,98 @@ fn test_disconnected_block() {
     );
 }
 
+#[test]
+fn test_on_new_block_disconnected() {
+    let s = include_str!("../../test-data/holesky/2.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow();
+    let hash = sealed.hash();
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+
+    let outcome = test_harness.tree.on_new_block(sealed.clone()).unwrap();
+    assert!(outcome.outcome.is_syncing());
+
+    // ensure block is buffered
+    let buffered = test_harness.tree.state.buffer.block(&hash).unwrap();
+    assert_eq!(*buffered, sealed);
+}
+
+/// Consensus that accepts all blocks before execution and rejects them after execution, like a
+/// proof-of-authority engine that verifies the seal against the parent state.
+#[derive(Debug)]
+struct RejectAfterExecution;
+
+impl HeaderValidator<alloy_consensus::Header> for RejectAfterExecution {
+    fn validate_header(
+        &self,
+        _header: &SealedHeader<alloy_consensus::Header>,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        _header: &SealedHeader<alloy_consensus::Header>,
+        _parent: &SealedHeader<alloy_consensus::Header>,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+}
+
+impl Consensus<Block> for RejectAfterExecution {
+    fn validate_body_against_header(
+        &self,
+        _body: &reth_ethereum_primitives::BlockBody,
+        _header: &SealedHeader<alloy_consensus::Header>,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+
+    fn validate_block_pre_execution(
+        &self,
+        _block: &SealedBlock<Block>,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+}
+
+impl FullConsensus<EthPrimitives> for RejectAfterExecution {
+    fn validate_block_post_execution(
+        &self,
+        _block: &RecoveredBlock<Block>,
+        _result: &BlockExecutionResult<reth_ethereum_primitives::Receipt>,
+        _receipt_root_bloom: Option<reth_consensus::ReceiptRootBloom>,
+    ) -> Result<(), ConsensusError> {
+        Err(ConsensusError::Other("invalid seal".to_string()))
+    }
+}
+
+#[test]
+fn test_on_new_block_rejected_after_execution() {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = MAINNET.clone();
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..3).collect();
+    let parent = blocks.last().unwrap().recovered_block().clone_sealed_header();
+    let mut test_harness =
+        TestHarness::with_consensus(chain_spec, Arc::new(RejectAfterExecution)).with_blocks(blocks);
+
+    let block = test_block_builder.generate_random_block(parent.number + 1, parent.hash());
+    let hash = block.hash();
+    test_harness.tree.evm_config.extend([ExecutionOutcome::default()]);
+
+    let outcome = test_harness.tree.on_new_block(block).unwrap();
+    assert_matches!(
+        &outcome.outcome.status,
+        PayloadStatusEnum::Invalid { validation_error } if validation_error.contains("invalid seal")
+    );
+    assert_eq!(outcome.outcome.latest_valid_hash, Some(parent.hash()));
+    assert!(test_harness.tree.state.invalid_headers.get(&hash).is_some());
+}
+
 #[tokio::test]
 async fn test_holesky_payload() {
     let s = include_str!("../../test-data/holesky/1.rlp");
@@ -1448,6 +1549,83 @@ fn test_validate_block_multiple_scenarios() {
     );
 }
 
+/// Test that a block that doesn't satisfy its inclusion list is recorded as such, but stays valid
+#[test]
+fn test_unsatisfied_inclusion_list_keeps_block_valid() {
+    use alloy_consensus::{Header, TxEip1559};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, TxKind, U256};
+    use reth_engine_primitives::inclusion_list::InclusionListStore;
+    use reth_execution_types::{BlockExecutionOutput, BlockExecutionResult};
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_revm::db::BundleState;
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+    use revm_state::AccountInfo;
+
+    reth_tracing::init_test_tracing();
+
+    let inclusion_lists = InclusionListStore::default();
+    let mut test_harness = ValidatorTestHarness::new(MAINNET.clone());
+    test_harness.validator = test_harness.validator.with_inclusion_lists(inclusion_lists.clone());
+
+    let tx = TxEip1559 {
+        chain_id: 1,
+        gas_limit: 21_000,
+        max_fee_per_gas: 100,
+        to: TxKind::Call(Address::with_last_byte(1)),
+        ..Default::default()
+    };
+    let tx = sign_tx_with_random_key_pair(&mut generators::rng(), tx.into());
+    let sender = tx.recover_signer().unwrap();
+
+    let parent = SealedHeader::seal_slow(MAINNET.genesis_header().clone());
+    let block = Block {
+        header: Header {
+            parent_hash: parent.hash(),
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        },
+        body: Default::default(),
+    }
+    .seal_slow()
+    .try_recover()
+    .unwrap();
+    inclusion_lists.insert(block.hash(), vec![tx.encoded_2718().into()]);
+
+    let output = |balance| BlockExecutionOutput {
+        result: BlockExecutionResult::<reth_ethereum_primitives::Receipt> {
+            receipts: Vec::new(),
+            requests: Default::default(),
+            gas_used: 0,
+            blob_gas_used: 0,
+        },
+        state: BundleState::builder(1..=1)
+            .state_present_account_info(
+                sender,
+                AccountInfo { balance: U256::from(balance), ..Default::default() },
+            )
+            .build(),
+    };
+
+    // the sender can't pay for the transaction, so the block satisfies the list
+    let ctx = TreeCtx::new(
+        &mut test_harness.harness.tree.state,
+        &test_harness.harness.tree.canonical_in_memory_state,
+    );
+    test_harness.validator.check_inclusion_list(&block, &parent, &output(0), &ctx).unwrap();
+    assert_eq!(inclusion_lists.unsatisfied(&block.hash()), None);
+
+    // the transaction could have been appended to the block
+    let ctx = TreeCtx::new(
+        &mut test_harness.harness.tree.state,
+        &test_harness.harness.tree.canonical_in_memory_state,
+    );
+    test_harness.validator.check_inclusion_list(&block, &parent, &output(u64::MAX), &ctx).unwrap();
+    assert_eq!(inclusion_lists.unsatisfied(&block.hash()), Some(*tx.tx_hash()));
+}
+
 /// Test suite for the `check_invalid_ancestors` method
 #[cfg(test)]
 mod check_invalid_ancestors_tests {
@@ -1,20 +1,25 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_rpc_types_engine::{ForkchoiceState, ForkchoiceUpdated, PayloadStatus};
 use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload, OnForkChoiceUpdated};
 use reth_fs_util as fs;
 use reth_payload_primitives::PayloadTypes;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// Suffix of the files that store the response to the engine API message in the file of the same
+/// name without the suffix.
+const RESPONSE_SUFFIX: &str = ".response.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,6 +39,63 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+/// The response to an engine API message that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The response to an `engine_forkchoiceUpdated` method call.
+    ForkchoiceUpdated(ForkchoiceUpdated),
+    /// The response to an `engine_newPayload` method call.
+    NewPayload(PayloadStatus),
+    /// The method call failed with the given error.
+    Error(String),
+}
+
+impl StoredEngineApiResponse {
+    /// Compares the response to the `expected` one and describes how they differ, if they do.
+    ///
+    /// Only the status, latest valid hash and payload id are compared: validation and error
+    /// messages are not stable across versions.
+    pub fn mismatch(&self, expected: &Self) -> Option<String> {
+        let payload_status_mismatch = |actual: &PayloadStatus, expected: &PayloadStatus| {
+            if actual.status.as_str() != expected.status.as_str() {
+                return Some(format!(
+                    "status {} != expected {}",
+                    actual.status.as_str(),
+                    expected.status.as_str()
+                ))
+            }
+            if actual.latest_valid_hash != expected.latest_valid_hash {
+                return Some(format!(
+                    "latest valid hash {:?} != expected {:?}",
+                    actual.latest_valid_hash, expected.latest_valid_hash
+                ))
+            }
+            None
+        };
+
+        match (self, expected) {
+            (Self::NewPayload(actual), Self::NewPayload(expected)) => {
+                payload_status_mismatch(actual, expected)
+            }
+            (Self::ForkchoiceUpdated(actual), Self::ForkchoiceUpdated(expected)) => {
+                payload_status_mismatch(&actual.payload_status, &expected.payload_status).or_else(
+                    || {
+                        (actual.payload_id != expected.payload_id).then(|| {
+                            format!(
+                                "payload id {:?} != expected {:?}",
+                                actual.payload_id, expected.payload_id
+                            )
+                        })
+                    },
+                )
+            }
+            (Self::Error(_), Self::Error(_)) => None,
+            (actual, expected) => Some(format!("{actual:?} != expected {expected:?}")),
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
@@ -49,6 +111,36 @@ impl EngineMessageStore {
         Self { path }
     }
 
+    /// Returns the path the [`BeaconEngineMessage`] received at `received_at` is stored at, or
+    /// `None` if the message is not stored.
+    pub fn message_path<T>(
+        &self,
+        msg: &BeaconEngineMessage<T>,
+        received_at: SystemTime,
+    ) -> Option<PathBuf>
+    where
+        T: PayloadTypes,
+    {
+        let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
+        let filename = match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, .. } => {
+                format!("{}-fcu-{}.json", timestamp, state.head_block_hash)
+            }
+            BeaconEngineMessage::NewPayload { payload, .. } |
+            BeaconEngineMessage::RethNewPayload { payload, .. } => {
+                format!("{}-new_payload-{}.json", timestamp, payload.block_hash())
+            }
+            // sealed blocks are produced in-process and are not part of the Engine API
+            BeaconEngineMessage::NewBlock { .. } => return None,
+        };
+        Some(self.path.join(filename))
+    }
+
+    /// Returns the path the response to the message stored at the given path is stored at.
+    pub fn response_path(message_path: &Path) -> PathBuf {
+        message_path.with_extension(&RESPONSE_SUFFIX[1..])
+    }
+
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
     pub fn on_message<T>(
@@ -59,8 +151,8 @@ impl EngineMessageStore {
     where
         T: PayloadTypes,
     {
+        let Some(path) = self.message_path(msg, received_at) else { return Ok(()) };
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
-        let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
         match msg {
             BeaconEngineMessage::ForkchoiceUpdated {
                 state,
@@ -68,9 +160,8 @@ impl EngineMessageStore {
                 tx: _tx,
                 version: _version,
             } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
                 fs::write(
-                    self.path.join(filename),
+                    path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
@@ -79,24 +170,34 @@ impl EngineMessageStore {
             }
             BeaconEngineMessage::NewPayload { payload, .. } |
             BeaconEngineMessage::RethNewPayload { payload, .. } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
                 fs::write(
-                    self.path.join(filename),
+                    path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
                         payload: payload.clone(),
                     })?,
                 )?;
             }
+            // sealed blocks are produced in-process and are not part of the Engine API
+            BeaconEngineMessage::NewBlock { .. } => {}
         };
         Ok(())
     }
 
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
+        Ok(self.engine_messages()?.into_iter().map(|(_, path)| path))
+    }
+
+    /// Returns all stored engine API message files with the unix timestamp in milliseconds they
+    /// were received at, ordered by timestamp.
+    pub fn engine_messages(&self) -> eyre::Result<Vec<(u64, PathBuf)>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(RESPONSE_SUFFIX)) {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -108,11 +209,110 @@ impl EngineMessageStore {
                 tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
             }
         }
-        Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
+        Ok(filenames_by_ts
+            .into_iter()
+            .flat_map(|(timestamp, paths)| paths.into_iter().map(move |path| (timestamp, path)))
+            .collect())
+    }
+
+    /// Reads the engine API message stored at the given path.
+    pub fn read_message<T>(path: &Path) -> eyre::Result<StoredEngineApiMessage<T>>
+    where
+        T: PayloadTypes<PayloadAttributes: DeserializeOwned, ExecutionData: DeserializeOwned>,
+    {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Reads the response to the engine API message stored at the given path, if it was stored.
+    pub fn read_response(message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
+/// Stores the response to the message stored at `message_path` once the engine sent it, and
+/// forwards it to the original sender of the message.
+fn store_response<T: PayloadTypes>(
+    msg: BeaconEngineMessage<T>,
+    message_path: PathBuf,
+) -> BeaconEngineMessage<T> {
+    let write = move |response: StoredEngineApiResponse| {
+        let path = EngineMessageStore::response_path(&message_path);
+        if let Err(error) = serde_json::to_vec(&response)
+            .map_err(eyre::Report::from)
+            .and_then(|response| Ok(fs::write(&path, response)?))
+        {
+            error!(target: "engine::stream::store", ?path, %error, "Error storing Engine API response");
+        }
+    };
+
+    match msg {
+        BeaconEngineMessage::NewPayload { payload, tx } => {
+            let (engine_tx, engine_rx) = oneshot::channel();
+            let msg = BeaconEngineMessage::NewPayload { payload, tx: engine_tx };
+            tokio::spawn(async move {
+                let Ok(result) = engine_rx.await else { return };
+                write(match &result {
+                    Ok(status) => StoredEngineApiResponse::NewPayload(status.clone()),
+                    Err(error) => StoredEngineApiResponse::Error(error.to_string()),
+                });
+                let _ = tx.send(result);
+            });
+            msg
+        }
+        BeaconEngineMessage::RethNewPayload { payload, tx } => {
+            let (engine_tx, engine_rx) = oneshot::channel();
+            let msg = BeaconEngineMessage::RethNewPayload { payload, tx: engine_tx };
+            tokio::spawn(async move {
+                let Ok(result) = engine_rx.await else { return };
+                write(match &result {
+                    Ok((status, _)) => StoredEngineApiResponse::NewPayload(status.clone()),
+                    Err(error) => StoredEngineApiResponse::Error(error.to_string()),
+                });
+                let _ = tx.send(result);
+            });
+            msg
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+            let (engine_tx, engine_rx) = oneshot::channel();
+            let msg = BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs,
+                version,
+                tx: engine_tx,
+            };
+            tokio::spawn(async move {
+                let Ok(result) = engine_rx.await else { return };
+                let result = match result {
+                    Ok(on_updated) => {
+                        // the payload id is only known once the payload job was started
+                        let forkchoice_status = on_updated.forkchoice_status();
+                        let outcome = on_updated.await;
+                        write(match &outcome {
+                            Ok(updated) => {
+                                StoredEngineApiResponse::ForkchoiceUpdated(updated.clone())
+                            }
+                            Err(error) => StoredEngineApiResponse::Error(error.to_string()),
+                        });
+                        Ok(OnForkChoiceUpdated::resolved(forkchoice_status, outcome))
+                    }
+                    Err(error) => {
+                        write(StoredEngineApiResponse::Error(error.to_string()));
+                        Err(error)
+                    }
+                };
+                let _ = tx.send(result);
+            });
+            msg
+        }
+        msg @ BeaconEngineMessage::NewBlock { .. } => msg,
+    }
+}
+
+/// A wrapper stream that stores Engine API messages and the responses to them in
 /// the specified directory.
 #[derive(Debug)]
 #[pin_project::pin_project]
@@ -140,12 +340,70 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next &&
-            let Err(error) = this.store.on_message(msg, SystemTime::now())
-        {
+        let Some(msg) = ready!(this.stream.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        let received_at = SystemTime::now();
+        if let Err(error) = this.store.on_message(&msg, received_at) {
             error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+            return Poll::Ready(Some(msg))
         }
-        Poll::Ready(next)
+        let msg = match this.store.message_path(&msg, received_at) {
+            Some(path) => store_response(msg, path),
+            None => msg,
+        };
+        Poll::Ready(Some(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::{PayloadId, PayloadStatusEnum};
+
+    #[test]
+    fn response_mismatch() {
+        let valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::with_last_byte(1)));
+        let invalid = PayloadStatus::new(
+            PayloadStatusEnum::Invalid { validation_error: "bad".to_string() },
+            Some(B256::ZERO),
+        );
+        let response = StoredEngineApiResponse::NewPayload(valid.clone());
+        assert_eq!(response.mismatch(&response), None);
+        assert!(response.mismatch(&StoredEngineApiResponse::NewPayload(invalid.clone())).is_some());
+        assert!(response
+            .mismatch(&StoredEngineApiResponse::NewPayload(PayloadStatus::from_status(
+                PayloadStatusEnum::Valid
+            )))
+            .is_some());
+
+        // validation errors are not compared
+        let other_invalid = PayloadStatus::new(
+            PayloadStatusEnum::Invalid { validation_error: "worse".to_string() },
+            Some(B256::ZERO),
+        );
+        assert_eq!(
+            StoredEngineApiResponse::NewPayload(invalid)
+                .mismatch(&StoredEngineApiResponse::NewPayload(other_invalid)),
+            None
+        );
+
+        let updated = ForkchoiceUpdated::new(valid).with_payload_id(PayloadId::new([1; 8]));
+        let response = StoredEngineApiResponse::ForkchoiceUpdated(updated.clone());
+        assert_eq!(response.mismatch(&response), None);
+        assert!(response
+            .mismatch(&StoredEngineApiResponse::ForkchoiceUpdated(
+                updated.with_payload_id(PayloadId::new([2; 8]))
+            ))
+            .is_some());
+        assert!(response.mismatch(&StoredEngineApiResponse::Error("error".to_string())).is_some());
+    }
+
+    #[test]
+    fn response_path() {
+        let path = Path::new("/store/1-fcu-0x01.json");
+        assert_eq!(
+            EngineMessageStore::response_path(path),
+            Path::new("/store/1-fcu-0x01.response.json")
+        );
     }
 }
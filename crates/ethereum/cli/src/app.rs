@@ -199,6 +199,10 @@ where
         Commands::ReExecute(command) => {
             runner.run_until_ctrl_c(command.execute::<N>(components, rt))
         }
+        Commands::Devnet(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        Commands::Engine(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        Commands::ExEx(command) => runner.run_until_ctrl_c(command.execute()),
+        Commands::Stateless(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::Ext(command) => command.execute(runner),
     }
 }
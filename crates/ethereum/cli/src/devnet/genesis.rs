@@ -0,0 +1,78 @@
+//! Genesis of the devnet.
+
+use alloy_genesis::Genesis;
+use alloy_primitives::{Address, U256};
+use alloy_signer_local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
+use eyre::ensure;
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardforks, DEV};
+use std::sync::Arc;
+
+/// The well-known development mnemonic, also used by the `--dev` chain and most dev tooling.
+pub const DEFAULT_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// Derives `count` accounts from the mnemonic, using the default derivation path
+/// `m/44'/60'/0'/0/<index>`.
+pub fn derive_accounts(mnemonic: &str, count: u32) -> eyre::Result<Vec<PrivateKeySigner>> {
+    let builder = MnemonicBuilder::<English>::default().phrase(mnemonic);
+    (0..count).map(|index| Ok(builder.clone().index(index)?.build()?)).collect()
+}
+
+/// Returns the chain spec of the devnet.
+///
+/// Without a custom genesis, the devnet runs the `--dev` chain. The given accounts are funded with
+/// `balance` wei on top of the genesis allocations.
+pub fn devnet_chain_spec(
+    genesis: Option<Genesis>,
+    accounts: impl IntoIterator<Item = Address>,
+    balance: U256,
+) -> eyre::Result<Arc<ChainSpec>> {
+    let fund = |mut genesis: Genesis| {
+        for address in accounts {
+            genesis.alloc.entry(address).or_default().balance = balance;
+        }
+        genesis
+    };
+
+    let chain_spec = match genesis {
+        Some(genesis) => ChainSpec::from_genesis(fund(genesis)),
+        None => ChainSpecBuilder::default()
+            .chain(DEV.chain)
+            .genesis(fund(DEV.genesis.clone()))
+            .with_forks(DEV.hardforks.clone())
+            .build(),
+    };
+
+    // blocks are produced through the engine API, so the merge must have happened at genesis
+    ensure!(
+        chain_spec.is_paris_active_at_block(0),
+        "devnet genesis must activate the merge at genesis (`terminalTotalDifficulty: 0`)"
+    );
+    Ok(Arc::new(chain_spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_chainspec::EthChainSpec;
+
+    #[test]
+    fn derive_dev_accounts() {
+        let accounts = derive_accounts(DEFAULT_MNEMONIC, 2).unwrap();
+        assert_eq!(accounts[0].address(), address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert_eq!(accounts[1].address(), address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"));
+    }
+
+    #[test]
+    fn fund_accounts() {
+        let account = Address::with_last_byte(1);
+        let balance = U256::from(42);
+        let chain_spec = devnet_chain_spec(None, [account], balance).unwrap();
+        assert_eq!(chain_spec.chain(), DEV.chain());
+        assert_eq!(chain_spec.genesis().alloc[&account].balance, balance);
+        assert_ne!(chain_spec.genesis_hash(), DEV.genesis_hash());
+
+        // pre-merge genesis files can't be driven through the engine API
+        assert!(devnet_chain_spec(Some(Genesis::default()), [], balance).is_err());
+    }
+}
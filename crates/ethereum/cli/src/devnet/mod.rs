@@ -0,0 +1,233 @@
+//! `reth devnet` command.
+//!
+//! Launches a local network of in-process nodes on loopback. The first node produces blocks, the
+//! other nodes follow its chain over devp2p. Scripts can reorg the block producer and partition the
+//! network to reproduce p2p and reorg issues locally, see [`script`] for the format.
+//!
+//! Nodes listen on loopback, on ports assigned by the OS, and serve the `eth`, `net` and `web3`
+//! RPC modules over HTTP by default. The devnet exits as soon as one of its nodes does.
+
+use alloy_consensus::constants::ETH_TO_WEI;
+use alloy_genesis::Genesis;
+use alloy_primitives::U256;
+use clap::Parser;
+use eyre::WrapErr;
+use futures::future;
+use orchestrator::{Devnet, DevnetNode};
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli_runner::CliContext;
+use reth_db::init_db;
+use reth_network_api::PeersInfo;
+use reth_node_builder::{NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::{
+    args::{DatabaseArgs, DatadirArgs, DiscoveryArgs, NetworkArgs, RpcServerArgs},
+    dirs::MaybePlatformPath,
+    exit::NodeExitFuture,
+};
+use reth_node_ethereum::EthereumNode;
+use reth_rpc_server_types::RpcModuleSelection;
+use reth_tasks::TaskExecutor;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{info, info_span, Instrument};
+
+mod genesis;
+mod orchestrator;
+pub mod script;
+
+pub use genesis::{derive_accounts, devnet_chain_spec, DEFAULT_MNEMONIC};
+pub use script::{parse_script, Step};
+
+/// Size of the cross-block cache of every node, in megabytes.
+const CROSS_BLOCK_CACHE_SIZE_MB: usize = 64;
+
+/// Launch a local multi-node devnet
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Number of nodes to launch. The first node produces blocks, all other nodes sync from it.
+    #[arg(long, short, default_value_t = 3, value_parser = clap::value_parser!(u16).range(1..=100))]
+    nodes: u16,
+
+    /// Genesis JSON file to start the network from. Defaults to the `--dev` chain.
+    ///
+    /// The merge must be activated at genesis.
+    #[arg(long, value_name = "FILE")]
+    genesis: Option<PathBuf>,
+
+    /// Number of accounts derived from the mnemonic that are funded at genesis.
+    #[arg(long, default_value_t = 10)]
+    accounts: u32,
+
+    /// Mnemonic to derive the funded accounts from.
+    #[arg(long, default_value = DEFAULT_MNEMONIC)]
+    mnemonic: String,
+
+    /// Balance of each funded account, in ether.
+    #[arg(long, default_value_t = 10_000)]
+    balance: u64,
+
+    /// Interval at which blocks are produced.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "2s")]
+    block_time: Duration,
+
+    /// Directory that holds the data directories of the nodes, in `node-<index>` subdirectories.
+    ///
+    /// Defaults to a temporary directory that is removed on exit.
+    #[arg(long, value_name = "DIR")]
+    datadir: Option<PathBuf>,
+
+    /// Script of reorg and partition steps that is run once all nodes are up.
+    ///
+    /// Blocks keep being produced at the block time in between steps, and after the script ends,
+    /// unless it ends with `exit`.
+    #[arg(long, value_name = "FILE")]
+    script: Option<PathBuf>,
+
+    /// RPC modules served over HTTP by every node.
+    #[arg(long = "http.api", default_value = "eth,net,web3")]
+    http_api: RpcModuleSelection,
+
+    /// Log the private keys of the funded accounts.
+    #[arg(long)]
+    print_keys: bool,
+}
+
+impl Command {
+    /// Execute `devnet` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let genesis = self
+            .genesis
+            .as_ref()
+            .map(|path| -> eyre::Result<Genesis> {
+                let genesis = reth_fs_util::read_to_string(path)?;
+                serde_json::from_str(&genesis).wrap_err("failed to parse genesis")
+            })
+            .transpose()?;
+        let steps = self
+            .script
+            .as_ref()
+            .map(|path| parse_script(&reth_fs_util::read_to_string(path)?))
+            .transpose()?
+            .unwrap_or_default();
+
+        let accounts = derive_accounts(&self.mnemonic, self.accounts)?;
+        let balance = U256::from(self.balance) * U256::from(ETH_TO_WEI);
+        let chain_spec =
+            devnet_chain_spec(genesis, accounts.iter().map(|account| account.address()), balance)?;
+
+        let tempdir;
+        let datadir = match &self.datadir {
+            Some(datadir) => datadir.as_path(),
+            None => {
+                tempdir = tempfile::tempdir()?;
+                tempdir.path()
+            }
+        };
+
+        let mut nodes = Vec::with_capacity(self.nodes as usize);
+        let mut exits = Vec::with_capacity(self.nodes as usize);
+        for index in 0..self.nodes {
+            let (node, exit) = launch_node(
+                index,
+                chain_spec.clone(),
+                datadir,
+                self.http_api.clone(),
+                ctx.task_executor.clone(),
+            )
+            .instrument(info_span!("node", index))
+            .await?;
+            nodes.push(node);
+            exits.push(exit);
+        }
+
+        info!(target: "reth::cli", genesis = %chain_spec.genesis_hash(), chain_id = chain_spec.chain_id(), "Launched devnet");
+        for (index, node) in nodes.iter().enumerate() {
+            let role = if index == 0 { "block producer" } else { "follower" };
+            let http = node.rpc_server_handles.rpc.http_local_addr();
+            info!(target: "reth::cli", index, role, ?http, enode = %node.network.local_node_record(), "Devnet node");
+        }
+        for (index, account) in accounts.iter().enumerate() {
+            if self.print_keys {
+                info!(target: "reth::cli", index, address = %account.address(), key = %account.to_bytes(), balance = self.balance, "Funded account");
+            } else {
+                info!(target: "reth::cli", index, address = %account.address(), balance = self.balance, "Funded account");
+            }
+        }
+
+        info!(target: "reth::cli", nodes = nodes.len(), steps = steps.len(), "Starting devnet");
+        let devnet = Devnet::new(nodes, chain_spec, self.block_time).await?;
+        tokio::select! {
+            res = devnet.run(steps) => res,
+            (res, index, _) = future::select_all(exits) => {
+                // a node shut down, e.g. because its engine failed
+                res.wrap_err_with(|| format!("node {index} exited"))?;
+                eyre::bail!("node {index} exited")
+            }
+        }
+    }
+}
+
+/// Launches the node with the given index and returns it with its exit future.
+///
+/// All nodes listen on loopback only, on ports assigned by the OS. IPC is disabled.
+async fn launch_node(
+    index: u16,
+    chain_spec: Arc<ChainSpec>,
+    datadir: &Path,
+    http_api: RpcModuleSelection,
+    executor: TaskExecutor,
+) -> eyre::Result<(DevnetNode, NodeExitFuture)> {
+    let mut rpc = RpcServerArgs::default().with_http().with_http_api(http_api);
+    rpc.ipcdisable = true;
+    let mut config = NodeConfig::new(chain_spec)
+        .with_datadir_args(DatadirArgs {
+            datadir: MaybePlatformPath::from(datadir.join(format!("node-{index}"))),
+            ..Default::default()
+        })
+        .with_network(NetworkArgs {
+            addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            discovery: DiscoveryArgs { disable_discovery: true, ..Default::default() },
+            ..Default::default()
+        })
+        .with_rpc(rpc)
+        .with_unused_ports();
+
+    // all nodes share the memory of one machine
+    config.engine.cross_block_cache_size = CROSS_BLOCK_CACHE_SIZE_MB;
+
+    if index == 0 {
+        // the block producer builds competing chains on top of canonical ancestors for reorgs
+        config.engine.always_process_payload_attributes_on_canonical_head = true;
+        config.engine.allow_unwind_canonical_header = true;
+    }
+
+    let database = init_db(config.datadir().db(), DatabaseArgs::default().database_args())?;
+    let NodeHandle { node, node_exit_future } = NodeBuilder::new(config)
+        .with_database(database)
+        .with_launch_context(executor)
+        .launch_node(EthereumNode::default())
+        .await?;
+
+    Ok((node, node_exit_future))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command() {
+        let cmd = Command::parse_from(["reth", "--nodes", "4", "--block-time", "500ms"]);
+        assert_eq!(cmd.nodes, 4);
+        assert_eq!(cmd.block_time, Duration::from_millis(500));
+        assert_eq!(cmd.mnemonic, DEFAULT_MNEMONIC);
+        assert_eq!(cmd.http_api.to_selection(), RpcModuleSelection::standard_modules());
+        assert!(!cmd.print_keys);
+
+        assert!(Command::try_parse_from(["reth", "--nodes", "0"]).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! Drives block production and the network topology of the devnet.
+
+use super::script::Step;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::ForkchoiceState;
+use eyre::{ensure, eyre, OptionExt};
+use reth_chainspec::ChainSpec;
+use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_network_api::{NetworkInfo, PeerKind, Peers, PeersInfo};
+use reth_node_api::{EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes};
+use reth_node_builder::FullNodeFor;
+use reth_node_ethereum::{EthEngineTypes, EthereumNode};
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{BlockHashReader, BlockNumReader, HeaderProvider};
+use std::{future, sync::Arc, time::Duration};
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+/// A node of the devnet.
+pub(crate) type DevnetNode = FullNodeFor<EthereumNode>;
+
+/// How long to wait for peers to (dis)connect after the topology changed.
+const TOPOLOGY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval at which sync and peer conditions are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A set of nodes, where the first node produces blocks and all other nodes follow its chain over
+/// devp2p.
+///
+/// The devnet acts as the consensus layer of all nodes: new heads of the block producer are sent
+/// to every node that can reach it, which then download the blocks from their peers.
+pub(crate) struct Devnet {
+    /// The nodes, the first one is the block producer.
+    nodes: Vec<DevnetNode>,
+    /// Partition of every node, nodes can only reach nodes of the same partition.
+    partitions: Vec<usize>,
+    /// Builds the payload attributes of new blocks.
+    attributes: LocalPayloadAttributesBuilder<ChainSpec>,
+    /// Head of the block producer.
+    head: SealedHeader,
+    /// Produces blocks at the configured block time.
+    block_time: Interval,
+    /// Whether blocks are currently produced at the configured block time.
+    paused: bool,
+}
+
+impl Devnet {
+    /// Connects all nodes with each other and sends the genesis as the initial head.
+    pub(crate) async fn new(
+        nodes: Vec<DevnetNode>,
+        chain_spec: Arc<ChainSpec>,
+        block_time: Duration,
+    ) -> eyre::Result<Self> {
+        let producer = nodes.first().ok_or_eyre("devnet has no nodes")?;
+        let head = producer
+            .provider
+            .sealed_header(producer.provider.best_block_number()?)?
+            .ok_or_eyre("head of the block producer not found")?;
+
+        let mut block_time = tokio::time::interval_at(Instant::now() + block_time, block_time);
+        block_time.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let devnet = Self {
+            partitions: vec![0; nodes.len()],
+            nodes,
+            attributes: LocalPayloadAttributesBuilder::new(chain_spec),
+            head,
+            block_time,
+            paused: false,
+        };
+        devnet.apply_topology().await?;
+        devnet.broadcast_head().await?;
+        Ok(devnet)
+    }
+
+    /// Runs the script steps in order, then keeps producing blocks until the node is shut down.
+    pub(crate) async fn run(mut self, steps: Vec<Step>) -> eyre::Result<()> {
+        for step in steps {
+            info!(target: "reth::cli", %step, "Running devnet step");
+            match step {
+                Step::Mine(blocks) => self.mine(blocks).await?,
+                Step::Sleep(duration) => self.sleep(duration).await,
+                Step::Pause => self.paused = true,
+                Step::Resume => {
+                    self.paused = false;
+                    self.block_time.reset();
+                }
+                Step::Reorg { depth, length } => self.reorg(depth, length).await?,
+                Step::Partition(groups) => self.partition(groups).await?,
+                Step::Heal => self.heal().await?,
+                Step::WaitSync(timeout) => self.wait_sync(timeout).await?,
+                Step::Exit => return Ok(()),
+            }
+        }
+
+        loop {
+            if self.paused {
+                future::pending::<()>().await;
+            }
+            self.block_time.tick().await;
+            self.mine_logged().await;
+        }
+    }
+
+    /// Returns the block producer.
+    fn producer(&self) -> &DevnetNode {
+        &self.nodes[0]
+    }
+
+    /// Returns the indices of the nodes that are in the same partition as the block producer.
+    fn reachable(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(|idx| self.partitions[*idx] == self.partitions[0])
+    }
+
+    /// Produces blocks at the configured block time for the given duration.
+    async fn sleep(&mut self, duration: Duration) {
+        let deadline = tokio::time::sleep(duration);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => return,
+                _ = self.block_time.tick(), if !self.paused => self.mine_logged().await,
+            }
+        }
+    }
+
+    /// Produces a block and logs any error.
+    async fn mine_logged(&mut self) {
+        if let Err(err) = self.mine(1).await {
+            warn!(target: "reth::cli", %err, "Failed to produce block");
+        }
+    }
+
+    /// Produces the given number of blocks on top of the current head.
+    async fn mine(&mut self, blocks: u64) -> eyre::Result<()> {
+        for _ in 0..blocks {
+            let parent = self.head.clone();
+            let header = self.build_block(&parent).await?;
+            info!(target: "reth::cli", number = header.number(), hash = %header.hash(), "Produced block");
+            self.make_canonical(header).await?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the last `depth` canonical blocks of the block producer with `length` new blocks.
+    async fn reorg(&mut self, depth: u64, length: u64) -> eyre::Result<()> {
+        ensure!(length > 0, "reorg must produce at least one block");
+        let number = self.head.number().checked_sub(depth).ok_or_else(|| {
+            eyre!("can't reorg {depth} blocks below block {}", self.head.number())
+        })?;
+        let mut parent = self
+            .producer()
+            .provider
+            .sealed_header(number)?
+            .ok_or_else(|| eyre!("block {number} not found"))?;
+
+        for _ in 0..length {
+            parent = self.build_block(&parent).await?;
+        }
+
+        let old = self.head.num_hash();
+        let new = parent.num_hash();
+        self.make_canonical(parent).await?;
+        info!(target: "reth::cli", ?old, ?new, depth, length, "Reorged block producer");
+        Ok(())
+    }
+
+    /// Builds a block on top of the given parent and inserts it into the block producer, without
+    /// making it canonical.
+    async fn build_block(&self, parent: &SealedHeader) -> eyre::Result<SealedHeader> {
+        let producer = self.producer();
+        let engine = &producer.beacon_engine_handle;
+
+        let res = engine
+            .fork_choice_updated(
+                forkchoice_state(parent.hash()),
+                Some(self.attributes.build(parent)),
+                EngineApiMessageVersion::default(),
+            )
+            .await?;
+        ensure!(res.is_valid(), "invalid forkchoice state on block {}", parent.hash());
+        let payload_id = res.payload_id.ok_or_eyre("no payload id")?;
+
+        let payload = producer
+            .payload_builder_handle
+            .resolve_kind(payload_id, PayloadKind::WaitForPending)
+            .await
+            .ok_or_eyre("no payload")??;
+        let block = payload.block();
+
+        let status = engine
+            .new_payload(<EthEngineTypes as PayloadTypes>::block_to_payload(block.clone()))
+            .await?;
+        ensure!(status.is_valid(), "invalid payload {}: {status:?}", block.hash());
+
+        Ok(block.clone_sealed_header())
+    }
+
+    /// Makes the given block the head of the block producer and sends it to all reachable nodes.
+    async fn make_canonical(&mut self, head: SealedHeader) -> eyre::Result<()> {
+        let res = self
+            .producer()
+            .beacon_engine_handle
+            .fork_choice_updated(
+                forkchoice_state(head.hash()),
+                None,
+                EngineApiMessageVersion::default(),
+            )
+            .await?;
+        ensure!(res.is_valid(), "invalid forkchoice state on block {}: {res:?}", head.hash());
+
+        self.head = head;
+        self.broadcast_head().await
+    }
+
+    /// Sends the head of the block producer to all nodes that can reach it.
+    async fn broadcast_head(&self) -> eyre::Result<()> {
+        let state = forkchoice_state(self.head.hash());
+        for idx in self.reachable().skip(1) {
+            self.nodes[idx]
+                .beacon_engine_handle
+                .fork_choice_updated(state, None, EngineApiMessageVersion::default())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Splits the network into the given groups of nodes. Nodes that aren't listed form another
+    /// group.
+    async fn partition(&mut self, groups: Vec<Vec<usize>>) -> eyre::Result<()> {
+        let unlisted = groups.len();
+        let mut partitions = vec![unlisted; self.nodes.len()];
+        for (partition, group) in groups.into_iter().enumerate() {
+            for idx in group {
+                ensure!(idx < self.nodes.len(), "node {idx} doesn't exist");
+                ensure!(partitions[idx] == unlisted, "node {idx} is listed twice");
+                partitions[idx] = partition;
+            }
+        }
+
+        self.partitions = partitions;
+        self.apply_topology().await
+    }
+
+    /// Reconnects all nodes and sends them the current head.
+    async fn heal(&mut self) -> eyre::Result<()> {
+        self.partitions = vec![0; self.nodes.len()];
+        self.apply_topology().await?;
+        self.broadcast_head().await
+    }
+
+    /// Connects all nodes of the same partition and disconnects all other nodes, then waits until
+    /// all sessions are (dis)connected.
+    async fn apply_topology(&self) -> eyre::Result<()> {
+        for (a, node) in self.nodes.iter().enumerate() {
+            for (b, other) in self.nodes.iter().enumerate().skip(a + 1) {
+                if self.partitions[a] == self.partitions[b] {
+                    node.network.add_peer(*other.network.peer_id(), other.network.local_addr());
+                } else {
+                    // remove the peers on both sides, so that neither redials the other
+                    node.network.remove_peer(*other.network.peer_id(), PeerKind::Basic);
+                    other.network.remove_peer(*node.network.peer_id(), PeerKind::Basic);
+                    node.network.disconnect_peer(*other.network.peer_id());
+                }
+            }
+        }
+
+        let expected = |idx: usize| {
+            self.partitions.iter().filter(|partition| **partition == self.partitions[idx]).count() -
+                1
+        };
+        poll_until(TOPOLOGY_TIMEOUT, || {
+            self.nodes
+                .iter()
+                .enumerate()
+                .all(|(idx, node)| node.network.num_connected_peers() == expected(idx))
+        })
+        .await
+        .map_err(|_| eyre!("peers did not (dis)connect within {TOPOLOGY_TIMEOUT:?}"))
+    }
+
+    /// Waits until all nodes that can reach the block producer have synced its head.
+    async fn wait_sync(&self, timeout: Duration) -> eyre::Result<()> {
+        let head = self.head.num_hash();
+        poll_until(timeout, || {
+            self.reachable().all(|idx| {
+                let provider = &self.nodes[idx].provider;
+                provider.best_block_number().ok() == Some(head.number) &&
+                    provider.block_hash(head.number).ok().flatten() == Some(head.hash)
+            })
+        })
+        .await
+        .map_err(|_| eyre!("nodes did not sync block {head:?} within {timeout:?}"))?;
+        info!(target: "reth::cli", ?head, "Nodes synced");
+        Ok(())
+    }
+}
+
+/// Polls the condition until it holds or the timeout elapses.
+async fn poll_until(
+    timeout: Duration,
+    mut condition: impl FnMut() -> bool,
+) -> Result<(), tokio::time::error::Elapsed> {
+    tokio::time::timeout(timeout, async {
+        while !condition() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+}
+
+/// Forkchoice state with the given head.
+///
+/// Blocks are never marked safe or finalized, so that reorgs of any depth can be scripted.
+const fn forkchoice_state(head_block_hash: B256) -> ForkchoiceState {
+    ForkchoiceState {
+        head_block_hash,
+        safe_block_hash: B256::ZERO,
+        finalized_block_hash: B256::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devnet::{devnet_chain_spec, launch_node};
+    use alloy_primitives::U256;
+    use reth_rpc_server_types::RpcModuleSelection;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_through_partition_and_reorg() {
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = devnet_chain_spec(None, [], U256::ZERO).unwrap();
+        let datadir = tempfile::tempdir().unwrap();
+        let executor = reth_tasks::Runtime::test();
+        let mut nodes = Vec::new();
+        for index in 0..3 {
+            let (node, _) = launch_node(
+                index,
+                chain_spec.clone(),
+                datadir.path(),
+                RpcModuleSelection::Standard,
+                executor.clone(),
+            )
+            .await
+            .unwrap();
+            nodes.push(node);
+        }
+        for node in &nodes {
+            assert!(node.rpc_server_handles.rpc.ipc_endpoint().is_none());
+        }
+
+        // blocks are only produced by the steps below
+        let mut devnet = Devnet::new(nodes, chain_spec, Duration::from_secs(3600)).await.unwrap();
+        devnet.mine(3).await.unwrap();
+        devnet.wait_sync(Duration::from_secs(30)).await.unwrap();
+
+        // node 2 keeps the old chain while the block producer reorgs
+        let old = devnet.head.num_hash();
+        devnet.partition(vec![vec![0, 1], vec![2]]).await.unwrap();
+        devnet.reorg(1, 2).await.unwrap();
+        devnet.wait_sync(Duration::from_secs(30)).await.unwrap();
+        assert_eq!(devnet.head.number(), old.number + 1);
+        assert_eq!(devnet.nodes[2].provider.block_hash(old.number).unwrap(), Some(old.hash));
+
+        // after healing, node 2 reorgs to the new chain
+        devnet.heal().await.unwrap();
+        devnet.wait_sync(Duration::from_secs(60)).await.unwrap();
+        let head = devnet.head.num_hash();
+        for node in &devnet.nodes {
+            assert_eq!(node.provider.best_block_number().unwrap(), head.number);
+            assert_eq!(node.provider.block_hash(head.number).unwrap(), Some(head.hash));
+            assert_ne!(node.provider.block_hash(old.number).unwrap(), Some(old.hash));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Control scripts for the devnet.
+//!
+//! A script is a list of steps, one per line. Empty lines and everything after a `#` are ignored:
+//!
+//! ```text
+//! # let the network run for a bit
+//! mine 5
+//! wait-sync
+//! # isolate the last node and build a competing chain on top of block head-2
+//! partition 0,1 2
+//! reorg 2
+//! sleep 10s
+//! heal
+//! wait-sync 30s
+//! ```
+
+use eyre::{bail, eyre, WrapErr};
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Default time to wait for the nodes to reach the head of the block producer.
+pub(crate) const DEFAULT_SYNC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A single step of a devnet script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// `mine <N>`: produce `N` blocks right away.
+    Mine(u64),
+    /// `sleep <DURATION>`: keep producing blocks at the configured block time for the duration.
+    Sleep(Duration),
+    /// `pause`: stop producing blocks at the configured block time.
+    Pause,
+    /// `resume`: continue producing blocks at the configured block time.
+    Resume,
+    /// `reorg <DEPTH> [<LENGTH>]`: replace the last `DEPTH` blocks of the block producer with
+    /// `LENGTH` new blocks, `DEPTH + 1` by default.
+    Reorg {
+        /// Number of canonical blocks to replace.
+        depth: u64,
+        /// Number of blocks of the new chain.
+        length: u64,
+    },
+    /// `partition <NODES> <NODES>...`: split the network into groups of comma separated node
+    /// indices that can't reach each other. Nodes that are not listed form another group.
+    Partition(Vec<Vec<usize>>),
+    /// `heal`: reconnect all nodes.
+    Heal,
+    /// `wait-sync [<TIMEOUT>]`: wait until all nodes that can reach the block producer have
+    /// synced its head.
+    WaitSync(Duration),
+    /// `exit`: shut down the devnet.
+    Exit,
+}
+
+impl FromStr for Step {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let Some(cmd) = words.next() else { bail!("empty step") };
+        let args = words.collect::<Vec<_>>();
+
+        let step = match (cmd, args.as_slice()) {
+            ("mine", [n]) => Self::Mine(n.parse()?),
+            ("sleep", duration) if !duration.is_empty() => {
+                Self::Sleep(humantime::parse_duration(&duration.join(" "))?)
+            }
+            ("pause", []) => Self::Pause,
+            ("resume", []) => Self::Resume,
+            ("reorg", [depth]) => {
+                let depth = depth.parse()?;
+                Self::Reorg { depth, length: depth + 1 }
+            }
+            ("reorg", [depth, length]) => {
+                Self::Reorg { depth: depth.parse()?, length: length.parse()? }
+            }
+            ("partition", groups) if !groups.is_empty() => Self::Partition(
+                groups
+                    .iter()
+                    .map(|group| group.split(',').map(usize::from_str).collect())
+                    .collect::<Result<_, _>>()?,
+            ),
+            ("heal", []) => Self::Heal,
+            ("wait-sync", []) => Self::WaitSync(DEFAULT_SYNC_TIMEOUT),
+            ("wait-sync", timeout) => {
+                Self::WaitSync(humantime::parse_duration(&timeout.join(" "))?)
+            }
+            ("exit", []) => Self::Exit,
+            _ => bail!("invalid step `{s}`"),
+        };
+        Ok(step)
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mine(n) => write!(f, "mine {n}"),
+            Self::Sleep(duration) => write!(f, "sleep {}", humantime::format_duration(*duration)),
+            Self::Pause => f.write_str("pause"),
+            Self::Resume => f.write_str("resume"),
+            Self::Reorg { depth, length } => write!(f, "reorg {depth} {length}"),
+            Self::Partition(groups) => {
+                f.write_str("partition")?;
+                for group in groups {
+                    let nodes = group.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    write!(f, " {}", nodes.join(","))?;
+                }
+                Ok(())
+            }
+            Self::Heal => f.write_str("heal"),
+            Self::WaitSync(timeout) => {
+                write!(f, "wait-sync {}", humantime::format_duration(*timeout))
+            }
+            Self::Exit => f.write_str("exit"),
+        }
+    }
+}
+
+/// Parses a devnet script.
+pub fn parse_script(script: &str) -> eyre::Result<Vec<Step>> {
+    script
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            (!line.is_empty()).then(|| line.parse().wrap_err_with(|| eyre!("line {}", idx + 1)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steps() {
+        let script = r"
+            # warm up
+            mine 5
+            wait-sync
+            partition 0,1 2   # isolate node 2
+            reorg 2
+            reorg 3 1
+            sleep 1500ms
+            pause
+            resume
+            heal
+            wait-sync 30s
+            exit
+        ";
+        let steps = parse_script(script).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                Step::Mine(5),
+                Step::WaitSync(DEFAULT_SYNC_TIMEOUT),
+                Step::Partition(vec![vec![0, 1], vec![2]]),
+                Step::Reorg { depth: 2, length: 3 },
+                Step::Reorg { depth: 3, length: 1 },
+                Step::Sleep(Duration::from_millis(1500)),
+                Step::Pause,
+                Step::Resume,
+                Step::Heal,
+                Step::WaitSync(Duration::from_secs(30)),
+                Step::Exit,
+            ]
+        );
+
+        for step in steps {
+            assert_eq!(step.to_string().parse::<Step>().unwrap(), step);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_steps() {
+        for step in ["mine", "mine x", "partition", "partition 0,a", "sleep", "heal 1", "fork 2"] {
+            assert!(step.parse::<Step>().is_err(), "{step}");
+        }
+
+        let err = parse_script("mine 1\n\nreorg\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3");
+    }
+}
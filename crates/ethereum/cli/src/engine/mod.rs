@@ -0,0 +1,30 @@
+//! `reth engine` command.
+
+use clap::{Parser, Subcommand};
+use reth_cli_runner::CliContext;
+
+pub mod replay;
+
+/// Engine API debugging utilities
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth engine` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Replay engine API messages recorded with `--debug.engine-api-store` and check that the
+    /// responses match the recorded ones.
+    Replay(Box<replay::Command>),
+}
+
+impl Command {
+    /// Execute `engine` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Replay(command) => command.execute(ctx).await,
+        }
+    }
+}
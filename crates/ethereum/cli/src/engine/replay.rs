@@ -0,0 +1,266 @@
+//! `reth engine replay` command.
+//!
+//! Replays engine API messages recorded with `--debug.engine-api-store` against a node that is
+//! launched on the given datadir, and checks that every response matches the recorded one.
+//!
+//! Before the replay, the datadir is unwound to the parent of the first recorded payload, the block
+//! the node was at when the recording started. The node is launched without networking, so the
+//! recording must contain every payload the node needs.
+
+use crate::chainspec::EthereumChainSpecParser;
+use alloy_primitives::{BlockNumber, B256};
+use clap::Parser;
+use eyre::{bail, ensure, WrapErr};
+use reth_chainspec::ChainSpecProvider;
+use reth_cli_commands::{
+    common::{AccessRights, Environment, EnvironmentArgs},
+    stage::unwind::unwind_to_block,
+};
+use reth_cli_runner::CliContext;
+use reth_db::init_db;
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
+use reth_node_api::{ConsensusEngineHandle, EngineApiMessageVersion};
+use reth_node_builder::{FullNodeFor, NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::{args::RpcServerArgs, exit::NodeExitFuture};
+use reth_node_ethereum::{EthEngineTypes, EthEvmConfig, EthereumNode};
+use reth_provider::{BlockHashReader, BlockNumReader};
+use reth_tasks::TaskExecutor;
+use std::{path::PathBuf, time::Duration};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// `reth engine replay` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs<EthereumChainSpecParser>,
+
+    /// Directory the engine API messages were recorded to with `--debug.engine-api-store`.
+    #[arg(long, value_name = "DIR")]
+    from_store: PathBuf,
+
+    /// Replays the messages this many times faster than they were recorded.
+    #[arg(long, value_name = "FACTOR", value_parser = parse_speed, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Sends every message as soon as the response to the previous one arrived, ignoring the
+    /// recorded timing.
+    #[arg(long, conflicts_with = "speed")]
+    no_delay: bool,
+
+    /// Stops at the first response that does not match the recorded one.
+    #[arg(long)]
+    fail_fast: bool,
+}
+
+/// An engine API message read from the store.
+#[derive(Debug)]
+struct RecordedMessage {
+    /// Unix timestamp in milliseconds the message was received at.
+    timestamp: u64,
+    /// Path of the message in the store.
+    path: PathBuf,
+    /// The message.
+    message: StoredEngineApiMessage<EthEngineTypes>,
+    /// The recorded response, if any. Older recordings do not contain responses.
+    response: Option<StoredEngineApiResponse>,
+}
+
+impl Command {
+    /// Execute `engine replay` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let store = EngineMessageStore::new(self.from_store.clone());
+        let messages = store
+            .engine_messages()
+            .wrap_err_with(|| format!("failed to read store {}", self.from_store.display()))?
+            .into_iter()
+            .map(|(timestamp, path)| {
+                Ok(RecordedMessage {
+                    timestamp,
+                    message: EngineMessageStore::read_message(&path)
+                        .wrap_err_with(|| format!("failed to read {}", path.display()))?,
+                    response: EngineMessageStore::read_response(&path).wrap_err_with(|| {
+                        format!("failed to read response to {}", path.display())
+                    })?,
+                    path,
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        ensure!(!messages.is_empty(), "no engine API messages in {}", self.from_store.display());
+
+        if let Some((number, hash)) = starting_block(&messages) {
+            self.restore(ctx.task_executor.clone(), number, hash)?;
+        } else {
+            warn!(target: "reth::cli", "No payloads recorded, replaying on top of the current head");
+        }
+
+        let (node, node_exit_future) = self.launch_node(ctx.task_executor).await?;
+        let engine = node.add_ons_handle.beacon_engine_handle.clone();
+        tokio::select! {
+            res = self.replay(&engine, messages) => res,
+            res = node_exit_future => {
+                res?;
+                bail!("node exited during the replay")
+            }
+        }
+    }
+
+    /// Unwinds the datadir to the given block, which must be the parent of the first recorded
+    /// payload.
+    fn restore(&self, runtime: TaskExecutor, number: BlockNumber, hash: B256) -> eyre::Result<()> {
+        let Environment { provider_factory, config, data_dir: _ } =
+            self.env.init::<EthereumNode>(AccessRights::RW, runtime)?;
+
+        let last = provider_factory.provider()?.last_block_number()?;
+        if last < number {
+            bail!("datadir is at block {last}, behind the starting block {number} of the recording")
+        }
+        if last > number {
+            info!(target: "reth::cli", from = last, to = number, "Restoring datadir to the starting block of the recording");
+            let evm_config = EthEvmConfig::new(provider_factory.chain_spec());
+            unwind_to_block(provider_factory.clone(), config, evm_config, number, false)?;
+        }
+
+        let restored = provider_factory.block_hash(number)?;
+        ensure!(
+            restored == Some(hash),
+            "block {number} of the datadir is {restored:?}, but the recording starts on top of {hash}"
+        );
+        Ok(())
+    }
+
+    /// Launches a node on the datadir, without networking and with all RPC servers but the
+    /// authenticated one disabled.
+    async fn launch_node(
+        &self,
+        executor: TaskExecutor,
+    ) -> eyre::Result<(FullNodeFor<EthereumNode>, NodeExitFuture)> {
+        let rpc = RpcServerArgs { ipcdisable: true, ..Default::default() };
+        let mut config = NodeConfig::new(self.env.chain.clone())
+            .with_datadir_args(self.env.datadir.clone())
+            .with_db(self.env.db)
+            .with_storage(self.env.storage)
+            .with_rpc(rpc)
+            .with_disabled_discovery()
+            .with_unused_ports();
+        config.config = self.env.config.clone();
+        config.static_files = self.env.static_files;
+
+        let database = init_db(config.datadir().db(), self.env.db.database_args())?;
+        let NodeHandle { node, node_exit_future } = NodeBuilder::new(config)
+            .with_database(database)
+            .with_launch_context(executor)
+            .launch_node(EthereumNode::default())
+            .await?;
+
+        Ok((node, node_exit_future))
+    }
+
+    /// Sends the messages to the engine and compares the responses to the recorded ones.
+    async fn replay(
+        &self,
+        engine: &ConsensusEngineHandle<EthEngineTypes>,
+        messages: Vec<RecordedMessage>,
+    ) -> eyre::Result<()> {
+        let total = messages.len();
+        let first_timestamp = messages[0].timestamp;
+        let start = Instant::now();
+        let (mut matched, mut mismatched, mut unchecked) = (0usize, 0usize, 0usize);
+        info!(target: "reth::cli", total, speed = self.speed, no_delay = self.no_delay, "Replaying engine API messages");
+
+        for RecordedMessage { timestamp, path, message, response } in messages {
+            if !self.no_delay {
+                let offset = Duration::from_millis(timestamp - first_timestamp).div_f64(self.speed);
+                tokio::time::sleep_until(start + offset).await;
+            }
+
+            let actual = match message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    match engine
+                        .fork_choice_updated(
+                            state,
+                            payload_attrs,
+                            EngineApiMessageVersion::default(),
+                        )
+                        .await
+                    {
+                        Ok(updated) => StoredEngineApiResponse::ForkchoiceUpdated(updated),
+                        Err(error) => StoredEngineApiResponse::Error(error.to_string()),
+                    }
+                }
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    match engine.new_payload(payload).await {
+                        Ok(status) => StoredEngineApiResponse::NewPayload(status),
+                        Err(error) => StoredEngineApiResponse::Error(error.to_string()),
+                    }
+                }
+            };
+
+            let Some(expected) = response else {
+                debug!(target: "reth::cli", ?path, ?actual, "No recorded response");
+                unchecked += 1;
+                continue
+            };
+            match actual.mismatch(&expected) {
+                None => {
+                    debug!(target: "reth::cli", ?path, "Response matches");
+                    matched += 1;
+                }
+                Some(mismatch) => {
+                    warn!(target: "reth::cli", ?path, %mismatch, "Response does not match the recorded one");
+                    mismatched += 1;
+                    if self.fail_fast {
+                        bail!("response to {} does not match: {mismatch}", path.display())
+                    }
+                }
+            }
+        }
+
+        info!(target: "reth::cli", total, matched, mismatched, unchecked, elapsed = ?start.elapsed(), "Replayed engine API messages");
+        ensure!(
+            mismatched == 0,
+            "{mismatched} of {total} responses do not match the recorded ones"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the number and hash of the parent of the first recorded payload.
+fn starting_block(messages: &[RecordedMessage]) -> Option<(BlockNumber, B256)> {
+    messages.iter().find_map(|recorded| match &recorded.message {
+        StoredEngineApiMessage::NewPayload { payload } => {
+            Some((payload.block_number().saturating_sub(1), payload.parent_hash()))
+        }
+        StoredEngineApiMessage::ForkchoiceUpdated { .. } => None,
+    })
+}
+
+/// Parses a replay speed, which must be a positive number.
+fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        Ok(_) => Err("speed must be a positive number".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command() {
+        let cmd = Command::parse_from(["reth", "--from-store", "store", "--speed", "2.5"]);
+        assert_eq!(cmd.from_store, PathBuf::from("store"));
+        assert_eq!(cmd.speed, 2.5);
+        assert!(!cmd.no_delay);
+
+        let cmd = Command::parse_from(["reth", "--from-store", "store", "--no-delay"]);
+        assert!(cmd.no_delay);
+
+        assert!(Command::try_parse_from(["reth", "--from-store", "store", "--speed", "0"]).is_err());
+        assert!(Command::try_parse_from(["reth", "--speed", "2"]).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+//! `reth exex` command.
+
+use clap::{Parser, Subcommand};
+
+pub mod wal;
+
+/// `ExEx` utilities
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Inspect and repair the `ExEx` write-ahead log while the node is stopped.
+    Wal(Box<wal::Command>),
+}
+
+impl Command {
+    /// Execute `exex` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Wal(command) => command.execute().await,
+        }
+    }
+}
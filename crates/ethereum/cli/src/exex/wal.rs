@@ -0,0 +1,247 @@
+//! `reth exex wal` command.
+//!
+//! Reads and modifies the files of the `ExEx` write-ahead log directly, so that a WAL that the node
+//! fails to load, e.g. because of a corrupted file or because it has grown above the configured
+//! retention limits, can be inspected and repaired. The node must not be running.
+
+use crate::chainspec::EthereumChainSpecParser;
+use alloy_primitives::BlockNumber;
+use clap::{Args, Parser, Subcommand};
+use eyre::{bail, WrapErr};
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_exex::{ExExNotification, Storage};
+use reth_node_core::args::DatadirArgs;
+use std::{ops::RangeInclusive, path::PathBuf, sync::Arc};
+
+/// `reth exex wal` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Lists the WAL entries with their file sizes and block ranges.
+    List {
+        /// Location of the WAL.
+        #[command(flatten)]
+        wal: WalArgs,
+    },
+    /// Prints a WAL entry as JSON.
+    Dump {
+        /// Location of the WAL.
+        #[command(flatten)]
+        wal: WalArgs,
+
+        /// ID of the entry to print.
+        id: u32,
+    },
+    /// Decodes every WAL entry and reports the ones that fail to decode.
+    Verify {
+        /// Location of the WAL.
+        #[command(flatten)]
+        wal: WalArgs,
+    },
+    /// Removes WAL entries.
+    Truncate {
+        /// Location of the WAL.
+        #[command(flatten)]
+        wal: WalArgs,
+
+        /// Removes the entries whose highest block is at or below this block. `ExEx`es can no
+        /// longer revert to the removed blocks.
+        #[arg(long, value_name = "BLOCK_NUMBER", required_unless_present = "corrupted")]
+        to_block: Option<BlockNumber>,
+
+        /// Removes the entries that fail to decode.
+        #[arg(long)]
+        corrupted: bool,
+    },
+}
+
+/// Arguments to locate the WAL.
+#[derive(Debug, Args)]
+pub struct WalArgs {
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = EthereumChainSpecParser::help_message(),
+        default_value = EthereumChainSpecParser::default_value(),
+        value_parser = EthereumChainSpecParser::parser()
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// Path to the WAL directory. Defaults to the `exex/wal` directory of the datadir.
+    #[arg(long, value_name = "DIR")]
+    wal_dir: Option<PathBuf>,
+}
+
+impl WalArgs {
+    /// Opens the WAL storage.
+    fn storage(self) -> eyre::Result<Storage> {
+        let path = self
+            .wal_dir
+            .unwrap_or_else(|| self.datadir.resolve_datadir(self.chain.chain()).exex_wal());
+        if !path.is_dir() {
+            bail!("WAL directory {} does not exist", path.display())
+        }
+        Storage::new(&path).wrap_err_with(|| format!("failed to open WAL at {}", path.display()))
+    }
+}
+
+impl Command {
+    /// Execute `exex wal` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::List { wal } => {
+                let storage = wal.storage()?;
+                println!(
+                    "{:>10} {:>12} {:>10} {:>20}  ENTRY",
+                    "ID", "SIZE", "COMPRESSED", "MODIFIED"
+                );
+                for id in storage.file_ids()? {
+                    let Some(info) = storage.file_info(id)? else { continue };
+                    let modified = humantime::format_rfc3339_seconds(info.modified);
+                    let entry = match storage.read_notification(id) {
+                        Ok(Some((notification, _))) => describe(&notification),
+                        Ok(None) => continue,
+                        Err(err) => format!("corrupted: {err}"),
+                    };
+                    println!(
+                        "{id:>10} {:>12} {:>10} {modified:>20}  {entry}",
+                        info.size, info.compressed
+                    );
+                }
+            }
+            Subcommands::Dump { wal, id } => {
+                let (notification, _) = wal
+                    .storage()?
+                    .read_notification(id)?
+                    .ok_or_else(|| eyre::eyre!("WAL entry {id} does not exist"))?;
+                println!("{}", serde_json::to_string_pretty(&notification)?);
+            }
+            Subcommands::Verify { wal } => {
+                let storage = wal.storage()?;
+                let file_ids = storage.file_ids()?;
+                let corrupted = corrupted_entries(&storage, &file_ids);
+                for (id, err) in &corrupted {
+                    println!("{id}: {err}");
+                }
+                if !corrupted.is_empty() {
+                    bail!(
+                        "{} of {} WAL entries are corrupted, remove them with `reth exex wal truncate --corrupted`",
+                        corrupted.len(),
+                        file_ids.len()
+                    )
+                }
+                println!("All {} WAL entries are valid", file_ids.len());
+            }
+            Subcommands::Truncate { wal, to_block, corrupted } => {
+                let storage = wal.storage()?;
+                let file_ids = storage.file_ids()?;
+
+                let mut removed = Vec::new();
+                if corrupted {
+                    removed.extend(corrupted_entries(&storage, &file_ids).into_iter().map(|e| e.0));
+                }
+                if let Some(to_block) = to_block {
+                    for &id in &file_ids {
+                        if let Ok(Some((notification, _))) = storage.read_notification(id) &&
+                            max_block(&notification) <= to_block
+                        {
+                            removed.push(id);
+                        }
+                    }
+                }
+
+                let (removed_entries, removed_size) = storage.remove_notifications(removed)?;
+                println!(
+                    "Removed {removed_entries} of {} WAL entries, {removed_size} bytes",
+                    file_ids.len()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the IDs of the entries that fail to decode, along with the errors.
+fn corrupted_entries(storage: &Storage, file_ids: &[u32]) -> Vec<(u32, String)> {
+    file_ids
+        .iter()
+        .filter_map(|&id| storage.read_notification(id).err().map(|err| (id, err.to_string())))
+        .collect()
+}
+
+/// Returns the highest block of the committed and reverted chains of the notification.
+fn max_block(notification: &ExExNotification) -> BlockNumber {
+    let committed = notification.committed_chain().map(|chain| *chain.range().end());
+    let reverted = notification.reverted_chain().map(|chain| *chain.range().end());
+    committed.max(reverted).unwrap_or_default()
+}
+
+/// Describes the kind and the block ranges of the notification.
+fn describe(notification: &ExExNotification) -> String {
+    let range = |range: RangeInclusive<BlockNumber>| format!("{}..={}", range.start(), range.end());
+    match notification {
+        ExExNotification::ChainCommitted { new } => format!("committed {}", range(new.range())),
+        ExExNotification::ChainReorged { old, new } => {
+            format!("reorged {} to {}", range(old.range()), range(new.range()))
+        }
+        ExExNotification::ChainReverted { old } => format!("reverted {}", range(old.range())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[tokio::test]
+    async fn truncate_wal() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir)?;
+
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=2,
+            BlockRangeParams { tx_count: 0..1, ..Default::default() },
+        );
+        for (id, block) in blocks.into_iter().enumerate() {
+            let notification = ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(
+                    vec![block.try_recover()?],
+                    Default::default(),
+                    Default::default(),
+                )),
+            };
+            storage.write_notification(id as u32, &notification)?;
+        }
+        std::fs::write(temp_dir.path().join("3.wal"), b"corrupted")?;
+
+        let run = |args: &[&str]| {
+            let wal_dir = temp_dir.path().to_str().unwrap();
+            Command::parse_from(std::iter::once(&"wal").chain(args).chain(&["--wal-dir", wal_dir]))
+                .execute()
+        };
+
+        assert!(run(&["verify"]).await.is_err());
+        run(&["truncate", "--corrupted", "--to-block", "1"]).await?;
+        run(&["verify"]).await?;
+        assert_eq!(storage.file_ids()?, vec![2]);
+
+        Ok(())
+    }
+}
@@ -318,6 +318,18 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Launch a local multi-node devnet
+    #[command(name = "devnet")]
+    Devnet(Box<crate::devnet::Command>),
+    /// Engine API debugging utilities
+    #[command(name = "engine")]
+    Engine(Box<crate::engine::Command>),
+    /// `ExEx` utilities
+    #[command(name = "exex")]
+    ExEx(Box<crate::exex::Command>),
+    /// Stateless block validation
+    #[command(name = "stateless")]
+    Stateless(Box<crate::stateless::Command>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -358,6 +370,10 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Devnet(_) => None,
+            Self::Engine(_) => None,
+            Self::ExEx(_) => None,
+            Self::Stateless(_) => None,
             Self::Ext(_) => None,
         }
     }
@@ -12,7 +12,15 @@
 pub mod app;
 /// Chain specification parser.
 pub mod chainspec;
+/// Local multi-node devnet.
+pub mod devnet;
+/// Engine API debugging utilities.
+pub mod engine;
+/// `ExEx` utilities.
+pub mod exex;
 pub mod interface;
+/// Stateless block validation.
+pub mod stateless;
 
 pub use app::{CliApp, ExtendedCommand};
 pub use interface::{Cli, Commands, NoSubCmd};
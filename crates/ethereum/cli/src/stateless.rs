@@ -0,0 +1,123 @@
+//! `reth stateless` command.
+//!
+//! Validates blocks without a database, using only the block and its execution witness, see
+//! [`reth_stateless`].
+
+use crate::chainspec::EthereumChainSpecParser;
+use alloy_primitives::hex;
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_ethereum_primitives::Block;
+use reth_node_ethereum::EthEvmConfig;
+use reth_stateless::stateless_validation;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Stateless block validation
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth stateless` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Verify a block against its execution witness
+    Verify(VerifyCommand),
+}
+
+/// Verify a block against its execution witness
+#[derive(Debug, Parser)]
+pub struct VerifyCommand {
+    /// The chain the block belongs to.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = EthereumChainSpecParser::help_message(),
+        default_value = EthereumChainSpecParser::default_value(),
+        value_parser = EthereumChainSpecParser::parser()
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// File with the RLP encoded block, either as raw bytes or as hex string like the result of
+    /// `debug_getRawBlock`.
+    #[arg(long, value_name = "FILE")]
+    block: PathBuf,
+
+    /// JSON file with the execution witness of the block, like the result of
+    /// `debug_executionWitness`.
+    #[arg(long, value_name = "FILE")]
+    witness: PathBuf,
+}
+
+impl Command {
+    /// Execute `stateless` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Verify(command) => command.execute().await,
+        }
+    }
+}
+
+impl VerifyCommand {
+    /// Execute `stateless verify` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let block = read_block(&self.block)
+            .wrap_err_with(|| format!("failed to read block from {}", self.block.display()))?;
+        let witness: ExecutionWitness = serde_json::from_slice(&reth_fs_util::read(&self.witness)?)
+            .wrap_err_with(|| format!("failed to read witness from {}", self.witness.display()))?;
+
+        let number = block.header.number;
+        let hash =
+            stateless_validation(block, witness, self.chain.clone(), EthEvmConfig::new(self.chain))
+                .wrap_err_with(|| format!("block {number} is invalid"))?;
+
+        info!(target: "reth::cli", number, %hash, "Block is valid");
+        Ok(())
+    }
+}
+
+/// Reads an RLP encoded block, which is either stored as raw bytes or as a hex string.
+fn read_block(path: &Path) -> eyre::Result<Block> {
+    let contents = reth_fs_util::read(path)?;
+    let bytes = std::str::from_utf8(&contents)
+        .ok()
+        .and_then(|text| hex::decode(text.trim().trim_matches('"')).ok())
+        .unwrap_or(contents);
+    Ok(Block::decode(&mut bytes.as_slice())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_ethereum_primitives::BlockBody;
+
+    #[test]
+    fn reads_hex_and_raw_blocks() {
+        let block = Block {
+            header: Header { number: 1, ..Default::default() },
+            body: BlockBody::default(),
+        };
+        let encoded = alloy_rlp::encode(&block);
+        let dir = tempfile::tempdir().unwrap();
+
+        let raw = dir.path().join("block.rlp");
+        reth_fs_util::write(&raw, &encoded).unwrap();
+        assert_eq!(read_block(&raw).unwrap(), block);
+
+        let hex = dir.path().join("block.hex");
+        reth_fs_util::write(&hex, format!("\"{}\"\n", hex::encode_prefixed(&encoded))).unwrap();
+        assert_eq!(read_block(&hex).unwrap(), block);
+    }
+}
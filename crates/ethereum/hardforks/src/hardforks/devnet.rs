@@ -0,0 +1,12 @@
+use alloy_hardforks::hardfork;
+
+hardfork!(
+    /// Ethereum hardforks that are only scheduled on devnets.
+    ///
+    /// These forks have no activation on any public network, they are enabled through the genesis
+    /// config of a devnet, e.g. `"focilTime": 0`.
+    EthereumDevnetHardfork {
+        /// Fork-choice enforced inclusion lists: <https://eips.ethereum.org/EIPS/eip-7805>
+        Focil,
+    }
+);
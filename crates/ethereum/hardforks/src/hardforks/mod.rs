@@ -1,6 +1,9 @@
 mod dev;
 pub use dev::DEV_HARDFORKS;
 
+mod devnet;
+pub use devnet::EthereumDevnetHardfork;
+
 use crate::{ForkCondition, ForkFilter, ForkId, Hardfork, Head};
 #[cfg(feature = "std")]
 use rustc_hash::FxHashMap;
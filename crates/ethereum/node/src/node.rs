@@ -5,7 +5,7 @@ use crate::{EthEngineTypes, EthEvmConfig};
 use alloy_eips::{eip7840::BlobParams, merge::EPOCH_SLOTS};
 use alloy_network::Ethereum;
 use alloy_rpc_types_engine::ExecutionData;
-use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, Hardforks};
+use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, ForkCondition, Hardforks};
 use reth_engine_local::LocalPayloadAttributesBuilder;
 use reth_engine_primitives::EngineTypes;
 use reth_ethereum_consensus::EthBeaconConsensus;
@@ -34,14 +34,19 @@ use reth_node_builder::{
     },
     BuilderContext, DebugNode, Node, NodeAdapter,
 };
+use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::PayloadTypes;
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
 use reth_rpc::{
     eth::core::{EthApiFor, EthRpcConverterFor},
-    TestingApi, ValidationApi,
+    MinerApi, TestingApi, ValidationApi,
+};
+use reth_rpc_api::servers::{
+    BlockSubmissionValidationApiServer, EngineInclusionListApiServer, MinerApiServer,
+    TestingApiServer,
 };
-use reth_rpc_api::servers::{BlockSubmissionValidationApiServer, TestingApiServer};
 use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
+use reth_rpc_engine_api::InclusionListEngineApi;
 use reth_rpc_eth_api::{
     helpers::{
         config::{EthConfigApiServer, EthConfigHandler},
@@ -294,6 +299,22 @@ where
 
         let testing_skip_invalid_transactions = ctx.config.rpc.testing_skip_invalid_transactions;
 
+        let miner_api = MinerApi::new(ctx.builder_settings.clone());
+
+        // the inclusion list methods are only served on chains that schedule them
+        let inclusion_list_api = (ctx.config.chain.focil_activation() != ForkCondition::Never)
+            .then(|| {
+                InclusionListEngineApi::new(
+                    ctx.node.provider().clone(),
+                    ctx.config.chain.clone(),
+                    ctx.beacon_engine_handle.clone(),
+                    PayloadStore::new(ctx.node.payload_builder_handle().clone()),
+                    ctx.node.pool().clone(),
+                    ctx.inclusion_lists.clone(),
+                    ctx.config.engine.accept_execution_requests_hash,
+                )
+            });
+
         self.inner
             .launch_add_ons_with(ctx, move |container| {
                 container.modules.merge_if_module_configured(
@@ -305,6 +326,16 @@ where
                     .modules
                     .merge_if_module_configured(RethRpcModule::Eth, eth_config.into_rpc())?;
 
+                // the miner_ handlers retarget the payload builder, so they're only exposed on the
+                // authenticated and IPC transports, HTTP and WS keep the no-op handlers
+                let miner_module = miner_api.into_rpc();
+                container.modules.add_or_replace_ipc(miner_module.clone())?;
+                container.auth_module.merge_auth_methods(miner_module)?;
+
+                if let Some(inclusion_list_api) = inclusion_list_api {
+                    container.auth_module.merge_auth_methods(inclusion_list_api.into_rpc())?;
+                }
+
                 // testing_buildBlockV1: only wire when the hidden testing module is explicitly
                 // requested on any transport. Default stays disabled to honor security guidance.
                 let mut testing_api = TestingApi::new(
@@ -492,6 +523,7 @@ where
                 .kzg_settings(ctx.kzg_settings()?)
                 .with_max_tx_input_bytes(ctx.config().txpool.max_tx_input_bytes)
                 .with_local_transactions_config(pool_config.local_transactions_config.clone())
+                .with_inclusion_policy(ctx.inclusion_policy().clone())
                 .set_tx_fee_cap(ctx.config().rpc.rpc_tx_fee_cap)
                 .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
                 .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
@@ -56,6 +56,9 @@ where
                 .with_gas_limit(gas_limit)
                 .with_max_blobs_per_block(conf.max_blobs_per_block())
                 .with_extra_data(conf.extra_data()),
-        ))
+        )
+        .with_settings(ctx.builder_settings().clone())
+        .with_inclusion_policy(ctx.inclusion_policy().clone())
+        .with_inclusion_lists(ctx.inclusion_lists().clone()))
     }
 }
@@ -1,5 +1,6 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
 use alloy_primitives::Bytes;
+use reth_payload_builder_primitives::BuilderSettings;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
 
 /// Settings for the Ethereum builder.
@@ -16,6 +17,10 @@ pub struct EthereumBuilderConfig {
     pub max_blobs_per_block: Option<u64>,
     /// Extra data for built blocks.
     pub extra_data: Bytes,
+    /// Minimum priority fee per gas a transaction must pay to be included.
+    ///
+    /// If `None`, transactions are not filtered by their tip.
+    pub min_priority_fee: Option<u128>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -32,6 +37,7 @@ impl EthereumBuilderConfig {
             await_payload_on_missing: true,
             max_blobs_per_block: None,
             extra_data: Bytes::new(),
+            min_priority_fee: None,
         }
     }
 
@@ -59,6 +65,31 @@ impl EthereumBuilderConfig {
         self.extra_data = extra_data;
         self
     }
+
+    /// Set the minimum priority fee per gas for included transactions.
+    pub const fn with_min_priority_fee(mut self, min_priority_fee: Option<u128>) -> Self {
+        self.min_priority_fee = min_priority_fee;
+        self
+    }
+
+    /// Applies the runtime overrides of the given [`BuilderSettings`] on top of this config.
+    pub fn with_overrides(mut self, settings: &BuilderSettings) -> Self {
+        let BuilderSettings { gas_limit, extra_data, max_blobs_per_block, min_priority_fee } =
+            settings;
+        if let Some(gas_limit) = gas_limit {
+            self.desired_gas_limit = *gas_limit;
+        }
+        if let Some(extra_data) = extra_data {
+            self.extra_data = extra_data.clone();
+        }
+        if max_blobs_per_block.is_some() {
+            self.max_blobs_per_block = *max_blobs_per_block;
+        }
+        if min_priority_fee.is_some() {
+            self.min_priority_fee = *min_priority_fee;
+        }
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -10,14 +10,16 @@
 #![allow(clippy::useless_let_if_seq)]
 
 use alloy_consensus::Transaction;
-use alloy_primitives::U256;
+use alloy_primitives::{map::B256Set, U256};
 use alloy_rlp::Encodable;
+use alloy_rpc_types_engine::PayloadId;
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_consensus_common::validation::MAX_RLP_BLOCK_SIZE;
+use reth_engine_primitives::inclusion_list::{decode_inclusion_list, InclusionListStore};
 use reth_errors::{BlockExecutionError, BlockValidationError, ConsensusError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
@@ -26,13 +28,14 @@ use reth_evm::{
 };
 use reth_evm_ethereum::EthEvmConfig;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
-use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_payload_builder_primitives::{BuilderSettingsHandle, PayloadBuilderError};
 use reth_payload_primitives::PayloadBuilderAttributes;
-use reth_primitives_traits::transaction::error::InvalidTransactionError;
+use reth_primitives_traits::{transaction::error::InvalidTransactionError, Recovered};
 use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
+    policy::InclusionPolicyHandle,
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
@@ -61,6 +64,12 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// Runtime overrides applied on top of the builder configuration, if any.
+    settings: Option<BuilderSettingsHandle>,
+    /// Policy deciding which pool transactions may be included, if any.
+    inclusion_policy: Option<InclusionPolicyHandle>,
+    /// The inclusion lists payloads have to include, if any.
+    inclusion_lists: Option<InclusionListStore>,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
@@ -71,7 +80,74 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
         evm_config: EvmConfig,
         builder_config: EthereumBuilderConfig,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self {
+            client,
+            pool,
+            evm_config,
+            builder_config,
+            settings: None,
+            inclusion_policy: None,
+            inclusion_lists: None,
+        }
+    }
+
+    /// Configures the [`BuilderSettingsHandle`] whose overrides are applied to every build.
+    pub fn with_settings(mut self, settings: BuilderSettingsHandle) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Configures the [`InclusionPolicyHandle`] that filters the pool transactions of every build.
+    pub fn with_inclusion_policy(mut self, inclusion_policy: InclusionPolicyHandle) -> Self {
+        self.inclusion_policy = Some(inclusion_policy);
+        self
+    }
+
+    /// Configures the [`InclusionListStore`] whose inclusion lists are included by the payloads
+    /// they were set for.
+    pub fn with_inclusion_lists(mut self, inclusion_lists: InclusionListStore) -> Self {
+        self.inclusion_lists = Some(inclusion_lists);
+        self
+    }
+
+    /// Returns the builder configuration with the current runtime overrides applied.
+    pub fn builder_config(&self) -> EthereumBuilderConfig {
+        match &self.settings {
+            Some(settings) => self.builder_config.clone().with_overrides(&settings.get()),
+            None => self.builder_config.clone(),
+        }
+    }
+
+    /// Returns the best transactions from the pool, skipping those excluded by the inclusion
+    /// policy.
+    fn best_transactions(
+        &self,
+        attributes: BestTransactionsAttributes,
+    ) -> BestTransactionsIter<Pool>
+    where
+        Pool: TransactionPool,
+    {
+        let best = self.pool.best_transactions_with_attributes(attributes);
+        match &self.inclusion_policy {
+            Some(policy) => Box::new(policy.filter_best_transactions(best)),
+            None => best,
+        }
+    }
+
+    /// Returns the transactions of the inclusion list that was set for the payload with the given
+    /// id, skipping those excluded by the inclusion policy.
+    fn inclusion_list(&self, payload_id: PayloadId) -> Vec<Recovered<TransactionSigned>> {
+        let Some(inclusion_list) =
+            self.inclusion_lists.as_ref().and_then(|lists| lists.get_for_payload(&payload_id))
+        else {
+            return Vec::new()
+        };
+
+        let mut transactions = decode_inclusion_list::<TransactionSigned>(&inclusion_list);
+        if let Some(policy) = &self.inclusion_policy {
+            transactions.retain(|tx| policy.check_inclusion(tx.signer(), tx.inner()).is_none());
+        }
+        transactions
     }
 }
 
@@ -89,13 +165,15 @@ where
         &self,
         args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload(
+        let inclusion_list = self.inclusion_list(args.config.attributes.payload_id());
+        build_payload(
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
-            self.builder_config.clone(),
+            self.builder_config(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            inclusion_list,
+            |attributes| self.best_transactions(attributes),
         )
     }
 
@@ -103,7 +181,7 @@ where
         &self,
         _args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
     ) -> MissingPayloadBehaviour<Self::BuiltPayload> {
-        if self.builder_config.await_payload_on_missing {
+        if self.builder_config().await_payload_on_missing {
             MissingPayloadBehaviour::AwaitInProgress
         } else {
             MissingPayloadBehaviour::RaceEmptyPayload
@@ -120,9 +198,9 @@ where
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
-            self.builder_config.clone(),
+            self.builder_config(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            |attributes| self.best_transactions(attributes),
         )?
         .into_payload()
         .ok_or_else(|| PayloadBuilderError::MissingPayload)
@@ -143,6 +221,29 @@ pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
     args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     best_txs: F,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+{
+    build_payload(evm_config, client, pool, builder_config, args, Vec::new(), best_txs)
+}
+
+/// Constructs an Ethereum transaction payload like [`default_ethereum_payload`], executing the
+/// transactions of the given inclusion list before the best transactions from the pool.
+///
+/// Inclusion list transactions that don't pay the configured minimum tip, don't fit into the block
+/// or are invalid are skipped.
+fn build_payload<EvmConfig, Client, Pool, F>(
+    evm_config: EvmConfig,
+    client: Client,
+    pool: Pool,
+    builder_config: EthereumBuilderConfig,
+    args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
+    inclusion_list: Vec<Recovered<TransactionSigned>>,
+    best_txs: F,
+) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
@@ -213,7 +314,49 @@ where
 
     let withdrawals_rlp_length = attributes.withdrawals().length();
 
+    // execute the transactions of the inclusion list first, so that the payload satisfies it
+    let mut inclusion_list_txs = B256Set::default();
+    for tx in inclusion_list {
+        if cumulative_gas_used + tx.gas_limit() > block_gas_limit {
+            continue
+        }
+
+        // the configured minimum tip applies to inclusion list transactions as well
+        if let Some(minimum_priority_fee) = builder_config.min_priority_fee &&
+            tx.effective_tip_per_gas(base_fee).unwrap_or_default() < minimum_priority_fee
+        {
+            trace!(target: "payload_builder", ?tx, "skipping inclusion list transaction below minimum tip");
+            continue
+        }
+
+        let tx_rlp_len = tx.inner().length();
+        let gas_used = match builder.execute_transaction(tx.clone()) {
+            Ok(gas_used) => gas_used,
+            Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
+                error, ..
+            })) => {
+                trace!(target: "payload_builder", %error, ?tx, "skipping invalid inclusion list transaction");
+                continue
+            }
+            // this is an error that we should treat as fatal for this attempt
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        };
+
+        block_transactions_rlp_length += tx_rlp_len;
+
+        let miner_fee =
+            tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
+        total_fees += U256::from(miner_fee) * U256::from(gas_used);
+        cumulative_gas_used += gas_used;
+        inclusion_list_txs.insert(*tx.tx_hash());
+    }
+
     while let Some(pool_tx) = best_txs.next() {
+        // skip transactions that were already included from the inclusion list
+        if inclusion_list_txs.contains(pool_tx.hash()) {
+            continue
+        }
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -231,6 +374,17 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // skip transactions that don't pay the configured minimum tip
+        if let Some(minimum_priority_fee) = builder_config.min_priority_fee &&
+            pool_tx.effective_tip_per_gas(base_fee).unwrap_or_default() < minimum_priority_fee
+        {
+            best_txs.mark_invalid(
+                &pool_tx,
+                &InvalidPoolTransactionError::PriorityFeeBelowMinimum { minimum_priority_fee },
+            );
+            continue
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
@@ -352,8 +506,16 @@ where
         }
     }
 
+    // a payload that includes inclusion list transactions the best payload is missing is better,
+    // regardless of its fees
+    let includes_more_of_inclusion_list = best_payload.as_ref().is_some_and(|best| {
+        let best_txs: B256Set =
+            best.block().body().transactions().map(|tx| *tx.tx_hash()).collect();
+        !inclusion_list_txs.is_subset(&best_txs)
+    });
+
     // check if we have a better block
-    if !is_better_payload(best_payload.as_ref(), total_fees) {
+    if !includes_more_of_inclusion_list && !is_better_payload(best_payload.as_ref(), total_fees) {
         // Release db
         drop(builder);
         // can skip building the block
@@ -383,3 +545,95 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxEip1559};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, TxKind, B256};
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_primitives_traits::{SealedHeader, SignedTransaction};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+    use reth_transaction_pool::{noop::NoopTransactionPool, policy::DenylistPolicy};
+
+    fn transfer(rng: &mut impl rand::Rng, to: Address) -> Recovered<TransactionSigned> {
+        let tx = TxEip1559 {
+            chain_id: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(to),
+            ..Default::default()
+        };
+        sign_tx_with_random_key_pair(rng, tx.into()).try_into_recovered().unwrap()
+    }
+
+    #[test]
+    fn includes_inclusion_list_transactions_allowed_by_policy() {
+        let mut rng = generators::rng();
+        let allowed = transfer(&mut rng, Address::with_last_byte(1));
+        let denied = transfer(&mut rng, Address::with_last_byte(2));
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+        let provider = MockEthProvider::default().with_chain_spec(chain_spec.clone());
+        for tx in [&allowed, &denied] {
+            provider.add_account(
+                tx.signer(),
+                ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))),
+            );
+        }
+
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            ..Default::default()
+        });
+        let attributes = EthPayloadBuilderAttributes::new(
+            parent.hash(),
+            PayloadAttributes {
+                timestamp: 12,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Some(Vec::new()),
+                parent_beacon_block_root: Some(B256::ZERO),
+            },
+        );
+
+        let inclusion_lists = InclusionListStore::default();
+        inclusion_lists.insert_for_payload(
+            attributes.payload_id(),
+            [&allowed, &denied].into_iter().map(|tx| tx.inner().encoded_2718().into()).collect(),
+        );
+        let policy = InclusionPolicyHandle::new(DenylistPolicy {
+            recipients: std::iter::once(Address::with_last_byte(2)).collect(),
+            ..Default::default()
+        });
+
+        let builder = EthereumPayloadBuilder::new(
+            provider,
+            NoopTransactionPool::default(),
+            EthEvmConfig::new(chain_spec),
+            EthereumBuilderConfig::new(),
+        )
+        .with_inclusion_policy(policy)
+        .with_inclusion_lists(inclusion_lists);
+
+        let args = BuildArguments::new(
+            Default::default(),
+            PayloadConfig::new(Arc::new(parent), attributes),
+            Default::default(),
+            None,
+        );
+        let BuildOutcome::Better { payload, .. } = builder.try_build(args).unwrap() else {
+            panic!("expected a better payload")
+        };
+        let included: Vec<_> =
+            payload.block().body().transactions().map(|tx| *tx.tx_hash()).collect();
+        assert_eq!(included, vec![*allowed.tx_hash()]);
+    }
+}
@@ -0,0 +1,382 @@
+//! Execution profiling per contract, function selector and precompile.
+//!
+//! The [`ExecutionProfiler`] is a shared handle to a profile that is aggregated across all blocks
+//! executed with a [`ProfilingInspector`] obtained from it.
+
+use alloc::{sync::Arc, vec::Vec};
+use alloy_primitives::{
+    map::{AddressMap, HashMap},
+    Address, Selector,
+};
+use core::{
+    cmp::Reverse,
+    hash::{BuildHasher, Hash},
+    mem,
+    time::Duration,
+};
+use parking_lot::Mutex;
+use reth_primitives_traits::FastInstant as Instant;
+use revm::{
+    context_interface::{ContextTr, LocalContextTr},
+    interpreter::{
+        CallInput, CallInputs, CallOutcome, CreateInputs, CreateOutcome, InterpreterTypes,
+    },
+    Inspector,
+};
+
+/// Maximum number of entries tracked per category.
+///
+/// Once exceeded, the profile keeps the half of the entries with the most execution time.
+const MAX_ENTRIES: usize = 10_000;
+
+/// Gas and time spent executing a contract, selector or precompile.
+///
+/// For contracts and selectors this only includes the execution of the contract's own code, not
+/// the calls it makes to other contracts or precompiles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// Number of calls.
+    pub calls: u64,
+    /// Gas spent.
+    pub gas_used: u64,
+    /// Wall time spent.
+    pub time: Duration,
+}
+
+impl ExecutionStats {
+    fn record(&mut self, gas_used: u64, time: Duration) {
+        self.calls += 1;
+        self.gas_used += gas_used;
+        self.time += time;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.calls += other.calls;
+        self.gas_used += other.gas_used;
+        self.time += other.time;
+    }
+}
+
+/// The entries of an [`ExecutionProfile`] with the most execution time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionProfile {
+    /// Number of blocks the profile was aggregated over.
+    pub blocks: u64,
+    /// Contracts by the address of the executed code.
+    pub contracts: Vec<(Address, ExecutionStats)>,
+    /// Function selectors called, across all contracts.
+    pub selectors: Vec<(Selector, ExecutionStats)>,
+    /// Precompiles by address.
+    pub precompiles: Vec<(Address, ExecutionStats)>,
+}
+
+/// Aggregated execution stats.
+#[derive(Debug, Default)]
+struct Profile {
+    blocks: u64,
+    contracts: AddressMap<ExecutionStats>,
+    selectors: HashMap<Selector, ExecutionStats>,
+    precompiles: AddressMap<ExecutionStats>,
+}
+
+impl Profile {
+    fn merge(&mut self, other: Self) {
+        self.blocks += other.blocks;
+        merge_entries(&mut self.contracts, other.contracts);
+        merge_entries(&mut self.selectors, other.selectors);
+        merge_entries(&mut self.precompiles, other.precompiles);
+    }
+}
+
+#[derive(Debug)]
+struct ProfilerInner {
+    /// Default number of entries per category to report.
+    top: usize,
+    profile: Mutex<Profile>,
+}
+
+/// Shared handle to an execution profile aggregated across blocks.
+#[derive(Debug, Clone)]
+pub struct ExecutionProfiler {
+    inner: Arc<ProfilerInner>,
+}
+
+impl ExecutionProfiler {
+    /// Creates a new profiler that reports the `top` entries per category by default.
+    pub fn new(top: usize) -> Self {
+        Self { inner: Arc::new(ProfilerInner { top, profile: Default::default() }) }
+    }
+
+    /// Returns the default number of entries per category to report.
+    pub fn top(&self) -> usize {
+        self.inner.top
+    }
+
+    /// Returns an inspector that profiles the execution of a block.
+    ///
+    /// The collected stats are added to the profile when the inspector is dropped.
+    pub fn inspector(&self) -> ProfilingInspector {
+        ProfilingInspector {
+            profiler: self.clone(),
+            frames: Vec::new(),
+            profile: Profile { blocks: 1, ..Default::default() },
+        }
+    }
+
+    /// Returns up to `limit` entries per category with the most execution time.
+    pub fn profile(&self, limit: usize) -> ExecutionProfile {
+        let profile = self.inner.profile.lock();
+        ExecutionProfile {
+            blocks: profile.blocks,
+            contracts: top_entries(&profile.contracts, limit),
+            selectors: top_entries(&profile.selectors, limit),
+            precompiles: top_entries(&profile.precompiles, limit),
+        }
+    }
+
+    /// Clears the profile.
+    pub fn reset(&self) {
+        *self.inner.profile.lock() = Profile::default();
+    }
+}
+
+/// A call or create frame.
+#[derive(Debug)]
+struct Frame {
+    started: Instant,
+    /// Selector of the called function, `None` for creates and calls without calldata.
+    selector: Option<Selector>,
+    /// Gas spent by the nested frames.
+    children_gas: u64,
+    /// Time spent in the nested frames.
+    children_time: Duration,
+}
+
+impl Frame {
+    fn new(selector: Option<Selector>) -> Self {
+        Self { started: Instant::now(), selector, children_gas: 0, children_time: Duration::ZERO }
+    }
+}
+
+/// An [`Inspector`] that measures the gas and wall time of every call frame.
+///
+/// Time spent in nested calls is attributed to the callee, so the stats of a contract only
+/// include the execution of its own code. Contract creations are not attributed, but their
+/// nested calls are.
+#[derive(Debug)]
+pub struct ProfilingInspector {
+    profiler: ExecutionProfiler,
+    frames: Vec<Frame>,
+    profile: Profile,
+}
+
+impl ProfilingInspector {
+    /// Pops the current frame, returning its own gas and time, excluding nested frames.
+    fn exit_frame(&mut self, gas_used: u64) -> Option<(Frame, u64, Duration)> {
+        let frame = self.frames.pop()?;
+        let time = frame.started.elapsed();
+        if let Some(parent) = self.frames.last_mut() {
+            parent.children_gas += gas_used;
+            parent.children_time += time;
+        }
+        let own_gas = gas_used.saturating_sub(frame.children_gas);
+        let own_time = time.saturating_sub(frame.children_time);
+        Some((frame, own_gas, own_time))
+    }
+}
+
+impl<CTX, INTR> Inspector<CTX, INTR> for ProfilingInspector
+where
+    CTX: ContextTr,
+    INTR: InterpreterTypes,
+{
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        // the input has to be read here, a shared buffer may be overwritten once the call ends
+        let selector = match &inputs.input {
+            CallInput::Bytes(input) => input.get(..4).map(Selector::from_slice),
+            CallInput::SharedBuffer(range) => context
+                .local()
+                .shared_memory_buffer_slice(range.clone())
+                .and_then(|input| input.get(..4).map(Selector::from_slice)),
+        };
+        self.frames.push(Frame::new(selector));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        let Some((frame, own_gas, own_time)) = self.exit_frame(outcome.result.gas.spent()) else {
+            return
+        };
+        if outcome.was_precompile_called {
+            self.profile
+                .precompiles
+                .entry(inputs.bytecode_address)
+                .or_default()
+                .record(own_gas, own_time);
+            return
+        }
+        self.profile
+            .contracts
+            .entry(inputs.bytecode_address)
+            .or_default()
+            .record(own_gas, own_time);
+        if let Some(selector) = frame.selector {
+            self.profile.selectors.entry(selector).or_default().record(own_gas, own_time);
+        }
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frames.push(Frame::new(None));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.exit_frame(outcome.result.gas.spent());
+    }
+}
+
+impl Drop for ProfilingInspector {
+    fn drop(&mut self) {
+        let profile = mem::take(&mut self.profile);
+        self.profiler.inner.profile.lock().merge(profile);
+    }
+}
+
+/// Adds the stats of `other` to `entries`, keeping at most [`MAX_ENTRIES`] entries.
+fn merge_entries<K, S>(
+    entries: &mut HashMap<K, ExecutionStats, S>,
+    other: HashMap<K, ExecutionStats, S>,
+) where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    for (key, stats) in other {
+        entries.entry(key).or_default().merge(&stats);
+    }
+    if entries.len() > MAX_ENTRIES {
+        let mut times = entries.values().map(|stats| stats.time).collect::<Vec<_>>();
+        let (_, threshold, _) = times.select_nth_unstable_by(MAX_ENTRIES / 2, |a, b| b.cmp(a));
+        let threshold = *threshold;
+        entries.retain(|_, stats| stats.time > threshold);
+    }
+}
+
+/// Returns up to `limit` entries with the most execution time, in descending order.
+fn top_entries<K: Copy, S>(
+    entries: &HashMap<K, ExecutionStats, S>,
+    limit: usize,
+) -> Vec<(K, ExecutionStats)> {
+    let mut top = entries.iter().map(|(key, stats)| (*key, *stats)).collect::<Vec<_>>();
+    top.sort_unstable_by_key(|(_, stats)| Reverse(stats.time));
+    top.truncate(limit);
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthEvmFactory, Evm, EvmEnv, EvmFactory};
+    use alloy_primitives::{address, bytes, TxKind};
+    use revm::{
+        context::TxEnv,
+        database::{CacheDB, EmptyDB},
+        state::{AccountInfo, Bytecode},
+    };
+
+    fn stats(calls: u64, gas_used: u64, millis: u64) -> ExecutionStats {
+        ExecutionStats { calls, gas_used, time: Duration::from_millis(millis) }
+    }
+
+    #[test]
+    fn aggregates_profiles_across_blocks() {
+        let profiler = ExecutionProfiler::new(1);
+        let (hot, cold) = (address!("0x1000000000000000000000000000000000000001"), Address::ZERO);
+
+        for _ in 0..2 {
+            let mut inspector = profiler.inspector();
+            inspector.profile.contracts.insert(hot, stats(1, 100, 10));
+            inspector.profile.contracts.insert(cold, stats(1, 1_000, 1));
+            inspector.profile.selectors.insert(Selector::ZERO, stats(2, 1_100, 11));
+        }
+
+        let profile = profiler.profile(profiler.top());
+        assert_eq!(profile.blocks, 2);
+        assert_eq!(profile.contracts, vec![(hot, stats(2, 200, 20))]);
+        assert_eq!(profile.selectors, vec![(Selector::ZERO, stats(4, 2_200, 22))]);
+        assert!(profile.precompiles.is_empty());
+        assert_eq!(profiler.profile(2).contracts.len(), 2);
+
+        profiler.reset();
+        assert_eq!(profiler.profile(1), ExecutionProfile::default());
+    }
+
+    #[test]
+    fn profiles_contracts_selectors_and_precompiles() {
+        let contract = address!("0x1000000000000000000000000000000000000001");
+        let identity = address!("0x0000000000000000000000000000000000000004");
+        // STATICCALL(gas, 0x04, 0, 0, 0, 0) and STOP
+        let code = Bytecode::new_raw(bytes!("600060006000600060045afa00"));
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(contract, AccountInfo::default().with_code(code));
+
+        let profiler = ExecutionProfiler::new(10);
+        let mut evm = EthEvmFactory::default().create_evm_with_inspector(
+            db,
+            EvmEnv::default(),
+            profiler.inspector(),
+        );
+        let result = evm
+            .transact(TxEnv {
+                kind: TxKind::Call(contract),
+                data: bytes!("deadbeef"),
+                gas_limit: 100_000,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(result.result.is_success());
+        drop(evm);
+
+        let profile = profiler.profile(10);
+        assert_eq!(profile.blocks, 1);
+        assert_eq!(profile.contracts.len(), 1);
+        assert_eq!(profile.contracts[0].0, contract);
+        assert!(profile.contracts[0].1.gas_used > 0);
+        assert_eq!(profile.selectors[0].0, Selector::from(0xdeadbeef_u32.to_be_bytes()));
+        assert_eq!(profile.precompiles.len(), 1);
+        assert_eq!(profile.precompiles[0].0, identity);
+        assert_eq!(profile.precompiles[0].1.calls, 1);
+    }
+
+    #[test]
+    fn attributes_nested_frames_to_the_callee() {
+        let profiler = ExecutionProfiler::new(10);
+        let mut inspector = profiler.inspector();
+
+        let started = Instant::now();
+        inspector.frames.push(Frame::new(None));
+        inspector.frames.push(Frame::new(None));
+        let (_, child_gas, child_time) = inspector.exit_frame(300).unwrap();
+        let (_, parent_gas, parent_time) = inspector.exit_frame(1_000).unwrap();
+
+        assert_eq!((child_gas, parent_gas), (300, 700));
+        assert!(child_time + parent_time <= started.elapsed());
+        assert!(inspector.exit_frame(0).is_none());
+    }
+
+    #[test]
+    fn keeps_entries_with_most_time() {
+        let mut entries = HashMap::default();
+        let other =
+            (0..=MAX_ENTRIES as u64).map(|i| (i, stats(1, 1, i))).collect::<HashMap<_, _>>();
+        merge_entries(&mut entries, other);
+
+        assert_eq!(entries.len(), MAX_ENTRIES / 2);
+        assert!(entries.contains_key(&(MAX_ENTRIES as u64)));
+        assert!(!entries.contains_key(&0));
+    }
+}
@@ -106,6 +106,9 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+mod registry;
+pub use registry::*;
+
 mod wal;
 pub use wal::*;
 
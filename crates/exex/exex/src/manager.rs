@@ -28,7 +28,7 @@ use std::{
 };
 use tokio::sync::{
     mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
-    watch,
+    oneshot, watch,
 };
 use tokio_util::sync::{PollSendError, PollSender, ReusableBoxFuture};
 
@@ -123,6 +123,21 @@ impl<N: NodePrimitives> ExExHandle<N> {
         )
     }
 
+    /// Returns the ID of the `ExEx`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Sets the finished height of the `ExEx`, as if it had emitted an
+    /// [`ExExEvent::FinishedHeight`] for it.
+    ///
+    /// Used for `ExEx`es added at runtime with [`ExExManagerHandle::add_exex`], so that the blocks
+    /// after the height they start from are not pruned before they have processed them.
+    pub const fn with_finished_height(mut self, height: BlockNumHash) -> Self {
+        self.finished_height = Some(height);
+        self
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -204,6 +219,15 @@ pub struct ExExManagerMetrics {
     num_exexs: Gauge,
 }
 
+/// A request to the [`ExExManager`] to change its set of `ExEx`es at runtime.
+#[derive(Debug)]
+enum ExExManagerCommand<N: NodePrimitives> {
+    /// Adds the `ExEx` and replies with the node head it starts receiving notifications after.
+    Add { handle: ExExHandle<N>, tx: oneshot::Sender<eyre::Result<Option<BlockNumHash>>> },
+    /// Removes the `ExEx` with the given ID.
+    Remove { id: String, tx: oneshot::Sender<eyre::Result<()>> },
+}
+
 /// The execution extension manager.
 ///
 /// The manager is responsible for:
@@ -223,6 +247,12 @@ pub struct ExExManager<P, N: NodePrimitives> {
 
     /// [`ExExNotification`] channel from the [`ExExManagerHandle`]s.
     handle_rx: UnboundedReceiver<(ExExNotificationSource, ExExNotification<N>)>,
+    /// Requests from the [`ExExManagerHandle`]s to add or remove `ExEx`es.
+    command_rx: UnboundedReceiver<ExExManagerCommand<N>>,
+    /// Whether `ExEx`es can be added and removed at runtime.
+    dynamic: bool,
+    /// The tip of the last notification received by the manager.
+    node_head: Option<BlockNumHash>,
 
     /// The minimum notification ID currently present in the buffer.
     min_id: usize,
@@ -280,6 +310,7 @@ where
         let num_exexs = handles.len();
 
         let (handle_tx, handle_rx) = mpsc::unbounded_channel();
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
         let (is_ready_tx, is_ready_rx) = watch::channel(true);
         let (finished_height_tx, finished_height_rx) = watch::channel(if num_exexs == 0 {
             FinishedExExHeight::NoExExs
@@ -299,6 +330,9 @@ where
             exex_handles: handles,
 
             handle_rx,
+            command_rx,
+            dynamic: false,
+            node_head: None,
 
             min_id: 0,
             next_id: 0,
@@ -315,7 +349,9 @@ where
 
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
+                command_tx,
                 num_exexs,
+                dynamic: false,
                 is_ready_receiver: is_ready_rx.clone(),
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
@@ -340,6 +376,53 @@ where
         self
     }
 
+    /// Allows `ExEx`es to be added and removed at runtime with [`ExExManagerHandle::add_exex`] and
+    /// [`ExExManagerHandle::remove_exex`].
+    ///
+    /// The manager then receives all notifications even if no `ExEx` is installed, so that it
+    /// knows the node head an `ExEx` added later starts from.
+    pub const fn with_dynamic_exexs(mut self) -> Self {
+        self.dynamic = true;
+        self.handle.dynamic = true;
+        self
+    }
+
+    /// Handles a request to add or remove an `ExEx`.
+    fn on_command(&mut self, command: ExExManagerCommand<N>) {
+        match command {
+            ExExManagerCommand::Add { mut handle, tx } => {
+                let result = if self.exex_handles.iter().any(|exex| exex.id == handle.id) {
+                    Err(eyre::eyre!("ExEx {} is already installed", handle.id))
+                } else {
+                    debug!(target: "exex::manager", exex_id = %handle.id, node_head = ?self.node_head, "Adding ExEx");
+                    // the ExEx receives the notifications that arrive from now on
+                    handle.next_notification_id = self.next_id;
+                    if handle.finished_height.is_none() {
+                        let _ = self.finished_height.send(FinishedExExHeight::NotReady);
+                    }
+                    self.exex_handles.push(handle);
+                    Ok(self.node_head)
+                };
+                let _ = tx.send(result);
+            }
+            ExExManagerCommand::Remove { id, tx } => {
+                let result = match self.exex_handles.iter().position(|exex| exex.id == id) {
+                    Some(idx) => {
+                        debug!(target: "exex::manager", exex_id = %id, "Removing ExEx");
+                        self.exex_handles.remove(idx);
+                        if self.exex_handles.is_empty() {
+                            let _ = self.finished_height.send(FinishedExExHeight::NoExExs);
+                        }
+                        Ok(())
+                    }
+                    None => Err(eyre::eyre!("ExEx {id} is not installed")),
+                };
+                let _ = tx.send(result);
+            }
+        }
+        self.metrics.num_exexs.set(self.exex_handles.len() as f64);
+    }
+
     /// Updates the current buffer capacity and notifies all `is_ready` watchers of the manager's
     /// readiness to receive notifications.
     fn update_capacity(&self) {
@@ -356,6 +439,7 @@ where
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
     /// unique ID.
     fn push_notification(&mut self, notification: ExExNotification<N>) {
+        self.node_head = Some(notification.tip());
         let next_id = self.next_id;
         self.buffer.push_back((next_id, notification));
         self.next_id += 1;
@@ -445,6 +529,7 @@ where
     type Output = eyre::Result<()>;
 
     /// Main loop of the [`ExExManager`]. The order of operations is as follows:
+    /// 0. Add and remove the `ExEx`es requested through the [`ExExManagerHandle`]s.
     /// 1. Handle incoming ExEx events. We do it before finalizing the WAL, because it depends on
     ///    the latest state of [`ExExEvent::FinishedHeight`] events.
     /// 2. Finalize the WAL with the finalized header, if necessary.
@@ -458,6 +543,11 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        // Add and remove ExExes
+        while let Poll::Ready(Some(command)) = this.command_rx.poll_recv(cx) {
+            this.on_command(command);
+        }
+
         // Handle incoming ExEx events
         for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
@@ -508,8 +598,9 @@ where
         // Update capacity
         this.update_capacity();
 
-        // Advance all poll senders
-        let mut min_id = usize::MAX;
+        // Advance all poll senders. Without ExExes, everything up to the next notification is
+        // processed, which is where an ExEx added later starts.
+        let mut min_id = this.next_id;
         for idx in (0..this.exex_handles.len()).rev() {
             let mut exex = this.exex_handles.swap_remove(idx);
 
@@ -547,7 +638,9 @@ where
         let finished_height = this.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
             exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
         });
-        if let Ok(finished_height) = finished_height {
+        if let Ok(finished_height) = finished_height &&
+            !this.exex_handles.is_empty()
+        {
             let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
         }
 
@@ -560,8 +653,12 @@ where
 pub struct ExExManagerHandle<N: NodePrimitives = EthPrimitives> {
     /// Channel to send notifications to the `ExEx` manager.
     exex_tx: UnboundedSender<(ExExNotificationSource, ExExNotification<N>)>,
-    /// The number of `ExEx`'s running on the node.
+    /// Channel to request the `ExEx` manager to add or remove `ExEx`es.
+    command_tx: UnboundedSender<ExExManagerCommand<N>>,
+    /// The number of `ExEx`'s running on the node at launch.
     num_exexs: usize,
+    /// Whether `ExEx`es can be added and removed at runtime.
+    dynamic: bool,
     /// A watch channel denoting whether the manager is ready for new notifications or not.
     ///
     /// This is stored internally alongside a `ReusableBoxFuture` representation of the same value.
@@ -585,12 +682,15 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
     /// The handle will always be ready, and have a capacity of 0.
     pub fn empty() -> Self {
         let (exex_tx, _) = mpsc::unbounded_channel();
+        let (command_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
 
         Self {
             exex_tx,
+            command_tx,
             num_exexs: 0,
+            dynamic: false,
             is_ready_receiver: is_ready_rx.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
@@ -635,9 +735,43 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
         self.capacity() > 0
     }
 
-    /// Returns `true` if there are `ExEx`'s installed in the node.
+    /// Returns `true` if there are `ExEx`'s installed in the node, or if they can be added at
+    /// runtime.
     pub const fn has_exexs(&self) -> bool {
-        self.num_exexs > 0
+        self.num_exexs > 0 || self.dynamic
+    }
+
+    /// Adds an `ExEx` to the running manager.
+    ///
+    /// The `ExEx` receives the notifications that arrive after the returned node head, or after
+    /// the head the node was launched with if the manager hasn't received any notification yet.
+    /// Notifications for earlier blocks have to be backfilled, see
+    /// [`ExExNotifications::set_node_head`].
+    ///
+    /// Fails if the manager doesn't allow `ExEx`es to be added at runtime, see
+    /// [`ExExManager::with_dynamic_exexs`], or if an `ExEx` with the same ID is installed.
+    pub async fn add_exex(&self, handle: ExExHandle<N>) -> eyre::Result<Option<BlockNumHash>> {
+        if !self.dynamic {
+            eyre::bail!("ExExes can't be added at runtime")
+        }
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(ExExManagerCommand::Add { handle, tx })
+            .map_err(|_| eyre::eyre!("ExEx manager is not running"))?;
+        rx.await?
+    }
+
+    /// Removes the `ExEx` with the given ID from the running manager, so that it doesn't receive
+    /// notifications and hold back pruning anymore.
+    pub async fn remove_exex(&self, id: impl Into<String>) -> eyre::Result<()> {
+        if !self.dynamic {
+            eyre::bail!("ExExes can't be removed at runtime")
+        }
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(ExExManagerCommand::Remove { id: id.into(), tx })
+            .map_err(|_| eyre::eyre!("ExEx manager is not running"))?;
+        rx.await?
     }
 
     /// The finished height of all `ExEx`'s.
@@ -670,7 +804,9 @@ impl<N: NodePrimitives> Clone for ExExManagerHandle<N> {
     fn clone(&self) -> Self {
         Self {
             exex_tx: self.exex_tx.clone(),
+            command_tx: self.command_tx.clone(),
             num_exexs: self.num_exexs,
+            dynamic: self.dynamic,
             is_ready_receiver: self.is_ready_receiver.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
@@ -739,11 +875,116 @@ mod tests {
             .handle
             .has_exexs());
 
-        assert!(ExExManager::new((), vec![exex_handle_1], 0, wal, empty_finalized_header_stream())
+        assert!(ExExManager::new(
+            (),
+            vec![exex_handle_1],
+            0,
+            wal.clone(),
+            empty_finalized_header_stream()
+        )
+        .handle
+        .has_exexs());
+
+        assert!(ExExManager::new((), vec![], 0, wal, empty_finalized_header_stream())
+            .with_dynamic_exexs()
             .handle
             .has_exexs());
     }
 
+    #[tokio::test]
+    async fn test_add_remove_exex() -> eyre::Result<()> {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+        let wal_handle = wal.handle();
+        let new_exex_handle = |id: &str| {
+            ExExHandle::new(
+                id.to_string(),
+                Default::default(),
+                provider.clone(),
+                EthEvmConfig::mainnet(),
+                wal_handle.clone(),
+            )
+        };
+        let notification = |number: u64| {
+            let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+            block.set_hash(B256::random());
+            block.set_block_number(number);
+            ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+            }
+        };
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // ExExes can't be added to a manager that doesn't allow it
+        let static_manager = ExExManager::new(
+            provider.clone(),
+            vec![],
+            10,
+            wal.clone(),
+            empty_finalized_header_stream(),
+        );
+        assert!(static_manager.handle().add_exex(new_exex_handle("test_exex").0).await.is_err());
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider.clone(),
+            vec![],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        )
+        .with_dynamic_exexs());
+        let handle = exex_manager.handle();
+        let finished_height = handle.finished_height();
+        assert_eq!(*finished_height.borrow(), FinishedExExHeight::NoExExs);
+
+        // The manager tracks the node head even without ExExes
+        let first = notification(10);
+        handle.send(ExExNotificationSource::BlockchainTree, first.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(exex_manager.node_head, Some(first.tip()));
+
+        // The added ExEx starts after the node head and holds back pruning until it has finished
+        // a height
+        let (exex_handle, _, mut notifications) = new_exex_handle("test_exex");
+        let mut add = std::pin::pin!(handle.add_exex(exex_handle));
+        assert!(add.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(add.await?, Some(first.tip()));
+        assert_eq!(exex_manager.exex_handles.len(), 1);
+        assert_eq!(*finished_height.borrow(), FinishedExExHeight::NotReady);
+
+        // Only the notifications after the node head are delivered
+        let second = notification(11);
+        handle.send(ExExNotificationSource::BlockchainTree, second.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(notifications.try_poll_next_unpin(&mut cx)?, Poll::Ready(Some(second)));
+
+        // ExEx IDs are unique
+        let mut add = std::pin::pin!(handle.add_exex(new_exex_handle("test_exex").0));
+        assert!(add.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(add.await.is_err());
+
+        // Removing the last ExEx stops holding back pruning
+        let mut remove = std::pin::pin!(handle.remove_exex("test_exex"));
+        assert!(remove.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        remove.await?;
+        assert!(exex_manager.exex_handles.is_empty());
+        assert_eq!(*finished_height.borrow(), FinishedExExHeight::NoExExs);
+
+        let mut remove = std::pin::pin!(handle.remove_exex("test_exex"));
+        assert!(remove.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(remove.await.is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_has_capacity() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -111,6 +111,32 @@ where
             )),
         }
     }
+
+    /// Updates the node head the stream compares the ExEx head against.
+    ///
+    /// The node head is captured at launch, so an ExEx that reconfigures the stream with a new
+    /// head later on, e.g. after a consumer reconnected, must set the node head to the tip of the
+    /// last notification it has received, see [`ExExNotification::tip`]. Otherwise, blocks
+    /// committed since launch are backfilled a second time. Takes effect on the next
+    /// [`ExExNotificationsStream::set_with_head`] call.
+    ///
+    /// Notifications that were buffered during a backfill and not yielded yet are dropped by
+    /// [`ExExNotificationsStream::set_with_head`], so the node head is moved to the tip of the last
+    /// of them instead, and the blocks they contain are backfilled.
+    pub fn set_node_head(&mut self, node_head: BlockNumHash) {
+        match &mut self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => {
+                notifications.node_head = node_head
+            }
+            ExExNotificationsInner::WithHead(notifications) => {
+                notifications.initial_local_head = notifications
+                    .pending_notifications
+                    .back()
+                    .map_or(node_head, ExExNotification::tip)
+            }
+            ExExNotificationsInner::Invalid => {}
+        }
+    }
 }
 
 impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
@@ -958,4 +984,60 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn exex_notifications_set_node_head() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+        let genesis = BlockNumHash { number: genesis_block.number, hash: genesis_hash };
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        let block = random_block(
+            &mut rng,
+            genesis.number + 1,
+            BlockParams { parent: Some(genesis.hash), tx_count: Some(0), ..Default::default() },
+        )
+        .try_recover()?;
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.insert_block(&block)?;
+        provider_rw.commit()?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block.clone()], Default::default(), BTreeMap::new())),
+        };
+
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        notifications_tx.send(notification.clone()).await?;
+
+        // the node launched at genesis, and the ExEx consumes the notification for block 1
+        // without a head
+        let mut notifications = ExExNotifications::new(
+            genesis,
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        );
+        assert_eq!(notifications.next().await.transpose()?, Some(notification.clone()));
+
+        // an ExEx head at genesis must be backfilled up to the new node head
+        notifications.set_node_head(notification.tip());
+        notifications.set_with_head(ExExHead { block: genesis });
+        let Some(ExExNotification::ChainCommitted { new }) =
+            notifications.next().await.transpose()?
+        else {
+            panic!("expected a backfilled chain")
+        };
+        assert_eq!(new.tip().num_hash(), block.num_hash());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,185 @@
+use crate::{ExExContext, ExExHandle, ExExHead, ExExManagerHandle, WalHandle};
+use alloy_eips::BlockNumHash;
+use parking_lot::Mutex;
+use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
+use reth_node_core::node_config::NodeConfig;
+use reth_provider::StateProviderFactory;
+use reth_tracing::tracing::{debug, info, warn};
+use std::{collections::HashMap, fmt, future::Future, sync::Arc};
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// Registers and deregisters `ExEx`es on a running node.
+///
+/// A registered `ExEx` is added to the [`ExExManagerHandle`] of the node, which must allow
+/// `ExEx`es to be added at runtime, see
+/// [`ExExManager::with_dynamic_exexs`](crate::ExExManager::with_dynamic_exexs). Unlike the `ExEx`es
+/// installed at launch, an `ExEx` registered at runtime may exit, after which it is deregistered.
+pub struct ExExRegistry<Node: FullNodeComponents> {
+    inner: Arc<ExExRegistryInner<Node>>,
+}
+
+struct ExExRegistryInner<Node: FullNodeComponents> {
+    /// The head of the node at launch.
+    head: BlockNumHash,
+    /// The config of the node.
+    config: NodeConfig<<Node::Types as NodeTypes>::ChainSpec>,
+    /// The loaded node config.
+    reth_config: reth_config::Config,
+    /// Node components.
+    components: Node,
+    /// Handle to the `ExEx` WAL.
+    wal_handle: WalHandle<PrimitivesTy<Node::Types>>,
+    /// Handle to the `ExEx` manager.
+    manager: ExExManagerHandle<PrimitivesTy<Node::Types>>,
+    /// The tasks of the registered `ExEx`es, by ID.
+    exexes: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl<Node: FullNodeComponents> ExExRegistry<Node> {
+    /// Creates a new registry for the node launched at the given head.
+    pub fn new(
+        head: BlockNumHash,
+        config: NodeConfig<<Node::Types as NodeTypes>::ChainSpec>,
+        reth_config: reth_config::Config,
+        components: Node,
+        wal_handle: WalHandle<PrimitivesTy<Node::Types>>,
+        manager: ExExManagerHandle<PrimitivesTy<Node::Types>>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(ExExRegistryInner {
+                head,
+                config,
+                reth_config,
+                components,
+                wal_handle,
+                manager,
+                exexes: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the IDs of the `ExEx`es registered at runtime.
+    pub fn ids(&self) -> Vec<String> {
+        self.inner.exexes.lock().keys().cloned().collect()
+    }
+
+    /// Registers an `ExEx` with the given ID and spawns it.
+    ///
+    /// If a head is given, the `ExEx` receives the notifications after it: the blocks between the
+    /// head and the node head are backfilled first, and the blocks after the head are not pruned
+    /// until the `ExEx` has processed them. The state at the head must not have been pruned yet.
+    /// Otherwise, the `ExEx` only receives the notifications for blocks committed after it has
+    /// been registered.
+    ///
+    /// Fails if an `ExEx` with the same ID is installed, or if the `ExEx` fails to launch.
+    pub async fn register<F, Fut, E>(
+        &self,
+        id: String,
+        head: Option<ExExHead>,
+        exex: F,
+    ) -> eyre::Result<()>
+    where
+        F: FnOnce(ExExContext<Node>) -> Fut,
+        Fut: Future<Output = eyre::Result<E>>,
+        E: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        let inner = &self.inner;
+        if inner.exexes.lock().contains_key(&id) {
+            eyre::bail!("ExEx {id} is already registered")
+        }
+        if let Some(head) = head {
+            // backfill re-executes the blocks after the head on top of its state
+            inner.components.provider().history_by_block_number(head.block.number).map_err(
+                |err| eyre::eyre!("state at block {} is not available: {err}", head.block.number),
+            )?;
+        }
+
+        let (mut handle, events, mut notifications) = ExExHandle::new(
+            id.clone(),
+            inner.head,
+            inner.components.provider().clone(),
+            inner.components.evm_config().clone(),
+            inner.wal_handle.clone(),
+        );
+        if let Some(head) = head {
+            handle = handle.with_finished_height(head.block);
+        }
+        let node_head = inner.manager.add_exex(handle).await?.unwrap_or(inner.head);
+        notifications.set_node_head(node_head);
+
+        let mut context = ExExContext {
+            head: node_head,
+            config: inner.config.clone(),
+            reth_config: inner.reth_config.clone(),
+            events,
+            notifications,
+            components: inner.components.clone(),
+        };
+        if let Some(head) = head {
+            context.set_notifications_with_head(head);
+        }
+
+        debug!(target: "exex::registry", %id, ?head, ?node_head, "Registering ExEx");
+        let span = reth_tracing::tracing::info_span!("exex", id);
+        let exex = match exex(context).instrument(span.clone()).await {
+            Ok(exex) => exex,
+            Err(err) => {
+                let _ = inner.manager.remove_exex(id.clone()).await;
+                return Err(err)
+            }
+        };
+
+        // hold the lock while spawning, so that an ExEx that exits immediately is removed after
+        // it has been inserted
+        let mut exexes = inner.exexes.lock();
+        let registry = self.clone();
+        let task_id = id.clone();
+        let task = inner.components.task_executor().spawn_task(
+            async move {
+                info!(target: "exex::registry", "ExEx started");
+                match exex.await {
+                    Ok(()) => info!(target: "exex::registry", "ExEx finished"),
+                    Err(err) => warn!(target: "exex::registry", %err, "ExEx crashed"),
+                }
+                registry.inner.exexes.lock().remove(&task_id);
+                if let Err(err) = registry.inner.manager.remove_exex(task_id).await {
+                    debug!(target: "exex::registry", %err, "Failed to remove ExEx");
+                }
+            }
+            .instrument(span),
+        );
+        exexes.insert(id, task);
+
+        Ok(())
+    }
+
+    /// Stops the `ExEx` with the given ID that was registered at runtime and removes it from the
+    /// `ExEx` manager, so that it no longer holds back pruning.
+    pub async fn deregister(&self, id: &str) -> eyre::Result<()> {
+        let task = self
+            .inner
+            .exexes
+            .lock()
+            .remove(id)
+            .ok_or_else(|| eyre::eyre!("ExEx {id} is not registered"))?;
+        task.abort();
+        info!(target: "exex::registry", %id, "Deregistered ExEx");
+        self.inner.manager.remove_exex(id).await
+    }
+}
+
+impl<Node: FullNodeComponents> Clone for ExExRegistry<Node> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<Node: FullNodeComponents> fmt::Debug for ExExRegistry<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExRegistry")
+            .field("head", &self.inner.head)
+            .field("exexes", &self.ids())
+            .finish_non_exhaustive()
+    }
+}
@@ -63,6 +63,37 @@ impl BlockCache {
             }
         }
 
+        self.remove_committed_blocks(&file_ids);
+
+        file_ids
+    }
+
+    /// Removes the notifications with the given file IDs from the cache.
+    pub(super) fn remove_files(&mut self, file_ids: &HashSet<u32>) {
+        self.notification_max_blocks.retain(|Reverse((_, file_id))| !file_ids.contains(file_id));
+        self.remove_committed_blocks(file_ids);
+    }
+
+    /// Returns the lowest and highest committed block numbers of the notifications with the given
+    /// file IDs.
+    pub(super) fn committed_block_range(
+        &self,
+        file_ids: &HashSet<u32>,
+    ) -> Option<(BlockNumber, BlockNumber)> {
+        self.committed_blocks
+            .values()
+            .filter(|(file_id, _)| file_ids.contains(file_id))
+            .map(|(_, block)| block.block.number)
+            .fold(None, |range, number| {
+                Some(range.map_or((number, number), |(lowest, highest): (u64, u64)| {
+                    (lowest.min(number), highest.max(number))
+                }))
+            })
+    }
+
+    /// Removes the committed blocks of the notifications with the given file IDs, and updates
+    /// the lowest and highest committed block heights.
+    fn remove_committed_blocks(&mut self, file_ids: &HashSet<u32>) {
         let (mut lowest_committed_block_height, mut highest_committed_block_height) = (None, None);
         self.committed_blocks.retain(|_, (file_id, block)| {
             let retain = !file_ids.contains(file_id);
@@ -82,8 +113,6 @@ impl BlockCache {
         });
         self.lowest_committed_block_height = lowest_committed_block_height;
         self.highest_committed_block_height = highest_committed_block_height;
-
-        file_ids
     }
 
     /// Returns the file ID for the notification containing the given committed block hash, if it
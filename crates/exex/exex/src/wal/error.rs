@@ -26,4 +26,13 @@ pub enum WalError {
     /// Decode error
     #[error("failed to decode notification {0} from {1}: {2}")]
     Decode(u32, PathBuf, rmp_serde::decode::Error),
+    /// Decompression error
+    #[error("failed to decompress notification {0} from {1}: {2}")]
+    Decompress(u32, PathBuf, std::io::Error),
+    /// The WAL exceeds its retention limits and the retention policy is to fail
+    #[error(
+        "ExEx WAL exceeds its retention limits: {0}. An ExEx is not keeping up with the chain; \
+         raise the limits or set `retention_policy = \"evict\"` in the `[exex.wal]` config"
+    )]
+    RetentionExceeded(String),
 }
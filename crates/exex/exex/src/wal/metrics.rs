@@ -1,4 +1,4 @@
-use metrics::Gauge;
+use metrics::{Counter, Gauge};
 use reth_metrics::Metrics;
 
 /// Metrics for the [WAL](`super::Wal`)
@@ -15,4 +15,6 @@ pub(super) struct Metrics {
     pub lowest_committed_block_height: Gauge,
     /// Highest committed block height in WAL
     pub highest_committed_block_height: Gauge,
+    /// Number of notifications evicted from WAL because of its retention limits
+    pub evicted_notifications: Counter,
 }
@@ -5,25 +5,28 @@ pub use cache::BlockCache;
 mod storage;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_node_api::NodePrimitives;
-pub use storage::Storage;
+pub use storage::{Storage, StorageFileInfo};
 mod metrics;
 use metrics::Metrics;
 mod error;
 pub use error::{WalError, WalResult};
 
 use std::{
+    collections::{BTreeMap, HashSet},
     path::Path,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
     },
+    time::SystemTime,
 };
 
 use alloy_eips::BlockNumHash;
 use alloy_primitives::B256;
-use parking_lot::{RwLock, RwLockReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use reth_config::{ExExWalConfig, WalRetentionPolicy};
 use reth_exex_types::ExExNotification;
-use reth_tracing::tracing::{debug, instrument};
+use reth_tracing::tracing::{debug, error, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
 ///
@@ -35,6 +38,9 @@ use reth_tracing::tracing::{debug, instrument};
 /// 1. On every new canonical chain notification, call [`Wal::commit`].
 /// 2. When the chain is finalized, call [`Wal::finalize`] to prevent the infinite growth of the
 ///    WAL.
+///
+/// If an `ExEx` stalls, the chain can't be finalized in the WAL, so it can also be bounded by size
+/// and age, see [`ExExWalConfig`].
 #[derive(Debug, Clone)]
 pub struct Wal<N: NodePrimitives = EthPrimitives> {
     inner: Arc<WalInner<N>>,
@@ -46,7 +52,12 @@ where
 {
     /// Creates a new instance of [`Wal`].
     pub fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
-        Ok(Self { inner: Arc::new(WalInner::new(directory)?) })
+        Self::with_config(directory, ExExWalConfig::default())
+    }
+
+    /// Creates a new instance of [`Wal`] with the given compression and retention settings.
+    pub fn with_config(directory: impl AsRef<Path>, config: ExExWalConfig) -> WalResult<Self> {
+        Ok(Self { inner: Arc::new(WalInner::new(directory, config)?) })
     }
 
     /// Returns a read-only handle to the WAL.
@@ -55,6 +66,10 @@ where
     }
 
     /// Commits the notification to WAL.
+    ///
+    /// If the WAL then exceeds its retention limits, the oldest notifications are evicted, or
+    /// [`WalError::RetentionExceeded`] is returned, depending on the [`WalRetentionPolicy`]. The
+    /// notification is committed in both cases.
     pub fn commit(&self, notification: &ExExNotification<N>) -> WalResult<()> {
         self.inner.commit(notification)
     }
@@ -88,18 +103,34 @@ struct WalInner<N: NodePrimitives> {
     storage: Storage<N>,
     /// WAL block cache. See [`cache::BlockCache`] docs for more details.
     block_cache: RwLock<BlockCache>,
+    /// The files in the storage, by file ID. Only modified while holding the write lock of the
+    /// block cache.
+    files: Mutex<BTreeMap<u32, WalFile>>,
+    /// Compression and retention settings.
+    config: ExExWalConfig,
     metrics: Metrics,
 }
 
+/// A file in the WAL storage.
+#[derive(Debug, Clone, Copy)]
+struct WalFile {
+    /// The size of the file in bytes.
+    size: u64,
+    /// The time the notification was committed at.
+    committed_at: SystemTime,
+}
+
 impl<N> WalInner<N>
 where
     N: NodePrimitives,
 {
-    fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
+    fn new(directory: impl AsRef<Path>, config: ExExWalConfig) -> WalResult<Self> {
         let wal = Self {
             next_file_id: AtomicU32::new(0),
-            storage: Storage::new(directory)?,
+            storage: Storage::new(directory)?.with_compression(config.compression),
             block_cache: RwLock::new(BlockCache::default()),
+            files: Mutex::new(BTreeMap::new()),
+            config,
             metrics: Metrics::default(),
         };
         wal.fill_block_cache()?;
@@ -113,16 +144,21 @@ where
     /// Fills the block cache with the notifications from the storage.
     #[instrument(skip(self))]
     fn fill_block_cache(&self) -> WalResult<()> {
-        let Some(files_range) = self.storage.files_range()? else { return Ok(()) };
-        self.next_file_id.store(files_range.end() + 1, Ordering::Relaxed);
+        let file_ids = self.storage.file_ids()?;
+        let Some(last_file_id) = file_ids.last() else { return Ok(()) };
+        self.next_file_id.store(last_file_id + 1, Ordering::Relaxed);
 
         let mut block_cache = self.block_cache.write();
+        let mut files = self.files.lock();
         let mut notifications_size = 0;
 
-        for entry in self.storage.iter_notifications(files_range) {
+        for entry in self.storage.iter_notifications(file_ids) {
             let (file_id, size, notification) = entry?;
 
             notifications_size += size;
+            let committed_at =
+                self.storage.file_info(file_id)?.map_or_else(SystemTime::now, |info| info.modified);
+            files.insert(file_id, WalFile { size, committed_at });
 
             let committed_chain = notification.committed_chain();
             let reverted_chain = notification.reverted_chain();
@@ -156,8 +192,75 @@ where
         debug!(target: "exex::wal", ?file_id, "Inserting notification blocks into the block cache");
         block_cache.insert_notification_blocks_with_file_id(file_id, notification);
 
+        let mut files = self.files.lock();
+        files.insert(file_id, WalFile { size, committed_at: SystemTime::now() });
+
         self.update_metrics(&block_cache, size as i64);
 
+        self.enforce_retention(&mut block_cache, &mut files)
+    }
+
+    /// Evicts the oldest notifications while the WAL exceeds its retention limits, or fails if
+    /// the retention policy is [`WalRetentionPolicy::Fail`]. The newest notification is never
+    /// evicted.
+    fn enforce_retention(
+        &self,
+        block_cache: &mut BlockCache,
+        files: &mut BTreeMap<u32, WalFile>,
+    ) -> WalResult<()> {
+        let ExExWalConfig { max_size, max_age, retention_policy, .. } = self.config;
+        if max_size.is_none() && max_age.is_none() {
+            return Ok(())
+        }
+
+        let now = SystemTime::now();
+        let mut size = files.values().map(|file| file.size).sum::<u64>();
+        let mut evicted = HashSet::new();
+        let mut reason = None;
+
+        for (&file_id, file) in files.iter().take(files.len().saturating_sub(1)) {
+            let age = now.duration_since(file.committed_at).unwrap_or_default();
+            reason = match (max_size, max_age) {
+                (Some(max_size), _) if size > max_size => {
+                    Some(format!("size of {size} bytes is above the limit of {max_size} bytes"))
+                }
+                (_, Some(max_age)) if age > max_age => Some(format!(
+                    "oldest notification committed {age:?} ago, the limit is {max_age:?}"
+                )),
+                _ => break,
+            };
+
+            match retention_policy {
+                WalRetentionPolicy::Fail => {
+                    return Err(WalError::RetentionExceeded(reason.unwrap_or_default()))
+                }
+                WalRetentionPolicy::Evict => {
+                    evicted.insert(file_id);
+                    size -= file.size;
+                }
+            }
+        }
+
+        if evicted.is_empty() {
+            return Ok(())
+        }
+
+        let committed_blocks = block_cache.committed_block_range(&evicted);
+        block_cache.remove_files(&evicted);
+        files.retain(|file_id, _| !evicted.contains(file_id));
+        let (removed_notifications, removed_size) = self.storage.remove_notifications(evicted)?;
+        error!(
+            target: "exex::wal",
+            reason = reason.unwrap_or_default(),
+            ?removed_notifications,
+            ?removed_size,
+            ?committed_blocks,
+            "Evicted notifications from the ExEx WAL, ExExes on blocks reverted by them can't recover"
+        );
+
+        self.metrics.evicted_notifications.increment(removed_notifications as u64);
+        self.update_metrics(block_cache, -(removed_size as i64));
+
         Ok(())
     }
 
@@ -172,6 +275,7 @@ where
             return Ok(())
         }
 
+        self.files.lock().retain(|file_id, _| !file_ids.contains(file_id));
         let (removed_notifications, removed_size) = self.storage.remove_notifications(file_ids)?;
         debug!(target: "exex::wal", ?removed_notifications, ?removed_size, "Storage was finalized");
 
@@ -198,11 +302,8 @@ where
     fn iter_notifications(
         &self,
     ) -> WalResult<Box<dyn Iterator<Item = WalResult<ExExNotification<N>>> + '_>> {
-        let Some(range) = self.storage.files_range()? else {
-            return Ok(Box::new(std::iter::empty()))
-        };
-
-        Ok(Box::new(self.storage.iter_notifications(range).map(|entry| Ok(entry?.2))))
+        let file_ids = self.storage.file_ids()?;
+        Ok(Box::new(self.storage.iter_notifications(file_ids).map(|entry| Ok(entry?.2))))
     }
 }
 
@@ -212,6 +313,12 @@ pub struct WalHandle<N: NodePrimitives> {
     wal: Arc<WalInner<N>>,
 }
 
+impl<N: NodePrimitives> Clone for WalHandle<N> {
+    fn clone(&self) -> Self {
+        Self { wal: self.wal.clone() }
+    }
+}
+
 impl<N> WalHandle<N>
 where
     N: NodePrimitives,
@@ -235,24 +342,27 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::wal::{cache::CachedBlock, error::WalResult, Wal};
+    use crate::wal::{
+        cache::CachedBlock,
+        error::{WalError, WalResult},
+        Wal,
+    };
     use alloy_primitives::B256;
     use itertools::Itertools;
+    use reth_config::{ExExWalConfig, WalRetentionPolicy};
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, BlockParams, BlockRangeParams,
     };
-    use std::{collections::BTreeMap, sync::Arc};
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
     fn read_notifications(wal: &Wal) -> WalResult<Vec<ExExNotification>> {
-        wal.inner.storage.files_range()?.map_or(Ok(Vec::new()), |range| {
-            wal.inner
-                .storage
-                .iter_notifications(range)
-                .map(|entry| entry.map(|(_, _, n)| n))
-                .collect()
-        })
+        wal.inner
+            .storage
+            .iter_notifications(wal.inner.storage.file_ids()?)
+            .map(|entry| entry.map(|(_, _, n)| n))
+            .collect()
     }
 
     fn sort_committed_blocks(
@@ -520,6 +630,88 @@ mod tests {
         );
         assert_eq!(read_notifications(&wal)?, vec![committed_notification_2, reorged_notification]);
 
+        Ok(())
+    }
+    #[test]
+    fn test_wal_retention() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Empty blocks, so that all notifications have the same size
+        let mut rng = generators::rng();
+        let notifications = (0..=3)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { tx_count: Some(0), ommers_count: Some(0), ..Default::default() },
+                );
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(
+                        vec![block.try_recover()?],
+                        Default::default(),
+                        BTreeMap::new(),
+                    )),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        // Measure the size of a notification
+        let temp_dir = tempfile::tempdir()?;
+        let wal = Wal::new(&temp_dir)?;
+        wal.commit(&notifications[0])?;
+        let size = wal.inner.files.lock()[&0].size;
+
+        // Fail once the WAL is above the size of two notifications
+        let temp_dir = tempfile::tempdir()?;
+        let config = ExExWalConfig { max_size: Some(size * 5 / 2), ..Default::default() };
+        let wal = Wal::with_config(&temp_dir, config)?;
+        wal.commit(&notifications[0])?;
+        wal.commit(&notifications[1])?;
+        assert!(matches!(wal.commit(&notifications[2]), Err(WalError::RetentionExceeded(_))));
+        // The notification is committed anyway
+        assert_eq!(read_notifications(&wal)?, notifications[..3]);
+
+        // Evict the oldest notifications instead
+        let temp_dir = tempfile::tempdir()?;
+        let config = ExExWalConfig {
+            max_size: Some(size * 5 / 2),
+            retention_policy: WalRetentionPolicy::Evict,
+            ..Default::default()
+        };
+        let wal = Wal::with_config(&temp_dir, config)?;
+        for notification in &notifications {
+            wal.commit(notification)?;
+        }
+        assert_eq!(read_notifications(&wal)?, notifications[2..]);
+        assert_eq!(wal.inner.block_cache().blocks_sorted(), [(3, 3), (2, 2)]);
+        assert_eq!(wal.inner.block_cache().lowest_committed_block_height, Some(2));
+
+        // Every notification but the newest one is too old
+        let temp_dir = tempfile::tempdir()?;
+        let config = ExExWalConfig {
+            max_age: Some(Duration::ZERO),
+            retention_policy: WalRetentionPolicy::Evict,
+            ..Default::default()
+        };
+        let wal = Wal::with_config(&temp_dir, config)?;
+        for notification in &notifications {
+            std::thread::sleep(Duration::from_millis(1));
+            wal.commit(notification)?;
+        }
+        assert_eq!(read_notifications(&wal)?, notifications[3..]);
+
+        // Compressed notifications are read back, along with the uncompressed ones
+        let config = ExExWalConfig { compression: true, ..Default::default() };
+        let wal = Wal::with_config(&temp_dir, config)?;
+        wal.commit(&notifications[0])?;
+        assert!(!wal.inner.storage.file_info(3)?.unwrap().compressed);
+        assert!(wal.inner.storage.file_info(4)?.unwrap().compressed);
+        let wal = Wal::new(&temp_dir)?;
+        assert_eq!(
+            read_notifications(&wal)?,
+            vec![notifications[3].clone(), notifications[0].clone()]
+        );
+
         Ok(())
     }
 }
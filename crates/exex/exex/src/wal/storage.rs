@@ -1,7 +1,7 @@
 use std::{
-    fs::File,
     ops::RangeInclusive,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use crate::wal::{WalError, WalResult};
@@ -13,27 +13,64 @@ use tracing::instrument;
 
 static FILE_EXTENSION: &str = "wal";
 
+/// The magic number a zstd frame starts with.
+///
+/// A MessagePack-encoded notification starts with a map or array marker instead, so compressed and
+/// uncompressed files can be told apart by their first bytes.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// The zstd compression level of the WAL files.
+const ZSTD_LEVEL: i32 = 3;
+
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
+/// notification, optionally compressed with zstd.
 #[derive(Debug, Clone)]
 pub struct Storage<N: NodePrimitives = EthPrimitives> {
     /// The path to the WAL file.
     path: PathBuf,
+    /// Whether new notifications are compressed.
+    compression: bool,
     _pd: std::marker::PhantomData<N>,
 }
 
+/// Metadata of a WAL file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageFileInfo {
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The time the file was last modified at, which is when the notification was committed.
+    pub modified: SystemTime,
+    /// Whether the notification is compressed.
+    pub compressed: bool,
+}
+
 impl<N> Storage<N>
 where
     N: NodePrimitives,
 {
     /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
     /// it doesn't exist.
-    pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
+    pub fn new(path: impl AsRef<Path>) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            compression: false,
+            _pd: std::marker::PhantomData,
+        })
+    }
+
+    /// Sets whether new notifications are compressed with zstd.
+    pub const fn with_compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Returns the path of the directory the storage is backed by.
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 
     fn file_path(&self, id: u32) -> PathBuf {
@@ -72,23 +109,47 @@ where
     /// Returns the range of file IDs in the storage.
     ///
     /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
-        let mut min_id = None;
-        let mut max_id = None;
+    pub fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
+        let file_ids = self.file_ids()?;
+        Ok(file_ids.first().zip(file_ids.last()).map(|(min_id, max_id)| *min_id..=*max_id))
+    }
+
+    /// Returns the sorted IDs of the files in the storage.
+    ///
+    /// Files may be missing from the range of IDs, if notifications that are not the oldest ones
+    /// were finalized.
+    pub fn file_ids(&self) -> WalResult<Vec<u32>> {
+        let mut file_ids = Vec::new();
 
         for entry in reth_fs_util::read_dir(&self.path)? {
             let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
 
             if entry.path().extension() == Some(FILE_EXTENSION.as_ref()) {
                 let file_name = entry.file_name();
-                let file_id = Self::parse_filename(&file_name.to_string_lossy())?;
-
-                min_id = min_id.map_or(Some(file_id), |min_id: u32| Some(min_id.min(file_id)));
-                max_id = max_id.map_or(Some(file_id), |max_id: u32| Some(max_id.max(file_id)));
+                file_ids.push(Self::parse_filename(&file_name.to_string_lossy())?);
             }
         }
 
-        Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
+        file_ids.sort_unstable();
+        Ok(file_ids)
+    }
+
+    /// Returns the metadata of the file with the given ID, if it exists.
+    pub fn file_info(&self, file_id: u32) -> WalResult<Option<StorageFileInfo>> {
+        let file_path = self.file_path(file_id);
+        let metadata = match file_path.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(WalError::FileMetadata(file_id, err)),
+        };
+        let modified = metadata.modified().map_err(|err| WalError::FileMetadata(file_id, err))?;
+
+        let mut magic = [0; ZSTD_MAGIC.len()];
+        let compressed =
+            std::io::Read::read_exact(&mut reth_fs_util::open(&file_path)?, &mut magic)
+                .is_ok_and(|()| magic == ZSTD_MAGIC);
+
+        Ok(Some(StorageFileInfo { size: metadata.len(), modified, compressed }))
     }
 
     /// Removes notifications from the storage according to the given list of file IDs.
@@ -96,7 +157,7 @@ where
     /// # Returns
     ///
     /// Number of removed notifications and the total size of the removed files in bytes.
-    pub(super) fn remove_notifications(
+    pub fn remove_notifications(
         &self,
         file_ids: impl IntoIterator<Item = u32>,
     ) -> WalResult<(usize, u64)> {
@@ -113,11 +174,13 @@ where
         Ok((deleted_total, deleted_size))
     }
 
-    pub(super) fn iter_notifications(
+    /// Returns an iterator over the notifications with the given file IDs, along with their file
+    /// IDs and file sizes.
+    pub fn iter_notifications(
         &self,
-        range: RangeInclusive<u32>,
+        file_ids: impl IntoIterator<Item = u32> + 'static,
     ) -> impl Iterator<Item = WalResult<(u32, u64, ExExNotification<N>)>> + '_ {
-        range.map(move |id| {
+        file_ids.into_iter().map(move |id| {
             let (notification, size) =
                 self.read_notification(id)?.ok_or(WalError::FileNotFound(id))?;
 
@@ -126,25 +189,32 @@ where
     }
 
     /// Reads the notification from the file with the given ID.
+    ///
+    /// # Returns
+    ///
+    /// The notification and the size of the file in bytes, if the file exists.
     #[instrument(skip(self))]
-    pub(super) fn read_notification(
-        &self,
-        file_id: u32,
-    ) -> WalResult<Option<(ExExNotification<N>, u64)>> {
+    pub fn read_notification(&self, file_id: u32) -> WalResult<Option<(ExExNotification<N>, u64)>> {
         let file_path = self.file_path(file_id);
         debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
 
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
+        let bytes = match std::fs::read(&file_path) {
+            Ok(bytes) => bytes,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+            Err(err) => return Err(reth_fs_util::FsPathError::read(err, &file_path).into()),
         };
-        let size = file.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len();
+        let size = bytes.len() as u64;
 
         // Deserialize using the bincode- and msgpack-compatible serde wrapper
         let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
-            rmp_serde::decode::from_read(&mut file)
-                .map_err(|err| WalError::Decode(file_id, file_path, err))?;
+            if bytes.starts_with(&ZSTD_MAGIC) {
+                let decoder = zstd::stream::read::Decoder::new(bytes.as_slice())
+                    .map_err(|err| WalError::Decompress(file_id, file_path.clone(), err))?;
+                rmp_serde::decode::from_read(decoder)
+            } else {
+                rmp_serde::decode::from_slice(&bytes)
+            }
+            .map_err(|err| WalError::Decode(file_id, file_path, err))?;
 
         Ok(Some((notification.into(), size)))
     }
@@ -155,7 +225,7 @@ where
     ///
     /// The size of the file that was written in bytes.
     #[instrument(skip(self, notification))]
-    pub(super) fn write_notification(
+    pub fn write_notification(
         &self,
         file_id: u32,
         notification: &ExExNotification<N>,
@@ -168,7 +238,14 @@ where
             reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification);
 
         reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
+            if self.compression {
+                let mut encoder = zstd::stream::write::Encoder::new(file, ZSTD_LEVEL)?;
+                rmp_serde::encode::write(&mut encoder, &notification)?;
+                encoder.finish()?;
+            } else {
+                rmp_serde::encode::write(file, &notification)?;
+            }
+            Ok::<_, Box<dyn core::error::Error + Send + Sync>>(())
         })?;
 
         Ok(file_path.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len())
@@ -0,0 +1,67 @@
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_exex::{ExExHead, ExExNotification};
+use reth_primitives_traits::NodePrimitives;
+
+/// Remote `ExEx` API, served by the [`RemoteExExServer`](crate::RemoteExExServer).
+#[rpc(server, client, namespace = "exex")]
+pub trait RemoteExExApi {
+    /// Subscribes to the notifications of the remote `ExEx` with the given ID.
+    ///
+    /// If a head is provided, the notifications start after it: blocks between the head and the
+    /// node head are backfilled, and a head that is not on the canonical chain anymore is reverted
+    /// first. Otherwise, only the notifications for blocks committed after the subscription are
+    /// sent.
+    ///
+    /// Every notification is a MessagePack-encoded [`ExExNotification`], see
+    /// [`decode_notification`]. An `ExEx` has at most one subscriber, a new subscription replaces
+    /// the previous one.
+    #[subscription(name = "subscribe" => "notification", unsubscribe = "unsubscribe", item = Bytes)]
+    async fn subscribe_notifications(
+        &self,
+        exex_id: String,
+        head: Option<ExExHead>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Acknowledges that the remote `ExEx` with the given ID has processed all blocks up to and
+    /// including the given one, which makes them eligible for pruning.
+    ///
+    /// Fails if the `ExEx` has no subscriber, or if the block has not been sent to it yet.
+    #[method(name = "finishedHeight")]
+    async fn finished_height(&self, exex_id: String, height: BlockNumHash) -> RpcResult<()>;
+
+    /// Registers a remote `ExEx` with the given ID on the running node.
+    ///
+    /// If a head is provided, the blocks after it are not pruned until the `ExEx` has
+    /// acknowledged them, and its consumer can subscribe from it to have the missing blocks
+    /// backfilled. The state at the head must still be available.
+    ///
+    /// Only available if the server was started with
+    /// [`RemoteExExServer::serve_registry`](crate::RemoteExExServer::serve_registry).
+    #[method(name = "register")]
+    async fn register(&self, exex_id: String, head: Option<ExExHead>) -> RpcResult<()>;
+
+    /// Deregisters the remote `ExEx` with the given ID, which must have been registered with
+    /// [`register`](RemoteExExApiServer::register), so that it no longer holds back pruning.
+    #[method(name = "deregister")]
+    async fn deregister(&self, exex_id: String) -> RpcResult<()>;
+}
+
+/// Encodes a notification the way it is sent to remote `ExEx`es, which is the format of the WAL
+/// entries.
+pub fn encode_notification<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> Result<Bytes, rmp_serde::encode::Error> {
+    let notification = reth_exex::serde_bincode_compat::ExExNotification::<N>::from(notification);
+    rmp_serde::encode::to_vec(&notification).map(Into::into)
+}
+
+/// Decodes a notification received by a remote `ExEx`.
+pub fn decode_notification<N: NodePrimitives>(
+    bytes: &[u8],
+) -> Result<ExExNotification<N>, rmp_serde::decode::Error> {
+    let notification: reth_exex::serde_bincode_compat::ExExNotification<'_, N> =
+        rmp_serde::decode::from_slice(bytes)?;
+    Ok(notification.into())
+}
@@ -0,0 +1,80 @@
+use clap::Args;
+
+/// The default IPC endpoint of the [`RemoteExExServer`](crate::RemoteExExServer).
+#[cfg(windows)]
+pub const DEFAULT_REMOTE_EXEX_IPC_ENDPOINT: &str = r"\\.\pipe\reth-exex.ipc";
+
+/// The default IPC endpoint of the [`RemoteExExServer`](crate::RemoteExExServer).
+#[cfg(not(windows))]
+pub const DEFAULT_REMOTE_EXEX_IPC_ENDPOINT: &str = "/tmp/reth-exex.ipc";
+
+/// Parameters for out-of-process `ExEx`es.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Remote ExEx")]
+pub struct RemoteExExArgs {
+    /// IDs of the remote `ExEx`es to install.
+    ///
+    /// Every remote `ExEx` is served over IPC and makes progress as its consumer acknowledges the
+    /// blocks it has processed. Blocks it hasn't acknowledged are not pruned.
+    #[arg(long = "exex.remote", value_name = "ID", value_delimiter = ',')]
+    pub ids: Vec<String>,
+
+    /// Filename for the IPC socket/pipe the remote `ExEx`es are served on.
+    #[arg(long = "exex.remote.ipcpath", value_name = "PATH", default_value = DEFAULT_REMOTE_EXEX_IPC_ENDPOINT)]
+    pub ipcpath: String,
+
+    /// Allow remote `ExEx`es to be registered and deregistered on the running node.
+    ///
+    /// Starts the IPC server even if no remote `ExEx` is installed, and serves `exex_register` and
+    /// `exex_deregister` on it.
+    #[arg(long = "exex.remote.dynamic")]
+    pub dynamic: bool,
+}
+
+impl Default for RemoteExExArgs {
+    fn default() -> Self {
+        Self {
+            ids: Vec::new(),
+            ipcpath: DEFAULT_REMOTE_EXEX_IPC_ENDPOINT.to_string(),
+            dynamic: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_remote_exex_args() {
+        let default_args = RemoteExExArgs::default();
+        let args = CommandParser::<RemoteExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<RemoteExExArgs>::parse_from([
+            "reth",
+            "--exex.remote",
+            "indexer,bridge",
+            "--exex.remote.ipcpath",
+            "/tmp/exex.ipc",
+            "--exex.remote.dynamic",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            RemoteExExArgs {
+                ids: vec!["indexer".to_string(), "bridge".to_string()],
+                ipcpath: "/tmp/exex.ipc".to_string(),
+                dynamic: true,
+            }
+        );
+    }
+}
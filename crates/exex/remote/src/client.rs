@@ -0,0 +1,68 @@
+use crate::{decode_notification, RemoteExExApiClient};
+use alloy_eips::BlockNumHash;
+use futures::{Stream, StreamExt};
+use jsonrpsee::async_client::Client;
+use reth_exex::{ExExHead, ExExNotification};
+use reth_ipc::client::IpcClientBuilder;
+use reth_primitives_traits::NodePrimitives;
+
+/// Client of a remote `ExEx`, connected to a [`RemoteExExServer`](crate::RemoteExExServer) over
+/// IPC.
+///
+/// ```ignore
+/// let client = RemoteExExClient::connect(DEFAULT_REMOTE_EXEX_IPC_ENDPOINT, "indexer").await?;
+/// let mut notifications = client.notifications::<EthPrimitives>(Some(head)).await?;
+/// while let Some(notification) = notifications.next().await.transpose()? {
+///     // process the notification
+///     client.send_finished_height(notification.tip()).await?;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RemoteExExClient {
+    /// The ID of the remote `ExEx`.
+    exex_id: String,
+    client: Client,
+}
+
+impl RemoteExExClient {
+    /// Connects to the server at the given IPC endpoint, as the remote `ExEx` with the given ID.
+    pub async fn connect(endpoint: &str, exex_id: impl Into<String>) -> eyre::Result<Self> {
+        let client = IpcClientBuilder::default().build(endpoint).await?;
+        Ok(Self { exex_id: exex_id.into(), client })
+    }
+
+    /// Returns the ID of the remote `ExEx`.
+    pub fn exex_id(&self) -> &str {
+        &self.exex_id
+    }
+
+    /// Subscribes to the notifications after the given head, see
+    /// [`RemoteExExApiClient::subscribe_notifications`].
+    ///
+    /// The stream ends when the connection to the server is lost, after which the consumer should
+    /// reconnect with the head it has processed up to.
+    pub async fn notifications<N: NodePrimitives>(
+        &self,
+        head: Option<ExExHead>,
+    ) -> eyre::Result<impl Stream<Item = eyre::Result<ExExNotification<N>>> + Unpin> {
+        let subscription = self.client.subscribe_notifications(self.exex_id.clone(), head).await?;
+        Ok(subscription.map(|bytes| Ok(decode_notification(&bytes?)?)))
+    }
+
+    /// Acknowledges that the remote `ExEx` has processed all blocks up to and including the given
+    /// one, see [`RemoteExExApiClient::finished_height`].
+    pub async fn send_finished_height(&self, height: BlockNumHash) -> eyre::Result<()> {
+        Ok(self.client.finished_height(self.exex_id.clone(), height).await?)
+    }
+
+    /// Registers the remote `ExEx` on the running node, see [`RemoteExExApiClient::register`].
+    pub async fn register(&self, head: Option<ExExHead>) -> eyre::Result<()> {
+        Ok(RemoteExExApiClient::register(&self.client, self.exex_id.clone(), head).await?)
+    }
+
+    /// Deregisters the remote `ExEx` from the running node, see
+    /// [`RemoteExExApiClient::deregister`].
+    pub async fn deregister(&self) -> eyre::Result<()> {
+        Ok(RemoteExExApiClient::deregister(&self.client, self.exex_id.clone()).await?)
+    }
+}
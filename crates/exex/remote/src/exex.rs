@@ -0,0 +1,172 @@
+use crate::{encode_notification, server::RemoteExExRequest, RemoteExExServer};
+use alloy_eips::BlockNumHash;
+use futures::StreamExt;
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
+use reth_exex::{ExExContext, ExExHead, ExExNotification, ExExNotificationsStream};
+use reth_node_api::{FullNodeComponents, NodePrimitives, PrimitivesTy};
+use reth_stages_api::ExecutionStageThresholds;
+use reth_tracing::tracing::{debug, info, warn};
+use tokio::sync::mpsc;
+
+/// The maximum number of blocks in a backfilled notification, so that a consumer catching up
+/// doesn't receive the whole range at once.
+const BACKFILL_MAX_BLOCKS: u64 = 100;
+
+/// The `ExEx` that forwards the notifications of the node to the consumer of a remote `ExEx`, and
+/// its acknowledgements back to the node.
+pub(crate) struct RemoteExEx<Node: FullNodeComponents> {
+    /// The ID of the `ExEx`.
+    id: String,
+    ctx: ExExContext<Node>,
+    /// Requests of the consumers, sent by the [`RemoteExExServer`].
+    requests: mpsc::UnboundedReceiver<RemoteExExRequest>,
+    /// Keeps the server running while the `ExEx` is.
+    server: RemoteExExServer,
+    /// The node head, as of the last notification received from the node.
+    node_head: BlockNumHash,
+    /// The connected consumer.
+    subscriber: Option<Subscriber>,
+}
+
+/// A consumer subscribed to the notifications.
+struct Subscriber {
+    sink: SubscriptionSink,
+    /// The tip of the last notification sent to the consumer.
+    delivered: Option<BlockNumHash>,
+}
+
+impl<Node: FullNodeComponents> RemoteExEx<Node> {
+    pub(crate) const fn new(
+        id: String,
+        ctx: ExExContext<Node>,
+        requests: mpsc::UnboundedReceiver<RemoteExExRequest>,
+        server: RemoteExExServer,
+    ) -> Self {
+        let node_head = ctx.head;
+        Self { id, ctx, requests, server, node_head, subscriber: None }
+    }
+
+    /// Runs the `ExEx` until the node stops sending notifications.
+    pub(crate) async fn run(mut self) -> eyre::Result<()> {
+        let result = self.forward().await;
+        self.server.deregister(&self.id);
+        result
+    }
+
+    async fn forward(&mut self) -> eyre::Result<()> {
+        loop {
+            tokio::select! {
+                Some(request) = self.requests.recv() => self.on_request(request),
+                _ = subscriber_closed(&self.subscriber) => {
+                    info!(target: "exex::remote", id = %self.id, "Remote ExEx consumer disconnected");
+                    self.subscriber = None;
+                }
+                notification = self.ctx.notifications.next() => {
+                    let Some(notification) = notification else { return Ok(()) };
+                    self.on_notification(notification?).await;
+                }
+            }
+        }
+    }
+
+    fn on_request(&mut self, request: RemoteExExRequest) {
+        match request {
+            RemoteExExRequest::Subscribe { head, sink } => self.on_subscribe(head, sink),
+            RemoteExExRequest::FinishedHeight { height, tx } => {
+                let _ = tx.send(self.on_finished_height(height));
+            }
+        }
+    }
+
+    /// Restarts the notifications from the head of the new consumer, replacing the previous one.
+    fn on_subscribe(&mut self, head: Option<ExExHead>, sink: SubscriptionSink) {
+        info!(target: "exex::remote", id = %self.id, ?head, node_head = ?self.node_head, "Remote ExEx consumer subscribed");
+
+        // the notifications received while no consumer was connected were discarded, so they
+        // have to be backfilled from the new node head
+        self.ctx.notifications.set_node_head(self.node_head);
+        self.ctx.set_notifications_without_head();
+        if let Some(head) = head {
+            self.ctx.set_notifications_with_head(head);
+            self.ctx.notifications.set_backfill_thresholds(ExecutionStageThresholds {
+                max_blocks: Some(BACKFILL_MAX_BLOCKS),
+                ..Default::default()
+            });
+        }
+
+        self.subscriber = Some(Subscriber { sink, delivered: None });
+    }
+
+    /// Forwards the acknowledgement of the consumer to the node, if the block was delivered.
+    fn on_finished_height(&self, height: BlockNumHash) -> Result<(), String> {
+        let Some(subscriber) = &self.subscriber else {
+            return Err(format!("remote ExEx {} has no subscriber", self.id))
+        };
+        if subscriber.delivered.is_none_or(|delivered| height.number > delivered.number) {
+            return Err(format!(
+                "block {} was not delivered to remote ExEx {}",
+                height.number, self.id
+            ))
+        }
+
+        debug!(target: "exex::remote", id = %self.id, ?height, "Remote ExEx finished height");
+        self.ctx.send_finished_height(height).map_err(|err| err.to_string())
+    }
+
+    /// Sends the notification to the consumer, or discards it if there's none.
+    async fn on_notification(&mut self, notification: ExExNotification<PrimitivesTy<Node::Types>>) {
+        self.update_node_head(&notification);
+
+        let Some(subscriber) = &mut self.subscriber else {
+            debug!(target: "exex::remote", id = %self.id, tip = ?notification.tip(), "No remote ExEx consumer, discarding notification");
+            return
+        };
+
+        let message = match subscription_message(&subscriber.sink, &notification) {
+            Ok(message) => message,
+            Err(err) => {
+                warn!(target: "exex::remote", id = %self.id, %err, "Failed to encode notification, dropping consumer");
+                self.subscriber = None;
+                return
+            }
+        };
+
+        if subscriber.sink.send(message).await.is_err() {
+            info!(target: "exex::remote", id = %self.id, "Remote ExEx consumer disconnected");
+            self.subscriber = None;
+            return
+        }
+        subscriber.delivered = Some(notification.tip());
+    }
+
+    /// Tracks the node head through the notifications.
+    ///
+    /// Backfilled notifications are below the node head and the revert of a non-canonical
+    /// consumer head doesn't revert the node head, so only notifications that extend the node
+    /// head or revert it move it.
+    fn update_node_head(&mut self, notification: &ExExNotification<PrimitivesTy<Node::Types>>) {
+        let reverts_node_head = notification
+            .reverted_chain()
+            .is_some_and(|reverted| reverted.tip().num_hash() == self.node_head);
+        if reverts_node_head || notification.tip().number > self.node_head.number {
+            self.node_head = notification.tip();
+        }
+    }
+}
+
+/// Encodes the notification into a message of the subscription.
+fn subscription_message<N: NodePrimitives>(
+    sink: &SubscriptionSink,
+    notification: &ExExNotification<N>,
+) -> eyre::Result<SubscriptionMessage> {
+    let bytes = encode_notification(notification)?;
+    Ok(SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &bytes)?)
+}
+
+/// Resolves when the consumer disconnects, never if there's no consumer.
+async fn subscriber_closed(subscriber: &Option<Subscriber>) {
+    match subscriber {
+        Some(subscriber) => subscriber.sink.closed().await,
+        None => futures::future::pending().await,
+    }
+}
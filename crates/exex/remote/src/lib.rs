@@ -0,0 +1,43 @@
+//! Out-of-process execution extensions (`ExEx`).
+//!
+//! A remote `ExEx` is an `ExEx` that runs in a separate process and receives its
+//! [`ExExNotification`](reth_exex::ExExNotification)s over IPC from the [`RemoteExExServer`].
+//!
+//! On the node side, every remote `ExEx` is installed as a regular `ExEx` under its own ID, so the
+//! `ExExManager` tracks its progress like any other `ExEx`. A consumer subscribes with
+//! `exex_subscribe`, passing its ID and the [`ExExHead`](reth_exex::ExExHead) it has processed up
+//! to. The server backfills the blocks the consumer missed and then streams live notifications,
+//! serialized like the WAL entries. The consumer acknowledges the blocks it has processed with
+//! `exex_finishedHeight`, which is forwarded to the `ExExManager` as
+//! [`ExExEvent::FinishedHeight`](reth_exex::ExExEvent::FinishedHeight), so that pruning and WAL
+//! finalization keep working.
+//!
+//! While no consumer is connected, the notifications are discarded and the `ExEx` does not make
+//! progress. The WAL keeps every notification after the last acknowledged height, so a consumer
+//! that reconnects with its last processed head catches up without gaps, even across reorgs.
+//!
+//! Remote `ExEx`es can also be registered and deregistered on a running node with `exex_register`
+//! and `exex_deregister`, once the server serves the node's
+//! [`ExExRegistry`](reth_exex::ExExRegistry) with [`RemoteExExServer::serve_registry`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod api;
+pub use api::*;
+
+mod args;
+pub use args::*;
+
+mod client;
+pub use client::*;
+
+mod exex;
+
+mod server;
+pub use server::*;
@@ -0,0 +1,393 @@
+use crate::{exex::RemoteExEx, RemoteExExApiServer};
+use alloy_eips::BlockNumHash;
+use futures::future::BoxFuture;
+use jsonrpsee::{
+    core::{async_trait, RpcResult, SubscriptionResult},
+    server::ServerHandle,
+    types::{error::INVALID_PARAMS_CODE, ErrorObject, ErrorObjectOwned},
+    PendingSubscriptionSink, SubscriptionSink,
+};
+use parking_lot::Mutex;
+use reth_exex::{ExExContext, ExExHead, ExExRegistry};
+use reth_node_api::FullNodeComponents;
+use reth_tracing::tracing::info;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    sync::{Arc, OnceLock, Weak},
+};
+use tokio::sync::{mpsc, oneshot, OnceCell};
+
+/// Remote `ExEx`es registered with the server, by ID.
+type Registry = Arc<Mutex<HashMap<String, mpsc::UnboundedSender<RemoteExExRequest>>>>;
+
+/// A request from a consumer to a remote `ExEx`.
+#[derive(Debug)]
+pub(crate) enum RemoteExExRequest {
+    /// A consumer subscribed to the notifications.
+    Subscribe {
+        /// The head the consumer has processed up to.
+        head: Option<ExExHead>,
+        /// The sink to send the notifications to.
+        sink: SubscriptionSink,
+    },
+    /// A consumer acknowledged that it has processed the blocks up to the given height.
+    FinishedHeight {
+        /// The highest processed block.
+        height: BlockNumHash,
+        /// Sends the error message if the acknowledgement was rejected.
+        tx: oneshot::Sender<Result<(), String>>,
+    },
+}
+
+/// Registers remote `ExEx`es on a running node, implemented by [`ExExRegistry`].
+pub trait RemoteExExRegistrar: Debug + Send + Sync + 'static {
+    /// Registers a remote `ExEx` with the given ID, installed on the given server.
+    fn register(
+        &self,
+        server: RemoteExExServer,
+        exex_id: String,
+        head: Option<ExExHead>,
+    ) -> BoxFuture<'static, eyre::Result<()>>;
+
+    /// Deregisters the remote `ExEx` with the given ID.
+    fn deregister(&self, exex_id: String) -> BoxFuture<'static, eyre::Result<()>>;
+}
+
+impl<Node: FullNodeComponents> RemoteExExRegistrar for ExExRegistry<Node> {
+    fn register(
+        &self,
+        server: RemoteExExServer,
+        exex_id: String,
+        head: Option<ExExHead>,
+    ) -> BoxFuture<'static, eyre::Result<()>> {
+        let registry = self.clone();
+        Box::pin(async move {
+            registry
+                .register(exex_id.clone(), head, move |ctx| async move {
+                    server.install(exex_id, ctx).await
+                })
+                .await
+        })
+    }
+
+    fn deregister(&self, exex_id: String) -> BoxFuture<'static, eyre::Result<()>> {
+        let registry = self.clone();
+        Box::pin(async move { registry.deregister(&exex_id).await })
+    }
+}
+
+/// Serves the notifications of remote `ExEx`es over IPC.
+///
+/// The server is started when the first remote `ExEx` is installed with
+/// [`RemoteExExServer::install`], and stopped once all of them have exited. With
+/// [`RemoteExExServer::serve_registry`], it is started right away and remote `ExEx`es can be
+/// registered at runtime, for as long as the server is kept.
+#[derive(Debug, Clone)]
+pub struct RemoteExExServer {
+    inner: Arc<RemoteExExServerInner>,
+}
+
+#[derive(Debug)]
+struct RemoteExExServerInner {
+    /// The IPC endpoint to listen on.
+    endpoint: String,
+    /// The installed remote `ExEx`es.
+    exexes: Registry,
+    /// The handle of the running IPC server.
+    handle: OnceCell<ServerHandle>,
+    /// Registers remote `ExEx`es at runtime.
+    registrar: OnceLock<Box<dyn RemoteExExRegistrar>>,
+}
+
+impl RemoteExExServer {
+    /// Creates a new server listening on the given IPC endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            inner: Arc::new(RemoteExExServerInner {
+                endpoint: endpoint.into(),
+                exexes: Default::default(),
+                handle: OnceCell::new(),
+                registrar: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// Returns the IPC endpoint the server listens on.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.endpoint
+    }
+
+    /// Installs a remote `ExEx` with the given ID, starting the IPC server if it isn't running
+    /// yet, and returns the `ExEx` future.
+    ///
+    /// Meant to be called from the closure passed to `install_exex`, with the same ID:
+    ///
+    /// ```ignore
+    /// builder.install_exex("indexer", move |ctx| async move { server.install("indexer".to_string(), ctx).await })
+    /// ```
+    pub async fn install<Node: FullNodeComponents>(
+        &self,
+        exex_id: String,
+        ctx: ExExContext<Node>,
+    ) -> eyre::Result<impl Future<Output = eyre::Result<()>> + use<Node>> {
+        self.start().await?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        {
+            let mut exexes = self.inner.exexes.lock();
+            if exexes.contains_key(&exex_id) {
+                eyre::bail!("remote ExEx {exex_id} is already installed")
+            }
+            exexes.insert(exex_id.clone(), tx);
+        }
+
+        Ok(RemoteExEx::new(exex_id, ctx, rx, self.clone()).run())
+    }
+
+    /// Starts the IPC server and allows remote `ExEx`es to be registered and deregistered at
+    /// runtime with `exex_register` and `exex_deregister`, through the given registrar.
+    pub async fn serve_registry(&self, registrar: impl RemoteExExRegistrar) -> eyre::Result<()> {
+        self.inner
+            .registrar
+            .set(Box::new(registrar))
+            .map_err(|_| eyre::eyre!("remote ExEx registry is already served"))?;
+        self.start().await
+    }
+
+    /// Removes the remote `ExEx` with the given ID from the registry.
+    pub(crate) fn deregister(&self, exex_id: &str) {
+        self.inner.exexes.lock().remove(exex_id);
+    }
+
+    /// Starts the IPC server, if it isn't running yet.
+    async fn start(&self) -> eyre::Result<()> {
+        self.inner
+            .handle
+            .get_or_try_init(|| async {
+                let rpc = RemoteExExRpc {
+                    exexes: self.inner.exexes.clone(),
+                    server: Arc::downgrade(&self.inner),
+                };
+                let handle = reth_ipc::server::Builder::default()
+                    // notifications of large chains may exceed the default limit
+                    .max_response_body_size(u32::MAX)
+                    .build(self.inner.endpoint.clone())
+                    .start(rpc.into_rpc())
+                    .await?;
+                info!(target: "exex::remote", endpoint = %self.inner.endpoint, "Remote ExEx server started");
+                eyre::Ok(handle)
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// Implementation of the [`RemoteExExApiServer`] that forwards the requests to the remote `ExEx`
+/// tasks.
+#[derive(Debug)]
+struct RemoteExExRpc {
+    exexes: Registry,
+    /// The server, to register remote `ExEx`es on. Weak, so that the server stops once it is
+    /// dropped.
+    server: Weak<RemoteExExServerInner>,
+}
+
+impl RemoteExExRpc {
+    /// Sends the request to the remote `ExEx` with the given ID.
+    fn send(&self, exex_id: &str, request: RemoteExExRequest) -> Result<(), ErrorObjectOwned> {
+        self.exexes
+            .lock()
+            .get(exex_id)
+            .and_then(|tx| tx.send(request).ok())
+            .ok_or_else(|| invalid_params(format!("unknown remote ExEx {exex_id}")))
+    }
+
+    /// Returns the server, if remote `ExEx`es can be registered on it.
+    fn registry_server(&self) -> Result<RemoteExExServer, ErrorObjectOwned> {
+        self.server
+            .upgrade()
+            .filter(|inner| inner.registrar.get().is_some())
+            .map(|inner| RemoteExExServer { inner })
+            .ok_or_else(|| invalid_params("remote ExEx registration is disabled"))
+    }
+}
+
+#[async_trait]
+impl RemoteExExApiServer for RemoteExExRpc {
+    async fn subscribe_notifications(
+        &self,
+        pending: PendingSubscriptionSink,
+        exex_id: String,
+        head: Option<ExExHead>,
+    ) -> SubscriptionResult {
+        if !self.exexes.lock().contains_key(&exex_id) {
+            pending.reject(invalid_params(format!("unknown remote ExEx {exex_id}"))).await;
+            return Ok(())
+        }
+
+        let sink = pending.accept().await?;
+        self.send(&exex_id, RemoteExExRequest::Subscribe { head, sink })?;
+        Ok(())
+    }
+
+    async fn finished_height(&self, exex_id: String, height: BlockNumHash) -> RpcResult<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(&exex_id, RemoteExExRequest::FinishedHeight { height, tx })?;
+        rx.await
+            .map_err(|_| invalid_params(format!("remote ExEx {exex_id} exited")))?
+            .map_err(invalid_params)
+    }
+
+    async fn register(&self, exex_id: String, head: Option<ExExHead>) -> RpcResult<()> {
+        let server = self.registry_server()?;
+        let registrar = server.inner.registrar.get().expect("checked above");
+        let registration = registrar.register(server.clone(), exex_id.clone(), head);
+        registration.await.map_err(|err| invalid_params(err.to_string()))?;
+        info!(target: "exex::remote", %exex_id, ?head, "Registered remote ExEx");
+        Ok(())
+    }
+
+    async fn deregister(&self, exex_id: String) -> RpcResult<()> {
+        let server = self.registry_server()?;
+        let registrar = server.inner.registrar.get().expect("checked above");
+        registrar
+            .deregister(exex_id.clone())
+            .await
+            .map_err(|err| invalid_params(err.to_string()))?;
+        // the task of the `ExEx` was aborted, so it doesn't deregister itself
+        server.deregister(&exex_id);
+        Ok(())
+    }
+}
+
+fn invalid_params(msg: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObject::owned(INVALID_PARAMS_CODE, msg.into(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemoteExExClient;
+    use futures::StreamExt;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_exex::ExExNotification;
+    use reth_exex_test_utils::{test_exex_context, TestExExHandle};
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use std::collections::BTreeMap;
+    use tokio::task::JoinHandle;
+
+    /// The tasks of the registered remote `ExEx`es and their test handles, by ID.
+    type TestExExes = Arc<Mutex<HashMap<String, (JoinHandle<eyre::Result<()>>, TestExExHandle)>>>;
+
+    /// Registers remote `ExEx`es on test contexts.
+    #[derive(Debug, Default)]
+    struct TestRegistrar {
+        exexes: TestExExes,
+    }
+
+    impl RemoteExExRegistrar for TestRegistrar {
+        fn register(
+            &self,
+            server: RemoteExExServer,
+            exex_id: String,
+            _head: Option<ExExHead>,
+        ) -> BoxFuture<'static, eyre::Result<()>> {
+            let exexes = self.exexes.clone();
+            Box::pin(async move {
+                let (ctx, handle) = test_exex_context().await?;
+                let exex = tokio::spawn(server.install(exex_id.clone(), ctx).await?);
+                exexes.lock().insert(exex_id, (exex, handle));
+                Ok(())
+            })
+        }
+
+        fn deregister(&self, exex_id: String) -> BoxFuture<'static, eyre::Result<()>> {
+            let exex = self.exexes.lock().remove(&exex_id);
+            Box::pin(async move {
+                let (exex, _) =
+                    exex.ok_or_else(|| eyre::eyre!("ExEx {exex_id} is not registered"))?;
+                exex.abort();
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn acknowledged_delivery() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (ctx, mut handle) = test_exex_context().await?;
+        let genesis = handle.genesis.num_hash();
+
+        let dir = tempfile::tempdir()?;
+        let endpoint = dir.path().join("exex.ipc").to_string_lossy().into_owned();
+        let server = RemoteExExServer::new(endpoint.clone());
+        let exex = tokio::spawn(server.install("remote".to_string(), ctx).await?);
+        assert!(server.install("remote".to_string(), test_exex_context().await?.0).await.is_err());
+
+        let client = RemoteExExClient::connect(&endpoint, "remote").await?;
+        let err = client.send_finished_height(genesis).await.unwrap_err();
+        assert!(err.to_string().contains("has no subscriber"), "{err}");
+
+        let mut notifications = client.notifications::<EthPrimitives>(None).await?;
+        // acknowledgements are handled in order with the subscription, so once this one is
+        // rejected, the subscriber is registered
+        let err = client.send_finished_height(genesis).await.unwrap_err();
+        assert!(err.to_string().contains("was not delivered"), "{err}");
+
+        let block = random_block(
+            &mut rng,
+            genesis.number + 1,
+            BlockParams { parent: Some(genesis.hash), tx_count: Some(0), ..Default::default() },
+        )
+        .try_recover()?;
+        let chain = Chain::new(vec![block.clone()], Default::default(), BTreeMap::new());
+        handle.send_notification_chain_committed(chain.clone()).await?;
+
+        let notification = notifications.next().await.unwrap()?;
+        assert_eq!(notification, ExExNotification::ChainCommitted { new: Arc::new(chain) });
+        handle.assert_events_empty();
+
+        client.send_finished_height(block.num_hash()).await?;
+        handle.assert_event_finished_height(block.num_hash())?;
+
+        let unknown = RemoteExExClient::connect(&endpoint, "unknown").await?;
+        assert!(unknown.notifications::<EthPrimitives>(None).await.is_err());
+
+        exex.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_at_runtime() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let endpoint = dir.path().join("exex.ipc").to_string_lossy().into_owned();
+        let server = RemoteExExServer::new(endpoint.clone());
+        let (ctx, _handle) = test_exex_context().await?;
+        let exex = tokio::spawn(server.install("installed".to_string(), ctx).await?);
+
+        let client = RemoteExExClient::connect(&endpoint, "remote").await?;
+        let err = client.register(None).await.unwrap_err();
+        assert!(err.to_string().contains("registration is disabled"), "{err}");
+
+        server.serve_registry(TestRegistrar::default()).await?;
+        assert!(server.serve_registry(TestRegistrar::default()).await.is_err());
+
+        client.register(None).await?;
+        assert!(client.register(None).await.is_err());
+        let _notifications = client.notifications::<EthPrimitives>(None).await?;
+
+        // only ExExes registered at runtime can be deregistered
+        let installed = RemoteExExClient::connect(&endpoint, "installed").await?;
+        assert!(installed.deregister().await.is_err());
+
+        client.deregister().await?;
+        assert!(client.notifications::<EthPrimitives>(None).await.is_err());
+        assert!(client.deregister().await.is_err());
+
+        exex.abort();
+        Ok(())
+    }
+}
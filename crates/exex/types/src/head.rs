@@ -4,6 +4,7 @@ use alloy_eips::BlockNumHash;
 /// internal ExEx state. I.e. the latest block that the ExEx has fully
 /// processed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExExHead {
     /// The head block.
     pub block: BlockNumHash,
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use alloy_eips::BlockNumHash;
 use reth_chain_state::CanonStateNotification;
 use reth_execution_types::Chain;
 use reth_primitives_traits::NodePrimitives;
@@ -46,6 +47,17 @@ impl<N: NodePrimitives> ExExNotification<N> {
         }
     }
 
+    /// Returns the tip of the canonical chain once the notification is applied: the tip of the
+    /// committed chain, or the parent of the first reverted block.
+    pub fn tip(&self) -> BlockNumHash {
+        match self {
+            Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => {
+                new.tip().num_hash()
+            }
+            Self::ChainReverted { old } => old.fork_block(),
+        }
+    }
+
     /// Converts the notification into a notification that is the inverse of the original one.
     ///
     /// - For [`Self::ChainCommitted`], it's [`Self::ChainReverted`].
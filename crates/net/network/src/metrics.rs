@@ -191,6 +191,8 @@ pub struct TransactionsManagerMetrics {
     pub(crate) skipped_transactions_pending_pool_imports_at_capacity: Counter,
     /// The time it took to prepare transactions for import. This is mostly sender recovery.
     pub(crate) pool_import_prepare_duration: Histogram,
+    /// Total number of nonce gaps reported by the pool.
+    pub(crate) nonce_gaps_reported: Counter,
 
     /* ================ POLL DURATION ================ */
 
@@ -245,6 +247,12 @@ pub struct TransactionsManagerMetrics {
     ///
     /// Duration in seconds.
     pub(crate) acc_duration_poll_commands: Gauge,
+    /// Accumulated time spent streaming nonce gaps reported by the pool and requesting candidates
+    /// for the missing transactions, in one call to poll the
+    /// [`TransactionsManager`](crate::transactions::TransactionsManager) future.
+    ///
+    /// Duration in seconds.
+    pub(crate) acc_duration_poll_nonce_gaps: Gauge,
 }
 
 /// Metrics for the [`TransactionsManager`](crate::transactions::TransactionsManager).
@@ -272,6 +280,9 @@ pub struct TransactionFetcherMetrics {
     /// [`PooledTransactions`](reth_eth_wire::PooledTransactions) responses, that weren't
     /// requested.
     pub(crate) unsolicited_transactions: Counter,
+    /// Total number of [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) requests
+    /// sent to fill a nonce gap reported by the pool.
+    pub(crate) nonce_gap_requests: Counter,
     /* ================ SEARCH DURATION ================ */
     /// Time spent searching for an idle peer in call to
     /// [`TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash`](crate::transactions::TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash).
@@ -493,6 +493,72 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         true
     }
 
+    /// Tries to request hashes pending fetch that are candidates for a transaction missing from the
+    /// pool, i.e. one that leaves a nonce gap for its sender.
+    ///
+    /// The missing transaction's hash is unknown, but it has most likely been announced by the
+    /// same peers that announced the sender's queued transactions. Hence, hashes pending fetch
+    /// that have been seen by one of the given `announcers` are requested from the first idle
+    /// announcer that has seen any. The search is limited by the same budget as
+    /// [`Self::on_fetch_pending_hashes`].
+    ///
+    /// Returns `true` if a request was sent.
+    pub fn on_nonce_gap(
+        &mut self,
+        announcers: impl IntoIterator<Item = PeerId>,
+        peers: &HashMap<PeerId, PeerMetadata<N>>,
+        has_capacity_wrt_pending_pool_imports: impl Fn(usize) -> bool,
+    ) -> bool {
+        let budget_fill_request = self
+            .search_breadth_budget_find_intersection_pending_hashes_and_hashes_seen_by_peer(
+                &has_capacity_wrt_pending_pool_imports,
+            );
+
+        for peer_id in announcers {
+            if !self.is_idle(&peer_id) {
+                continue
+            }
+            let Some(peer) = peers.get(&peer_id) else { continue };
+
+            let mut hashes_to_request = RequestTxHashes::with_capacity(
+                DEFAULT_MARGINAL_COUNT_HASHES_GET_POOLED_TRANSACTIONS_REQUEST,
+            );
+            let Some(&hash) = self
+                .hashes_pending_fetch
+                .iter()
+                .take(budget_fill_request.unwrap_or(usize::MAX))
+                .find(|hash| peer.seen_transactions.contains(hash))
+            else {
+                continue
+            };
+            hashes_to_request.insert(hash);
+
+            self.fill_request_from_hashes_pending_fetch(
+                &mut hashes_to_request,
+                &peer.seen_transactions,
+                budget_fill_request,
+            );
+
+            trace!(target: "net::tx",
+                peer_id=format!("{peer_id:#}"),
+                hashes=?*hashes_to_request,
+                "requesting candidates for transaction missing from nonce gap from peer"
+            );
+
+            if let Some(failed_to_request_hashes) =
+                self.request_transactions_from_peer(hashes_to_request, peer)
+            {
+                self.buffer_hashes(failed_to_request_hashes, Some(peer_id));
+                return false
+            }
+
+            self.metrics.nonce_gap_requests.increment(1);
+            return true
+        }
+
+        false
+    }
+
     /// Filters out hashes that have been seen before. For hashes that have already been seen, the
     /// peer is added as fallback peer.
     pub fn filter_unseen_and_pending_hashes(
@@ -64,7 +64,7 @@ use reth_primitives_traits::SignedTransaction;
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
-    AddedTransactionOutcome, GetPooledTransactionLimit, PoolTransaction, PropagateKind,
+    AddedTransactionOutcome, GetPooledTransactionLimit, NonceGap, PoolTransaction, PropagateKind,
     PropagatedTransactions, TransactionPool, ValidPoolTransaction,
 };
 use std::{
@@ -78,7 +78,7 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
 /// The future for importing transactions into the pool.
@@ -332,6 +332,11 @@ pub struct TransactionsManager<Pool, N: NetworkPrimitives = EthNetworkPrimitives
     ///   - all dynamic fee requirements are (currently) met
     ///   - account has enough balance to cover the transaction's gas
     pending_transactions: mpsc::Receiver<TxHash>,
+    /// A stream that yields nonce gaps of senders whose transactions were queued by the pool.
+    ///
+    /// The missing transactions are actively requested from the peers that announced the
+    /// sender's queued transactions.
+    nonce_gaps: ReceiverStream<NonceGap>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent<N>>,
     /// How the `TransactionsManager` is configured.
@@ -389,6 +394,8 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
         // install a listener for new __pending__ transactions that are allowed to be propagated
         // over the network
         let pending = pool.pending_transactions_listener();
+        // install a listener for nonce gaps, so that missing transactions can be fetched
+        let nonce_gaps = pool.nonce_gap_listener();
         let pending_pool_imports_info = PendingPoolImportsInfo::default();
         let metrics = TransactionsManagerMetrics::default();
         metrics
@@ -410,6 +417,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
             command_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
             pending_transactions: pending,
+            nonce_gaps: ReceiverStream::new(nonce_gaps),
             transaction_events: UnboundedMeteredReceiver::new(
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
@@ -520,6 +528,47 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
             .on_fetch_pending_hashes(&self.peers, has_capacity_wrt_pending_pool_imports)
     }
 
+    /// Invoked when the pool queued a transaction because its sender has a nonce gap.
+    ///
+    /// Requests hashes pending fetch from the peers that announced the sender's queued
+    /// transactions, since these peers most likely announced the missing transaction too.
+    fn on_nonce_gap(&mut self, nonce_gap: NonceGap) {
+        if self.network.is_initially_syncing() || self.network.tx_gossip_disabled() {
+            return
+        }
+        self.metrics.nonce_gaps_reported.increment(1);
+
+        if !self.has_capacity_for_fetching_pending_hashes() {
+            return
+        }
+
+        let NonceGap { sender, missing_nonce, queued } = nonce_gap;
+        let announcers = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| queued.iter().any(|hash| peer.seen_transactions.contains(hash)))
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+
+        trace!(target: "net::tx",
+            %sender,
+            missing_nonce,
+            announcers=announcers.len(),
+            "nonce gap reported by pool"
+        );
+
+        let info = &self.pending_pool_imports_info;
+        let max_pending_pool_imports = info.max_pending_pool_imports;
+        let has_capacity_wrt_pending_pool_imports =
+            |divisor| info.has_capacity(max_pending_pool_imports / divisor);
+
+        self.transaction_fetcher.on_nonce_gap(
+            announcers,
+            &self.peers,
+            has_capacity_wrt_pending_pool_imports,
+        );
+    }
+
     fn on_request_error(&self, peer_id: PeerId, req_err: RequestError) {
         let kind = match req_err {
             RequestError::UnsupportedCapability => ReputationChangeKind::BadProtocol,
@@ -545,6 +594,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
             acc_fetch_events,
             acc_pending_fetch,
             acc_cmds,
+            acc_nonce_gaps,
         } = poll_durations;
 
         // update metrics for whole poll function
@@ -557,6 +607,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
         metrics.acc_duration_poll_fetch_events.set(acc_fetch_events.as_secs_f64());
         metrics.acc_duration_fetch_pending_hashes.set(acc_pending_fetch.as_secs_f64());
         metrics.acc_duration_poll_commands.set(acc_cmds.as_secs_f64());
+        metrics.acc_duration_poll_nonce_gaps.set(acc_nonce_gaps.as_secs_f64());
     }
 }
 
@@ -1645,6 +1696,19 @@ where
             |batch_results| this.on_batch_import_result(batch_results)
         );
 
+        // Advance nonce gaps reported by the pool (fetch txns missing from queued senders).
+        //
+        // Requests for the missing transactions take precedence over draining the hashes
+        // pending fetch below, but are subject to the same capacity checks.
+        let maybe_more_nonce_gaps = metered_poll_nested_stream_with_budget!(
+            poll_durations.acc_nonce_gaps,
+            "net::tx",
+            "Nonce gaps stream",
+            DEFAULT_BUDGET_TRY_DRAIN_STREAM,
+            this.nonce_gaps.poll_next_unpin(cx),
+            |nonce_gap| this.on_nonce_gap(nonce_gap)
+        );
+
         // Tries to drain hashes pending fetch cache if the tx manager currently has
         // capacity for this (fetch txns).
         //
@@ -1678,7 +1742,8 @@ where
             maybe_more_tx_events ||
             maybe_more_tx_fetch_events ||
             maybe_more_pool_imports ||
-            maybe_more_pending_txns
+            maybe_more_pending_txns ||
+            maybe_more_nonce_gaps
         {
             // make sure we're woken up again
             cx.waker().wake_by_ref();
@@ -2146,6 +2211,7 @@ struct TxManagerPollDurations {
     acc_fetch_events: Duration,
     acc_pending_fetch: Duration,
     acc_cmds: Duration,
+    acc_nonce_gaps: Duration,
 }
 
 #[cfg(test)]
@@ -2160,7 +2226,7 @@ mod tests {
         NetworkConfigBuilder, NetworkManager,
     };
     use alloy_consensus::{TxEip1559, TxLegacy};
-    use alloy_primitives::{hex, Signature, TxKind, U256};
+    use alloy_primitives::{hex, Address, Signature, TxKind, U256};
     use alloy_rlp::Decodable;
     use futures::FutureExt;
     use reth_chainspec::MIN_TRANSACTION_GAS;
@@ -2801,6 +2867,52 @@ mod tests {
         assert_eq!(tx_fetcher.active_peers.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_nonce_gap_requests_candidates_from_announcer() {
+        reth_tracing::init_test_tracing();
+
+        let mut tx_manager = new_tx_manager().await.0;
+
+        let peer_id_1 = PeerId::new([1; 64]);
+        let peer_id_2 = PeerId::new([2; 64]);
+        let eth_version = EthVersion::Eth66;
+        let queued_hash = B256::from_slice(&[1; 32]);
+        let candidate_hash = B256::from_slice(&[2; 32]);
+
+        // peer_1 announced both the sender's queued transaction and the candidate
+        let (mut peer_1, mut to_mock_session_rx_1) = new_mock_session(peer_id_1, eth_version);
+        peer_1.seen_transactions.insert(queued_hash);
+        peer_1.seen_transactions.insert(candidate_hash);
+        tx_manager.peers.insert(peer_id_1, peer_1);
+
+        // peer_2 only announced the candidate
+        let (mut peer_2, mut to_mock_session_rx_2) = new_mock_session(peer_id_2, eth_version);
+        peer_2.seen_transactions.insert(candidate_hash);
+        tx_manager.peers.insert(peer_id_2, peer_2);
+
+        let tx_fetcher = &mut tx_manager.transaction_fetcher;
+        buffer_hash_to_tx_fetcher(tx_fetcher, candidate_hash, peer_id_2, 0, None);
+        buffer_hash_to_tx_fetcher(tx_fetcher, candidate_hash, peer_id_1, 0, None);
+
+        tx_manager.on_nonce_gap(NonceGap {
+            sender: Address::random(),
+            missing_nonce: 0,
+            queued: vec![queued_hash],
+        });
+
+        let tx_fetcher = &tx_manager.transaction_fetcher;
+        assert_eq!(tx_fetcher.num_pending_hashes(), 0);
+        assert!(!tx_fetcher.is_idle(&peer_id_1));
+
+        let req = to_mock_session_rx_1
+            .recv()
+            .await
+            .expect("peer_1 session should receive request for candidate");
+        let PeerRequest::GetPooledTransactions { request, .. } = req else { unreachable!() };
+        assert_eq!(request.0, vec![candidate_hash]);
+        assert!(to_mock_session_rx_2.try_recv().is_err());
+    }
+
     #[test]
     fn test_transaction_builder_empty() {
         let mut builder =
@@ -3,14 +3,17 @@
 use crate::PayloadTypes;
 use alloy_rpc_types_engine::JwtSecret;
 use reth_basic_payload_builder::PayloadBuilder;
+use reth_config::Config;
 use reth_consensus::FullConsensus;
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
-use reth_evm::ConfigureEvm;
+use reth_engine_primitives::{
+    inclusion_list::InclusionListStore, ConsensusEngineEvent, ConsensusEngineHandle,
+};
+use reth_evm::{profiler::ExecutionProfiler, ConfigureEvm};
 use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
-use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_builder::{BuilderSettingsHandle, PayloadBuilderHandle};
 use reth_provider::FullProvider;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
@@ -109,12 +112,20 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub node: N,
     /// Node configuration.
     pub config: &'a NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+    /// Configuration loaded from the reth.toml file.
+    pub toml_config: &'a Config,
     /// Handle to the beacon consensus engine.
     pub beacon_engine_handle: ConsensusEngineHandle<<N::Types as NodeTypes>::Payload>,
     /// Notification channel for engine API events
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Shared handle to the payload builder settings that can be adjusted at runtime.
+    pub builder_settings: BuilderSettingsHandle,
+    /// Inclusion lists received over the engine API that new payloads have to satisfy.
+    pub inclusion_lists: InclusionListStore,
+    /// Profiler of the executed contracts and precompiles, if execution profiling is enabled.
+    pub execution_profiler: Option<ExecutionProfiler>,
 }
 
 /// Customizable node add-on types.
@@ -12,6 +12,9 @@ pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub hooks: NodeHooks<Node, AddOns>,
     /// The `ExExs` (execution extensions) of the node.
     pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// Whether `ExEx`es can be registered at runtime, see
+    /// [`FullNode::exex_registry`](crate::FullNode::exex_registry).
+    pub exex_registry: bool,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
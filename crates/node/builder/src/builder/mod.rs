@@ -13,6 +13,7 @@ use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use reth_engine_primitives::inclusion_list::InclusionListStore;
 use reth_exex::ExExContext;
 use reth_network::{
     transactions::{
@@ -31,12 +32,15 @@ use reth_node_core::{
     node_config::NodeConfig,
     primitives::Head,
 };
+use reth_payload_builder::BuilderSettingsHandle;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    policy::InclusionPolicyHandle, PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -657,6 +661,13 @@ where
         }
     }
 
+    /// Allows `ExEx`es to be registered and deregistered while the node is running.
+    ///
+    /// See [`NodeBuilderWithComponents::enable_exex_registry`].
+    pub fn enable_exex_registry(self) -> Self {
+        Self { builder: self.builder.enable_exex_registry(), task_executor: self.task_executor }
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -806,6 +817,24 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().builder.clone()
     }
 
+    /// Returns the shared handle to the payload builder settings that can be adjusted at runtime,
+    /// e.g. via the `miner_` RPC namespace.
+    pub const fn builder_settings(&self) -> &BuilderSettingsHandle {
+        &self.config_container.builder_settings
+    }
+
+    /// Returns the shared handle to the transaction inclusion policy that is enforced by the
+    /// transaction pool and the payload builder.
+    pub const fn inclusion_policy(&self) -> &InclusionPolicyHandle {
+        &self.config_container.inclusion_policy
+    }
+
+    /// Returns the shared store of the inclusion lists received over the engine API, that new
+    /// payloads have to satisfy and the payload builder has to include.
+    pub const fn inclusion_lists(&self) -> &InclusionListStore {
+        &self.config_container.inclusion_lists
+    }
+
     /// Convenience function to start the network tasks.
     ///
     /// Spawns the configured network and associated tasks and returns the [`NetworkHandle`]
@@ -47,7 +47,12 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons: () },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                exex_registry: false,
+                add_ons: (),
+            },
         }
     }
 }
@@ -173,7 +178,12 @@ where
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                exex_registry: false,
+                add_ons,
+            },
         }
     }
 }
@@ -219,6 +229,16 @@ where
         self
     }
 
+    /// Allows `ExEx`es to be registered and deregistered while the node is running, through the
+    /// [`ExExRegistry`](reth_exex::ExExRegistry) of the launched
+    /// [`FullNode::exex_registry`](crate::FullNode::exex_registry).
+    ///
+    /// The `ExEx` manager is then launched even if no `ExEx` is installed.
+    pub const fn enable_exex_registry(mut self) -> Self {
+        self.add_ons.exex_registry = true;
+        self
+    }
+
     /// Launches the node with the given closure.
     pub fn launch_with_fn<L, R>(self, launcher: L) -> R
     where
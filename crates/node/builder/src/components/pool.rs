@@ -6,11 +6,12 @@ use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, NodeTypes, TxTy};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction,
+    blobstore::DiskFileBlobStore, BlobStore, ConfigurableOrdering, PoolConfig, PoolTransaction,
     SubPoolLimit, TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor,
     TransactionValidator,
 };
 use std::future::Future;
+use tracing::info;
 
 /// A type that knows how to build the transaction pool.
 pub trait PoolBuilder<Node: FullNodeTypes, Evm>: Send {
@@ -138,29 +139,29 @@ where
 {
     /// Consume the ype and build the [`reth_transaction_pool::Pool`] with the given config and blob
     /// store.
+    ///
+    /// The transactions are ordered as configured with `--txpool.ordering`.
     pub fn build<BS>(
         self,
         blob_store: BS,
         pool_config: PoolConfig,
     ) -> reth_transaction_pool::Pool<
         TransactionValidationTaskExecutor<V>,
-        CoinbaseTipOrdering<V::Transaction>,
+        ConfigurableOrdering<V::Transaction>,
         BS,
     >
     where
         BS: BlobStore,
     {
+        let ordering = configured_ordering(self.ctx);
         let TxPoolBuilder { validator, .. } = self;
-        reth_transaction_pool::Pool::new(
-            validator,
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config,
-        )
+        reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config)
     }
 
     /// Build the transaction pool and spawn its maintenance tasks.
     /// This method creates the blob store, builds the pool, and spawns maintenance tasks.
+    ///
+    /// The transactions are ordered as configured with `--txpool.ordering`.
     pub fn build_and_spawn_maintenance_task<BS>(
         self,
         blob_store: BS,
@@ -168,18 +169,15 @@ where
     ) -> eyre::Result<
         reth_transaction_pool::Pool<
             TransactionValidationTaskExecutor<V>,
-            CoinbaseTipOrdering<V::Transaction>,
+            ConfigurableOrdering<V::Transaction>,
             BS,
         >,
     >
     where
         BS: BlobStore,
     {
-        self.build_with_ordering_and_spawn_maintenance_task(
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config,
-        )
+        let ordering = configured_ordering(self.ctx);
+        self.build_with_ordering_and_spawn_maintenance_task(ordering, blob_store, pool_config)
     }
 
     /// Build the transaction pool with a custom [`TransactionOrdering`] and spawn its maintenance
@@ -198,6 +196,7 @@ where
 
         let transaction_pool =
             reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config.clone());
+        let transaction_pool = with_configured_recorder(ctx, transaction_pool)?;
 
         spawn_maintenance_tasks(ctx, transaction_pool.clone(), &pool_config)?;
 
@@ -205,6 +204,33 @@ where
     }
 }
 
+/// Returns the [`ConfigurableOrdering`] configured with `--txpool.ordering`.
+fn configured_ordering<Node: FullNodeTypes, T>(
+    ctx: &BuilderContext<Node>,
+) -> ConfigurableOrdering<T> {
+    let config = ctx.config().txpool.ordering_config();
+    info!(target: "reth::cli", ?config, "Transaction pool ordering");
+    ConfigurableOrdering::new(config)
+}
+
+/// Attaches a [`PoolRecorder`](reth_transaction_pool::recorder::PoolRecorder) to the pool if
+/// recording is enabled with `--txpool.record`.
+fn with_configured_recorder<Node, V, T, S>(
+    ctx: &BuilderContext<Node>,
+    pool: reth_transaction_pool::Pool<V, T, S>,
+) -> eyre::Result<reth_transaction_pool::Pool<V, T, S>>
+where
+    Node: FullNodeTypes,
+    V: TransactionValidator,
+    T: TransactionOrdering<Transaction = V::Transaction>,
+    S: BlobStore,
+{
+    let Some(path) = &ctx.config().txpool.record_path else { return Ok(pool) };
+    let recorder = reth_transaction_pool::recorder::PoolRecorder::create(path)?;
+    info!(target: "reth::cli", path = %path.display(), "Recording transaction pool arrivals");
+    Ok(pool.with_recorder(recorder))
+}
+
 /// Create blob store with default configuration.
 pub fn create_blob_store<Node: FullNodeTypes>(
     ctx: &BuilderContext<Node>,
@@ -230,6 +256,20 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Create a [`SegmentedBlobStore`](reth_transaction_pool::blobstore::SegmentedBlobStore) in the
+/// blob store directory, which appends blobs to rolling segment files instead of writing a file per
+/// transaction.
+pub fn create_segmented_blob_store<Node: FullNodeTypes>(
+    ctx: &BuilderContext<Node>,
+) -> eyre::Result<reth_transaction_pool::blobstore::SegmentedBlobStore> {
+    let config = reth_transaction_pool::blobstore::SegmentedBlobStoreConfig::default()
+        .with_max_cached_entries(ctx.config().txpool.max_cached_entries);
+    Ok(reth_transaction_pool::blobstore::SegmentedBlobStore::open(
+        ctx.config().datadir().blobstore(),
+        config,
+    )?)
+}
+
 /// Spawn local transaction backup task if enabled.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
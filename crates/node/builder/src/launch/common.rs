@@ -34,24 +34,26 @@ use crate::{
     hooks::OnComponentInitializedHook,
     BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
 };
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_eips::eip2124::Head;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::Context;
 use rayon::ThreadPoolBuilder;
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_config::{config::EtlConfig, BuilderConfig, InclusionPolicyConfig, PruneConfig};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis_with_settings, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
+use reth_engine_primitives::inclusion_list::InclusionListStore;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
-    args::DefaultEraHost,
+    args::{DefaultEraHost, DefaultPayloadBuilderValues, PayloadBuilderArgs},
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
     primitives::BlockHeader,
@@ -64,6 +66,7 @@ use reth_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
+use reth_payload_builder::{BuilderSettings, BuilderSettingsHandle};
 use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
     BlockHashReader, BlockNumReader, ProviderError, ProviderFactory, ProviderResult,
@@ -83,9 +86,14 @@ use reth_tracing::{
     throttle,
     tracing::{debug, error, info, warn},
 };
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{
+    policy::{DenylistPolicy, InclusionPolicyHandle},
+    TransactionPool,
+};
 use reth_trie_db::ChangesetCache;
-use std::{num::NonZeroUsize, sync::Arc, thread::available_parallelism, time::Duration};
+use std::{
+    num::NonZeroUsize, path::Path, sync::Arc, thread::available_parallelism, time::Duration,
+};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
     oneshot, watch,
@@ -144,7 +152,19 @@ impl LaunchContext {
         ChainSpec: EthChainSpec + reth_chainspec::EthereumHardforks,
     {
         let toml_config = self.load_toml_config(&config)?;
-        Ok(self.with(WithConfigs { config, toml_config }))
+        let builder_settings = BuilderSettingsHandle::new(builder_settings_from_config(
+            &toml_config.builder,
+            &config.builder,
+        )?);
+        let inclusion_policy =
+            InclusionPolicyHandle::new(inclusion_policy_from_config(&toml_config.inclusion_policy));
+        Ok(self.with(WithConfigs {
+            config,
+            toml_config,
+            builder_settings,
+            inclusion_policy,
+            inclusion_lists: InclusionListStore::default(),
+        }))
     }
 
     /// Loads the reth config with the configured `data_dir` and overrides settings according to the
@@ -392,6 +412,53 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         &mut self.left_mut().toml_config
     }
 
+    /// Returns the shared [`BuilderSettingsHandle`] of the node.
+    pub const fn builder_settings(&self) -> &BuilderSettingsHandle {
+        &self.left().builder_settings
+    }
+
+    /// Returns the shared [`InclusionPolicyHandle`] of the node.
+    pub const fn inclusion_policy(&self) -> &InclusionPolicyHandle {
+        &self.left().inclusion_policy
+    }
+
+    /// Returns the shared [`InclusionListStore`] of the node.
+    pub const fn inclusion_lists(&self) -> &InclusionListStore {
+        &self.left().inclusion_lists
+    }
+
+    /// Spawns a task that re-applies the runtime-adjustable sections of the reth.toml whenever the
+    /// process receives `SIGHUP`:
+    ///  - `[builder]` replaces the settings of the [`BuilderSettingsHandle`], values that are not
+    ///    set in the file fall back to the CLI arguments, including values that were previously set
+    ///    through the `miner_` RPC namespace.
+    ///  - `[inclusion_policy]` replaces the policy of the [`InclusionPolicyHandle`].
+    pub fn spawn_config_reload(&self) {
+        #[cfg(unix)]
+        {
+            let config_path =
+                self.node_config().config.clone().unwrap_or_else(|| self.data_dir().config());
+            let builder_args = self.node_config().builder.clone();
+            let settings = self.builder_settings().clone();
+            let inclusion_policy = self.inclusion_policy().clone();
+            self.task_executor().spawn_task(Box::pin(async move {
+                let mut hangup = match tokio::signal::unix::signal(
+                    tokio::signal::unix::SignalKind::hangup(),
+                ) {
+                    Ok(hangup) => hangup,
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to install SIGHUP handler, config can't be reloaded");
+                        return
+                    }
+                };
+
+                while hangup.recv().await.is_some() {
+                    reload_config(&config_path, &builder_args, &settings, &inclusion_policy);
+                }
+            }));
+        }
+    }
+
     /// Returns the configured chain spec.
     pub fn chain_spec(&self) -> Arc<ChainSpec> {
         self.node_config().chain.clone()
@@ -1199,11 +1266,95 @@ pub struct WithConfigs<ChainSpec> {
     pub config: NodeConfig<ChainSpec>,
     /// The loaded reth.toml config.
     pub toml_config: reth_config::Config,
+    /// The runtime-adjustable payload builder settings, shared by all clones.
+    pub builder_settings: BuilderSettingsHandle,
+    /// The runtime-adjustable transaction inclusion policy, shared by all clones.
+    pub inclusion_policy: InclusionPolicyHandle,
+    /// The inclusion lists received over the engine API, shared by all clones.
+    pub inclusion_lists: InclusionListStore,
 }
 
 impl<ChainSpec> Clone for WithConfigs<ChainSpec> {
     fn clone(&self) -> Self {
-        Self { config: self.config.clone(), toml_config: self.toml_config.clone() }
+        Self {
+            config: self.config.clone(),
+            toml_config: self.toml_config.clone(),
+            builder_settings: self.builder_settings.clone(),
+            inclusion_policy: self.inclusion_policy.clone(),
+            inclusion_lists: self.inclusion_lists.clone(),
+        }
+    }
+}
+
+/// Re-applies the runtime-adjustable sections of the reth.toml at the given path.
+///
+/// If the file can't be read or parsed, the current settings and policy are kept.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn reload_config(
+    config_path: &Path,
+    builder_args: &PayloadBuilderArgs,
+    settings: &BuilderSettingsHandle,
+    inclusion_policy: &InclusionPolicyHandle,
+) {
+    let config = match reth_config::Config::read_from_path(config_path) {
+        Ok(config) => config,
+        Err(err) => {
+            warn!(target: "reth::cli", path = ?config_path, %err, "Failed to reload config, keeping current settings");
+            return
+        }
+    };
+
+    match builder_settings_from_config(&config.builder, builder_args) {
+        Ok(overrides) => {
+            settings.update(|settings| *settings = overrides);
+            info!(target: "reth::cli", path = ?config_path, settings = ?settings.get(), "Reloaded builder settings");
+        }
+        Err(err) => {
+            warn!(target: "reth::cli", path = ?config_path, %err, "Invalid builder settings, keeping current settings");
+        }
+    }
+
+    let policy = inclusion_policy_from_config(&config.inclusion_policy);
+    info!(target: "reth::cli", path = ?config_path, ?policy, "Reloaded inclusion policy");
+    inclusion_policy.set(policy);
+}
+
+/// Converts the `[builder]` section of the reth.toml into [`BuilderSettings`] overrides.
+///
+/// Explicit `--builder.*` arguments take precedence, values that are also set on the command line
+/// are dropped.
+fn builder_settings_from_config(
+    config: &BuilderConfig,
+    args: &PayloadBuilderArgs,
+) -> eyre::Result<BuilderSettings> {
+    let BuilderConfig { gas_limit, extra_data, max_blobs_per_block, min_priority_fee } =
+        config.clone();
+
+    if let Some(extra_data) = &extra_data {
+        eyre::ensure!(
+            extra_data.len() <= MAXIMUM_EXTRA_DATA_SIZE,
+            "builder extra data exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit"
+        );
+    }
+
+    let extra_data_is_default = args.extra_data.as_ref() ==
+        DefaultPayloadBuilderValues::get_global().extra_data().as_bytes();
+    Ok(BuilderSettings {
+        gas_limit: gas_limit.filter(|_| args.gas_limit.is_none()),
+        extra_data: extra_data.filter(|_| extra_data_is_default),
+        max_blobs_per_block: max_blobs_per_block.filter(|_| args.max_blobs_per_block.is_none()),
+        min_priority_fee,
+    })
+}
+
+/// Converts the `[inclusion_policy]` section of the reth.toml into a [`DenylistPolicy`].
+fn inclusion_policy_from_config(config: &InclusionPolicyConfig) -> DenylistPolicy {
+    let InclusionPolicyConfig { senders, recipients, touched, selectors } = config;
+    DenylistPolicy {
+        senders: senders.iter().copied().collect(),
+        recipients: recipients.iter().copied().collect(),
+        touched: touched.iter().copied().collect(),
+        selectors: selectors.iter().copied().collect(),
     }
 }
 
@@ -1264,9 +1415,13 @@ pub fn metrics_hooks<N: NodeTypesWithDB>(provider_factory: &ProviderFactory<N>)
 
 #[cfg(test)]
 mod tests {
-    use super::{LaunchContext, NodeConfig};
-    use reth_config::Config;
-    use reth_node_core::args::PruningArgs;
+    use super::{builder_settings_from_config, reload_config, LaunchContext, NodeConfig};
+    use alloy_consensus::{constants::MAXIMUM_EXTRA_DATA_SIZE, TxLegacy};
+    use alloy_primitives::{address, Bytes};
+    use reth_config::{BuilderConfig, Config};
+    use reth_node_core::args::{PayloadBuilderArgs, PruningArgs};
+    use reth_payload_builder::BuilderSettingsHandle;
+    use reth_transaction_pool::policy::{InclusionPolicyHandle, PolicyViolation};
 
     const EXTENSION: &str = "toml";
 
@@ -1317,4 +1472,78 @@ mod tests {
             assert_eq!(reth_config, loaded_config);
         })
     }
+
+    #[test]
+    fn builder_settings_cli_takes_precedence() {
+        let config = BuilderConfig {
+            gas_limit: Some(60_000_000),
+            extra_data: Some(Bytes::from_static(b"toml")),
+            max_blobs_per_block: Some(3),
+            min_priority_fee: Some(1),
+        };
+
+        let settings =
+            builder_settings_from_config(&config, &PayloadBuilderArgs::default()).unwrap();
+        assert_eq!(settings.gas_limit, Some(60_000_000));
+        assert_eq!(settings.extra_data, Some(Bytes::from_static(b"toml")));
+        assert_eq!(settings.max_blobs_per_block, Some(3));
+        assert_eq!(settings.min_priority_fee, Some(1));
+
+        let args = PayloadBuilderArgs {
+            gas_limit: Some(30_000_000),
+            extra_data: Bytes::from_static(b"cli"),
+            max_blobs_per_block: Some(6),
+            ..Default::default()
+        };
+        let settings = builder_settings_from_config(&config, &args).unwrap();
+        assert_eq!(settings.gas_limit, None);
+        assert_eq!(settings.extra_data, None);
+        assert_eq!(settings.max_blobs_per_block, None);
+        assert_eq!(settings.min_priority_fee, Some(1));
+    }
+
+    #[test]
+    fn builder_settings_reject_oversized_extra_data() {
+        let config = BuilderConfig {
+            extra_data: Some(Bytes::from(vec![0u8; MAXIMUM_EXTRA_DATA_SIZE + 1])),
+            ..Default::default()
+        };
+        assert!(builder_settings_from_config(&config, &PayloadBuilderArgs::default()).is_err());
+    }
+
+    #[test]
+    fn reload_keeps_policy_on_missing_or_invalid_config() {
+        with_tempdir("reload-test", |config_path| {
+            let sender = address!("0x000000000000000000000000000000000000dead");
+            let args = PayloadBuilderArgs::default();
+            let settings = BuilderSettingsHandle::default();
+            let policy = InclusionPolicyHandle::default();
+            let is_denied = || {
+                policy
+                    .current()
+                    .check(sender, &TxLegacy::default())
+                    .is_some_and(|violation| violation == PolicyViolation::Sender(sender))
+            };
+
+            std::fs::write(
+                config_path,
+                format!("[builder]\ngas_limit = 60000000\n[inclusion_policy]\nsenders = [\"{sender}\"]\n"),
+            )
+            .unwrap();
+            reload_config(config_path, &args, &settings, &policy);
+            assert!(is_denied());
+            assert_eq!(settings.get().gas_limit, Some(60_000_000));
+
+            // a missing file is not recreated with defaults
+            std::fs::remove_file(config_path).unwrap();
+            reload_config(config_path, &args, &settings, &policy);
+            assert!(!config_path.exists());
+            assert!(is_denied());
+            assert_eq!(settings.get().gas_limit, Some(60_000_000));
+
+            std::fs::write(config_path, "[inclusion_policy\n").unwrap();
+            reload_config(config_path, &args, &settings, &policy);
+            assert!(is_denied());
+        })
+    }
 }
@@ -18,6 +18,7 @@ use reth_engine_tree::{
     tree::TreeConfig,
 };
 use reth_engine_util::EngineMessageStreamExt;
+use reth_evm::profiler::ExecutionProfiler;
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
@@ -82,7 +83,7 @@ impl EngineNodeLauncher {
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, exex_registry, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -125,7 +126,11 @@ impl EngineNodeLauncher {
             .with_components(components_builder, on_component_initialized).await?;
 
         // spawn exexs if any
-        let maybe_exex_manager_handle = ctx.launch_exex(installed_exex).await?;
+        let (maybe_exex_manager_handle, exex_registry) = ctx
+            .exex_launcher(installed_exex)
+            .with_registry(exex_registry)
+            .launch_with_registry()
+            .await?;
 
         // create pipeline
         let network_handle = ctx.components().network().clone();
@@ -181,12 +186,22 @@ impl EngineNodeLauncher {
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // allow the builder settings and inclusion policy to be reloaded from the reth.toml
+        ctx.spawn_config_reload();
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
+            toml_config: ctx.toml_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            builder_settings: ctx.builder_settings().clone(),
+            inclusion_lists: ctx.inclusion_lists().clone(),
+            execution_profiler: node_config
+                .engine
+                .execution_profile_enabled
+                .then(|| ExecutionProfiler::new(node_config.engine.execution_profile_top)),
         };
         let validator_builder = add_ons.engine_validator_builder();
 
@@ -395,6 +410,7 @@ impl EngineNodeLauncher {
                 beacon_engine_handle,
                 engine_shutdown,
             },
+            exex_registry,
         };
         // Notify on node started
         on_node_started.on_event(FullNode::clone(&full_node))?;
@@ -5,8 +5,8 @@ use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY, DEFAULT_WAL_BLOCKS_WARNING,
+    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, ExExRegistry,
+    Wal, DEFAULT_EXEX_MANAGER_CAPACITY, DEFAULT_WAL_BLOCKS_WARNING,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
@@ -24,6 +24,8 @@ pub struct ExExLauncher<Node: FullNodeComponents> {
     config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     /// The threshold for the number of blocks in the WAL before emitting a warning.
     wal_blocks_warning: usize,
+    /// Whether `ExEx`es can be registered at runtime.
+    registry: bool,
 }
 
 impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
@@ -40,6 +42,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components,
             config_container,
             wal_blocks_warning: DEFAULT_WAL_BLOCKS_WARNING,
+            registry: false,
         }
     }
 
@@ -53,6 +56,13 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         self
     }
 
+    /// Sets whether `ExEx`es can be registered at runtime with the [`ExExRegistry`] returned by
+    /// [`Self::launch_with_registry`].
+    pub const fn with_registry(mut self, registry: bool) -> Self {
+        self.registry = registry;
+        self
+    }
+
     /// Launches all execution extensions.
     ///
     /// Spawns all extensions and returns the handle to the exex manager if any extensions are
@@ -60,22 +70,37 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub async fn launch(
         self,
     ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>> {
-        let Self { head, extensions, components, config_container, wal_blocks_warning } = self;
+        Ok(self.launch_with_registry().await?.0)
+    }
+
+    /// Launches all execution extensions.
+    ///
+    /// Spawns all extensions and returns the handle to the exex manager if any extensions are
+    /// installed or the registry is enabled, along with the [`ExExRegistry`] if it is enabled.
+    pub async fn launch_with_registry(
+        self,
+    ) -> eyre::Result<(
+        Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>,
+        Option<ExExRegistry<Node>>,
+    )> {
+        let Self { head, extensions, components, config_container, wal_blocks_warning, registry } =
+            self;
         let head = BlockNumHash::new(head.number, head.hash);
 
-        if extensions.is_empty() {
+        if extensions.is_empty() && !registry {
             // nothing to launch
-            return Ok(None)
+            return Ok((None, None))
         }
 
         info!(target: "reth::cli", "Loading ExEx Write-Ahead Log...");
-        let exex_wal = Wal::new(
+        let exex_wal = Wal::with_config(
             config_container
                 .config
                 .datadir
                 .clone()
                 .resolve_datadir(config_container.config.chain.chain())
                 .exex_wal(),
+            config_container.toml_config.exex.wal,
         )?;
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
@@ -131,7 +156,8 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         // spawn exex manager
         debug!(target: "reth::cli", "spawning exex manager");
-        let exex_manager = ExExManager::new(
+        let wal_handle = exex_wal.handle();
+        let mut exex_manager = ExExManager::new(
             components.provider().clone(),
             exex_handles,
             DEFAULT_EXEX_MANAGER_CAPACITY,
@@ -139,6 +165,9 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components.provider().finalized_block_stream(),
         )
         .with_wal_blocks_warning(wal_blocks_warning);
+        if registry {
+            exex_manager = exex_manager.with_dynamic_exexs();
+        }
         let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical_task("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
@@ -161,7 +190,18 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         info!(target: "reth::cli", "ExEx Manager started");
 
-        Ok(Some(exex_manager_handle))
+        let exex_registry = registry.then(|| {
+            ExExRegistry::new(
+                head,
+                config_container.config,
+                config_container.toml_config,
+                components,
+                wal_handle,
+                exex_manager_handle.clone(),
+            )
+        });
+
+        Ok((Some(exex_manager_handle), exex_registry))
     }
 }
 
@@ -173,6 +213,7 @@ impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
             .field("components", &"...")
             .field("config_container", &self.config_container)
             .field("wal_blocks_warning", &self.wal_blocks_warning)
+            .field("registry", &self.registry)
             .finish()
     }
 }
@@ -6,6 +6,7 @@ use crate::{
     components::NodeComponentsBuilder, rpc::RethRpcAddOns, NodeAdapter, NodeAddOns, NodeHandle,
     RethFullAdapter,
 };
+use reth_exex::ExExRegistry;
 use reth_node_api::{EngineTypes, FullNodeComponents, PayloadTypes};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -123,6 +124,9 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub data_dir: ChainPath<DataDirPath>,
     /// The handle to launched add-ons
     pub add_ons_handle: AddOns::Handle,
+    /// Registers `ExEx`es at runtime, if enabled with
+    /// [`NodeBuilderWithComponents::enable_exex_registry`](crate::NodeBuilderWithComponents::enable_exex_registry).
+    pub exex_registry: Option<ExExRegistry<Node>>,
 }
 
 impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node, AddOns> {
@@ -137,6 +141,7 @@ impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node
             config: self.config.clone(),
             data_dir: self.data_dir.clone(),
             add_ons_handle: self.add_ons_handle.clone(),
+            exex_registry: self.exex_registry.clone(),
         }
     }
 }
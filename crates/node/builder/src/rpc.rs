@@ -15,7 +15,9 @@ use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
 use parking_lot::Mutex;
 use reth_chain_state::CanonStateSubscriptions;
-use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
+use reth_chainspec::{
+    ChainSpecProvider, EthChainSpec, EthereumHardforks, ForkCondition, Hardforks,
+};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineApiValidator, EngineTypes, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
@@ -26,21 +28,28 @@ use reth_node_core::{
     version::{version_metadata, CLIENT_CODE},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
+use reth_provider::BlockNumReader;
 use reth_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
-    AdminApi,
+    AdminApi, ExecutionProfileApi,
+};
+use reth_rpc_api::{
+    eth::helpers::EthTransactions, ExecutionProfileApiServer, IntoEngineApiRpcModule,
 };
-use reth_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    listener::RpcListener,
+    quota::RpcQuotas,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
+    collections::BTreeMap,
     fmt::{self, Debug},
     future::Future,
     ops::{Deref, DerefMut},
@@ -57,6 +66,8 @@ pub struct RethRpcServerHandles {
     pub rpc: RpcServerHandle,
     /// The handle to the auth server (engine API)
     pub auth: AuthServerHandle,
+    /// The handles to the named listeners configured in `reth.toml`.
+    pub listeners: BTreeMap<String, RpcServerHandle>,
 }
 
 /// Contains hooks that are called during the rpc setup.
@@ -494,6 +505,8 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     modules: TransportRpcModules,
     auth_module: AuthRpcModule,
     auth_config: reth_rpc_builder::auth::AuthServerConfig,
+    quotas: Option<RpcQuotas>,
+    listeners: Vec<RpcListener>,
     registry: RpcRegistry<Node, EthApi>,
     on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     engine_events: EventSender<ConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
@@ -850,6 +863,8 @@ where
             mut modules,
             mut auth_module,
             auth_config: _,
+            quotas,
+            listeners,
             mut registry,
             on_rpc_started,
             engine_events,
@@ -859,12 +874,26 @@ where
         let server_config = config
             .rpc
             .rpc_server_config()
-            .set_rpc_middleware(rpc_middleware)
-            .with_tokio_runtime(tokio_runtime);
+            .set_rpc_middleware(rpc_middleware.clone())
+            .with_quotas(quotas.clone())
+            .with_tokio_runtime(tokio_runtime.clone());
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
+        let listeners = Self::launch_listeners_internal(
+            listeners,
+            &mut registry,
+            &modules,
+            config,
+            quotas,
+            rpc_middleware,
+            tokio_runtime,
+        )
+        .await?;
 
-        let handles =
-            RethRpcServerHandles { rpc: rpc_server_handle.clone(), auth: AuthServerHandle::noop() };
+        let handles = RethRpcServerHandles {
+            rpc: rpc_server_handle.clone(),
+            auth: AuthServerHandle::noop(),
+            listeners,
+        };
         Self::finalize_rpc_setup(
             &mut registry,
             &mut modules,
@@ -921,6 +950,8 @@ where
             mut modules,
             mut auth_module,
             auth_config,
+            quotas,
+            listeners,
             mut registry,
             on_rpc_started,
             engine_events,
@@ -930,8 +961,9 @@ where
         let server_config = config
             .rpc
             .rpc_server_config()
-            .set_rpc_middleware(rpc_middleware)
-            .with_tokio_runtime(tokio_runtime);
+            .set_rpc_middleware(rpc_middleware.clone())
+            .with_quotas(quotas.clone())
+            .with_tokio_runtime(tokio_runtime.clone());
 
         let (rpc, auth) = if disable_auth {
             // Only launch the RPC server, use a noop auth handle
@@ -948,7 +980,18 @@ where
             (rpc, auth)
         };
 
-        let handles = RethRpcServerHandles { rpc, auth };
+        let listeners = Self::launch_listeners_internal(
+            listeners,
+            &mut registry,
+            &modules,
+            config,
+            quotas,
+            rpc_middleware,
+            tokio_runtime,
+        )
+        .await?;
+
+        let handles = RethRpcServerHandles { rpc, auth, listeners };
 
         Self::finalize_rpc_setup(
             &mut registry,
@@ -981,7 +1024,16 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            toml_config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            execution_profiler,
+            ..
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -1010,6 +1062,30 @@ where
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
+        let quotas = toml_config
+            .rpc
+            .quota
+            .as_ref()
+            .map(|quota| {
+                let provider = node.provider().clone();
+                RpcQuotas::new(quota)
+                    .map(|quotas| {
+                        quotas.with_head_block(move || {
+                            provider.best_block_number().unwrap_or_default()
+                        })
+                    })
+                    .map_err(|err| eyre::eyre!("invalid rpc.quota.api_key_header: {err}"))
+            })
+            .transpose()?;
+        let listeners = toml_config
+            .rpc
+            .listeners
+            .iter()
+            .map(|(name, listener)| {
+                RpcListener::new(name.clone(), listener)
+                    .map_err(|err| eyre::eyre!("invalid JWT secret of RPC listener {name}: {err}"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
@@ -1022,6 +1098,11 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api, engine_events.clone());
 
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            ExecutionProfileApi::new(execution_profiler).into_rpc(),
+        )?;
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             let signers = DevSigner::from_mnemonic(config.dev.dev_mnemonic.as_str(), 20);
@@ -1052,6 +1133,8 @@ where
             modules,
             auth_module,
             auth_config,
+            quotas,
+            listeners,
             registry,
             on_rpc_started,
             engine_events,
@@ -1082,6 +1165,39 @@ where
         Ok(handle)
     }
 
+    /// Helper to launch the named listeners, their methods are created by the shared registry.
+    async fn launch_listeners_internal<M>(
+        listeners: Vec<RpcListener>,
+        registry: &mut RpcRegistry<N, EthB::EthApi>,
+        modules: &TransportRpcModules,
+        config: &NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+        quotas: Option<RpcQuotas>,
+        rpc_middleware: M,
+        tokio_runtime: Option<tokio::runtime::Handle>,
+    ) -> eyre::Result<BTreeMap<String, RpcServerHandle>>
+    where
+        M: RethRpcMiddleware,
+    {
+        let mut handles = BTreeMap::new();
+        for listener in listeners {
+            let registry_module = registry.module_for(listener.modules());
+            let listener_modules = listener.transport_modules(registry_module, modules);
+            let handle = listener
+                .server_config(config.rpc.http_ws_server_builder())
+                .set_rpc_middleware(rpc_middleware.clone())
+                .with_quotas(quotas.clone())
+                .with_tokio_runtime(tokio_runtime.clone())
+                .start(&listener_modules)
+                .await?;
+
+            if let Some(addr) = handle.http_local_addr().or_else(|| handle.ws_local_addr()) {
+                info!(target: "reth::cli", name = listener.name(), url=%addr, "RPC listener started");
+            }
+            handles.insert(listener.name().to_string(), handle);
+        }
+        Ok(handles)
+    }
+
     /// Helper to launch the auth server
     async fn launch_auth_server_internal(
         auth_module: AuthRpcModule,
@@ -1342,6 +1458,11 @@ where
         let validator = self.payload_validator_builder.build(ctx).await?;
         let data_dir = ctx.config.datadir.clone().resolve_datadir(ctx.config.chain.chain());
         let invalid_block_hook = ctx.create_invalid_block_hook(&data_dir).await?;
+        let execution_cache_snapshot = ctx
+            .config
+            .engine
+            .execution_cache_snapshot_enabled
+            .then(|| data_dir.execution_cache_snapshot());
 
         Ok(BasicEngineValidator::new(
             ctx.node.provider().clone(),
@@ -1352,7 +1473,10 @@ where
             invalid_block_hook,
             changeset_cache,
             ctx.node.task_executor().clone(),
-        ))
+        )
+        .with_inclusion_lists(ctx.inclusion_lists.clone())
+        .with_execution_profiler(ctx.execution_profiler.clone())
+        .with_execution_cache_snapshot(execution_cache_snapshot))
     }
 }
 
@@ -1396,6 +1520,12 @@ where
             commit: version_metadata().vergen_git_sha.to_string(),
         };
 
+        // the inclusion list methods are only served on chains that schedule them
+        let mut capabilities = EngineCapabilities::default();
+        if ctx.config.chain.focil_activation() != ForkCondition::Never {
+            capabilities = capabilities.with_inclusion_lists();
+        }
+
         Ok(EngineApi::new(
             ctx.node.provider().clone(),
             ctx.config.chain.clone(),
@@ -1404,7 +1534,7 @@ where
             ctx.node.pool().clone(),
             ctx.node.task_executor().clone(),
             client,
-            EngineCapabilities::default(),
+            capabilities,
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
             ctx.node.network().clone(),
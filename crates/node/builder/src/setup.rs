@@ -104,6 +104,11 @@ where
         builder = builder.with_max_block(max_block)
     }
 
+    if let Some(window) = stage_config.pipeline.window {
+        debug!(target: "reth::cli", window, "Configuring builder to run stages in block windows");
+        builder = builder.with_window(window)
+    }
+
     let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
 
     let pipeline = builder
@@ -44,6 +44,9 @@ pub struct DefaultEngineValues {
     sparse_trie_max_storage_tries: usize,
     disable_sparse_trie_cache_pruning: bool,
     state_root_task_timeout: Option<String>,
+    execution_profile_enabled: bool,
+    execution_profile_top: usize,
+    execution_cache_snapshot_enabled: bool,
 }
 
 impl DefaultEngineValues {
@@ -203,6 +206,24 @@ impl DefaultEngineValues {
         self.state_root_task_timeout = v;
         self
     }
+
+    /// Set whether to enable execution profiling by default
+    pub const fn with_execution_profile_enabled(mut self, v: bool) -> Self {
+        self.execution_profile_enabled = v;
+        self
+    }
+
+    /// Set the default number of execution profile entries to report per category
+    pub const fn with_execution_profile_top(mut self, v: usize) -> Self {
+        self.execution_profile_top = v;
+        self
+    }
+
+    /// Set whether to snapshot the execution cache on shutdown by default
+    pub const fn with_execution_cache_snapshot_enabled(mut self, v: bool) -> Self {
+        self.execution_cache_snapshot_enabled = v;
+        self
+    }
 }
 
 impl Default for DefaultEngineValues {
@@ -232,6 +253,9 @@ impl Default for DefaultEngineValues {
             sparse_trie_max_storage_tries: DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES,
             disable_sparse_trie_cache_pruning: false,
             state_root_task_timeout: Some("1s".to_string()),
+            execution_profile_enabled: false,
+            execution_profile_top: 20,
+            execution_cache_snapshot_enabled: false,
         }
     }
 }
@@ -389,6 +413,25 @@ pub struct EngineArgs {
         default_value = DefaultEngineValues::get_global().state_root_task_timeout.as_deref().unwrap_or("1s"),
     )]
     pub state_root_task_timeout: Option<Duration>,
+
+    /// Enable execution profiling. Live blocks are executed with an inspector that aggregates the
+    /// gas and time spent per contract, function selector and precompile, which adds overhead to
+    /// block execution.
+    ///
+    /// The top entries are exported as metrics and served by the `reth_executionProfile` RPC.
+    #[arg(long = "engine.execution-profile", default_value_t = DefaultEngineValues::get_global().execution_profile_enabled)]
+    pub execution_profile_enabled: bool,
+
+    /// Number of execution profile entries to report per category.
+    #[arg(long = "engine.execution-profile-top", default_value_t = DefaultEngineValues::get_global().execution_profile_top)]
+    pub execution_profile_top: usize,
+
+    /// Enable snapshotting the hottest execution cache entries on graceful shutdown.
+    ///
+    /// The snapshot is written to the datadir and reloaded into the execution cache on the next
+    /// startup if it was taken at the persisted tip, so the first blocks don't execute cold.
+    #[arg(long = "engine.execution-cache-snapshot", default_value_t = DefaultEngineValues::get_global().execution_cache_snapshot_enabled)]
+    pub execution_cache_snapshot_enabled: bool,
 }
 
 #[allow(deprecated)]
@@ -419,6 +462,9 @@ impl Default for EngineArgs {
             sparse_trie_max_storage_tries,
             disable_sparse_trie_cache_pruning,
             state_root_task_timeout,
+            execution_profile_enabled,
+            execution_profile_top,
+            execution_cache_snapshot_enabled,
         } = DefaultEngineValues::get_global().clone();
         Self {
             persistence_threshold,
@@ -451,6 +497,9 @@ impl Default for EngineArgs {
             state_root_task_timeout: state_root_task_timeout
                 .as_deref()
                 .map(|s| humantime::parse_duration(s).expect("valid default duration")),
+            execution_profile_enabled,
+            execution_profile_top,
+            execution_cache_snapshot_enabled,
         }
     }
 }
@@ -537,6 +586,9 @@ mod tests {
             sparse_trie_max_storage_tries: 100,
             disable_sparse_trie_cache_pruning: true,
             state_root_task_timeout: Some(Duration::from_secs(2)),
+            execution_profile_enabled: true,
+            execution_profile_top: 50,
+            execution_cache_snapshot_enabled: true,
         };
 
         let parsed_args = CommandParser::<EngineArgs>::parse_from([
@@ -576,6 +628,10 @@ mod tests {
             "--engine.disable-sparse-trie-cache-pruning",
             "--engine.state-root-task-timeout",
             "2s",
+            "--engine.execution-profile",
+            "--engine.execution-profile-top",
+            "50",
+            "--engine.execution-cache-snapshot",
         ])
         .args;
 
@@ -40,6 +40,11 @@ impl DefaultPayloadBuilderValues {
         PAYLOAD_BUILDER_DEFAULTS.get_or_init(Self::default)
     }
 
+    /// Returns the default extra data
+    pub fn extra_data(&self) -> &str {
+        &self.extra_data
+    }
+
     /// Set the default extra data
     pub fn with_extra_data(mut self, v: impl Into<String>) -> Self {
         self.extra_data = v.into();
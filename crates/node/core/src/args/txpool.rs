@@ -3,14 +3,15 @@
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use clap::{builder::Resettable, Args};
+use clap::{builder::Resettable, Args, ValueEnum};
 use reth_cli_util::{parse_duration_from_secs_or_ms, parsers::format_duration_as_secs_or_ms};
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    LocalTransactionConfig, OrderingConfig, OrderingStrategy, PoolConfig, PriceBumpConfig,
+    SubPoolLimit, DEFAULT_FEE_BUCKET_SIZE, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
@@ -411,6 +412,45 @@ pub struct TxPoolArgs {
     /// Max batch size for transaction pool insertions
     #[arg(long = "txpool.max-batch-size", default_value_t = DefaultTxPoolValues::get_global().max_batch_size)]
     pub max_batch_size: usize,
+
+    /// Records all transactions submitted to the pool and the canonical state changes of the pool
+    /// to this file, so that they can be replayed for benchmarking.
+    #[arg(long = "txpool.record", value_name = "PATH")]
+    pub record_path: Option<PathBuf>,
+
+    /// How pending transactions are ordered when building blocks.
+    #[arg(long = "txpool.ordering", value_enum, default_value_t = TxPoolOrdering::CoinbaseTip)]
+    pub ordering: TxPoolOrdering,
+
+    /// Size of the fee buckets in wei for the `fifo-fee-buckets` ordering.
+    #[arg(long = "txpool.ordering-bucket-size", value_name = "WEI", value_parser = parse_fee_bucket_size, default_value_t = DEFAULT_FEE_BUCKET_SIZE)]
+    pub ordering_bucket_size: u128,
+
+    /// Max number of transactions of a single sender that are picked when building a block.
+    ///
+    /// Unlimited if not set.
+    #[arg(long = "txpool.max-txs-per-sender", value_name = "COUNT")]
+    pub max_txs_per_sender: Option<usize>,
+}
+
+/// Parses a fee bucket size, which must not be zero.
+fn parse_fee_bucket_size(s: &str) -> Result<u128, String> {
+    match s.parse::<u128>() {
+        Ok(0) => Err("fee bucket size must not be zero".to_string()),
+        Ok(size) => Ok(size),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// The transaction ordering strategies selectable with `--txpool.ordering`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TxPoolOrdering {
+    /// Highest coinbase tip first.
+    #[default]
+    CoinbaseTip,
+    /// Coinbase tip grouped into buckets of `--txpool.ordering-bucket-size`, first come first
+    /// served within a bucket.
+    FifoFeeBuckets,
 }
 
 impl TxPoolArgs {
@@ -421,6 +461,17 @@ impl TxPoolArgs {
         self.with_protocol_base_fee(0)
     }
 
+    /// Returns the configuration of the transaction ordering.
+    pub const fn ordering_config(&self) -> OrderingConfig {
+        let strategy = match self.ordering {
+            TxPoolOrdering::CoinbaseTip => OrderingStrategy::CoinbaseTip,
+            TxPoolOrdering::FifoFeeBuckets => {
+                OrderingStrategy::FifoFeeBuckets { bucket_size: self.ordering_bucket_size }
+            }
+        };
+        OrderingConfig { strategy, max_transactions_per_sender: self.max_txs_per_sender }
+    }
+
     /// Configures the minimal protocol base fee that should be enforced.
     ///
     /// Ethereum's EIP-1559 base fee can't drop below [`MIN_PROTOCOL_BASE_FEE`] hence this is
@@ -496,6 +547,10 @@ impl Default for TxPoolArgs {
             transactions_backup_path,
             disable_transactions_backup,
             max_batch_size,
+            record_path: None,
+            ordering: TxPoolOrdering::default(),
+            ordering_bucket_size: DEFAULT_FEE_BUCKET_SIZE,
+            max_txs_per_sender: None,
         }
     }
 }
@@ -625,6 +680,10 @@ mod tests {
             transactions_backup_path: Some(PathBuf::from("/tmp/txpool-backup")),
             disable_transactions_backup: false,
             max_batch_size: 10,
+            record_path: Some(PathBuf::from("/tmp/txpool.rec")),
+            ordering: TxPoolOrdering::FifoFeeBuckets,
+            ordering_bucket_size: 100,
+            max_txs_per_sender: Some(16),
         };
 
         let parsed_args = CommandParser::<TxPoolArgs>::parse_from([
@@ -685,6 +744,14 @@ mod tests {
             "/tmp/txpool-backup",
             "--txpool.max-batch-size",
             "10",
+            "--txpool.record",
+            "/tmp/txpool.rec",
+            "--txpool.ordering",
+            "fifo-fee-buckets",
+            "--txpool.ordering-bucket-size",
+            "100",
+            "--txpool.max-txs-per-sender",
+            "16",
         ])
         .args;
 
@@ -375,6 +375,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the execution cache snapshot for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/execution_cache.snapshot`
+    pub fn execution_cache_snapshot(&self) -> PathBuf {
+        self.data_dir().join("execution_cache.snapshot")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
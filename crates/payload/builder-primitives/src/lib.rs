@@ -11,4 +11,7 @@
 mod events;
 pub use crate::events::{Events, PayloadEvents};
 
+mod settings;
+pub use settings::{BuilderSettings, BuilderSettingsHandle};
+
 pub use reth_payload_primitives::PayloadBuilderError;
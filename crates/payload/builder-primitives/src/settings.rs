@@ -0,0 +1,106 @@
+use alloy_primitives::Bytes;
+use tokio::sync::watch;
+
+/// Payload builder settings that can be changed while the node is running.
+///
+/// Every field is an override: `None` means the value the builder was launched with is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuilderSettings {
+    /// Target gas limit for built blocks.
+    pub gas_limit: Option<u64>,
+    /// Extra data for built blocks.
+    pub extra_data: Option<Bytes>,
+    /// Maximum number of blobs to include per block (EIP-7872).
+    pub max_blobs_per_block: Option<u64>,
+    /// Minimum priority fee per gas a transaction must pay to be included in a built block.
+    pub min_priority_fee: Option<u128>,
+}
+
+impl BuilderSettings {
+    /// Merges `other` into `self`, overwriting every field that is set in `other`.
+    pub fn merge(&mut self, other: Self) {
+        let Self { gas_limit, extra_data, max_blobs_per_block, min_priority_fee } = other;
+        if gas_limit.is_some() {
+            self.gas_limit = gas_limit;
+        }
+        if extra_data.is_some() {
+            self.extra_data = extra_data;
+        }
+        if max_blobs_per_block.is_some() {
+            self.max_blobs_per_block = max_blobs_per_block;
+        }
+        if min_priority_fee.is_some() {
+            self.min_priority_fee = min_priority_fee;
+        }
+    }
+}
+
+/// A shared handle to the [`BuilderSettings`] of a running node.
+///
+/// Clones of the handle observe the same settings, which allows e.g. the `miner_` RPC namespace to
+/// retarget the payload builder without a restart.
+#[derive(Debug, Clone)]
+pub struct BuilderSettingsHandle {
+    inner: watch::Sender<BuilderSettings>,
+}
+
+impl BuilderSettingsHandle {
+    /// Creates a new handle with the given initial settings.
+    pub fn new(settings: BuilderSettings) -> Self {
+        Self { inner: watch::Sender::new(settings) }
+    }
+
+    /// Returns a copy of the current settings.
+    pub fn get(&self) -> BuilderSettings {
+        self.inner.borrow().clone()
+    }
+
+    /// Modifies the current settings in place and notifies all subscribers.
+    pub fn update(&self, f: impl FnOnce(&mut BuilderSettings)) {
+        self.inner.send_modify(f);
+    }
+
+    /// Returns a receiver that is notified whenever the settings change.
+    pub fn subscribe(&self) -> watch::Receiver<BuilderSettings> {
+        self.inner.subscribe()
+    }
+}
+
+impl Default for BuilderSettingsHandle {
+    fn default() -> Self {
+        Self::new(BuilderSettings::default())
+    }
+}
+
+impl PartialEq for BuilderSettingsHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.same_channel(&other.inner)
+    }
+}
+
+impl Eq for BuilderSettingsHandle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_settings() {
+        let handle = BuilderSettingsHandle::default();
+        let other = handle.clone();
+        assert_eq!(handle, other);
+
+        other.update(|settings| settings.gas_limit = Some(60_000_000));
+        assert_eq!(handle.get().gas_limit, Some(60_000_000));
+        assert_ne!(handle, BuilderSettingsHandle::default());
+    }
+
+    #[test]
+    fn merge_keeps_unset_fields() {
+        let mut settings =
+            BuilderSettings { gas_limit: Some(1), min_priority_fee: Some(2), ..Default::default() };
+        settings.merge(BuilderSettings { gas_limit: Some(3), ..Default::default() });
+        assert_eq!(settings.gas_limit, Some(3));
+        assert_eq!(settings.min_priority_fee, Some(2));
+    }
+}
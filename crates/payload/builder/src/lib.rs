@@ -117,7 +117,9 @@ pub mod noop;
 pub mod test_utils;
 
 pub use alloy_rpc_types::engine::PayloadId;
-pub use reth_payload_builder_primitives::PayloadBuilderError;
+pub use reth_payload_builder_primitives::{
+    BuilderSettings, BuilderSettingsHandle, PayloadBuilderError,
+};
 pub use reth_payload_primitives::PayloadKind;
 pub use service::{
     PayloadBuilderHandle, PayloadBuilderService, PayloadServiceCommand, PayloadStore,
@@ -0,0 +1,47 @@
+//! Engine API extensions of fork-choice enforced inclusion lists.
+
+use alloy_eips::eip7685::RequestsOrHash;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_engine::{ExecutionPayloadV3, PayloadId};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_engine_primitives::inclusion_list::InclusionListPayloadStatus;
+
+/// Engine API methods of fork-choice enforced inclusion lists, see
+/// [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+///
+/// These methods are only served on chains that schedule the `Focil` devnet hardfork.
+///
+/// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/experimental/eip7805.md>
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "engine"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "engine"))]
+pub trait EngineInclusionListApi {
+    /// Handles a payload like `engine_newPayloadV4`, and additionally checks whether it satisfies
+    /// the given inclusion list.
+    ///
+    /// A valid payload that doesn't satisfy its inclusion list is reported as
+    /// `INCLUSION_LIST_UNSATISFIED`, it's still a valid block.
+    #[method(name = "newPayloadWithInclusionListV1")]
+    async fn new_payload_with_inclusion_list_v1(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: RequestsOrHash,
+        inclusion_list_transactions: Vec<Bytes>,
+    ) -> RpcResult<InclusionListPayloadStatus>;
+
+    /// Returns an inclusion list, built from the transaction pool, for the block on top of the
+    /// given parent.
+    #[method(name = "getInclusionListV1")]
+    async fn get_inclusion_list_v1(&self, parent_hash: B256) -> RpcResult<Vec<Bytes>>;
+
+    /// Sets the inclusion list the payload that is being built with the given id has to include.
+    ///
+    /// Returns the id of the payload, the payload keeps its id.
+    #[method(name = "updatePayloadWithInclusionListV1")]
+    async fn update_payload_with_inclusion_list_v1(
+        &self,
+        payload_id: PayloadId,
+        inclusion_list_transactions: Vec<Bytes>,
+    ) -> RpcResult<Option<PayloadId>>;
+}
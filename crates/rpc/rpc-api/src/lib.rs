@@ -19,6 +19,7 @@ mod anvil;
 mod debug;
 mod engine;
 mod hardhat;
+mod inclusion_list;
 mod mev;
 mod miner;
 mod net;
@@ -43,11 +44,16 @@ pub mod servers {
         admin::AdminApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        inclusion_list::EngineInclusionListApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{
+            AddressExecutionStats, ExecutionProfile, ExecutionProfileApiServer, ExecutionStats,
+            RethApiServer, SelectorExecutionStats, TransactionLifecycleEvent,
+            TransactionLifecycleEventKind, TransactionLifecycleFilter,
+        },
         reth_engine::{RethEngineApiServer, RethPayloadStatus},
         rpc::RpcApiServer,
         testing::TestingApiServer,
@@ -75,11 +81,12 @@ pub mod clients {
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         hardhat::HardhatApiClient,
+        inclusion_list::EngineInclusionListApiClient,
         mev::{MevFullApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{ExecutionProfileApiClient, RethApiClient},
         reth_engine::RethEngineApiClient,
         rpc::RpcApiServer,
         testing::TestingApiClient,
@@ -1,10 +1,130 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{map::AddressMap, U256, U64};
+use alloy_primitives::{map::AddressMap, Address, Selector, TxHash, B256, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::{pool::QueuedReason, DiscardReason};
+use serde::{Deserialize, Serialize};
 
 // Required for the subscription attributes below
 use reth_chain_state as _;
 
+/// Selects the transactions to stream with `reth_subscribeTransactionLifecycle`.
+///
+/// A transaction is tracked if its hash is listed, or if it was sent by one of the listed senders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionLifecycleFilter {
+    /// Hashes of the transactions to track.
+    #[serde(default)]
+    pub hashes: Vec<TxHash>,
+    /// Senders whose transactions to track.
+    #[serde(default)]
+    pub senders: Vec<Address>,
+}
+
+impl TransactionLifecycleFilter {
+    /// Returns `true` if the filter doesn't select any transaction.
+    pub const fn is_empty(&self) -> bool {
+        self.hashes.is_empty() && self.senders.is_empty()
+    }
+}
+
+/// A lifecycle event of a transaction in the pool, emitted by
+/// `reth_subscribeTransactionLifecycle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionLifecycleEvent {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// What happened to the transaction.
+    #[serde(flatten)]
+    pub kind: TransactionLifecycleEventKind,
+}
+
+/// What happened to a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TransactionLifecycleEventKind {
+    /// The transaction was added to the pending pool.
+    Pending,
+    /// The transaction was added to the queued pool.
+    Queued {
+        /// Why the transaction was queued, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<QueuedReason>,
+    },
+    /// The transaction was included in a block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The hash of the block that includes the transaction.
+        block_hash: B256,
+    },
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The hash of the replacement.
+        replaced_by: TxHash,
+    },
+    /// The transaction was removed from the pool without being mined.
+    Discarded {
+        /// Why the transaction was removed.
+        reason: DiscardReason,
+    },
+    /// The transaction became invalid.
+    Invalid {
+        /// The validation error.
+        error: String,
+    },
+}
+
+/// Gas and time spent executing a contract, function selector or precompile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionStats {
+    /// Number of calls.
+    pub calls: u64,
+    /// Gas spent.
+    pub gas_used: u64,
+    /// Wall time spent, in nanoseconds.
+    pub time_nanos: u64,
+}
+
+/// An entry of the execution profile keyed by address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressExecutionStats {
+    /// The address of the executed contract or precompile.
+    pub address: Address,
+    /// The execution stats.
+    #[serde(flatten)]
+    pub stats: ExecutionStats,
+}
+
+/// An entry of the execution profile keyed by function selector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectorExecutionStats {
+    /// The 4-byte function selector.
+    pub selector: Selector,
+    /// The execution stats.
+    #[serde(flatten)]
+    pub stats: ExecutionStats,
+}
+
+/// The contracts, function selectors and precompiles with the most execution time, returned by
+/// `reth_executionProfile`.
+///
+/// The stats of contracts and selectors only include the execution of the contract's own code,
+/// not of the contracts and precompiles it calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionProfile {
+    /// Number of blocks the profile was aggregated over.
+    pub blocks: u64,
+    /// Contracts by the address of the executed code.
+    pub contracts: Vec<AddressExecutionStats>,
+    /// Function selectors, across all contracts.
+    pub selectors: Vec<SelectorExecutionStats>,
+    /// Precompiles.
+    pub precompiles: Vec<AddressExecutionStats>,
+}
+
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
@@ -58,4 +178,31 @@ pub trait RethApi {
     async fn reth_subscribe_finalized_chain_notifications(
         &self,
     ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Subscribe to the lifecycle events of pool transactions.
+    ///
+    /// Streams the events of the transactions selected by the filter, from their arrival in the
+    /// pool until they're mined, replaced, discarded or become invalid. Discards carry the reason,
+    /// e.g. the subpool whose limit evicted the transaction.
+    #[subscription(
+        name = "subscribeTransactionLifecycle",
+        unsubscribe = "unsubscribeTransactionLifecycle",
+        item = TransactionLifecycleEvent
+    )]
+    async fn reth_subscribe_transaction_lifecycle(
+        &self,
+        filter: TransactionLifecycleFilter,
+    ) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Reth API namespace for the execution profile of live blocks.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait ExecutionProfileApi {
+    /// Returns the contracts, function selectors and precompiles with the most execution time
+    /// since the node started, up to `limit` entries each.
+    ///
+    /// Requires the node to run with `--engine.execution-profile`.
+    #[method(name = "executionProfile")]
+    fn reth_execution_profile(&self, limit: Option<usize>) -> RpcResult<ExecutionProfile>;
 }
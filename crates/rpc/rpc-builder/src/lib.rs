@@ -111,6 +111,13 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+/// Additional named servers
+pub mod listener;
+
+/// Per-client quotas
+pub mod quota;
+use quota::{RpcQuotaHttpLayer, RpcQuotas};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -758,7 +765,10 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Pool: TransactionPool + Clone + 'static,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
@@ -847,9 +857,13 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider, EvmConfig> {
+    pub fn reth_api(&self) -> RethApi<Provider, Pool, EvmConfig>
+    where
+        Pool: Clone,
+    {
         RethApi::new(
             self.provider.clone(),
+            self.pool.clone(),
             self.evm_config.clone(),
             self.blocking_pool_guard.clone(),
             self.executor.clone(),
@@ -1008,6 +1022,7 @@ where
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => RethApi::new(
                             self.provider.clone(),
+                            self.pool.clone(),
                             self.evm_config.clone(),
                             self.blocking_pool_guard.clone(),
                             self.executor.clone(),
@@ -1093,6 +1108,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Per-client quotas of the http and ws servers
+    quotas: Option<RpcQuotas>,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1113,6 +1130,7 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            quotas: None,
             rpc_middleware: Default::default(),
         }
     }
@@ -1177,6 +1195,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            quotas: self.quotas,
             rpc_middleware,
         }
     }
@@ -1256,6 +1275,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the per-client quotas of the http and ws servers.
+    pub fn with_quotas(mut self, quotas: Option<RpcQuotas>) -> Self {
+        self.quotas = quotas;
+        self
+    }
+
     /// Configures a custom tokio runtime for the rpc server.
     pub fn with_tokio_runtime(mut self, tokio_runtime: Option<tokio::runtime::Handle>) -> Self {
         let Some(tokio_runtime) = tokio_runtime else { return self };
@@ -1307,6 +1332,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the HTTP layer of the quotas if any
+    fn maybe_quota_layer(&self) -> Option<RpcQuotaHttpLayer> {
+        self.quotas.as_ref().map(|quotas| quotas.http_layer(self.jwt_secret.is_some()))
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
@@ -1340,6 +1370,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        let quota_layer = self.maybe_quota_layer();
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
@@ -1380,6 +1412,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(quota_layer.clone())
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
@@ -1394,6 +1427,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .option_layer(self.quotas.clone())
                             .layer(self.rpc_middleware.clone()),
                     )
                     .set_config(config.build())
@@ -1433,11 +1467,13 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(quota_layer.clone()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .option_layer(self.quotas.clone())
                         .layer(self.rpc_middleware.clone()),
                 )
                 .build(ws_socket_addr)
@@ -1459,6 +1495,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(quota_layer.clone())
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
@@ -1466,6 +1503,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .option_layer(self.quotas.clone())
                         .layer(self.rpc_middleware.clone()),
                 )
                 .build(http_socket_addr)
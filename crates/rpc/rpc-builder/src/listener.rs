@@ -0,0 +1,177 @@
+//! Additional named RPC servers with method-level access lists.
+
+use crate::{methods_by, RpcServerConfig, TransportRpcModuleConfig, TransportRpcModules};
+use jsonrpsee::{server::ServerConfigBuilder, Methods, RpcModule};
+use reth_config::{RpcListenerConfig, RpcListenerTransport};
+use reth_rpc_layer::{JwtError, JwtSecret};
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+use std::net::SocketAddr;
+
+/// An additional HTTP or WS server that serves a subset of the methods of the registry.
+///
+/// The methods of the selected namespaces are created by the registry, so they share their state
+/// with the methods of the other servers, and methods that were added to or replaced in the http
+/// and ws modules are taken from these modules. The method filter is applied last.
+#[derive(Debug, Clone)]
+pub struct RpcListener {
+    /// Name of the listener.
+    name: String,
+    /// Transport of the server.
+    transport: RpcListenerTransport,
+    /// Address of the server.
+    addr: SocketAddr,
+    /// Allowed CORS domains.
+    cors: Option<String>,
+    /// JWT secret for authentication.
+    jwt_secret: Option<JwtSecret>,
+    /// Served namespaces.
+    modules: RpcModuleSelection,
+    /// Served methods of the namespaces.
+    methods: RpcMethodFilter,
+}
+
+impl RpcListener {
+    /// Creates the listener from the given configuration, reading the JWT secret if configured.
+    pub fn new(name: impl Into<String>, config: &RpcListenerConfig) -> Result<Self, JwtError> {
+        let modules = config.modules.as_ref().map_or(RpcModuleSelection::All, |modules| {
+            modules
+                .iter()
+                .map(|module| {
+                    module.parse().unwrap_or_else(|_| RethRpcModule::Other(module.clone()))
+                })
+                .collect()
+        });
+        Ok(Self {
+            name: name.into(),
+            transport: config.transport,
+            addr: config.addr,
+            cors: config.cors.clone(),
+            jwt_secret: config.jwt_secret.as_deref().map(JwtSecret::from_file).transpose()?,
+            modules,
+            methods: RpcMethodFilter::new(config.allow.clone(), config.deny.clone()),
+        })
+    }
+
+    /// Returns the name of the listener.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the served namespaces.
+    pub const fn modules(&self) -> &RpcModuleSelection {
+        &self.modules
+    }
+
+    /// Returns the filter of the served methods.
+    pub const fn methods(&self) -> &RpcMethodFilter {
+        &self.methods
+    }
+
+    /// Returns the [`RpcServerConfig`] of the listener's server.
+    pub fn server_config(&self, builder: ServerConfigBuilder) -> RpcServerConfig {
+        let config = match self.transport {
+            RpcListenerTransport::Http => RpcServerConfig::http(builder)
+                .with_http_address(self.addr)
+                .with_http_cors(self.cors.clone()),
+            RpcListenerTransport::Ws => RpcServerConfig::ws(builder)
+                .with_ws_address(self.addr)
+                .with_ws_cors(self.cors.clone()),
+        };
+        config.with_jwt_secret(self.jwt_secret)
+    }
+
+    /// Returns the [`TransportRpcModules`] to start the listener's server with.
+    ///
+    /// `registry_module` holds the methods of the listener's namespaces created by the registry,
+    /// see [`RpcRegistryInner::module_for`](crate::RpcRegistryInner::module_for). Methods of these
+    /// namespaces that are installed in the http or ws module of `modules` replace them.
+    pub fn transport_modules(
+        &self,
+        mut registry_module: RpcModule<()>,
+        modules: &TransportRpcModules,
+    ) -> TransportRpcModules {
+        let in_namespaces = |name: &str| {
+            name.split_once('_').is_some_and(|(namespace, _)| {
+                namespace.parse().is_ok_and(|namespace| self.modules.contains(&namespace))
+            })
+        };
+        let mut installed = modules.http_methods(in_namespaces).unwrap_or_default();
+        if let Some(ws) =
+            modules.ws_methods(|name| in_namespaces(name) && installed.method(name).is_none())
+        {
+            installed.merge(ws).expect("no duplicate methods");
+        }
+        for name in installed.method_names() {
+            registry_module.remove_method(name);
+        }
+        registry_module.merge(installed).expect("methods were removed");
+
+        let mut module = RpcModule::new(());
+        module.merge(self.methods.filter(&registry_module)).expect("empty module");
+
+        let config = TransportRpcModuleConfig::default();
+        match self.transport {
+            RpcListenerTransport::Http => TransportRpcModules::default()
+                .with_config(config.with_http(self.modules.clone()))
+                .with_http(module),
+            RpcListenerTransport::Ws => TransportRpcModules::default()
+                .with_config(config.with_ws(self.modules.clone()))
+                .with_ws(module),
+        }
+    }
+}
+
+/// Method-level allowlist and denylist.
+///
+/// Entries ending with `*` match all methods with that prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcMethodFilter {
+    /// Allowed methods, all methods if empty.
+    allow: Vec<String>,
+    /// Denied methods, takes precedence over the allowed methods.
+    deny: Vec<String>,
+}
+
+impl RpcMethodFilter {
+    /// Creates a new filter from the given allowlist and denylist.
+    pub const fn new(allow: Vec<String>, deny: Vec<String>) -> Self {
+        Self { allow, deny }
+    }
+
+    /// Returns whether the method passes the filter.
+    pub fn is_allowed(&self, method: &str) -> bool {
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => pattern == method,
+        };
+        (self.allow.is_empty() || self.allow.iter().any(matches)) && !self.deny.iter().any(matches)
+    }
+
+    /// Returns the methods of the module that pass the filter.
+    pub fn filter(&self, module: &RpcModule<()>) -> Methods {
+        methods_by(module, |name| self.is_allowed(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_filter() {
+        let filter = RpcMethodFilter::new(
+            vec!["eth_*".to_string(), "net_version".to_string()],
+            vec!["eth_sendTransaction".to_string(), "eth_sign*".to_string()],
+        );
+        assert!(filter.is_allowed("eth_call"));
+        assert!(filter.is_allowed("eth_sendRawTransaction"));
+        assert!(filter.is_allowed("net_version"));
+        assert!(!filter.is_allowed("net_peerCount"));
+        assert!(!filter.is_allowed("eth_sendTransaction"));
+        assert!(!filter.is_allowed("eth_sign"));
+        assert!(!filter.is_allowed("eth_signTypedData"));
+        assert!(!filter.is_allowed("debug_traceTransaction"));
+
+        assert!(RpcMethodFilter::default().is_allowed("admin_addPeer"));
+    }
+}
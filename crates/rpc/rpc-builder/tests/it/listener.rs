@@ -0,0 +1,54 @@
+//! Named listener tests
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::{ClientT, Error as ClientError},
+    rpc_params,
+    types::error::ErrorCode,
+    RpcModule,
+};
+use reth_config::RpcListenerConfig;
+use reth_rpc_builder::{listener::RpcListener, TransportRpcModuleConfig};
+use reth_rpc_server_types::RpcModuleSelection;
+use reth_tokio_util::EventSender;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_listener() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let mut registry = builder.into_registry(Default::default(), eth_api, EventSender::new(1));
+    let mut modules = registry
+        .create_transport_rpc_modules(TransportRpcModuleConfig::set_http(RpcModuleSelection::All));
+
+    // replaced methods are served by the listener as well
+    let mut replacement = RpcModule::new(());
+    replacement.register_method("eth_protocolVersion", |_, _, _| "replaced").unwrap();
+    modules.replace_http(replacement).unwrap();
+
+    let listener = RpcListener::new(
+        "public",
+        &RpcListenerConfig {
+            transport: Default::default(),
+            addr: test_address(),
+            cors: None,
+            jwt_secret: None,
+            modules: Some(vec!["eth".to_string(), "net".to_string()]),
+            allow: Vec::new(),
+            deny: vec!["eth_chainId".to_string(), "net_peer*".to_string()],
+        },
+    )
+    .unwrap();
+    let listener_modules =
+        listener.transport_modules(registry.module_for(listener.modules()), &modules);
+    let handle = listener.server_config(Default::default()).start(&listener_modules).await.unwrap();
+    let client = handle.http_client().unwrap();
+
+    let call = |method| client.request::<serde_json::Value, _>(method, rpc_params![]);
+    let is_method_not_found = |err: ClientError| matches!(err, ClientError::Call(err) if err.code() == ErrorCode::MethodNotFound.code());
+
+    assert_eq!(call("eth_protocolVersion").await.unwrap(), "replaced");
+    call("net_version").await.unwrap();
+    assert!(is_method_not_found(call("eth_chainId").await.unwrap_err()));
+    assert!(is_method_not_found(call("net_peerCount").await.unwrap_err()));
+    assert!(is_method_not_found(call("web3_clientVersion").await.unwrap_err()));
+}
@@ -2,6 +2,7 @@
 
 mod auth;
 mod http;
+mod listener;
 mod middleware;
 mod serde;
 mod startup;
@@ -1,16 +1,22 @@
 use crate::utils::{test_address, test_rpc_builder};
 use alloy_rpc_types_eth::{Block, Header, Receipt, Transaction, TransactionRequest};
 use jsonrpsee::{
-    core::middleware::{Batch, Notification},
+    core::{
+        client::{ClientT, Error as ClientError},
+        middleware::{Batch, Notification},
+    },
+    rpc_params,
     server::middleware::rpc::RpcServiceT,
     types::Request,
 };
+use reth_config::RpcQuotaConfig;
 use reth_ethereum_primitives::TransactionSigned;
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_builder::{quota::RpcQuotas, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_eth_api::EthApiClient;
 use reth_rpc_server_types::RpcModuleSelection;
 use reth_tokio_util::EventSender;
 use std::{
+    collections::BTreeMap,
     future::Future,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -98,3 +104,42 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_quotas() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules = builder.build(
+        TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
+        eth_api,
+        EventSender::new(1),
+    );
+
+    // the bucket is never refilled
+    let quotas = RpcQuotas::new(&RpcQuotaConfig {
+        rate: 0,
+        burst: 3,
+        costs: BTreeMap::from([("eth_protocolVersion".to_string(), 2)]),
+        ..Default::default()
+    })
+    .unwrap();
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_quotas(Some(quotas))
+        .start(&modules)
+        .await
+        .unwrap();
+
+    let client = handle.http_client().unwrap();
+    let call = |method| client.request::<serde_json::Value, _>(method, rpc_params![]);
+    call("eth_protocolVersion").await.unwrap();
+
+    // one token left, the call is rejected by the RPC middleware
+    let err = call("eth_protocolVersion").await.unwrap_err();
+    assert!(matches!(err, ClientError::Call(err) if err.code() == -32005));
+
+    // the last token is taken, further requests are rejected with 429
+    call("eth_chainId").await.unwrap();
+    let err = call("eth_chainId").await.unwrap_err();
+    assert!(err.to_string().contains("429"), "{err}");
+}
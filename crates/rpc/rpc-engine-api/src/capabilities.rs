@@ -36,6 +36,16 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_getBlobsV3",
 ];
 
+/// Engine API capabilities of fork-choice enforced inclusion lists, see
+/// [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+///
+/// Only supported on chains that schedule the `Focil` devnet hardfork.
+pub const INCLUSION_LIST_CAPABILITIES: &[&str] = &[
+    "engine_newPayloadWithInclusionListV1",
+    "engine_getInclusionListV1",
+    "engine_updatePayloadWithInclusionListV1",
+];
+
 /// Engine API capabilities set.
 #[derive(Debug, Clone)]
 pub struct EngineCapabilities {
@@ -48,6 +58,12 @@ impl EngineCapabilities {
         Self { inner: capabilities.into_iter().map(Into::into).collect() }
     }
 
+    /// Adds the [`INCLUSION_LIST_CAPABILITIES`].
+    pub fn with_inclusion_lists(mut self) -> Self {
+        self.inner.extend(INCLUSION_LIST_CAPABILITIES.iter().map(|cap| cap.to_string()));
+        self
+    }
+
     /// Returns the capabilities as a list of strings.
     pub fn list(&self) -> Vec<String> {
         self.inner.iter().cloned().collect()
@@ -204,6 +220,16 @@ mod tests {
         assert_eq!(result.missing_in_cl, vec!["a_method", "z_method"]);
     }
 
+    #[test]
+    fn test_inclusion_list_capabilities() {
+        let default = EngineCapabilities::default();
+        let with_inclusion_lists = default.clone().with_inclusion_lists();
+        for cap in INCLUSION_LIST_CAPABILITIES {
+            assert!(!default.as_set().contains(*cap));
+            assert!(with_inclusion_lists.as_set().contains(*cap));
+        }
+    }
+
     #[test]
     fn test_is_critical_method() {
         assert!(is_critical_method("engine_forkchoiceUpdatedV1"));
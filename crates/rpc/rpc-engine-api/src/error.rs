@@ -80,6 +80,15 @@ pub enum EngineApiError {
     /// The payload or attributes are known to be malformed before processing.
     #[error(transparent)]
     EngineObjectValidationError(#[from] EngineObjectValidationError),
+    /// The parent block of a requested inclusion list is unknown.
+    #[error("unknown parent block {0}")]
+    UnknownParent(B256),
+    /// The inclusion list exceeds the maximum size.
+    #[error("inclusion list exceeds {max} bytes")]
+    InclusionListTooLarge {
+        /// The maximum size of an inclusion list, in bytes.
+        max: usize,
+    },
     /// Requests hash provided, but can't be accepted by the API.
     #[error("requests hash cannot be accepted by the API without `--engine.accept-execution-requests-hash` flag")]
     UnexpectedRequestsHash,
@@ -123,6 +132,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 // Fixes: https://github.com/paradigmxyz/reth/issues/8732
                 EngineObjectValidationError::PayloadAttributes(_),
             ) |
+            EngineApiError::UnknownParent(_) |
+            EngineApiError::InclusionListTooLarge { .. } |
             EngineApiError::UnexpectedRequestsHash => {
                 // Note: the data field is not required by the spec, but is also included by other
                 // clients
@@ -0,0 +1,432 @@
+//! Engine API methods of fork-choice enforced inclusion lists, see
+//! [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+
+use crate::{EngineApiError, EngineApiResult};
+use alloy_eips::{eip7685::RequestsOrHash, merge::SLOT_DURATION, Encodable2718};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionData, ExecutionPayloadSidecar, ExecutionPayloadV3,
+    PayloadAttributes, PayloadId, PraguePayloadFields,
+};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::{
+    inclusion_list::{
+        inclusion_list_size, InclusionList, InclusionListPayloadStatus, InclusionListStore,
+        MAX_BYTES_PER_INCLUSION_LIST,
+    },
+    ConsensusEngineHandle,
+};
+use reth_payload_builder::PayloadStore;
+use reth_payload_primitives::{
+    validate_execution_requests, validate_version_specific_fields, EngineApiMessageVersion,
+    EngineObjectValidationError, ExecutionPayload, PayloadOrAttributes, PayloadTypes,
+};
+use reth_primitives_traits::AlloyBlockHeader;
+use reth_rpc_api::EngineInclusionListApiServer;
+use reth_storage_api::HeaderProvider;
+use reth_transaction_pool::{BestTransactionsAttributes, PoolTransaction, TransactionPool};
+use std::sync::Arc;
+use tracing::trace;
+
+/// The engine API methods of fork-choice enforced inclusion lists.
+///
+/// Inclusion lists received with new payloads are put into the [`InclusionListStore`], the engine
+/// tree checks the payloads against them. Inclusion lists for payloads that are being built are
+/// put into the store as well, for the payload builder to include.
+///
+/// Only meant to be served on chains that schedule the `Focil` devnet hardfork, see
+/// [`EthChainSpec::focil_activation`].
+pub struct InclusionListEngineApi<Provider, PayloadT: PayloadTypes, Pool, ChainSpec> {
+    inner: Arc<InclusionListEngineApiInner<Provider, PayloadT, Pool, ChainSpec>>,
+}
+
+struct InclusionListEngineApiInner<Provider, PayloadT: PayloadTypes, Pool, ChainSpec> {
+    /// The provider to interact with the chain.
+    provider: Provider,
+    /// Consensus configuration
+    chain_spec: Arc<ChainSpec>,
+    /// The channel to send messages to the beacon consensus engine.
+    beacon_consensus: ConsensusEngineHandle<PayloadT>,
+    /// The type that can communicate with the payload service to retrieve payloads.
+    payload_store: PayloadStore<PayloadT>,
+    /// The transaction pool inclusion lists are built from.
+    tx_pool: Pool,
+    /// The inclusion lists of new payloads and payloads that are being built.
+    inclusion_lists: InclusionListStore,
+    /// Whether execution requests may be passed as a hash.
+    accept_execution_requests_hash: bool,
+}
+
+impl<Provider, PayloadT, Pool, ChainSpec>
+    InclusionListEngineApi<Provider, PayloadT, Pool, ChainSpec>
+where
+    Provider: HeaderProvider + Send + Sync + 'static,
+    PayloadT: PayloadTypes<ExecutionData = ExecutionData>,
+    Pool: TransactionPool + 'static,
+    ChainSpec: EthChainSpec<Header = Provider::Header> + EthereumHardforks + 'static,
+{
+    /// Creates a new instance of [`InclusionListEngineApi`].
+    pub fn new(
+        provider: Provider,
+        chain_spec: Arc<ChainSpec>,
+        beacon_consensus: ConsensusEngineHandle<PayloadT>,
+        payload_store: PayloadStore<PayloadT>,
+        tx_pool: Pool,
+        inclusion_lists: InclusionListStore,
+        accept_execution_requests_hash: bool,
+    ) -> Self {
+        let inner = Arc::new(InclusionListEngineApiInner {
+            provider,
+            chain_spec,
+            beacon_consensus,
+            payload_store,
+            tx_pool,
+            inclusion_lists,
+            accept_execution_requests_hash,
+        });
+        Self { inner }
+    }
+
+    /// Sends the payload to the engine, after recording the inclusion list it has to satisfy.
+    ///
+    /// The payload is validated like an `engine_newPayloadV4` payload. If it's valid, but the
+    /// engine found that it doesn't satisfy the inclusion list, it's reported as
+    /// `INCLUSION_LIST_UNSATISFIED`.
+    pub async fn new_payload_with_inclusion_list(
+        &self,
+        payload: ExecutionData,
+        inclusion_list: InclusionList,
+    ) -> EngineApiResult<InclusionListPayloadStatus> {
+        if !self.inner.chain_spec.is_focil_active_at_timestamp(payload.timestamp()) {
+            return Err(EngineObjectValidationError::UnsupportedFork.into())
+        }
+        ensure_inclusion_list_size(&inclusion_list)?;
+
+        let payload_or_attrs =
+            PayloadOrAttributes::<'_, ExecutionData, PayloadAttributes>::from_execution_payload(
+                &payload,
+            );
+        payload_or_attrs
+            .execution_requests()
+            .map(|requests| validate_execution_requests(requests))
+            .transpose()?;
+        validate_version_specific_fields(
+            &*self.inner.chain_spec,
+            EngineApiMessageVersion::V4,
+            payload_or_attrs,
+        )?;
+
+        let block_hash = payload.block_hash();
+        self.inner.inclusion_lists.insert(block_hash, inclusion_list);
+
+        let status = self.inner.beacon_consensus.new_payload(payload).await?;
+        if status.is_valid() && self.inner.inclusion_lists.unsatisfied(&block_hash).is_some() {
+            return Ok(InclusionListPayloadStatus::unsatisfied(block_hash))
+        }
+
+        Ok(status.into())
+    }
+
+    /// Builds an inclusion list for the block on top of the given parent from the best
+    /// transactions of the pool.
+    ///
+    /// The block is expected one slot after its parent: the fork has to be active at that
+    /// timestamp, and only transactions that pay the base fee of that block are considered.
+    pub fn get_inclusion_list(&self, parent_hash: B256) -> EngineApiResult<InclusionList> {
+        let parent = self
+            .inner
+            .provider
+            .header(parent_hash)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?
+            .ok_or(EngineApiError::UnknownParent(parent_hash))?;
+
+        let timestamp = parent.timestamp() + SLOT_DURATION.as_secs();
+        if !self.inner.chain_spec.is_focil_active_at_timestamp(timestamp) {
+            return Err(EngineObjectValidationError::UnsupportedFork.into())
+        }
+
+        let base_fee =
+            self.inner.chain_spec.next_block_base_fee(&parent, timestamp).unwrap_or_default();
+        let best_transactions = self
+            .inner
+            .tx_pool
+            .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee));
+
+        let mut size = 0;
+        let mut inclusion_list = Vec::new();
+        for tx in best_transactions {
+            // blob transactions can't be part of an inclusion list
+            if tx.is_eip4844() {
+                continue
+            }
+            let encoded = tx.transaction.clone_into_consensus().into_inner().encoded_2718();
+            if size + encoded.len() > MAX_BYTES_PER_INCLUSION_LIST {
+                break
+            }
+            size += encoded.len();
+            inclusion_list.push(encoded.into());
+        }
+
+        Ok(inclusion_list)
+    }
+
+    /// Records the inclusion list the payload with the given id has to include.
+    pub async fn update_payload_with_inclusion_list(
+        &self,
+        payload_id: PayloadId,
+        inclusion_list: InclusionList,
+    ) -> EngineApiResult<PayloadId> {
+        let timestamp = self
+            .inner
+            .payload_store
+            .payload_timestamp(payload_id)
+            .await
+            .ok_or(EngineApiError::UnknownPayload)??;
+        if !self.inner.chain_spec.is_focil_active_at_timestamp(timestamp) {
+            return Err(EngineObjectValidationError::UnsupportedFork.into())
+        }
+        ensure_inclusion_list_size(&inclusion_list)?;
+
+        self.inner.inclusion_lists.insert_for_payload(payload_id, inclusion_list);
+        Ok(payload_id)
+    }
+}
+
+/// Returns an error if the inclusion list exceeds [`MAX_BYTES_PER_INCLUSION_LIST`].
+fn ensure_inclusion_list_size(inclusion_list: &[Bytes]) -> EngineApiResult<()> {
+    if inclusion_list_size(inclusion_list) > MAX_BYTES_PER_INCLUSION_LIST {
+        return Err(EngineApiError::InclusionListTooLarge { max: MAX_BYTES_PER_INCLUSION_LIST })
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl<Provider, PayloadT, Pool, ChainSpec> EngineInclusionListApiServer
+    for InclusionListEngineApi<Provider, PayloadT, Pool, ChainSpec>
+where
+    Provider: HeaderProvider + Send + Sync + 'static,
+    PayloadT: PayloadTypes<ExecutionData = ExecutionData>,
+    Pool: TransactionPool + 'static,
+    ChainSpec: EthChainSpec<Header = Provider::Header> + EthereumHardforks + 'static,
+{
+    /// Handler for `engine_newPayloadWithInclusionListV1`
+    async fn new_payload_with_inclusion_list_v1(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: RequestsOrHash,
+        inclusion_list_transactions: Vec<Bytes>,
+    ) -> RpcResult<InclusionListPayloadStatus> {
+        trace!(target: "rpc::engine", "Serving engine_newPayloadWithInclusionListV1");
+
+        // Accept requests as a hash only if it is explicitly allowed
+        if execution_requests.is_hash() && !self.inner.accept_execution_requests_hash {
+            return Err(EngineApiError::UnexpectedRequestsHash.into());
+        }
+
+        let payload = ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::v4(
+                CancunPayloadFields { versioned_hashes, parent_beacon_block_root },
+                PraguePayloadFields { requests: execution_requests },
+            ),
+        };
+
+        Ok(self.new_payload_with_inclusion_list(payload, inclusion_list_transactions).await?)
+    }
+
+    /// Handler for `engine_getInclusionListV1`
+    async fn get_inclusion_list_v1(&self, parent_hash: B256) -> RpcResult<Vec<Bytes>> {
+        trace!(target: "rpc::engine", %parent_hash, "Serving engine_getInclusionListV1");
+        Ok(self.get_inclusion_list(parent_hash)?)
+    }
+
+    /// Handler for `engine_updatePayloadWithInclusionListV1`
+    async fn update_payload_with_inclusion_list_v1(
+        &self,
+        payload_id: PayloadId,
+        inclusion_list_transactions: Vec<Bytes>,
+    ) -> RpcResult<Option<PayloadId>> {
+        trace!(target: "rpc::engine", %payload_id, "Serving engine_updatePayloadWithInclusionListV1");
+        Ok(Some(
+            self.update_payload_with_inclusion_list(payload_id, inclusion_list_transactions)
+                .await?,
+        ))
+    }
+}
+
+impl<Provider, PayloadT, Pool, ChainSpec> std::fmt::Debug
+    for InclusionListEngineApi<Provider, PayloadT, Pool, ChainSpec>
+where
+    PayloadT: PayloadTypes,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InclusionListEngineApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, PayloadT, Pool, ChainSpec> Clone
+    for InclusionListEngineApi<Provider, PayloadT, Pool, ChainSpec>
+where
+    PayloadT: PayloadTypes,
+{
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_rpc_types_engine::{
+        ExecutionPayloadV1, ExecutionPayloadV2, PayloadStatus, PayloadStatusEnum,
+    };
+    use assert_matches::assert_matches;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumDevnetHardfork, ForkCondition};
+    use reth_engine_primitives::{
+        inclusion_list::InclusionListPayloadStatusKind, BeaconEngineMessage,
+    };
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_payload_builder::test_utils::spawn_test_payload_service;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::noop::NoopTransactionPool;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+    /// The timestamp the `Focil` fork activates at in the tests.
+    const FOCIL_TIME: u64 = 100;
+
+    type TestApi = InclusionListEngineApi<
+        Arc<MockEthProvider>,
+        EthEngineTypes,
+        NoopTransactionPool,
+        ChainSpec,
+    >;
+
+    fn setup_api(
+    ) -> (Arc<MockEthProvider>, UnboundedReceiver<BeaconEngineMessage<EthEngineTypes>>, TestApi)
+    {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .osaka_activated()
+                .with_fork(EthereumDevnetHardfork::Focil, ForkCondition::Timestamp(FOCIL_TIME))
+                .build(),
+        );
+        let provider = Arc::new(MockEthProvider::default());
+        let (to_engine, engine_rx) = unbounded_channel();
+        let api = InclusionListEngineApi::new(
+            provider.clone(),
+            chain_spec,
+            ConsensusEngineHandle::new(to_engine),
+            spawn_test_payload_service().into(),
+            NoopTransactionPool::default(),
+            InclusionListStore::default(),
+            false,
+        );
+        (provider, engine_rx, api)
+    }
+
+    fn payload(timestamp: u64) -> ExecutionData {
+        let payload = ExecutionPayloadV3 {
+            payload_inner: ExecutionPayloadV2 {
+                payload_inner: ExecutionPayloadV1 {
+                    parent_hash: B256::ZERO,
+                    fee_recipient: Default::default(),
+                    state_root: B256::ZERO,
+                    receipts_root: B256::ZERO,
+                    logs_bloom: Default::default(),
+                    prev_randao: B256::ZERO,
+                    block_number: 1,
+                    gas_limit: 30_000_000,
+                    gas_used: 0,
+                    timestamp,
+                    extra_data: Bytes::new(),
+                    base_fee_per_gas: Default::default(),
+                    block_hash: B256::with_last_byte(1),
+                    transactions: Vec::new(),
+                },
+                withdrawals: Vec::new(),
+            },
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        };
+        ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::v4(
+                CancunPayloadFields {
+                    versioned_hashes: Vec::new(),
+                    parent_beacon_block_root: B256::ZERO,
+                },
+                PraguePayloadFields { requests: RequestsOrHash::Requests(Default::default()) },
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_inclusion_list_gates_on_parent() {
+        let (provider, _engine_rx, api) = setup_api();
+
+        let unknown = B256::with_last_byte(1);
+        assert_matches!(
+            api.get_inclusion_list(unknown),
+            Err(EngineApiError::UnknownParent(hash)) if hash == unknown
+        );
+
+        // the next block would be before the fork
+        let parent = B256::with_last_byte(2);
+        provider.add_header(parent, Header { timestamp: FOCIL_TIME - 13, ..Default::default() });
+        assert_matches!(
+            api.get_inclusion_list(parent),
+            Err(EngineApiError::EngineObjectValidationError(
+                EngineObjectValidationError::UnsupportedFork
+            ))
+        );
+
+        // the next block is the first one of the fork
+        let parent = B256::with_last_byte(3);
+        provider.add_header(parent, Header { timestamp: FOCIL_TIME - 12, ..Default::default() });
+        assert_matches!(api.get_inclusion_list(parent), Ok(list) if list.is_empty());
+    }
+
+    #[tokio::test]
+    async fn new_payload_reports_unsatisfied_inclusion_list() {
+        let (_provider, mut engine_rx, api) = setup_api();
+
+        assert_matches!(
+            api.new_payload_with_inclusion_list(payload(FOCIL_TIME - 1), Vec::new()).await,
+            Err(EngineApiError::EngineObjectValidationError(
+                EngineObjectValidationError::UnsupportedFork
+            ))
+        );
+        assert_matches!(
+            api.new_payload_with_inclusion_list(
+                payload(FOCIL_TIME),
+                vec![Bytes::from(vec![0; MAX_BYTES_PER_INCLUSION_LIST + 1])]
+            )
+            .await,
+            Err(EngineApiError::InclusionListTooLarge { .. })
+        );
+
+        // the engine finds the block valid, but records that it doesn't satisfy its list
+        let inclusion_lists = api.inner.inclusion_lists.clone();
+        tokio::spawn(async move {
+            while let Some(BeaconEngineMessage::NewPayload { payload, tx }) = engine_rx.recv().await
+            {
+                let block_hash = payload.block_hash();
+                assert!(inclusion_lists.get(&block_hash).is_some());
+                inclusion_lists.set_unsatisfied(&block_hash, B256::with_last_byte(0xff));
+                let _ = tx.send(Ok(PayloadStatus::new(PayloadStatusEnum::Valid, Some(block_hash))));
+            }
+        });
+
+        let status = api
+            .new_payload_with_inclusion_list(payload(FOCIL_TIME), vec![Bytes::from_static(&[1])])
+            .await
+            .unwrap();
+        assert_eq!(status.status, InclusionListPayloadStatusKind::InclusionListUnsatisfied);
+        assert_eq!(status.latest_valid_hash, Some(B256::with_last_byte(1)));
+    }
+}
@@ -19,6 +19,10 @@ pub use capabilities::EngineCapabilities;
 /// Engine API error.
 mod error;
 
+/// Engine API methods of fork-choice enforced inclusion lists.
+mod inclusion_list;
+pub use inclusion_list::InclusionListEngineApi;
+
 /// Engine API metrics.
 mod metrics;
 
@@ -49,7 +49,7 @@ pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{ExecutionProfileApi, RethApi};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
 pub use testing::TestingApi;
@@ -1,25 +1,94 @@
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_primitives::{Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_payload_builder_primitives::{BuilderSettings, BuilderSettingsHandle};
 use reth_rpc_api::MinerApiServer;
+use reth_rpc_eth_types::EthApiError;
+use tracing::info;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
+///
+/// If created with a [`BuilderSettingsHandle`], updates are applied to the shared settings and are
+/// picked up by the payload builder for the next payload it builds. Otherwise all updates are
+/// rejected by returning `false`.
+///
+/// Since this retargets the payload builder, the mutating API should only be exposed on trusted
+/// transports.
 #[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+pub struct MinerApi {
+    settings: Option<BuilderSettingsHandle>,
+}
+
+impl MinerApi {
+    /// Creates a new instance of `MinerApi` that updates the given settings.
+    pub const fn new(settings: BuilderSettingsHandle) -> Self {
+        Self { settings: Some(settings) }
+    }
+
+    /// Applies the update to the settings, if any.
+    fn update(&self, f: impl FnOnce(&mut BuilderSettings)) -> bool {
+        let Some(settings) = &self.settings else { return false };
+        settings.update(f);
+        true
+    }
+}
 
 #[async_trait]
 impl MinerApiServer for MinerApi {
-    fn set_extra(&self, _record: Bytes) -> RpcResult<bool> {
-        Ok(false)
+    fn set_extra(&self, record: Bytes) -> RpcResult<bool> {
+        if record.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(EthApiError::InvalidParams(format!(
+                "extra data exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit"
+            ))
+            .into())
+        }
+        info!(target: "rpc::miner", extra_data = %record, "Updating builder extra data");
+        Ok(self.update(|settings| settings.extra_data = Some(record)))
     }
 
-    fn set_gas_price(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool> {
+        let min_priority_fee = gas_price.to::<u128>();
+        info!(target: "rpc::miner", min_priority_fee, "Updating builder minimum priority fee");
+        Ok(self.update(|settings| settings.min_priority_fee = Some(min_priority_fee)))
+    }
+
+    fn set_gas_limit(&self, gas_limit: U128) -> RpcResult<bool> {
+        let gas_limit = u64::try_from(gas_limit)
+            .map_err(|_| EthApiError::InvalidParams("gas limit exceeds u64".to_string()))?;
+        info!(target: "rpc::miner", gas_limit, "Updating builder target gas limit");
+        Ok(self.update(|settings| settings.gas_limit = Some(gas_limit)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_shared_settings() {
+        let settings = BuilderSettingsHandle::default();
+        let api = MinerApi::new(settings.clone());
+
+        assert!(api.set_gas_limit(U128::from(60_000_000)).unwrap());
+        assert!(api.set_gas_price(U128::from(1_000_000_000)).unwrap());
+        assert!(api.set_extra(Bytes::from_static(b"reth")).unwrap());
+
+        let current = settings.get();
+        assert_eq!(current.gas_limit, Some(60_000_000));
+        assert_eq!(current.min_priority_fee, Some(1_000_000_000));
+        assert_eq!(current.extra_data, Some(Bytes::from_static(b"reth")));
+
+        assert!(api.set_extra(Bytes::from(vec![0u8; MAXIMUM_EXTRA_DATA_SIZE + 1])).is_err());
+        assert!(api.set_gas_limit(U128::from(u64::MAX) + U128::from(1)).is_err());
     }
 
-    fn set_gas_limit(&self, _gas_limit: U128) -> RpcResult<bool> {
-        Ok(false)
+    #[test]
+    fn rejects_updates_without_settings() {
+        let api = MinerApi::default();
+        assert!(!api.set_gas_limit(U128::from(60_000_000)).unwrap());
+        assert!(!api.set_extra(Bytes::from_static(b"reth")).unwrap());
     }
 }
@@ -1,8 +1,8 @@
-use std::{future::Future, sync::Arc};
+use std::{collections::HashSet, future::Future, sync::Arc};
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockId;
-use alloy_primitives::{map::AddressMap, U256, U64};
+use alloy_primitives::{map::AddressMap, Address, TxHash, U256, U64};
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
@@ -11,33 +11,48 @@ use reth_chain_state::{
     PersistedBlockSubscriptions,
 };
 use reth_errors::RethResult;
-use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_evm::{
+    execute::Executor,
+    profiler::{self, ExecutionProfiler},
+    ConfigureEvm,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
-use reth_rpc_api::RethApiServer;
+use reth_rpc_api::{
+    AddressExecutionStats, ExecutionProfile, ExecutionProfileApiServer, ExecutionStats,
+    RethApiServer, SelectorExecutionStats, TransactionLifecycleEvent,
+    TransactionLifecycleEventKind, TransactionLifecycleFilter,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory, TransactionVariant,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
+use reth_transaction_pool::{FullTransactionEvent, PoolTransaction, TransactionPool};
 use serde::Serialize;
 use tokio::sync::oneshot;
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider, EvmConfig> {
-    inner: Arc<RethApiInner<Provider, EvmConfig>>,
+pub struct RethApi<Provider, Pool, EvmConfig> {
+    inner: Arc<RethApiInner<Provider, Pool, EvmConfig>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider, EvmConfig> RethApi<Provider, EvmConfig> {
+impl<Provider, Pool, EvmConfig> RethApi<Provider, Pool, EvmConfig> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// The transaction pool.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
     /// The evm config.
     pub fn evm_config(&self) -> &EvmConfig {
         &self.inner.evm_config
@@ -46,19 +61,26 @@ impl<Provider, EvmConfig> RethApi<Provider, EvmConfig> {
     /// Create a new instance of the [`RethApi`]
     pub fn new(
         provider: Provider,
+        pool: Pool,
         evm_config: EvmConfig,
         blocking_task_guard: BlockingTaskGuard,
         task_spawner: Runtime,
     ) -> Self {
-        let inner =
-            Arc::new(RethApiInner { provider, evm_config, blocking_task_guard, task_spawner });
+        let inner = Arc::new(RethApiInner {
+            provider,
+            pool,
+            evm_config,
+            blocking_task_guard,
+            task_spawner,
+        });
         Self { inner }
     }
 }
 
-impl<Provider, EvmConfig> RethApi<Provider, EvmConfig>
+impl<Provider, Pool, EvmConfig> RethApi<Provider, Pool, EvmConfig>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Pool: Send + Sync + 'static,
     EvmConfig: Send + Sync + 'static,
 {
     /// Executes the future on a new blocking task.
@@ -106,7 +128,7 @@ where
     }
 }
 
-impl<N, Provider, EvmConfig> RethApi<Provider, EvmConfig>
+impl<N, Provider, Pool, EvmConfig> RethApi<Provider, Pool, EvmConfig>
 where
     N: NodePrimitives,
     Provider: BlockReaderIdExt
@@ -115,6 +137,7 @@ where
         + BlockReader<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + 'static,
+    Pool: Send + Sync + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
 {
     /// Re-executes one or more consecutive blocks and returns the execution outcome.
@@ -183,7 +206,7 @@ where
 }
 
 #[async_trait]
-impl<Provider, EvmConfig> RethApiServer for RethApi<Provider, EvmConfig>
+impl<Provider, Pool, EvmConfig> RethApiServer for RethApi<Provider, Pool, EvmConfig>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
@@ -193,6 +216,7 @@ where
         + ForkChoiceSubscriptions<Header = <Provider::Primitives as NodePrimitives>::BlockHeader>
         + PersistedBlockSubscriptions
         + 'static,
+    Pool: TransactionPool + 'static,
     EvmConfig: ConfigureEvm<Primitives = Provider::Primitives> + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
@@ -261,6 +285,113 @@ where
 
         Ok(())
     }
+
+    /// Handler for `reth_subscribeTransactionLifecycle`
+    async fn reth_subscribe_transaction_lifecycle(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: TransactionLifecycleFilter,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        if filter.is_empty() {
+            pending.reject(invalid_params_rpc_err("filter must contain hashes or senders")).await;
+            return Ok(())
+        }
+
+        // subscribe before collecting the sender transactions, so that no event is missed
+        let events = self.pool().all_transactions_event_listener();
+        let mut tracker = TransactionLifecycleTracker::new(filter);
+        for sender in tracker.senders.clone() {
+            for tx in self.pool().get_transactions_by_sender(sender) {
+                tracker.tracked.insert(*tx.hash());
+            }
+        }
+
+        let sink = pending.accept().await?;
+        let pool = self.pool().clone();
+        let stream = events.filter_map(move |event| {
+            let event = tracker.on_event(event, |hash| pool.get(hash).map(|tx| tx.sender()));
+            std::future::ready(event)
+        });
+        self.inner.task_spawner.spawn_task(pipe_from_stream(sink, stream));
+
+        Ok(())
+    }
+}
+
+/// Selects the pool events of the transactions matching a [`TransactionLifecycleFilter`].
+///
+/// Transactions requested by hash are tracked for the lifetime of the subscription. Transactions
+/// of the requested senders are tracked while they're in the pool.
+#[derive(Debug)]
+struct TransactionLifecycleTracker {
+    /// Hashes requested by the filter.
+    hashes: HashSet<TxHash>,
+    /// Senders requested by the filter.
+    senders: HashSet<Address>,
+    /// Transactions of the requested senders that are currently in the pool.
+    tracked: HashSet<TxHash>,
+}
+
+impl TransactionLifecycleTracker {
+    fn new(filter: TransactionLifecycleFilter) -> Self {
+        Self {
+            hashes: filter.hashes.into_iter().collect(),
+            senders: filter.senders.into_iter().collect(),
+            tracked: HashSet::default(),
+        }
+    }
+
+    /// Converts the pool event into a lifecycle event if it belongs to a tracked transaction.
+    ///
+    /// `sender_of` looks up the sender of a pool transaction whose event doesn't carry it.
+    fn on_event<T: PoolTransaction>(
+        &mut self,
+        event: FullTransactionEvent<T>,
+        sender_of: impl FnOnce(&TxHash) -> Option<Address>,
+    ) -> Option<TransactionLifecycleEvent> {
+        let (hash, kind, sender, is_final) = match event {
+            FullTransactionEvent::Pending(hash) => {
+                (hash, TransactionLifecycleEventKind::Pending, None, false)
+            }
+            FullTransactionEvent::Queued(hash, reason) => {
+                (hash, TransactionLifecycleEventKind::Queued { reason }, None, false)
+            }
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                (tx_hash, TransactionLifecycleEventKind::Mined { block_hash }, None, true)
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => (
+                *transaction.hash(),
+                TransactionLifecycleEventKind::Replaced { replaced_by },
+                Some(transaction.sender()),
+                true,
+            ),
+            FullTransactionEvent::Discarded { tx_hash, reason } => {
+                (tx_hash, TransactionLifecycleEventKind::Discarded { reason }, None, true)
+            }
+            FullTransactionEvent::Invalid { tx_hash, sender, error } => {
+                (tx_hash, TransactionLifecycleEventKind::Invalid { error }, Some(sender), true)
+            }
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+
+        let selected = self.hashes.contains(&hash) ||
+            self.tracked.contains(&hash) ||
+            (!self.senders.is_empty() &&
+                sender
+                    .or_else(|| sender_of(&hash))
+                    .is_some_and(|s| self.senders.contains(&s)));
+        if !selected {
+            return None
+        }
+
+        if is_final {
+            self.tracked.remove(&hash);
+        } else if !self.hashes.contains(&hash) {
+            self.tracked.insert(hash);
+        }
+
+        Some(TransactionLifecycleEvent { hash, kind })
+    }
 }
 
 /// Pipes all stream items to the subscription sink.
@@ -358,21 +489,23 @@ async fn finalized_chain_notifications<N>(
     }
 }
 
-impl<Provider, EvmConfig> std::fmt::Debug for RethApi<Provider, EvmConfig> {
+impl<Provider, Pool, EvmConfig> std::fmt::Debug for RethApi<Provider, Pool, EvmConfig> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider, EvmConfig> Clone for RethApi<Provider, EvmConfig> {
+impl<Provider, Pool, EvmConfig> Clone for RethApi<Provider, Pool, EvmConfig> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider, EvmConfig> {
+struct RethApiInner<Provider, Pool, EvmConfig> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The transaction pool, for transaction lifecycle subscriptions.
+    pool: Pool,
     /// The EVM configuration used to create block executors.
     evm_config: EvmConfig,
     /// Guard to restrict the number of concurrent block re-execution requests.
@@ -380,3 +513,139 @@ struct RethApiInner<Provider, EvmConfig> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Runtime,
 }
+
+/// `reth_executionProfile` implementation, backed by the [`ExecutionProfiler`] of the engine.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionProfileApi {
+    profiler: Option<ExecutionProfiler>,
+}
+
+impl ExecutionProfileApi {
+    /// Creates a new instance, `None` if execution profiling is disabled.
+    pub const fn new(profiler: Option<ExecutionProfiler>) -> Self {
+        Self { profiler }
+    }
+}
+
+impl ExecutionProfileApiServer for ExecutionProfileApi {
+    fn reth_execution_profile(&self, limit: Option<usize>) -> RpcResult<ExecutionProfile> {
+        let Some(profiler) = &self.profiler else {
+            return Err(internal_rpc_err(
+                "execution profiling is disabled, enable it with --engine.execution-profile",
+            ))
+        };
+        let profile = profiler.profile(limit.unwrap_or_else(|| profiler.top()));
+        let stats = |stats: profiler::ExecutionStats| ExecutionStats {
+            calls: stats.calls,
+            gas_used: stats.gas_used,
+            time_nanos: stats.time.as_nanos() as u64,
+        };
+        Ok(ExecutionProfile {
+            blocks: profile.blocks,
+            contracts: profile
+                .contracts
+                .into_iter()
+                .map(|(address, s)| AddressExecutionStats { address, stats: stats(s) })
+                .collect(),
+            selectors: profile
+                .selectors
+                .into_iter()
+                .map(|(selector, s)| SelectorExecutionStats { selector, stats: stats(s) })
+                .collect(),
+            precompiles: profile
+                .precompiles
+                .into_iter()
+                .map(|(address, s)| AddressExecutionStats { address, stats: stats(s) })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_transaction_pool::{test_utils::MockTransaction, DiscardReason};
+
+    #[test]
+    fn execution_profile_requires_profiler() {
+        assert!(ExecutionProfileApi::default().reth_execution_profile(None).is_err());
+
+        let profiler = ExecutionProfiler::new(5);
+        drop(profiler.inspector());
+        let profile =
+            ExecutionProfileApi::new(Some(profiler)).reth_execution_profile(None).unwrap();
+        assert_eq!(profile, ExecutionProfile { blocks: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn tracks_lifecycle_of_filtered_transactions() {
+        let tx = MockTransaction::eip1559();
+        let sender = tx.sender();
+        let other = MockTransaction::eip1559().with_sender(Address::random());
+        let requested = B256::random();
+
+        let mut tracker = TransactionLifecycleTracker::new(TransactionLifecycleFilter {
+            hashes: vec![requested],
+            senders: vec![sender],
+        });
+        let sender_of = |hash: &TxHash| (*hash == *tx.hash()).then_some(sender);
+
+        // transactions of the sender are picked up when they enter the pool
+        let event = tracker
+            .on_event(FullTransactionEvent::<MockTransaction>::Pending(*tx.hash()), sender_of)
+            .unwrap();
+        assert_eq!(event.kind, TransactionLifecycleEventKind::Pending);
+        assert!(tracker.tracked.contains(tx.hash()));
+
+        // other transactions are ignored
+        assert!(tracker
+            .on_event(FullTransactionEvent::<MockTransaction>::Pending(*other.hash()), sender_of)
+            .is_none());
+
+        // the final event stops the tracking of the sender transaction
+        let discarded = FullTransactionEvent::<MockTransaction>::Discarded {
+            tx_hash: *tx.hash(),
+            reason: DiscardReason::NonceTooLow,
+        };
+        let event = tracker.on_event(discarded.clone(), |_| None).unwrap();
+        assert_eq!(
+            event.kind,
+            TransactionLifecycleEventKind::Discarded { reason: DiscardReason::NonceTooLow }
+        );
+        assert!(tracker.tracked.is_empty());
+        assert!(tracker.on_event(discarded, |_| None).is_none());
+
+        // requested hashes are reported without a known sender
+        let event = tracker
+            .on_event(
+                FullTransactionEvent::<MockTransaction>::Discarded {
+                    tx_hash: requested,
+                    reason: DiscardReason::Removed,
+                },
+                |_| None,
+            )
+            .unwrap();
+        assert_eq!(event.hash, requested);
+    }
+
+    #[test]
+    fn serializes_lifecycle_events() {
+        let event = TransactionLifecycleEvent {
+            hash: B256::ZERO,
+            kind: TransactionLifecycleEventKind::Discarded {
+                reason: DiscardReason::PoolLimit {
+                    subpool: reth_transaction_pool::SubPool::Pending,
+                },
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "hash": B256::ZERO,
+                "event": "discarded",
+                "reason": { "kind": "poolLimit", "subpool": "pending" }
+            })
+        );
+    }
+}
@@ -11,6 +11,8 @@ pub struct PipelineBuilder<Provider> {
     stages: Vec<BoxedStage<Provider>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
+    /// The number of blocks in a window, if the stages run in block windows.
+    window: Option<u64>,
     /// A Sender for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
@@ -49,6 +51,13 @@ impl<Provider> PipelineBuilder<Provider> {
         self
     }
 
+    /// Run the stages after the header stages in windows of the given number of blocks, see
+    /// [`Pipeline`] for details.
+    pub const fn with_window(mut self, blocks: u64) -> Self {
+        self.window = Some(if blocks == 0 { 1 } else { blocks });
+        self
+    }
+
     /// Set the tip sender.
     pub fn with_tip_sender(mut self, tip_tx: watch::Sender<B256>) -> Self {
         self.tip_tx = Some(tip_tx);
@@ -77,11 +86,12 @@ impl<Provider> PipelineBuilder<Provider> {
         N: ProviderNodeTypes,
         ProviderFactory<N>: DatabaseProviderFactory<ProviderRW = Provider>,
     {
-        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind } = self;
+        let Self { stages, max_block, window, tip_tx, metrics_tx, fail_on_unwind } = self;
         Pipeline {
             provider_factory,
             stages,
             max_block,
+            window,
             static_file_producer,
             tip_tx,
             event_sender: Default::default(),
@@ -99,6 +109,7 @@ impl<Provider> Default for PipelineBuilder<Provider> {
         Self {
             stages: Vec::new(),
             max_block: None,
+            window: None,
             tip_tx: None,
             metrics_tx: None,
             fail_on_unwind: false,
@@ -111,6 +122,7 @@ impl<Provider> std::fmt::Debug for PipelineBuilder<Provider> {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("window", &self.window)
             .field("fail_on_unwind", &self.fail_on_unwind)
             .finish()
     }
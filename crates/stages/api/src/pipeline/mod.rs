@@ -15,6 +15,7 @@ use reth_prune::PrunerBuilder;
 use reth_static_file::StaticFileProducer;
 use reth_tokio_util::{EventSender, EventStream};
 use std::{
+    ops::Range,
     pin::Pin,
     time::{Duration, Instant},
 };
@@ -63,6 +64,17 @@ pub type PipelineWithResult<N> = (Pipeline<N>, Result<ControlFlow, PipelineError
 /// pipeline will unwind the stages in reverse order of execution. It is also possible to
 /// request an unwind manually (see [`Pipeline::unwind`]).
 ///
+/// # Block windows
+///
+/// By default, each stage runs to the target before the next stage starts, e.g. no block is
+/// executed before all bodies up to the target are downloaded. With
+/// [`PipelineBuilder::with_window`], the stages after the header stages run in block windows
+/// instead: all stages process a window before the next one, and the stages are asked to
+/// [prefetch](Stage::prefetch) the next window while the later stages process the current one. This
+/// way, the bodies of the next window are downloaded while the current window is executed and
+/// hashed. Checkpoints are committed per stage and window, and unwinds work the same as without
+/// windows.
+///
 /// # Defaults
 ///
 /// The [`DefaultStages`](crate::sets::DefaultStages) are used to fully sync reth.
@@ -73,6 +85,8 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     stages: Vec<BoxedStage<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW>>,
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
+    /// The number of blocks in a window, if the stages run in block windows.
+    window: Option<u64>,
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     /// Sender for events the pipeline emits.
     event_sender: EventSender<PipelineEvent>,
@@ -223,41 +237,145 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
         self.move_to_static_files()?;
 
+        let first_windowed_stage = self.first_windowed_stage();
         let mut previous_stage = None;
         for stage_index in 0..self.stages.len() {
-            let stage = &self.stages[stage_index];
-            let stage_id = stage.id();
+            if stage_index == first_windowed_stage &&
+                let Some(window) = self.window &&
+                let Some(target) = self.max_block.or(previous_stage)
+            {
+                return self
+                    .run_windows(stage_index..self.stages.len(), previous_stage, target, window)
+                    .await
+            }
 
-            trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+            let next = self.run_stage(stage_index, self.max_block.or(previous_stage)).await?;
+            if next.is_unwind() {
+                return Ok(next)
+            }
+            if let Some(block_number) = next.block_number() {
+                self.progress.update(block_number);
+            }
 
-            trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+            previous_stage = Some(self.stage_checkpoint(stage_index)?);
+        }
 
-            match next {
-                ControlFlow::NoProgress { block_number } => {
-                    if let Some(block_number) = block_number {
-                        self.progress.update(block_number);
-                    }
+        Ok(self.progress.next_ctrl())
+    }
+
+    /// Returns the index of the first stage that runs in block windows, if the pipeline runs in
+    /// block windows.
+    ///
+    /// The stages that download headers run to completion first, because headers are downloaded
+    /// from the tip backwards and the later stages need them to determine their target.
+    fn first_windowed_stage(&self) -> usize {
+        if self.window.is_none() {
+            return self.stages.len()
+        }
+        self.stages
+            .iter()
+            .position(|stage| !matches!(stage.id(), StageId::Era | StageId::Headers))
+            .unwrap_or(self.stages.len())
+    }
+
+    /// Runs the given stages in block windows of the given size, up to the target.
+    ///
+    /// Each window is processed like a regular pipeline run that targets the end of the window:
+    /// every stage executes and commits the window before the next stage executes it, and if a
+    /// stage fails, the pipeline is unwound. Therefore, a stage is never ahead of the stages before
+    /// it. Once a stage has finished a window, it is asked to [prefetch](Stage::prefetch) the next
+    /// one, so that e.g. the bodies of the next window are downloaded while the current window is
+    /// executed.
+    ///
+    /// A window starts at the lowest checkpoint of the stages, so that stages that are behind the
+    /// others catch up in windows as well.
+    async fn run_windows(
+        &mut self,
+        stages: Range<usize>,
+        previous_stage: Option<BlockNumber>,
+        target: BlockNumber,
+        window: u64,
+    ) -> Result<ControlFlow, PipelineError> {
+        let mut last_window_start = None;
+        loop {
+            let lowest_checkpoint = stages
+                .clone()
+                .map(|stage_index| self.stage_checkpoint(stage_index))
+                .try_fold(u64::MAX, |lowest, checkpoint| checkpoint.map(|c| lowest.min(c)))?;
+            // Stop once all stages reached the target, or if the last window made no progress
+            if lowest_checkpoint >= target ||
+                last_window_start.is_some_and(|start| lowest_checkpoint <= start)
+            {
+                break
+            }
+            last_window_start = Some(lowest_checkpoint);
+            let window_end = target.min(lowest_checkpoint.saturating_add(window));
+            let next_window_end = (window_end < target).then(|| target.min(window_end + window));
+            debug!(target: "sync::pipeline", from = lowest_checkpoint, to = window_end, "Running stages in block window");
+
+            let mut previous_stage = previous_stage;
+            for stage_index in stages.clone() {
+                let stage_target = self.max_block.or(previous_stage).map(|t| t.min(window_end));
+                let next = self.run_stage(stage_index, stage_target).await?;
+                if next.is_unwind() {
+                    return Ok(next)
                 }
-                ControlFlow::Continue { block_number } => self.progress.update(block_number),
-                ControlFlow::Unwind { target, bad_block } => {
-                    self.unwind(target, Some(bad_block.block.number))?;
-                    return Ok(ControlFlow::Unwind { target, bad_block })
+
+                let checkpoint = self.stage_checkpoint(stage_index)?;
+                if let Some(next_window_end) = next_window_end {
+                    let input = ExecInput {
+                        target: Some(next_window_end),
+                        checkpoint: Some(StageCheckpoint::new(checkpoint)),
+                    };
+                    self.stage(stage_index).prefetch(input)?;
                 }
+                previous_stage = Some(checkpoint);
             }
+        }
 
-            previous_stage = Some(
-                self.provider_factory
-                    .provider()?
-                    .get_stage_checkpoint(stage_id)?
-                    .unwrap_or_default()
-                    .block_number,
-            );
+        // The progress is only recorded once the stages finished all windows, because the minimum
+        // block number of the progress has to be the lowest block that the stages reached.
+        for stage_index in stages {
+            let checkpoint = self.stage_checkpoint(stage_index)?;
+            self.progress.update(checkpoint);
         }
 
         Ok(self.progress.next_ctrl())
     }
 
+    /// Executes the stage to completion.
+    ///
+    /// If the stage requests an unwind, the pipeline is unwound before the control flow is
+    /// returned.
+    async fn run_stage(
+        &mut self,
+        stage_index: usize,
+        target: Option<BlockNumber>,
+    ) -> Result<ControlFlow, PipelineError> {
+        let stage_id = self.stages[stage_index].id();
+
+        trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
+        let next = self.execute_stage_to_completion(target, stage_index).await?;
+
+        trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+
+        if let ControlFlow::Unwind { target, bad_block } = &next {
+            self.unwind(*target, Some(bad_block.block.number))?;
+        }
+
+        Ok(next)
+    }
+
+    /// Returns the block number of the checkpoint of the stage.
+    fn stage_checkpoint(&self, stage_index: usize) -> Result<BlockNumber, PipelineError> {
+        Ok(self
+            .provider_factory
+            .provider()?
+            .get_stage_checkpoint(self.stages[stage_index].id())?
+            .unwrap_or_default()
+            .block_number)
+    }
+
     /// Run [static file producer](StaticFileProducer) and [pruner](reth_prune::Pruner) to **move**
     /// all data from the database to static files for corresponding
     /// [segments](reth_static_file_types::StaticFileSegment), according to their [stage
@@ -412,14 +530,13 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
     async fn execute_stage_to_completion(
         &mut self,
-        previous_stage: Option<BlockNumber>,
+        target: Option<BlockNumber>,
         stage_index: usize,
     ) -> Result<ControlFlow, PipelineError> {
         let total_stages = self.stages.len();
 
         let stage_id = self.stage(stage_index).id();
         let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
 
         loop {
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
@@ -647,6 +764,7 @@ impl<N: ProviderNodeTypes> std::fmt::Debug for Pipeline<N> {
         f.debug_struct("Pipeline")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("window", &self.window)
             .field("event_sender", &self.event_sender)
             .field("fail_on_unwind", &self.fail_on_unwind)
             .finish()
@@ -1142,6 +1260,112 @@ mod tests {
         );
     }
 
+    /// Runs a pipeline in block windows.
+    ///
+    /// Both stages process a window before the next one, and stage A is asked to prefetch the next
+    /// window once it has finished the current one.
+    #[tokio::test]
+    async fn run_pipeline_in_windows() {
+        let provider_factory = create_test_provider_factory();
+
+        let (stage_a, prefetch_inputs_a) = TestStage::new(StageId::Other("A"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(25), done: true }))
+            .with_prefetch_inputs();
+        let stage_b = TestStage::new(StageId::Other("B"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true }))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(25), done: true }));
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(stage_a)
+            .add_stage(stage_b)
+            .with_max_block(25)
+            .with_window(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        let prepared = events
+            .filter_map(|event| match event {
+                PipelineEvent::Prepare { stage_id, target, .. } => Some((stage_id, target)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            prepared,
+            vec![
+                (StageId::Other("A"), Some(10)),
+                (StageId::Other("B"), Some(10)),
+                (StageId::Other("A"), Some(20)),
+                (StageId::Other("B"), Some(20)),
+                (StageId::Other("A"), Some(25)),
+                (StageId::Other("B"), Some(25)),
+            ]
+        );
+        assert_eq!(
+            *prefetch_inputs_a.lock().unwrap(),
+            vec![
+                ExecInput { target: Some(20), checkpoint: Some(StageCheckpoint::new(10)) },
+                ExecInput { target: Some(25), checkpoint: Some(StageCheckpoint::new(20)) },
+            ]
+        );
+    }
+
+    /// Unwinds a pipeline that runs in block windows when a stage fails in the second window.
+    ///
+    /// Stage A is unwound from the end of the second window to the checkpoint of stage B, so that
+    /// it is not ahead of stage B by more than the window that stage B failed in.
+    #[tokio::test]
+    async fn unwind_pipeline_in_windows() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(20), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(10) })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }))
+                    .add_exec(Err(StageError::Block {
+                        block: Box::new(random_block_with_parent(
+                            &mut generators::rng(),
+                            15,
+                            Default::default(),
+                        )),
+                        error: BlockErrorKind::Validation(ConsensusError::BaseFeeMissing),
+                    })),
+            )
+            .with_max_block(25)
+            .with_window(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+
+        let result = pipeline.run_loop().await.unwrap();
+        assert_matches!(result, ControlFlow::Unwind { target: 10, .. });
+
+        let provider = provider_factory.provider().unwrap();
+        for stage_id in [StageId::Other("A"), StageId::Other("B")] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(10))
+            );
+        }
+    }
+
     /// Checks that the pipeline re-runs stages on non-fatal errors and stops on fatal ones.
     #[tokio::test]
     async fn pipeline_error_handling() {
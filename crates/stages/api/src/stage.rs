@@ -276,6 +276,20 @@ pub trait Stage<Provider>: Send {
         Poll::Ready(Ok(()))
     }
 
+    /// Hints the stage that it will be executed with the given input next.
+    ///
+    /// This is called by a pipeline that runs in block windows (see
+    /// [`PipelineBuilder::with_window`](crate::PipelineBuilder::with_window)) once the stage has
+    /// finished a window, while the later stages are still processing it. Stages that fetch data
+    /// from outside the database, e.g. from a downloader, can start fetching the next window in
+    /// the background.
+    ///
+    /// The stage must not assume that it will be executed with the given input, the pipeline may
+    /// unwind instead.
+    fn prefetch(&mut self, _input: ExecInput) -> Result<(), StageError> {
+        Ok(())
+    }
+
     /// Execute the stage.
     /// It is expected that the stage will write all necessary data to the database
     /// upon invoking this method.
@@ -5,7 +5,7 @@ use std::{
     collections::VecDeque,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
 };
 
@@ -19,6 +19,7 @@ pub struct TestStage {
     unwind_outputs: VecDeque<Result<UnwindOutput, StageError>>,
     post_execute_commit_counter: Arc<AtomicUsize>,
     post_unwind_commit_counter: Arc<AtomicUsize>,
+    prefetch_inputs: Arc<Mutex<Vec<ExecInput>>>,
 }
 
 impl TestStage {
@@ -29,6 +30,7 @@ impl TestStage {
             unwind_outputs: VecDeque::new(),
             post_execute_commit_counter: Arc::new(AtomicUsize::new(0)),
             post_unwind_commit_counter: Arc::new(AtomicUsize::new(0)),
+            prefetch_inputs: Default::default(),
         }
     }
 
@@ -66,6 +68,12 @@ impl TestStage {
         self.post_unwind_commit_counter = counter.clone();
         (self, counter)
     }
+
+    pub fn with_prefetch_inputs(mut self) -> (Self, Arc<Mutex<Vec<ExecInput>>>) {
+        let inputs = Arc::new(Mutex::new(Vec::new()));
+        self.prefetch_inputs = inputs.clone();
+        (self, inputs)
+    }
 }
 
 impl<Provider> Stage<Provider> for TestStage {
@@ -73,6 +81,12 @@ impl<Provider> Stage<Provider> for TestStage {
         self.id
     }
 
+    fn prefetch(&mut self, input: ExecInput) -> Result<(), StageError> {
+        self.prefetch_inputs.lock().unwrap().push(input);
+
+        Ok(())
+    }
+
     fn execute(&mut self, _: &Provider, _input: ExecInput) -> Result<ExecOutput, StageError> {
         self.exec_outputs
             .pop_front()
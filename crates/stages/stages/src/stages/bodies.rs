@@ -184,6 +184,15 @@ where
         Poll::Ready(response)
     }
 
+    /// Extends the download range of the downloader to the next block window, so that the bodies
+    /// are downloaded while the later stages process the current one.
+    fn prefetch(&mut self, input: ExecInput) -> Result<(), StageError> {
+        if !input.target_reached() {
+            self.downloader.set_download_range(input.next_block_range())?;
+        }
+        Ok(())
+    }
+
     /// Download block bodies from the last checkpoint for this stage up until the latest synced
     /// header, limited by the stage's batch size.
     fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
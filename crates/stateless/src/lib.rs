@@ -0,0 +1,24 @@
+//! Stateless validation of blocks.
+//!
+//! A block is validated against an [`ExecutionWitness`](alloy_rpc_types_debug::ExecutionWitness)
+//! instead of a database: the witness carries the trie nodes, bytecodes and ancestor headers the
+//! block touches, which is enough to execute the block and recompute its state root.
+//!
+//! See [`stateless_validation`] for the entry point.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod trie;
+pub use trie::StatelessTrie;
+
+mod validation;
+pub use validation::{stateless_validation, StatelessValidationError};
+
+mod witness_db;
+pub use witness_db::WitnessDatabase;
@@ -0,0 +1,394 @@
+//! Sparse state trie revealed from the state nodes of an execution witness.
+
+use crate::StatelessValidationError;
+use alloy_primitives::{keccak256, map::B256Map, Address, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use itertools::Itertools;
+use reth_trie_common::{
+    proof::DecodedProofNodes, DecodedMultiProof, DecodedStorageMultiProof, HashedPostState,
+    Nibbles, TrieAccount, TrieNode, EMPTY_ROOT_HASH,
+};
+use reth_trie_sparse::{
+    provider::{DefaultTrieNodeProvider, DefaultTrieNodeProviderFactory},
+    RevealableSparseTrie, SparseStateTrie,
+};
+
+/// A [`SparseStateTrie`] revealed from the state nodes of an
+/// [`ExecutionWitness`](alloy_rpc_types_debug::ExecutionWitness).
+///
+/// Only the parts of the trie that are present in the witness are revealed. Reading or updating
+/// any other part fails, which means that the witness is incomplete for the block.
+#[derive(Debug)]
+pub struct StatelessTrie {
+    /// The revealed state trie.
+    inner: SparseStateTrie,
+}
+
+impl StatelessTrie {
+    /// Reveals the state trie with the given root from the witness state nodes.
+    ///
+    /// The nodes are looked up by their hash, starting at `pre_state_root`. Nodes that aren't
+    /// reachable from the root are ignored, subtries that are missing from the witness stay blind.
+    pub fn new(state: &[Bytes], pre_state_root: B256) -> Result<Self, StatelessValidationError> {
+        let nodes: B256Map<&Bytes> = state.iter().map(|node| (keccak256(node), node)).collect();
+
+        let mut multiproof = DecodedMultiProof::default();
+        let mut accounts = Vec::new();
+        multiproof.account_subtree = reveal_subtrie(&nodes, pre_state_root, |path, value| {
+            accounts.push((B256::from_slice(&path.pack()), TrieAccount::decode(&mut &value[..])?));
+            Ok(())
+        })?;
+
+        for (hashed_address, account) in accounts {
+            let storage = if account.storage_root == EMPTY_ROOT_HASH {
+                DecodedStorageMultiProof::empty()
+            } else if nodes.contains_key(&account.storage_root) {
+                DecodedStorageMultiProof {
+                    root: account.storage_root,
+                    subtree: reveal_subtrie(&nodes, account.storage_root, |_, _| Ok(()))?,
+                    branch_node_masks: Default::default(),
+                }
+            } else {
+                // the storage of the account isn't accessed by the block
+                continue
+            };
+            multiproof.storages.insert(hashed_address, storage);
+        }
+
+        let mut inner = SparseStateTrie::new();
+        inner.reveal_decoded_multiproof(multiproof)?;
+
+        Ok(Self { inner })
+    }
+
+    /// Returns the account with the given address, or `None` if the witness proves that it doesn't
+    /// exist.
+    pub fn account(
+        &self,
+        address: Address,
+    ) -> Result<Option<TrieAccount>, StatelessValidationError> {
+        let hashed_address = keccak256(address);
+        if !self.inner.check_valid_account_witness(hashed_address) {
+            return Err(StatelessValidationError::MissingAccount(address))
+        }
+
+        Ok(self
+            .inner
+            .get_account_value(&hashed_address)
+            .map(|value| TrieAccount::decode(&mut &value[..]))
+            .transpose()?)
+    }
+
+    /// Returns the value of the given storage slot of the account.
+    ///
+    /// Slots of accounts that don't exist or have an empty storage are zero.
+    pub fn storage(&self, address: Address, slot: U256) -> Result<U256, StatelessValidationError> {
+        let hashed_address = keccak256(address);
+        let hashed_slot = keccak256(B256::from(slot));
+
+        if self.inner.storage_trie_ref(&hashed_address).is_none() {
+            // the account doesn't exist or has an empty storage, its storage trie was revealed
+            // otherwise
+            return match self.account(address)? {
+                Some(account) if account.storage_root != EMPTY_ROOT_HASH => {
+                    Err(StatelessValidationError::MissingStorage { address, slot })
+                }
+                _ => Ok(U256::ZERO),
+            }
+        }
+
+        if !self.inner.check_valid_storage_witness(hashed_address, hashed_slot) {
+            return Err(StatelessValidationError::MissingStorage { address, slot })
+        }
+
+        Ok(self
+            .inner
+            .get_storage_slot_value(&hashed_address, &hashed_slot)
+            .map(|value| U256::decode(&mut &value[..]))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Applies the state changes of a block to the trie and returns the new state root.
+    ///
+    /// Storage tries are updated first, so that the accounts are updated with their new storage
+    /// roots.
+    pub fn calculate_state_root(
+        &mut self,
+        state: HashedPostState,
+    ) -> Result<B256, StatelessValidationError> {
+        let provider = DefaultTrieNodeProvider;
+        let mut storage_roots = B256Map::default();
+
+        for (hashed_address, storage) in
+            state.storages.into_iter().sorted_unstable_by_key(|(address, _)| *address)
+        {
+            let storage_trie = self.inner.get_or_create_storage_trie_mut(hashed_address);
+            if storage_trie.is_blind() {
+                // The storage trie of the account was revealed if it exists and isn't empty, so
+                // it's only blind for accounts without storage. It can only be revealed as empty
+                // if the witness proves this.
+                let pre_state_account = self
+                    .inner
+                    .check_valid_account_witness(hashed_address)
+                    .then(|| self.inner.get_account_value(&hashed_address))
+                    .ok_or(StatelessValidationError::MissingStorageTrie(hashed_address))?
+                    .map(|value| TrieAccount::decode(&mut &value[..]))
+                    .transpose()?;
+                if pre_state_account.is_some_and(|account| account.storage_root != EMPTY_ROOT_HASH)
+                {
+                    return Err(StatelessValidationError::MissingStorageTrie(hashed_address))
+                }
+                self.inner
+                    .insert_storage_trie(hashed_address, RevealableSparseTrie::revealed_empty());
+            }
+            let storage_trie = self.inner.get_or_create_storage_trie_mut(hashed_address);
+
+            if storage.wiped {
+                storage_trie.wipe()?;
+            }
+
+            for (hashed_slot, value) in
+                storage.storage.into_iter().sorted_unstable_by_key(|(slot, _)| *slot)
+            {
+                let path = Nibbles::unpack(hashed_slot);
+                if value.is_zero() {
+                    storage_trie.remove_leaf(&path, &provider)?;
+                } else {
+                    storage_trie.update_leaf(
+                        path,
+                        alloy_rlp::encode_fixed_size(&value).to_vec(),
+                        &provider,
+                    )?;
+                }
+            }
+
+            let storage_root = storage_trie
+                .root()
+                .ok_or(StatelessValidationError::MissingStorageTrie(hashed_address))?;
+            storage_roots.insert(hashed_address, storage_root);
+        }
+
+        for (hashed_address, account) in
+            state.accounts.into_iter().sorted_unstable_by_key(|(address, _)| *address)
+        {
+            let path = Nibbles::unpack(hashed_address);
+            let pre_state_value = self.inner.get_account_value(&hashed_address);
+
+            let storage_root = match storage_roots.remove(&hashed_address) {
+                Some(root) => root,
+                None => pre_state_value
+                    .map(|value| TrieAccount::decode(&mut &value[..]))
+                    .transpose()?
+                    .map_or(EMPTY_ROOT_HASH, |account| account.storage_root),
+            };
+
+            match account.filter(|account| !account.is_empty() || storage_root != EMPTY_ROOT_HASH) {
+                Some(account) => {
+                    let value = alloy_rlp::encode(account.into_trie_account(storage_root));
+                    self.inner.update_account_leaf(path, value, DefaultTrieNodeProviderFactory)?;
+                }
+                None if pre_state_value.is_some() => {
+                    self.inner.remove_account_leaf(&path, DefaultTrieNodeProviderFactory)?;
+                }
+                None => {}
+            }
+        }
+
+        // accounts whose storage changed but the account itself didn't
+        for (hashed_address, storage_root) in storage_roots {
+            let Some(value) = self.inner.get_account_value(&hashed_address) else { continue };
+            let mut account = TrieAccount::decode(&mut &value[..])?;
+            account.storage_root = storage_root;
+            self.inner.update_account_leaf(
+                Nibbles::unpack(hashed_address),
+                alloy_rlp::encode(account),
+                DefaultTrieNodeProviderFactory,
+            )?;
+        }
+
+        Ok(self.inner.root(DefaultTrieNodeProviderFactory)?)
+    }
+}
+
+/// Decodes the subtrie with the given root from the witness nodes.
+///
+/// Returns the decoded nodes by path and calls `on_leaf` with the full path and value of every
+/// leaf. Children that are embedded in their parent aren't returned, the sparse trie reveals them
+/// together with their parent.
+fn reveal_subtrie(
+    nodes: &B256Map<&Bytes>,
+    root: B256,
+    mut on_leaf: impl FnMut(Nibbles, &[u8]) -> Result<(), StatelessValidationError>,
+) -> Result<DecodedProofNodes, StatelessValidationError> {
+    let mut decoded = DecodedProofNodes::default();
+
+    let root_node = match nodes.get(&root) {
+        Some(node) => TrieNode::decode(&mut &node[..])?,
+        None if root == EMPTY_ROOT_HASH => TrieNode::EmptyRoot,
+        None => return Err(StatelessValidationError::MissingTrieNode(root)),
+    };
+
+    let mut stack = vec![(Nibbles::default(), root_node)];
+    while let Some((path, node)) = stack.pop() {
+        let mut push_child = |child_path: Nibbles, hash: Option<B256>| -> alloy_rlp::Result<()> {
+            if let Some(child) = hash.and_then(|hash| nodes.get(&hash)) {
+                stack.push((child_path, TrieNode::decode(&mut &child[..])?));
+            }
+            Ok(())
+        };
+
+        match &node {
+            TrieNode::Branch(branch) => {
+                for (nibble, child) in branch.as_ref().children() {
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    push_child(child_path, child.and_then(|child| child.as_hash()))?;
+                }
+            }
+            TrieNode::Extension(extension) => {
+                push_child(path.join(&extension.key), extension.child.as_hash())?;
+            }
+            TrieNode::Leaf(leaf) => on_leaf(path.join(&leaf.key), &leaf.value)?,
+            TrieNode::EmptyRoot => {}
+        }
+
+        decoded.insert(path, node);
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+    use reth_primitives_traits::Account;
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder, HashedStorage};
+    use std::collections::BTreeMap;
+
+    /// Builds the trie with the given leaves and returns its root and all of its nodes.
+    fn trie_nodes(leaves: &BTreeMap<B256, Vec<u8>>) -> (B256, Vec<Bytes>) {
+        let targets = leaves.keys().map(|key| Nibbles::unpack(*key)).collect();
+        let mut hash_builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(*key), value);
+        }
+        let root = hash_builder.root();
+        let nodes = hash_builder.take_proof_nodes().into_nodes_sorted();
+        (root, nodes.into_iter().map(|(_, node)| node).collect())
+    }
+
+    /// Builds a witness for the given accounts and storages, and returns the state root.
+    fn witness(
+        accounts: &[(Address, Account)],
+        storages: &[(Address, Vec<(U256, U256)>)],
+    ) -> (B256, Vec<Bytes>) {
+        let mut state = Vec::new();
+        let mut account_leaves = BTreeMap::new();
+        for (address, account) in accounts {
+            let storage = storages
+                .iter()
+                .find(|(storage_address, _)| storage_address == address)
+                .map(|(_, slots)| {
+                    slots
+                        .iter()
+                        .map(|(slot, value)| {
+                            (
+                                keccak256(B256::from(*slot)),
+                                alloy_rlp::encode_fixed_size(value).to_vec(),
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>()
+                })
+                .unwrap_or_default();
+            let (storage_root, storage_nodes) = trie_nodes(&storage);
+            state.extend(storage_nodes);
+            account_leaves.insert(
+                keccak256(address),
+                alloy_rlp::encode(account.into_trie_account(storage_root)),
+            );
+        }
+        let (root, account_nodes) = trie_nodes(&account_leaves);
+        state.extend(account_nodes);
+        (root, state)
+    }
+
+    #[test]
+    fn reads_and_updates_witness_state() {
+        let alice = address!("0x1000000000000000000000000000000000000001");
+        let bob = address!("0x2000000000000000000000000000000000000002");
+        let contract = address!("0x3000000000000000000000000000000000000003");
+        let absent = address!("0x4000000000000000000000000000000000000004");
+
+        let code_hash = b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+        let accounts = [
+            (alice, Account { nonce: 1, balance: U256::from(100), bytecode_hash: None }),
+            (bob, Account { nonce: 0, balance: U256::from(5), bytecode_hash: None }),
+            (contract, Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(code_hash) }),
+        ];
+        let storage = vec![(U256::from(1), U256::from(10)), (U256::from(2), U256::from(20))];
+        let (pre_state_root, state) = witness(&accounts, &[(contract, storage)]);
+
+        let mut trie = StatelessTrie::new(&state, pre_state_root).unwrap();
+        assert_eq!(trie.account(alice).unwrap().unwrap().balance, U256::from(100));
+        assert_eq!(trie.account(absent).unwrap(), None);
+        assert_eq!(trie.storage(contract, U256::from(2)).unwrap(), U256::from(20));
+        assert_eq!(trie.storage(contract, U256::from(3)).unwrap(), U256::ZERO);
+        assert_eq!(trie.storage(bob, U256::from(1)).unwrap(), U256::ZERO);
+
+        // alice pays bob, the contract changes a slot and a new account is created
+        let post_accounts = [
+            (alice, Account { nonce: 2, balance: U256::from(60), bytecode_hash: None }),
+            (bob, Account { nonce: 0, balance: U256::from(45), bytecode_hash: None }),
+            (contract, accounts[2].1),
+            (absent, Account { nonce: 0, balance: U256::from(1), bytecode_hash: None }),
+        ];
+        let post_storage = vec![(U256::from(1), U256::from(10)), (U256::from(3), U256::from(30))];
+        let (expected_root, _) = witness(&post_accounts, &[(contract, post_storage)]);
+
+        let mut post_state = HashedPostState::default();
+        for (address, account) in &post_accounts[..2] {
+            post_state.accounts.insert(keccak256(address), Some(*account));
+        }
+        post_state.accounts.insert(keccak256(absent), Some(post_accounts[3].1));
+        post_state.storages.insert(
+            keccak256(contract),
+            HashedStorage::from_iter(
+                false,
+                [
+                    (keccak256(B256::from(U256::from(2))), U256::ZERO),
+                    (keccak256(B256::from(U256::from(3))), U256::from(30)),
+                ],
+            ),
+        );
+
+        assert_eq!(trie.calculate_state_root(post_state).unwrap(), expected_root);
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let alice = address!("0x1000000000000000000000000000000000000001");
+        let bob = address!("0x2000000000000000000000000000000000000002");
+        let accounts = [
+            (alice, Account { nonce: 1, balance: U256::from(100), bytecode_hash: None }),
+            (bob, Account { nonce: 0, balance: U256::from(5), bytecode_hash: None }),
+        ];
+        let (pre_state_root, mut state) = witness(&accounts, &[]);
+
+        // without the root node nothing can be revealed
+        assert!(matches!(
+            StatelessTrie::new(&[], pre_state_root),
+            Err(StatelessValidationError::MissingTrieNode(root)) if root == pre_state_root
+        ));
+
+        // drop the leaf of bob, only the root is left
+        state.retain(|node| keccak256(node) == pre_state_root);
+        let trie = StatelessTrie::new(&state, pre_state_root).unwrap();
+        assert!(matches!(
+            trie.account(bob),
+            Err(StatelessValidationError::MissingAccount(address)) if address == bob
+        ));
+    }
+}
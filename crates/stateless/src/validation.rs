@@ -0,0 +1,217 @@
+//! Stateless validation of a block against its execution witness.
+
+use crate::{StatelessTrie, WitnessDatabase};
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_ethereum_primitives::{Block, EthPrimitives};
+use reth_evm::{
+    execute::{BlockExecutionError, Executor},
+    ConfigureEvm,
+};
+use reth_execution_errors::{SparseStateTrieError, SparseTrieError};
+use reth_primitives_traits::{Block as _, SealedHeader};
+use reth_revm::state::Bytecode;
+use reth_trie_common::{HashedPostState, KeccakKeyHasher};
+use std::{fmt::Debug, sync::Arc};
+
+/// Errors that can occur during stateless validation of a block.
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessValidationError {
+    /// The witness doesn't contain a trie node that's needed to reveal the state.
+    #[error("witness is missing trie node {0}")]
+    MissingTrieNode(B256),
+    /// The witness doesn't prove the state of an account.
+    #[error("witness is missing account {0}")]
+    MissingAccount(Address),
+    /// The witness doesn't prove the value of a storage slot.
+    #[error("witness is missing storage slot {slot} of account {address}")]
+    MissingStorage {
+        /// The address of the account.
+        address: Address,
+        /// The storage slot.
+        slot: U256,
+    },
+    /// The storage trie of an account that's updated by the block isn't part of the witness.
+    #[error("witness is missing the storage trie of hashed account {0}")]
+    MissingStorageTrie(B256),
+    /// The witness doesn't contain a bytecode that's executed by the block.
+    #[error("witness is missing bytecode {0}")]
+    MissingBytecode(B256),
+    /// The witness doesn't contain the header of an ancestor whose hash is requested.
+    #[error("witness is missing the header of ancestor block {0}")]
+    MissingAncestorHeader(u64),
+    /// The witness doesn't contain the parent header of the block.
+    #[error("witness is missing the parent header {0}")]
+    MissingParentHeader(B256),
+    /// The witness headers don't form a chain.
+    #[error("witness header {hash} of block {number} isn't the parent of the next header")]
+    NonContiguousHeaders {
+        /// The number of the header.
+        number: u64,
+        /// The hash of the header.
+        hash: B256,
+    },
+    /// Failed to recover the senders of the block transactions.
+    #[error("failed to recover the transaction senders of the block")]
+    SenderRecovery,
+    /// Failed to decode a witness header or trie node.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// Failed to reveal or update the sparse trie.
+    #[error(transparent)]
+    SparseTrie(#[from] SparseTrieError),
+    /// Failed to reveal or update the sparse state trie.
+    #[error(transparent)]
+    SparseStateTrie(#[from] SparseStateTrieError),
+    /// The block is invalid according to the consensus rules.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// The block failed to execute.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// The state root after execution doesn't match the state root of the block.
+    #[error("state root mismatch: computed {computed}, block has {expected}")]
+    StateRootMismatch {
+        /// The state root computed from the witness.
+        computed: B256,
+        /// The state root in the block header.
+        expected: B256,
+    },
+}
+
+/// Validates a block against its execution witness, without access to any other state.
+///
+/// This
+/// 1. checks that the witness headers form a chain that ends at the parent of the block,
+/// 2. validates the block header against its parent and the block body,
+/// 3. reveals the pre-state from the witness state nodes at the parent state root,
+/// 4. executes the block against the witness state, bytecodes and ancestor hashes,
+/// 5. validates the execution outcome and compares the resulting state root with the block.
+///
+/// Returns the hash of the block if it's valid.
+pub fn stateless_validation<ChainSpec, E>(
+    block: Block,
+    witness: ExecutionWitness,
+    chain_spec: Arc<ChainSpec>,
+    evm_config: E,
+) -> Result<B256, StatelessValidationError>
+where
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
+    E: ConfigureEvm<Primitives = EthPrimitives>,
+{
+    let block = block.try_into_recovered().map_err(|_| StatelessValidationError::SenderRecovery)?;
+
+    let mut ancestors = witness
+        .headers
+        .iter()
+        .map(|header| Header::decode(&mut &header[..]).map(SealedHeader::seal_slow))
+        .collect::<Result<Vec<_>, _>>()?;
+    ancestors.sort_unstable_by_key(|header| header.number);
+
+    for window in ancestors.windows(2) {
+        let (header, child) = (&window[0], &window[1]);
+        if header.number + 1 != child.number || header.hash() != child.parent_hash {
+            return Err(StatelessValidationError::NonContiguousHeaders {
+                number: header.number,
+                hash: header.hash(),
+            })
+        }
+    }
+    let parent = ancestors
+        .last()
+        .filter(|parent| parent.hash() == block.parent_hash)
+        .ok_or(StatelessValidationError::MissingParentHeader(block.parent_hash))?;
+
+    let consensus = EthBeaconConsensus::new(chain_spec);
+    consensus.validate_header(block.sealed_header())?;
+    consensus.validate_header_against_parent(block.sealed_header(), parent)?;
+    consensus.validate_block_pre_execution(block.sealed_block())?;
+
+    let mut trie = StatelessTrie::new(&witness.state, parent.state_root)?;
+    let bytecodes = witness
+        .codes
+        .iter()
+        .map(|code| (keccak256(code), Bytecode::new_raw(code.clone())))
+        .collect();
+    let block_hashes = ancestors.iter().map(|header| (header.number, header.hash())).collect();
+
+    let db = WitnessDatabase::new(&trie, bytecodes, block_hashes);
+    let output = evm_config.executor(db).execute(&block)?;
+
+    <EthBeaconConsensus<ChainSpec> as FullConsensus<EthPrimitives>>::validate_block_post_execution(
+        &consensus,
+        &block,
+        &output.result,
+        None,
+    )?;
+
+    let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(&output.state.state);
+    let computed = trie.calculate_state_root(hashed_state)?;
+    if computed != block.state_root {
+        return Err(StatelessValidationError::StateRootMismatch {
+            computed,
+            expected: block.state_root,
+        })
+    }
+
+    Ok(block.hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::BlockBody;
+    use reth_evm_ethereum::EthEvmConfig;
+
+    fn encode(header: &Header) -> Bytes {
+        alloy_rlp::encode(header).into()
+    }
+
+    #[test]
+    fn rejects_witness_without_parent() {
+        let parent = Header { number: 9, ..Default::default() };
+        let block = Block {
+            header: Header { number: 10, parent_hash: parent.hash_slow(), ..Default::default() },
+            body: BlockBody::default(),
+        };
+
+        let witness = ExecutionWitness::default();
+        assert!(matches!(
+            stateless_validation(block.clone(), witness, MAINNET.clone(), EthEvmConfig::mainnet()),
+            Err(StatelessValidationError::MissingParentHeader(hash)) if hash == parent.hash_slow()
+        ));
+
+        let unrelated = Header { number: 9, gas_limit: 1, ..Default::default() };
+        let witness = ExecutionWitness { headers: vec![encode(&unrelated)], ..Default::default() };
+        assert!(matches!(
+            stateless_validation(block, witness, MAINNET.clone(), EthEvmConfig::mainnet()),
+            Err(StatelessValidationError::MissingParentHeader(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_contiguous_headers() {
+        let grandparent = Header { number: 8, ..Default::default() };
+        let parent = Header { number: 9, ..Default::default() };
+        let block = Block {
+            header: Header { number: 10, parent_hash: parent.hash_slow(), ..Default::default() },
+            body: BlockBody::default(),
+        };
+
+        let witness = ExecutionWitness {
+            headers: vec![encode(&parent), encode(&grandparent)],
+            ..Default::default()
+        };
+        assert!(matches!(
+            stateless_validation(block, witness, MAINNET.clone(), EthEvmConfig::mainnet()),
+            Err(StatelessValidationError::NonContiguousHeaders { number: 8, .. })
+        ));
+    }
+}
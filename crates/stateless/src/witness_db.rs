@@ -0,0 +1,77 @@
+//! Database over the contents of an execution witness.
+
+use crate::{StatelessTrie, StatelessValidationError};
+use alloy_primitives::{map::B256Map, Address, B256, U256};
+use reth_errors::ProviderError;
+use reth_revm::{
+    state::{AccountInfo, Bytecode},
+    Database,
+};
+use std::collections::BTreeMap;
+
+/// A [`Database`] that serves the state of a block from its execution witness.
+///
+/// Accounts and storage are read from the [`StatelessTrie`], bytecodes from the witness codes and
+/// block hashes from the witness headers. Reading anything that isn't part of the witness fails.
+#[derive(Debug)]
+pub struct WitnessDatabase<'a> {
+    /// The pre-state trie.
+    trie: &'a StatelessTrie,
+    /// Bytecodes by their hash.
+    bytecodes: B256Map<Bytecode>,
+    /// Hashes of the ancestor headers by block number.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl<'a> WitnessDatabase<'a> {
+    /// Creates a new database over the given trie, bytecodes and ancestor block hashes.
+    pub const fn new(
+        trie: &'a StatelessTrie,
+        bytecodes: B256Map<Bytecode>,
+        block_hashes: BTreeMap<u64, B256>,
+    ) -> Self {
+        Self { trie, bytecodes, block_hashes }
+    }
+}
+
+impl Database for WitnessDatabase<'_> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(account) = self.trie.account(address).map_err(witness_error)? else {
+            return Ok(None)
+        };
+
+        Ok(Some(AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.code_hash,
+            code: None,
+            ..Default::default()
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.bytecodes
+            .get(&code_hash)
+            .cloned()
+            .ok_or_else(|| witness_error(StatelessValidationError::MissingBytecode(code_hash)))
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.trie.storage(address, index).map_err(witness_error)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .ok_or_else(|| witness_error(StatelessValidationError::MissingAncestorHeader(number)))
+    }
+}
+
+/// Wraps an error about incomplete witness data into a [`ProviderError`], so that it's reported
+/// by the executor.
+fn witness_error(err: StatelessValidationError) -> ProviderError {
+    ProviderError::TrieWitnessError(err.to_string())
+}
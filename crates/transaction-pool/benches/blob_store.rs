@@ -0,0 +1,107 @@
+#![allow(missing_docs)]
+use alloy_eips::{
+    eip4844::{kzg_to_versioned_hash, Blob, Bytes48},
+    eip7594::{BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant, CELLS_PER_EXT_BLOB},
+};
+use alloy_primitives::{TxHash, B256};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use reth_transaction_pool::blobstore::{
+    BlobStore, DiskFileBlobStore, DiskFileBlobStoreConfig, SegmentedBlobStore,
+    SegmentedBlobStoreConfig,
+};
+use std::hint::black_box;
+
+/// Generates `num` single blob EIP-7594 sidecars with their versioned hashes.
+fn generate_sidecars(num: usize) -> (Vec<(TxHash, BlobTransactionSidecarVariant)>, Vec<B256>) {
+    (0..num)
+        .map(|_| {
+            let commitment = Bytes48::random();
+            let sidecar = BlobTransactionSidecarEip7594::new(
+                vec![Blob::random()],
+                vec![commitment],
+                vec![Bytes48::random(); CELLS_PER_EXT_BLOB],
+            );
+            (
+                (TxHash::random(), BlobTransactionSidecarVariant::Eip7594(sidecar)),
+                kzg_to_versioned_hash(commitment.as_slice()),
+            )
+        })
+        .unzip()
+}
+
+fn stores(dir: &tempfile::TempDir) -> Vec<(&'static str, Box<dyn BlobStore>)> {
+    // cache a single sidecar, so reads hit the disk
+    vec![
+        (
+            "disk file",
+            Box::new(
+                DiskFileBlobStore::open(
+                    dir.path().join("disk"),
+                    DiskFileBlobStoreConfig::default().with_max_cached_entries(1),
+                )
+                .unwrap(),
+            ),
+        ),
+        (
+            "segmented",
+            Box::new(
+                SegmentedBlobStore::open(
+                    dir.path().join("segmented"),
+                    SegmentedBlobStoreConfig::default().with_max_cached_entries(1),
+                )
+                .unwrap(),
+            ),
+        ),
+    ]
+}
+
+/// Benchmarks inserting sidecars one by one and removing them again, as the pool does for incoming
+/// blob transactions and finalized blocks.
+fn insert_and_cleanup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Blob Store");
+    let dir = tempfile::tempdir().unwrap();
+
+    for (name, store) in stores(&dir) {
+        group.bench_function(format!("{name} | insert 64 + cleanup"), |b| {
+            b.iter_batched(
+                || generate_sidecars(64).0,
+                |sidecars| {
+                    let txs = sidecars.iter().map(|(tx, _)| *tx).collect();
+                    for (tx, sidecar) in sidecars {
+                        store.insert(tx, sidecar).unwrap();
+                    }
+                    store.delete_all(txs).unwrap();
+                    black_box(store.cleanup());
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks `engine_getBlobsV2` style lookups by versioned hash.
+fn get_by_versioned_hashes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Blob Store");
+    let dir = tempfile::tempdir().unwrap();
+    let (sidecars, versioned_hashes) = generate_sidecars(256);
+
+    for (name, store) in stores(&dir) {
+        store.insert_all(sidecars.clone()).unwrap();
+        group.bench_function(format!("{name} | get 6 blobs by versioned hash"), |b| {
+            b.iter(|| {
+                for hashes in versioned_hashes.chunks(6) {
+                    black_box(store.get_by_versioned_hashes_v3(hashes).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = blob_store;
+    config = Criterion::default();
+    targets = insert_and_cleanup, get_by_versioned_hashes
+}
+criterion_main!(blob_store);
@@ -0,0 +1,132 @@
+#![allow(missing_docs)]
+use alloy_consensus::TxType;
+use alloy_primitives::Address;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use reth_transaction_pool::{
+    test_utils::{testing_pool, MockTransaction, MockTransactionSet, TestPool},
+    PoolTransaction, TransactionOrigin, TransactionPool,
+};
+use std::{
+    hint::black_box,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Generates `txs_per_sender` dependent transactions for each of `num_senders` senders.
+fn generate_transactions(num_senders: usize, txs_per_sender: usize) -> Vec<MockTransaction> {
+    (0..num_senders)
+        .flat_map(|_| {
+            MockTransactionSet::dependent(Address::random(), 0, txs_per_sender, TxType::Eip1559)
+                .into_vec()
+        })
+        .collect()
+}
+
+/// Spawns `threads` background threads that run `f` against the pool until the returned flag is
+/// set.
+fn spawn_load(
+    pool: &TestPool,
+    threads: usize,
+    f: impl Fn(&TestPool) + Send + Sync + Clone + 'static,
+) -> (Arc<AtomicBool>, Vec<thread::JoinHandle<()>>) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let handles = (0..threads)
+        .map(|_| {
+            let (pool, stop, f) = (pool.clone(), stop.clone(), f.clone());
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    f(&pool);
+                }
+            })
+        })
+        .collect();
+    (stop, handles)
+}
+
+fn stop_load((stop, handles): (Arc<AtomicBool>, Vec<thread::JoinHandle<()>>)) {
+    stop.store(true, Ordering::Relaxed);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+/// Benchmarks lookups by hash and sender while other threads keep taking `best_transactions`
+/// snapshots.
+fn lookups_under_best_transactions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Transaction Pool Contention");
+    group.measurement_time(Duration::from_secs(5));
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let pool = testing_pool();
+    let txs = generate_transactions(1000, 5);
+    let hashes = txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+    let senders = txs.iter().map(|tx| tx.sender()).collect::<Vec<_>>();
+    rt.block_on(pool.add_transactions(TransactionOrigin::External, txs));
+
+    let load = spawn_load(&pool, 2, |pool| {
+        black_box(pool.best_transactions().take(100).count());
+    });
+
+    group.bench_function("txpool | get by hash | readers: best_transactions", |b| {
+        b.iter(|| {
+            for hash in hashes.iter().step_by(10) {
+                black_box(pool.get(hash));
+            }
+        })
+    });
+    group.bench_function("txpool | get by sender | readers: best_transactions", |b| {
+        b.iter(|| {
+            for sender in senders.iter().step_by(50) {
+                black_box(pool.get_transactions_by_sender(*sender));
+            }
+        })
+    });
+
+    stop_load(load);
+    group.finish();
+}
+
+/// Benchmarks batched inserts while other threads keep looking up transactions by hash.
+fn batch_insert_under_lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Transaction Pool Contention");
+    group.measurement_time(Duration::from_secs(5));
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let pool = testing_pool();
+    let txs = generate_transactions(1000, 5);
+    let hashes = Arc::new(txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>());
+    rt.block_on(pool.add_transactions(TransactionOrigin::External, txs));
+
+    let load = spawn_load(&pool, 2, move |pool| {
+        for hash in hashes.iter().step_by(10) {
+            black_box(pool.get(hash));
+        }
+    });
+
+    group.bench_function("txpool | batch insert 100 | readers: get by hash", |b| {
+        b.iter_batched(
+            || generate_transactions(20, 5),
+            |txs| {
+                let hashes = txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+                rt.block_on(pool.add_transactions(TransactionOrigin::External, txs));
+                // keep the pool size stable across iterations
+                pool.remove_transactions(hashes);
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    stop_load(load);
+    group.finish();
+}
+
+criterion_group! {
+    name = contention;
+    config = Criterion::default();
+    targets = lookups_under_best_transactions, batch_insert_under_lookups
+}
+criterion_main!(contention);
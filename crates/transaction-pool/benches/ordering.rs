@@ -0,0 +1,136 @@
+#![allow(missing_docs)]
+use alloy_primitives::Address;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_transaction_pool::{
+    pool::PendingPool,
+    test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
+    ConfigurableOrdering, FifoFeeBucketOrdering, OrderingConfig, OrderingStrategy,
+    SenderCappedOrdering, TransactionOrdering, DEFAULT_FEE_BUCKET_SIZE,
+};
+
+/// Per-sender cap used by the capped orderings.
+const MAX_TXS_PER_SENDER: usize = 4;
+
+/// Generates `depth` eip1559 transactions for each of `senders` senders, with priority fees
+/// between 0 and 100 gwei.
+fn generate_transactions(senders: usize, depth: usize) -> Vec<MockTransaction> {
+    let mut runner = TestRunner::deterministic();
+    let fees = prop::collection::vec(0u128..100 * DEFAULT_FEE_BUCKET_SIZE, senders * depth)
+        .new_tree(&mut runner)
+        .unwrap()
+        .current();
+
+    let mut txs = Vec::with_capacity(senders * depth);
+    for idx in 0..senders {
+        let sender = Address::with_last_byte(0).create(idx as u64);
+        for nonce in 0..depth {
+            let tip = fees[idx * depth + nonce];
+            let tx = MockTransaction::eip1559()
+                .with_sender(sender)
+                .with_nonce(nonce as u64)
+                .with_priority_fee(tip)
+                .with_max_fee(tip + DEFAULT_FEE_BUCKET_SIZE);
+            txs.push(tx);
+        }
+    }
+    txs
+}
+
+/// Benchmarks insertion into the pending pool and draining its best transactions for every
+/// ordering strategy.
+fn txpool_ordering(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Transaction Pool Ordering");
+
+    for (senders, depth) in [(100, 16), (1_000, 4), (5_000, 1)] {
+        let txs = generate_transactions(senders, depth);
+
+        ordering_bench(&mut group, "CoinbaseTip", MockOrdering::default(), &txs, senders);
+        ordering_bench(
+            &mut group,
+            "FifoFeeBuckets",
+            FifoFeeBucketOrdering::new(DEFAULT_FEE_BUCKET_SIZE),
+            &txs,
+            senders,
+        );
+        ordering_bench(
+            &mut group,
+            "SenderCapped(CoinbaseTip)",
+            SenderCappedOrdering::new(MockOrdering::default(), MAX_TXS_PER_SENDER),
+            &txs,
+            senders,
+        );
+        ordering_bench(
+            &mut group,
+            "Configurable(CoinbaseTip)",
+            ConfigurableOrdering::default(),
+            &txs,
+            senders,
+        );
+        ordering_bench(
+            &mut group,
+            "Configurable(FifoFeeBuckets, capped)",
+            ConfigurableOrdering::new(OrderingConfig {
+                strategy: OrderingStrategy::FifoFeeBuckets { bucket_size: DEFAULT_FEE_BUCKET_SIZE },
+                max_transactions_per_sender: Some(MAX_TXS_PER_SENDER),
+            }),
+            &txs,
+            senders,
+        );
+    }
+}
+
+fn ordering_bench<O>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    description: &str,
+    ordering: O,
+    seed: &[MockTransaction],
+    senders: usize,
+) where
+    O: TransactionOrdering<Transaction = MockTransaction> + Clone,
+{
+    let setup = || {
+        let mut f = MockTransactionFactory::default();
+        seed.iter().map(|tx| f.validated_arc(tx.clone())).collect::<Vec<_>>()
+    };
+
+    let group_id = format!(
+        "txpool | total txs: {} | total senders: {} | {} | insert",
+        seed.len(),
+        senders,
+        description,
+    );
+    group.bench_function(group_id, |b| {
+        b.iter_with_setup(setup, |txs| {
+            let mut pool = PendingPool::new(ordering.clone());
+            for tx in txs {
+                pool.add_transaction(tx, 0);
+            }
+            pool
+        });
+    });
+
+    let mut pool = PendingPool::new(ordering);
+    for tx in setup() {
+        pool.add_transaction(tx, 0);
+    }
+
+    let group_id = format!(
+        "txpool | total txs: {} | total senders: {} | {} | best",
+        seed.len(),
+        senders,
+        description,
+    );
+    group.bench_function(group_id, |b| {
+        b.iter(|| pool.best().count());
+    });
+}
+
+criterion_group! {
+    name = ordering;
+    config = Criterion::default();
+    targets = txpool_ordering
+}
+criterion_main!(ordering);
@@ -9,6 +9,7 @@ pub use converter::BlobSidecarConverter;
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
+pub use segment::{SegmentedBlobStore, SegmentedBlobStoreConfig};
 use std::{
     fmt,
     sync::{
@@ -22,6 +23,7 @@ mod converter;
 pub mod disk;
 mod mem;
 mod noop;
+pub mod segment;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
@@ -50,7 +52,7 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// the number of successfully deleted blobs and the number of failed deletions.
     ///
     /// This is intended to be called in the background to clean up any old or unused data, in case
-    /// the store uses deferred cleanup: [`DiskFileBlobStore`]
+    /// the store uses deferred cleanup: [`DiskFileBlobStore`], [`SegmentedBlobStore`]
     fn cleanup(&self) -> BlobStoreCleanupStat;
 
     /// Retrieves the decoded blob data for the given transaction hash.
@@ -0,0 +1,975 @@
+//! A blob store that appends blobs to rolling segment files.
+
+use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use alloy_eips::{
+    eip4844::{
+        Blob, BlobAndProofV1, BlobAndProofV2, BlobTransactionSidecar, Bytes48, BYTES_PER_BLOB,
+    },
+    eip7594::{BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant, CELLS_PER_EXT_BLOB},
+};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    TxHash, B256,
+};
+use parking_lot::{Mutex, RwLock};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, trace};
+
+/// How many [`BlobTransactionSidecarVariant`] to cache in memory.
+pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
+
+/// The default size at which the active segment is sealed and a new one is started.
+pub const DEFAULT_MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The default share of live data, in percent, below which sealed segments are compacted.
+pub const DEFAULT_MIN_LIVE_PERCENT: u8 = 50;
+
+/// Length of the header of a record: tx hash, kind, number of blobs and proofs per blob.
+const RECORD_HEADER_LEN: u64 = 32 + 1 + 4 + 4;
+
+/// Length of a KZG commitment or proof.
+const BYTES_PER_COMMITMENT: u64 = 48;
+
+/// A blob store that appends blob sidecars to rolling segment files.
+///
+/// Unlike [`DiskFileBlobStore`](super::DiskFileBlobStore), which writes one file per transaction,
+/// sidecars are appended to a single active segment file until it reaches
+/// [`SegmentedBlobStoreConfig::max_segment_size`], after which it is sealed and a new segment is
+/// started. The location of every sidecar and of every blob by its versioned hash is kept in an
+/// in-memory index.
+///
+/// Each blob is stored next to its proofs (a single proof for EIP-4844 sidecars, all cell proofs
+/// for EIP-7594 sidecars), so blobs requested by versioned hash are served with a single read
+/// without decoding the whole sidecar.
+///
+/// Deletions are deferred until [`BlobStore::cleanup`], which the pool maintenance task calls in
+/// the background when blobs are finalized. Cleanup also compacts sealed segments whose share of
+/// live data dropped below [`SegmentedBlobStoreConfig::min_live_percent`], by moving their live
+/// sidecars to the active segment and removing the segment file.
+#[derive(Clone, Debug)]
+pub struct SegmentedBlobStore {
+    inner: Arc<SegmentedBlobStoreInner>,
+}
+
+impl SegmentedBlobStore {
+    /// Opens and initializes a new segmented blob store in the given directory.
+    ///
+    /// Any existing content of the directory is removed.
+    pub fn open(
+        blob_dir: impl Into<PathBuf>,
+        config: SegmentedBlobStoreConfig,
+    ) -> Result<Self, SegmentedBlobStoreError> {
+        let blob_dir = blob_dir.into();
+        match fs::remove_dir_all(&blob_dir) {
+            Ok(_) => {
+                debug!(target:"txpool::blob", ?blob_dir, "Removed blob store directory");
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(SegmentedBlobStoreError::Open(blob_dir, err)),
+        }
+        fs::create_dir_all(&blob_dir)
+            .map_err(|err| SegmentedBlobStoreError::Open(blob_dir.clone(), err))?;
+
+        let active = ActiveSegment::create(&blob_dir, 0)?;
+        let mut index = Index::default();
+        index.segments.insert(0, SegmentUsage::default());
+
+        Ok(Self {
+            inner: Arc::new(SegmentedBlobStoreInner {
+                blob_cache: Mutex::new(LruMap::new(ByLength::new(config.max_cached_entries))),
+                blob_dir,
+                config,
+                index: RwLock::new(index),
+                active: Mutex::new(active),
+                segment_lock: RwLock::new(()),
+                txs_to_delete: Default::default(),
+                size_tracker: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the number of segment files.
+    pub fn segments_len(&self) -> usize {
+        self.inner.index.read().segments.len()
+    }
+}
+
+impl BlobStore for SegmentedBlobStore {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
+        RecordLayout::of(tx, &data)?;
+        self.inner.insert_many(vec![(tx, data)])
+    }
+
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        self.inner.insert_many(txs)
+    }
+
+    fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
+        if self.inner.index.read().txs.contains_key(&tx) {
+            self.inner.txs_to_delete.write().insert(tx);
+        }
+        Ok(())
+    }
+
+    fn delete_all(&self, txs: Vec<B256>) -> Result<(), BlobStoreError> {
+        if txs.is_empty() {
+            return Ok(())
+        }
+        let txs = {
+            let index = self.inner.index.read();
+            txs.into_iter().filter(|tx| index.txs.contains_key(tx)).collect::<Vec<_>>()
+        };
+        self.inner.txs_to_delete.write().extend(txs);
+        Ok(())
+    }
+
+    fn cleanup(&self) -> BlobStoreCleanupStat {
+        let stat = self.inner.remove_deleted();
+        self.inner.compact();
+        stat
+    }
+
+    fn get(&self, tx: B256) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        self.inner.get_one(tx)
+    }
+
+    fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
+        Ok(self.inner.index.read().txs.contains_key(&tx))
+    }
+
+    fn get_all(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<(B256, Arc<BlobTransactionSidecarVariant>)>, BlobStoreError> {
+        let mut res = Vec::with_capacity(txs.len());
+        for tx in txs {
+            if let Some(sidecar) = self.inner.get_one(tx)? {
+                res.push((tx, sidecar));
+            }
+        }
+        Ok(res)
+    }
+
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        txs.into_iter()
+            .map(|tx| self.inner.get_one(tx)?.ok_or(BlobStoreError::MissingSidecar(tx)))
+            .collect()
+    }
+
+    fn get_by_versioned_hashes_v1(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        versioned_hashes
+            .iter()
+            .map(|hash| {
+                Ok(self.inner.blob_and_proofs(*hash, SidecarKind::Eip4844)?.and_then(
+                    |BlobAndProofV2 { blob, proofs }| {
+                        proofs.into_iter().next().map(|proof| BlobAndProofV1 { blob, proof })
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        // only return the blobs if we found all requested versioned hashes
+        Ok(self.get_by_versioned_hashes_v3(versioned_hashes)?.into_iter().collect())
+    }
+
+    fn get_by_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        versioned_hashes
+            .iter()
+            .map(|hash| Ok(self.inner.blob_and_proofs(*hash, SidecarKind::Eip7594)?))
+            .collect()
+    }
+
+    fn data_size_hint(&self) -> Option<usize> {
+        Some(self.inner.size_tracker.data_size())
+    }
+
+    fn blobs_len(&self) -> usize {
+        self.inner.size_tracker.blobs_len()
+    }
+}
+
+struct SegmentedBlobStoreInner {
+    blob_dir: PathBuf,
+    config: SegmentedBlobStoreConfig,
+    blob_cache: Mutex<LruMap<TxHash, Arc<BlobTransactionSidecarVariant>, ByLength>>,
+    /// Locations of all stored sidecars.
+    ///
+    /// Lock order: `active` before `index`, `segment_lock` before `index`.
+    index: RwLock<Index>,
+    /// The segment new sidecars are appended to.
+    active: Mutex<ActiveSegment>,
+    /// Held for reading while reading from segment files, and for writing while removing them.
+    segment_lock: RwLock<()>,
+    txs_to_delete: RwLock<B256Set>,
+    size_tracker: BlobStoreSize,
+}
+
+impl SegmentedBlobStoreInner {
+    /// Returns the path of the segment file with the given id.
+    fn segment_file(&self, segment: u64) -> PathBuf {
+        segment_file(&self.blob_dir, segment)
+    }
+
+    /// Appends the sidecars that aren't stored yet to the active segment.
+    ///
+    /// Sidecars with an invalid layout are skipped.
+    fn insert_many(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
+        let mut records = Vec::with_capacity(txs.len());
+        {
+            let index = self.index.read();
+            for (tx, data) in &txs {
+                if index.txs.contains_key(tx) {
+                    trace!(target:"txpool::blob", ?tx, "Blob already exists");
+                    continue
+                }
+                let layout = match RecordLayout::of(*tx, data) {
+                    Ok(layout) => layout,
+                    Err(err) => {
+                        debug!(target:"txpool::blob", %err, "Failed to store blob");
+                        continue
+                    }
+                };
+                records.push((*tx, layout, encode_record(*tx, &layout, data), data));
+            }
+        }
+
+        let mut add = 0;
+        let mut num = 0;
+        let mut res: Result<(), SegmentedBlobStoreError> = Ok(());
+        {
+            let mut active = self.active.lock();
+            for (tx, layout, record, data) in records {
+                match self.append(&mut active, tx, layout, &record, None, Some(data)) {
+                    Ok(true) => {
+                        add += record.len();
+                        num += 1;
+                    }
+                    Ok(false) => {}
+                    Err(err) => {
+                        res = Err(err);
+                        break
+                    }
+                }
+            }
+        }
+        self.size_tracker.add_size(add);
+        self.size_tracker.inc_len(num);
+        res?;
+
+        let mut cache = self.blob_cache.lock();
+        for (tx, data) in txs {
+            cache.insert(tx, Arc::new(data));
+        }
+        Ok(())
+    }
+
+    /// Appends a record to the active segment and updates the index.
+    ///
+    /// If `replaces` is set, the record is a relocated copy of that record and only replaces it if
+    /// it is still stored there. Returns whether the index now points to the appended record.
+    fn append(
+        &self,
+        active: &mut ActiveSegment,
+        tx: TxHash,
+        layout: RecordLayout,
+        record: &[u8],
+        replaces: Option<RecordLocation>,
+        sidecar: Option<&BlobTransactionSidecarVariant>,
+    ) -> Result<bool, SegmentedBlobStoreError> {
+        let len = record.len() as u64;
+        if active.size > 0 && active.size + len > self.config.max_segment_size {
+            let next = ActiveSegment::create(&self.blob_dir, active.id + 1)?;
+            debug!(target:"txpool::blob", sealed = active.id, active = next.id, "Rolled blob store segment");
+            *active = next;
+            self.index.write().segments.insert(active.id, SegmentUsage::default());
+        }
+        active
+            .file
+            .write_all(record)
+            .map_err(|err| SegmentedBlobStoreError::WriteSegment(active.id, err))?;
+        let location = RecordLocation { segment: active.id, offset: active.size, layout };
+        active.size += len;
+
+        let mut index = self.index.write();
+        index.segments.entry(active.id).or_default().size += len;
+        let stored = match replaces {
+            None => !index.txs.contains_key(&tx),
+            Some(old) => index.txs.get(&tx) == Some(&old),
+        };
+        if !stored {
+            return Ok(false)
+        }
+
+        if let Some(old) = replaces &&
+            let Some(usage) = index.segments.get_mut(&old.segment)
+        {
+            usage.live -= len;
+        }
+        index.segments.entry(active.id).or_default().live += len;
+        index.txs.insert(tx, location);
+        if let Some(sidecar) = sidecar {
+            for (blob, hash) in sidecar.versioned_hashes().enumerate() {
+                index.versioned_hashes.insert(hash, (tx, blob as u32));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Removes all sidecars that were marked for deletion from the index.
+    fn remove_deleted(&self) -> BlobStoreCleanupStat {
+        let txs_to_delete = std::mem::take(&mut *self.txs_to_delete.write());
+        let mut stat = BlobStoreCleanupStat::default();
+        let mut subsize = 0;
+        debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from index");
+        {
+            let mut index = self.index.write();
+            let Index { txs, versioned_hashes, segments } = &mut *index;
+            for tx in &txs_to_delete {
+                let Some(location) = txs.remove(tx) else { continue };
+                let len = location.layout.len();
+                if let Some(usage) = segments.get_mut(&location.segment) {
+                    usage.live -= len;
+                }
+                subsize += len as usize;
+                stat.delete_succeed += 1;
+            }
+            versioned_hashes.retain(|_, (tx, _)| !txs_to_delete.contains(tx));
+        }
+
+        let mut cache = self.blob_cache.lock();
+        for tx in &txs_to_delete {
+            cache.remove(tx);
+        }
+
+        self.size_tracker.sub_size(subsize);
+        self.size_tracker.sub_len(stat.delete_succeed);
+        stat
+    }
+
+    /// Compacts sealed segments whose share of live data is below the configured minimum.
+    ///
+    /// Live records of these segments are moved to the active segment, after which the segment
+    /// files are removed.
+    fn compact(&self) {
+        let active = self.active.lock().id;
+        let min_live_percent = self.config.min_live_percent as u64;
+        let candidates = {
+            let index = self.index.read();
+            let mut candidates = index
+                .segments
+                .iter()
+                .filter(|(segment, usage)| {
+                    **segment != active && usage.live * 100 < usage.size * min_live_percent
+                })
+                .map(|(segment, _)| (*segment, Vec::new()))
+                .collect::<BTreeMap<_, _>>();
+            for (tx, location) in &index.txs {
+                if let Some(records) = candidates.get_mut(&location.segment) {
+                    records.push((*tx, *location));
+                }
+            }
+            candidates
+        };
+
+        for (segment, records) in candidates {
+            let moved = records.len();
+            if let Err(err) = self.relocate(records) {
+                debug!(target:"txpool::blob", %err, segment, "Failed to compact blob store segment");
+                continue
+            }
+
+            {
+                let _lock = self.segment_lock.write();
+                let mut index = self.index.write();
+                if index.txs.values().any(|location| location.segment == segment) {
+                    continue
+                }
+                index.segments.remove(&segment);
+            }
+            let path = self.segment_file(segment);
+            if let Err(err) = fs::remove_file(&path) {
+                let err = SegmentedBlobStoreError::DeleteSegment(segment, err);
+                debug!(target:"txpool::blob", %err, ?path);
+            }
+            debug!(target:"txpool::blob", segment, moved, "Compacted blob store segment");
+        }
+    }
+
+    /// Moves the given records to the active segment.
+    fn relocate(
+        &self,
+        records: Vec<(TxHash, RecordLocation)>,
+    ) -> Result<(), SegmentedBlobStoreError> {
+        for (tx, location) in records {
+            let record = {
+                let _lock = self.segment_lock.read();
+                self.read_at(location.segment, location.offset, location.layout.len())?
+            };
+            let mut active = self.active.lock();
+            self.append(&mut active, tx, location.layout, &record, Some(location), None)?;
+        }
+        Ok(())
+    }
+
+    /// Reads `len` bytes at `offset` of the segment.
+    ///
+    /// The caller must hold the `segment_lock` for reading.
+    fn read_at(
+        &self,
+        segment: u64,
+        offset: u64,
+        len: u64,
+    ) -> Result<Vec<u8>, SegmentedBlobStoreError> {
+        let read = || {
+            let mut file = File::open(self.segment_file(segment))?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut buf = vec![0; len as usize];
+            file.read_exact(&mut buf)?;
+            Ok(buf)
+        };
+        read().map_err(|err| SegmentedBlobStoreError::ReadSegment(segment, err))
+    }
+
+    /// Retrieves the sidecar for the given transaction hash from the blob cache or its segment.
+    fn get_one(
+        &self,
+        tx: B256,
+    ) -> Result<Option<Arc<BlobTransactionSidecarVariant>>, BlobStoreError> {
+        if let Some(blob) = self.blob_cache.lock().get(&tx) {
+            return Ok(Some(blob.clone()))
+        }
+
+        let record = {
+            let _lock = self.segment_lock.read();
+            let Some(location) = self.index.read().txs.get(&tx).copied() else { return Ok(None) };
+            self.read_at(location.segment, location.offset, location.layout.len())?
+        };
+        let sidecar = Arc::new(decode_record(tx, &record)?);
+        self.blob_cache.lock().insert(tx, sidecar.clone());
+        Ok(Some(sidecar))
+    }
+
+    /// Returns the blob with the given versioned hash and its proofs, if it is stored in a sidecar
+    /// of the given kind.
+    fn blob_and_proofs(
+        &self,
+        versioned_hash: B256,
+        kind: SidecarKind,
+    ) -> Result<Option<BlobAndProofV2>, SegmentedBlobStoreError> {
+        let _lock = self.segment_lock.read();
+        let Some((location, blob)) = ({
+            let index = self.index.read();
+            index
+                .versioned_hashes
+                .get(&versioned_hash)
+                .and_then(|(tx, blob)| index.txs.get(tx).map(|location| (*location, *blob)))
+        }) else {
+            return Ok(None)
+        };
+        let layout = location.layout;
+        if layout.kind != kind || blob >= layout.num_blobs {
+            return Ok(None)
+        }
+
+        let entry = self.read_at(
+            location.segment,
+            location.offset + layout.blob_offset(blob),
+            layout.blob_entry_len(),
+        )?;
+        let (blob, proofs) = entry.split_at(BYTES_PER_BLOB);
+        let proofs = proofs.chunks_exact(BYTES_PER_COMMITMENT as usize).map(Bytes48::from_slice);
+        Ok(Some(BlobAndProofV2 {
+            blob: Box::new(Blob::from_slice(blob)),
+            proofs: proofs.collect(),
+        }))
+    }
+}
+
+impl fmt::Debug for SegmentedBlobStoreInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SegmentedBlobStoreInner")
+            .field("blob_dir", &self.blob_dir)
+            .field("config", &self.config)
+            .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
+            .field("segments", &self.index.try_read().map(|index| index.segments.len()))
+            .field("txs_to_delete", &self.txs_to_delete.try_read())
+            .finish()
+    }
+}
+
+/// Returns the path of the segment file with the given id in `blob_dir`.
+fn segment_file(blob_dir: &Path, segment: u64) -> PathBuf {
+    blob_dir.join(format!("{segment:010}.seg"))
+}
+
+/// The segment that sidecars are appended to.
+#[derive(Debug)]
+struct ActiveSegment {
+    id: u64,
+    file: File,
+    size: u64,
+}
+
+impl ActiveSegment {
+    /// Creates the segment file with the given id.
+    fn create(blob_dir: &Path, id: u64) -> Result<Self, SegmentedBlobStoreError> {
+        let file = OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(segment_file(blob_dir, id))
+            .map_err(|err| SegmentedBlobStoreError::WriteSegment(id, err))?;
+        Ok(Self { id, file, size: 0 })
+    }
+}
+
+/// The in-memory index of a [`SegmentedBlobStore`].
+#[derive(Debug, Default)]
+struct Index {
+    /// Locations of the stored sidecars.
+    txs: B256Map<RecordLocation>,
+    /// The transaction and index of each stored blob by its versioned hash.
+    ///
+    /// Note: a blob can appear in multiple transactions but this only tracks the most recent one.
+    versioned_hashes: B256Map<(TxHash, u32)>,
+    /// Usage of all segment files, by segment id.
+    segments: BTreeMap<u64, SegmentUsage>,
+}
+
+/// Number of bytes written to a segment and how many of them belong to stored sidecars.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SegmentUsage {
+    size: u64,
+    live: u64,
+}
+
+/// The location of a sidecar record in a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RecordLocation {
+    segment: u64,
+    offset: u64,
+    layout: RecordLayout,
+}
+
+/// The kind of a stored sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SidecarKind {
+    /// An EIP-4844 sidecar with a single proof per blob.
+    Eip4844 = 0,
+    /// An EIP-7594 sidecar with the cell proofs of each blob.
+    Eip7594 = 1,
+}
+
+/// The layout of a sidecar record.
+///
+/// ```text
+/// tx hash (32) | kind (1) | blobs (4) | proofs per blob (4)
+/// commitments (48 * blobs)
+/// blob 0 (131072) | proofs of blob 0 (48 * proofs per blob)
+/// ...
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RecordLayout {
+    kind: SidecarKind,
+    num_blobs: u32,
+    proofs_per_blob: u32,
+}
+
+impl RecordLayout {
+    /// Returns the layout of the given sidecar.
+    ///
+    /// Fails if the sidecar doesn't have a commitment and the expected number of proofs for each
+    /// blob: a single proof for EIP-4844 sidecars and [`CELLS_PER_EXT_BLOB`] cell proofs for
+    /// EIP-7594 sidecars.
+    fn of(tx: TxHash, sidecar: &BlobTransactionSidecarVariant) -> Result<Self, BlobStoreError> {
+        let (kind, blobs, commitments, proofs, proofs_per_blob) = match sidecar {
+            BlobTransactionSidecarVariant::Eip4844(sidecar) => {
+                (SidecarKind::Eip4844, &sidecar.blobs, &sidecar.commitments, &sidecar.proofs, 1)
+            }
+            BlobTransactionSidecarVariant::Eip7594(sidecar) => (
+                SidecarKind::Eip7594,
+                &sidecar.blobs,
+                &sidecar.commitments,
+                &sidecar.cell_proofs,
+                CELLS_PER_EXT_BLOB,
+            ),
+        };
+        let num_blobs = blobs.len();
+        if commitments.len() != num_blobs || proofs.len() != num_blobs * proofs_per_blob {
+            return Err(SegmentedBlobStoreError::InvalidSidecar(tx).into())
+        }
+        Ok(Self { kind, num_blobs: num_blobs as u32, proofs_per_blob: proofs_per_blob as u32 })
+    }
+
+    /// Returns the length of a blob with its proofs.
+    const fn blob_entry_len(&self) -> u64 {
+        BYTES_PER_BLOB as u64 + self.proofs_per_blob as u64 * BYTES_PER_COMMITMENT
+    }
+
+    /// Returns the offset of the given blob in the record.
+    const fn blob_offset(&self, blob: u32) -> u64 {
+        RECORD_HEADER_LEN +
+            self.num_blobs as u64 * BYTES_PER_COMMITMENT +
+            blob as u64 * self.blob_entry_len()
+    }
+
+    /// Returns the length of the record.
+    const fn len(&self) -> u64 {
+        self.blob_offset(self.num_blobs)
+    }
+}
+
+/// Encodes the sidecar as a record with the given layout.
+fn encode_record(
+    tx: TxHash,
+    layout: &RecordLayout,
+    sidecar: &BlobTransactionSidecarVariant,
+) -> Vec<u8> {
+    let (blobs, commitments, proofs) = match sidecar {
+        BlobTransactionSidecarVariant::Eip4844(sidecar) => {
+            (&sidecar.blobs, &sidecar.commitments, &sidecar.proofs)
+        }
+        BlobTransactionSidecarVariant::Eip7594(sidecar) => {
+            (&sidecar.blobs, &sidecar.commitments, &sidecar.cell_proofs)
+        }
+    };
+    let mut buf = Vec::with_capacity(layout.len() as usize);
+    buf.extend_from_slice(tx.as_slice());
+    buf.push(layout.kind as u8);
+    buf.extend_from_slice(&layout.num_blobs.to_be_bytes());
+    buf.extend_from_slice(&layout.proofs_per_blob.to_be_bytes());
+    for commitment in commitments {
+        buf.extend_from_slice(commitment.as_slice());
+    }
+    for (blob, proofs) in blobs.iter().zip(proofs.chunks(layout.proofs_per_blob as usize)) {
+        buf.extend_from_slice(blob.as_slice());
+        for proof in proofs {
+            buf.extend_from_slice(proof.as_slice());
+        }
+    }
+    buf
+}
+
+/// Decodes the sidecar of the given transaction from its record.
+fn decode_record(
+    tx: TxHash,
+    record: &[u8],
+) -> Result<BlobTransactionSidecarVariant, SegmentedBlobStoreError> {
+    let corrupt = || SegmentedBlobStoreError::CorruptRecord(tx);
+    if record.len() < RECORD_HEADER_LEN as usize || record[..32] != tx[..] {
+        return Err(corrupt())
+    }
+    let kind = match record[32] {
+        0 => SidecarKind::Eip4844,
+        1 => SidecarKind::Eip7594,
+        _ => return Err(corrupt()),
+    };
+    let num_blobs = u32::from_be_bytes(record[33..37].try_into().expect("4 bytes"));
+    let proofs_per_blob = u32::from_be_bytes(record[37..41].try_into().expect("4 bytes"));
+    let layout = RecordLayout { kind, num_blobs, proofs_per_blob };
+    if record.len() as u64 != layout.len() {
+        return Err(corrupt())
+    }
+
+    let commitments_end = layout.blob_offset(0) as usize;
+    let commitments = record[RECORD_HEADER_LEN as usize..commitments_end]
+        .chunks_exact(BYTES_PER_COMMITMENT as usize)
+        .map(Bytes48::from_slice)
+        .collect();
+    let mut blobs = Vec::with_capacity(num_blobs as usize);
+    let mut proofs = Vec::with_capacity((num_blobs * proofs_per_blob) as usize);
+    for entry in record[commitments_end..].chunks_exact(layout.blob_entry_len() as usize) {
+        let (blob, blob_proofs) = entry.split_at(BYTES_PER_BLOB);
+        blobs.push(Blob::from_slice(blob));
+        proofs.extend(
+            blob_proofs.chunks_exact(BYTES_PER_COMMITMENT as usize).map(Bytes48::from_slice),
+        );
+    }
+
+    Ok(match kind {
+        SidecarKind::Eip4844 => BlobTransactionSidecarVariant::Eip4844(
+            BlobTransactionSidecar::new(blobs, commitments, proofs),
+        ),
+        SidecarKind::Eip7594 => BlobTransactionSidecarVariant::Eip7594(
+            BlobTransactionSidecarEip7594::new(blobs, commitments, proofs),
+        ),
+    })
+}
+
+/// Errors that can occur when interacting with a segmented blob store.
+#[derive(Debug, thiserror::Error)]
+pub enum SegmentedBlobStoreError {
+    /// Thrown during [`SegmentedBlobStore::open`] if the blob store directory cannot be opened.
+    #[error("failed to open blobstore at {0}: {1}")]
+    Open(PathBuf, io::Error),
+    /// Failure while reading from a segment file.
+    #[error("failed to read blob store segment {0}: {1}")]
+    ReadSegment(u64, io::Error),
+    /// Failure while writing to a segment file.
+    #[error("failed to write blob store segment {0}: {1}")]
+    WriteSegment(u64, io::Error),
+    /// Failure while deleting a segment file.
+    #[error("failed to delete blob store segment {0}: {1}")]
+    DeleteSegment(u64, io::Error),
+    /// The sidecar doesn't have a commitment and the expected number of proofs for each blob.
+    #[error("[{0}] sidecar has an inconsistent number of commitments or proofs")]
+    InvalidSidecar(TxHash),
+    /// The stored record of a sidecar is corrupt.
+    #[error("[{0}] stored blob sidecar is corrupt")]
+    CorruptRecord(TxHash),
+}
+
+impl From<SegmentedBlobStoreError> for BlobStoreError {
+    fn from(value: SegmentedBlobStoreError) -> Self {
+        Self::Other(Box::new(value))
+    }
+}
+
+/// Configuration for a segmented blob store.
+#[derive(Debug, Clone)]
+pub struct SegmentedBlobStoreConfig {
+    /// The maximum number of sidecars to keep in the in memory blob cache.
+    pub max_cached_entries: u32,
+    /// The size in bytes at which the active segment is sealed and a new one is started.
+    pub max_segment_size: u64,
+    /// Sealed segments with less live data than this percentage of their size are compacted.
+    pub min_live_percent: u8,
+}
+
+impl Default for SegmentedBlobStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            max_segment_size: DEFAULT_MAX_SEGMENT_SIZE,
+            min_live_percent: DEFAULT_MIN_LIVE_PERCENT,
+        }
+    }
+}
+
+impl SegmentedBlobStoreConfig {
+    /// Set maximum number of sidecars to keep in the in memory blob cache.
+    pub const fn with_max_cached_entries(mut self, max_cached_entries: u32) -> Self {
+        self.max_cached_entries = max_cached_entries;
+        self
+    }
+
+    /// Set the size at which the active segment is sealed.
+    pub const fn with_max_segment_size(mut self, max_segment_size: u64) -> Self {
+        self.max_segment_size = max_segment_size;
+        self
+    }
+
+    /// Set the share of live data, in percent, below which sealed segments are compacted.
+    pub const fn with_min_live_percent(mut self, min_live_percent: u8) -> Self {
+        self.min_live_percent = min_live_percent;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::kzg_to_versioned_hash;
+
+    fn versioned_hashes(commitments: &[Bytes48]) -> Vec<B256> {
+        commitments.iter().map(|commitment| kzg_to_versioned_hash(commitment.as_slice())).collect()
+    }
+
+    fn tmp_store(config: SegmentedBlobStoreConfig) -> (SegmentedBlobStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SegmentedBlobStore::open(dir.path(), config).unwrap();
+        (store, dir)
+    }
+
+    fn eip4844_sidecar(num_blobs: usize) -> BlobTransactionSidecarVariant {
+        BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::new(
+            (0..num_blobs).map(|_| Blob::random()).collect(),
+            (0..num_blobs).map(|_| Bytes48::random()).collect(),
+            (0..num_blobs).map(|_| Bytes48::random()).collect(),
+        ))
+    }
+
+    fn eip7594_sidecar(num_blobs: usize) -> BlobTransactionSidecarVariant {
+        BlobTransactionSidecarVariant::Eip7594(BlobTransactionSidecarEip7594::new(
+            (0..num_blobs).map(|_| Blob::random()).collect(),
+            (0..num_blobs).map(|_| Bytes48::random()).collect(),
+            (0..num_blobs * CELLS_PER_EXT_BLOB).map(|_| Bytes48::random()).collect(),
+        ))
+    }
+
+    #[test]
+    fn record_roundtrip() {
+        for sidecar in [eip4844_sidecar(2), eip7594_sidecar(3), eip4844_sidecar(0)] {
+            let tx = TxHash::random();
+            let layout = RecordLayout::of(tx, &sidecar).unwrap();
+            let record = encode_record(tx, &layout, &sidecar);
+            assert_eq!(record.len() as u64, layout.len());
+            assert_eq!(decode_record(tx, &record).unwrap(), sidecar);
+            assert!(decode_record(TxHash::random(), &record).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_inconsistent_sidecar() {
+        let (store, _dir) = tmp_store(Default::default());
+        let sidecar = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::new(
+            vec![Blob::random()],
+            vec![Bytes48::random()],
+            vec![],
+        ));
+        assert!(store.insert(TxHash::random(), sidecar).is_err());
+        assert_eq!(store.blobs_len(), 0);
+    }
+
+    #[test]
+    fn insert_get_delete() {
+        let (store, _dir) = tmp_store(Default::default());
+        let txs = (0..3).map(|_| (TxHash::random(), eip7594_sidecar(2))).collect::<Vec<_>>();
+        store.insert_all(txs.clone()).unwrap();
+        // inserting again doesn't store duplicates
+        store.insert(txs[0].0, txs[0].1.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 3);
+        let size = store.data_size_hint().unwrap();
+
+        store.inner.blob_cache.lock().clear();
+        for (tx, sidecar) in &txs {
+            assert!(store.contains(*tx).unwrap());
+            assert_eq!(*store.get(*tx).unwrap().unwrap(), *sidecar);
+        }
+        assert_eq!(store.get_exact(txs.iter().map(|(tx, _)| *tx).collect()).unwrap().len(), 3);
+
+        store.delete(txs[0].0).unwrap();
+        // deletion is deferred until cleanup
+        assert!(store.contains(txs[0].0).unwrap());
+        let stat = store.cleanup();
+        assert_eq!(stat.delete_succeed, 1);
+        assert!(!store.contains(txs[0].0).unwrap());
+        assert!(store.get(txs[0].0).unwrap().is_none());
+        assert_eq!(store.blobs_len(), 2);
+        assert_eq!(store.data_size_hint().unwrap(), size / 3 * 2);
+    }
+
+    #[test]
+    fn get_by_versioned_hashes() {
+        let (store, _dir) = tmp_store(Default::default());
+        let legacy = eip4844_sidecar(2);
+        let cell = eip7594_sidecar(2);
+        store
+            .insert_all(vec![(TxHash::random(), legacy.clone()), (TxHash::random(), cell.clone())])
+            .unwrap();
+
+        let BlobTransactionSidecarVariant::Eip4844(legacy) = legacy else { unreachable!() };
+        let BlobTransactionSidecarVariant::Eip7594(cell) = cell else { unreachable!() };
+        let legacy_hashes = versioned_hashes(&legacy.commitments);
+        let cell_hashes = versioned_hashes(&cell.commitments);
+
+        let v1 = store
+            .get_by_versioned_hashes_v1(&[legacy_hashes[1], cell_hashes[0], B256::random()])
+            .unwrap();
+        assert_eq!(
+            v1,
+            vec![
+                Some(BlobAndProofV1 { blob: Box::new(legacy.blobs[1]), proof: legacy.proofs[1] }),
+                None,
+                None
+            ]
+        );
+
+        let expected = BlobAndProofV2 {
+            blob: Box::new(cell.blobs[1]),
+            proofs: cell.cell_proofs[CELLS_PER_EXT_BLOB..].to_vec(),
+        };
+        let v3 = store.get_by_versioned_hashes_v3(&[cell_hashes[1], legacy_hashes[0]]).unwrap();
+        assert_eq!(v3, vec![Some(expected.clone()), None]);
+        assert_eq!(
+            store.get_by_versioned_hashes_v2(&[cell_hashes[1], legacy_hashes[0]]).unwrap(),
+            None
+        );
+        assert_eq!(
+            store.get_by_versioned_hashes_v2(&[cell_hashes[1]]).unwrap(),
+            Some(vec![expected])
+        );
+    }
+
+    #[test]
+    fn rolls_and_compacts_segments() {
+        // every record gets its own segment
+        let config = SegmentedBlobStoreConfig::default().with_max_segment_size(1);
+        let (store, dir) = tmp_store(config);
+        let txs = (0..4).map(|_| (TxHash::random(), eip4844_sidecar(1))).collect::<Vec<_>>();
+        store.insert_all(txs.clone()).unwrap();
+        assert_eq!(store.segments_len(), 4);
+
+        store.delete_all(vec![txs[0].0, txs[1].0]).unwrap();
+        store.cleanup();
+        // the two sealed segments without live data were removed
+        assert_eq!(store.segments_len(), 2);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        // compaction is skipped for segments with enough live data
+        let config = SegmentedBlobStoreConfig::default().with_max_segment_size(1);
+        let (store, _dir) = tmp_store(config.with_min_live_percent(0));
+        store.insert_all(txs.clone()).unwrap();
+        store.delete_all(vec![txs[0].0]).unwrap();
+        store.cleanup();
+        assert_eq!(store.segments_len(), 4);
+    }
+
+    #[test]
+    fn compaction_moves_live_records() {
+        let record_len = RecordLayout::of(TxHash::ZERO, &eip4844_sidecar(1)).unwrap().len();
+        // two records per segment
+        let config = SegmentedBlobStoreConfig::default()
+            .with_max_segment_size(2 * record_len)
+            .with_min_live_percent(75);
+        let (store, _dir) = tmp_store(config);
+        let txs = (0..4).map(|_| (TxHash::random(), eip4844_sidecar(1))).collect::<Vec<_>>();
+        store.insert_all(txs.clone()).unwrap();
+        assert_eq!(store.segments_len(), 2);
+
+        // the first segment is half empty and its live record is moved to a new segment
+        store.delete(txs[0].0).unwrap();
+        store.cleanup();
+        assert_eq!(store.segments_len(), 2);
+        assert_eq!(store.inner.index.read().txs[&txs[1].0].segment, 2);
+
+        store.inner.blob_cache.lock().clear();
+        for (tx, sidecar) in &txs[1..] {
+            assert_eq!(*store.get(*tx).unwrap().unwrap(), *sidecar);
+        }
+        let BlobTransactionSidecarVariant::Eip4844(moved) = &txs[1].1 else { unreachable!() };
+        let hash = versioned_hashes(&moved.commitments)[0];
+        assert!(store.get_by_versioned_hashes_v1(&[hash]).unwrap()[0].is_some());
+        assert_eq!(store.data_size_hint().unwrap() as u64, 3 * record_len);
+    }
+}
@@ -287,11 +287,16 @@ pub use crate::{
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        CoinbaseTipOrdering, ConfigurableOrdering, FifoFeeBucketOrdering, OrderingConfig,
+        OrderingStrategy, Priority, SenderCappedOrdering, TransactionOrdering,
+        DEFAULT_FEE_BUCKET_SIZE,
+    },
+    policy::{InclusionPolicy, InclusionPolicyHandle, PolicyViolation},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        AllTransactionsEvents, DiscardReason, FullTransactionEvent, NewTransactionEvent,
+        TransactionEvent, TransactionEvents, TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -299,12 +304,16 @@ pub use crate::{
         TransactionValidator, ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{
+    identifier::TransactionId,
+    pool::PoolInner,
+    recorder::{PoolRecorder, RecordedOutcome, TransactionRecord},
+};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2},
     eip7594::BlobTransactionSidecarVariant,
 };
-use alloy_primitives::{map::AddressSet, Address, TxHash, B256, U256};
+use alloy_primitives::{map::AddressSet, Address, Bytes, TxHash, B256, U256};
 use aquamarine as _;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_eth_wire_types::HandleMempoolData;
@@ -321,7 +330,9 @@ pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
+pub mod policy;
 pub mod pool;
+pub mod recorder;
 pub mod validate;
 
 pub mod batcher;
@@ -338,7 +349,7 @@ pub mod test_utils;
 /// Type alias for default ethereum transaction pool
 pub type EthTransactionPool<Client, S, Evm = EthEvmConfig, T = EthPooledTransaction> = Pool<
     TransactionValidationTaskExecutor<EthTransactionValidator<Client, T, Evm>>,
-    CoinbaseTipOrdering<T>,
+    ConfigurableOrdering<T>,
     S,
 >;
 
@@ -347,6 +358,8 @@ pub type EthTransactionPool<Client, S, Evm = EthEvmConfig, T = EthPooledTransact
 pub struct Pool<V, T: TransactionOrdering, S> {
     /// Arc'ed instance of the pool internals
     pool: Arc<PoolInner<V, T, S>>,
+    /// Records the transaction arrivals and canonical state changes, if enabled.
+    recorder: Option<PoolRecorder>,
 }
 
 // === impl Pool ===
@@ -359,7 +372,24 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
-        Self { pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)) }
+        Self {
+            pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)),
+            recorder: None,
+        }
+    }
+
+    /// Records all transactions submitted to the pool and all canonical state changes it's
+    /// updated with.
+    ///
+    /// See [`recorder`] for replaying the recording.
+    pub fn with_recorder(mut self, recorder: PoolRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Returns the recorder of the pool, if recording is enabled.
+    pub const fn recorder(&self) -> Option<&PoolRecorder> {
+        self.recorder.as_ref()
     }
 
     /// Returns the wrapped pool internals.
@@ -386,6 +416,30 @@ where
         self.pool.validator().validate_transaction(origin, transaction).await
     }
 
+    /// Records the validation outcomes of transactions submitted to the pool.
+    ///
+    /// `encoded` are the transactions as returned by [`Self::encode_for_recorder`] before they
+    /// were validated.
+    fn record_arrivals<'a>(
+        &self,
+        encoded: Option<Vec<Option<Bytes>>>,
+        outcomes: impl IntoIterator<
+            Item = (TransactionOrigin, &'a TransactionValidationOutcome<V::Transaction>),
+        >,
+    ) {
+        let (Some(recorder), Some(encoded)) = (&self.recorder, encoded) else { return };
+        let elapsed = recorder.elapsed();
+        for (raw, (origin, outcome)) in encoded.into_iter().zip(outcomes) {
+            let Some(raw) = raw else { continue };
+            recorder.record_transaction(TransactionRecord {
+                elapsed,
+                origin,
+                raw,
+                outcome: RecordedOutcome::of(outcome),
+            });
+        }
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.pool.len()
@@ -418,7 +472,8 @@ where
     Evm: ConfigureEvm + 'static,
 {
     /// Returns a new [`Pool`] that uses the default [`TransactionValidationTaskExecutor`] when
-    /// validating [`EthPooledTransaction`]s and ords via [`CoinbaseTipOrdering`]
+    /// validating [`EthPooledTransaction`]s and orders them by coinbase tip via the default
+    /// [`ConfigurableOrdering`]
     ///
     /// # Example
     ///
@@ -458,7 +513,48 @@ where
         blob_store: S,
         config: PoolConfig,
     ) -> Self {
-        Self::new(validator, CoinbaseTipOrdering::default(), blob_store, config)
+        Self::new(validator, ConfigurableOrdering::default(), blob_store, config)
+    }
+}
+
+impl<V, S, T> Pool<V, ConfigurableOrdering<T>, S>
+where
+    V: TransactionValidator<Transaction = T>,
+    T: PoolTransaction,
+    S: BlobStore,
+{
+    /// Returns the current configuration of the pool's ordering.
+    pub fn ordering_config(&self) -> OrderingConfig {
+        self.pool.get_pool_data().ordering().config()
+    }
+
+    /// Switches the ordering of the pool at runtime and reorders the pending transactions
+    /// accordingly.
+    ///
+    /// Note: [`BestTransactions`](crate::pool::BestTransactions) iterators that already exist keep
+    /// the previous order.
+    ///
+    /// # Panics
+    ///
+    /// If the configured fee bucket size is zero.
+    pub fn set_ordering_config(&self, config: OrderingConfig) {
+        self.pool.update_ordering(|ordering| ordering.set_config(config));
+    }
+}
+
+impl<V, T, S> Pool<V, T, S>
+where
+    V: TransactionValidator<Transaction: EthPoolTransaction>,
+    T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+    S: BlobStore,
+{
+    /// Encodes the transactions for the recorder, if recording is enabled.
+    fn encode_for_recorder<'a>(
+        &self,
+        transactions: impl IntoIterator<Item = &'a V::Transaction>,
+    ) -> Option<Vec<Option<Bytes>>> {
+        self.recorder.as_ref()?;
+        Some(transactions.into_iter().map(recorder::encode_pooled).collect())
     }
 }
 
@@ -485,7 +581,9 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        let encoded = self.encode_for_recorder([&transaction]);
         let tx = self.validate(origin, transaction).await;
+        self.record_arrivals(encoded, [(origin, &tx)]);
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
 
@@ -494,7 +592,9 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<AddedTransactionOutcome> {
+        let encoded = self.encode_for_recorder([&transaction]);
         let tx = self.validate(origin, transaction).await;
+        self.record_arrivals(encoded, [(origin, &tx)]);
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
     }
@@ -507,11 +607,13 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
+        let encoded = self.encode_for_recorder(&transactions);
         let validated = self
             .pool
             .validator()
             .validate_transactions(transactions.into_iter().map(|tx| (origin, tx)))
             .await;
+        self.record_arrivals(encoded, validated.iter().map(|tx| (origin, tx)));
         self.pool.add_transactions(origin, validated)
     }
 
@@ -523,7 +625,9 @@ where
             return Vec::new()
         }
         let origins: Vec<_> = transactions.iter().map(|(origin, _)| *origin).collect();
+        let encoded = self.encode_for_recorder(transactions.iter().map(|(_, tx)| tx));
         let validated = self.pool.validator().validate_transactions(transactions).await;
+        self.record_arrivals(encoded, origins.iter().copied().zip(&validated));
         self.pool.add_transactions_with_origins(origins.into_iter().zip(validated))
     }
 
@@ -543,6 +647,10 @@ where
         self.pool.add_blob_sidecar_listener()
     }
 
+    fn nonce_gap_listener(&self) -> Receiver<NonceGap> {
+        self.pool.add_nonce_gap_listener()
+    }
+
     fn new_transactions_listener_for(
         &self,
         kind: TransactionListenerKind,
@@ -823,6 +931,9 @@ where
     }
 
     fn on_canonical_state_change(&self, update: CanonicalStateUpdate<'_, Self::Block>) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_canonical_state_change(&update);
+        }
         self.pool.on_canonical_state_change(update);
     }
 
@@ -845,6 +956,6 @@ where
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
     fn clone(&self) -> Self {
-        Self { pool: Arc::clone(&self.pool) }
+        Self { pool: Arc::clone(&self.pool), recorder: self.recorder.clone() }
     }
 }
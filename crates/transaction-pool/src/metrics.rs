@@ -76,6 +76,10 @@ pub struct BlobStoreMetrics {
     pub blobstore_byte_size: Gauge,
     /// How many blobs are currently in the blobstore
     pub blobstore_entries: Gauge,
+    /// Time it took to insert a blob sidecar into the blobstore
+    pub blobstore_insert_duration_seconds: Histogram,
+    /// Time it took to clean up the blobstore
+    pub blobstore_cleanup_duration_seconds: Histogram,
 }
 
 /// Transaction pool maintenance metrics
@@ -154,3 +158,17 @@ pub struct TxPoolValidatorMetrics {
     /// Number of in-flight validation job sends waiting for channel capacity
     pub inflight_validation_jobs: Gauge,
 }
+
+/// Transaction inclusion policy metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "transaction_pool.inclusion_policy")]
+pub struct InclusionPolicyMetrics {
+    /// Number of transactions excluded because of their sender
+    pub excluded_by_sender: Counter,
+    /// Number of transactions excluded because of their recipient
+    pub excluded_by_recipient: Counter,
+    /// Number of transactions excluded because of an address in their access list
+    pub excluded_by_touched_address: Counter,
+    /// Number of transactions excluded because of their function selector
+    pub excluded_by_selector: Counter,
+}
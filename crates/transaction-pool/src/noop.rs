@@ -7,7 +7,7 @@ use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolError},
     pool::TransactionListenerKind,
-    traits::{BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar},
+    traits::{BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar, NonceGap},
     validate::ValidTransaction,
     AddedTransactionOutcome, AllPoolTransactions, AllTransactionsEvents, BestTransactions,
     BlockInfo, EthPoolTransaction, EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize,
@@ -134,6 +134,10 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
         mpsc::channel(1).1
     }
 
+    fn nonce_gap_listener(&self) -> Receiver<NonceGap> {
+        mpsc::channel(1).1
+    }
+
     fn new_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
@@ -1,5 +1,9 @@
 use crate::traits::PoolTransaction;
-use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+use parking_lot::RwLock;
+use std::{cmp::Ordering, fmt::Debug, marker::PhantomData, sync::Arc};
+
+/// Default width of a fee bucket of the [`FifoFeeBucketOrdering`]: 1 gwei.
+pub const DEFAULT_FEE_BUCKET_SIZE: u128 = 1_000_000_000;
 
 /// Priority of the transaction that can be missing.
 ///
@@ -56,6 +60,15 @@ pub trait TransactionOrdering: Debug + Send + Sync + 'static {
         transaction: &Self::Transaction,
         base_fee: u64,
     ) -> Priority<Self::PriorityValue>;
+
+    /// Returns the maximum number of transactions of a single sender that
+    /// [`BestTransactions`](crate::pool::BestTransactions) yields, if senders are capped.
+    ///
+    /// Once a sender reached the cap, its remaining transactions are skipped, which leaves room
+    /// for other senders.
+    fn max_transactions_per_sender(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Default ordering for the pool.
@@ -97,9 +110,219 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
     }
 }
 
+/// Orders transactions by their coinbase tip, grouped into buckets of `bucket_size` wei.
+///
+/// All transactions in the same bucket have the same priority, so the pool orders them by arrival
+/// time: the transaction that was added first is returned first.
+#[derive(Debug)]
+pub struct FifoFeeBucketOrdering<T> {
+    bucket_size: u128,
+    _tx: PhantomData<T>,
+}
+
+impl<T> FifoFeeBucketOrdering<T> {
+    /// Creates a new ordering with the given bucket size in wei.
+    ///
+    /// # Panics
+    ///
+    /// If the bucket size is zero.
+    pub fn new(bucket_size: u128) -> Self {
+        assert!(bucket_size > 0, "fee bucket size must not be zero");
+        Self { bucket_size, _tx: PhantomData }
+    }
+
+    /// Returns the size of the fee buckets in wei.
+    pub const fn bucket_size(&self) -> u128 {
+        self.bucket_size
+    }
+}
+
+impl<T> TransactionOrdering for FifoFeeBucketOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        fee_bucket(transaction, base_fee, self.bucket_size).into()
+    }
+}
+
+impl<T> Default for FifoFeeBucketOrdering<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_FEE_BUCKET_SIZE)
+    }
+}
+
+impl<T> Clone for FifoFeeBucketOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.bucket_size)
+    }
+}
+
+/// Wraps another [`TransactionOrdering`] and caps the number of transactions per sender that
+/// [`BestTransactions`](crate::pool::BestTransactions) yields, so that a single sender can't crowd
+/// out all others.
+#[derive(Debug, Clone)]
+pub struct SenderCappedOrdering<O> {
+    inner: O,
+    max_transactions_per_sender: usize,
+}
+
+impl<O> SenderCappedOrdering<O> {
+    /// Caps the transactions per sender of the given ordering.
+    pub const fn new(inner: O, max_transactions_per_sender: usize) -> Self {
+        Self { inner, max_transactions_per_sender }
+    }
+
+    /// Returns the wrapped ordering.
+    pub const fn inner(&self) -> &O {
+        &self.inner
+    }
+}
+
+impl<O: TransactionOrdering> TransactionOrdering for SenderCappedOrdering<O> {
+    type PriorityValue = O::PriorityValue;
+    type Transaction = O::Transaction;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.inner.priority(transaction, base_fee)
+    }
+
+    fn max_transactions_per_sender(&self) -> Option<usize> {
+        Some(self.max_transactions_per_sender)
+    }
+}
+
+/// The built-in ordering strategies that can be selected by the [`ConfigurableOrdering`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum OrderingStrategy {
+    /// Orders by coinbase tip, see [`CoinbaseTipOrdering`].
+    #[default]
+    CoinbaseTip,
+    /// Orders by arrival time within fee buckets of the given size in wei, see
+    /// [`FifoFeeBucketOrdering`].
+    FifoFeeBuckets {
+        /// The size of the fee buckets in wei.
+        bucket_size: u128,
+    },
+}
+
+/// Configuration of the [`ConfigurableOrdering`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderingConfig {
+    /// How transactions are prioritized.
+    pub strategy: OrderingStrategy,
+    /// Max number of transactions per sender yielded by
+    /// [`BestTransactions`](crate::pool::BestTransactions), unlimited if `None`.
+    pub max_transactions_per_sender: Option<usize>,
+}
+
+/// A [`TransactionOrdering`] that is selected by an [`OrderingConfig`], which can be changed at
+/// runtime.
+///
+/// Clones share the same configuration, so a clone can be kept as a handle to switch the ordering
+/// of a running pool. A new configuration only applies to priorities computed afterwards, use
+/// [`Pool::set_ordering_config`](crate::Pool::set_ordering_config) to also reorder the
+/// transactions that are already pending.
+#[derive(Debug)]
+pub struct ConfigurableOrdering<T> {
+    config: Arc<RwLock<OrderingConfig>>,
+    _tx: PhantomData<T>,
+}
+
+impl<T> ConfigurableOrdering<T> {
+    /// Creates a new ordering with the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the configured fee bucket size is zero.
+    pub fn new(config: OrderingConfig) -> Self {
+        let this = Self { config: Default::default(), _tx: PhantomData };
+        this.set_config(config);
+        this
+    }
+
+    /// Returns the current configuration.
+    pub fn config(&self) -> OrderingConfig {
+        *self.config.read()
+    }
+
+    /// Switches to the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// If the configured fee bucket size is zero.
+    pub fn set_config(&self, config: OrderingConfig) {
+        if let OrderingStrategy::FifoFeeBuckets { bucket_size } = config.strategy {
+            assert!(bucket_size > 0, "fee bucket size must not be zero");
+        }
+        *self.config.write() = config;
+    }
+}
+
+impl<T> TransactionOrdering for ConfigurableOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self.config.read().strategy {
+            OrderingStrategy::CoinbaseTip => transaction.effective_tip_per_gas(base_fee).into(),
+            OrderingStrategy::FifoFeeBuckets { bucket_size } => {
+                fee_bucket(transaction, base_fee, bucket_size).into()
+            }
+        }
+    }
+
+    fn max_transactions_per_sender(&self) -> Option<usize> {
+        self.config.read().max_transactions_per_sender
+    }
+}
+
+impl<T> Default for ConfigurableOrdering<T> {
+    fn default() -> Self {
+        Self::new(OrderingConfig::default())
+    }
+}
+
+impl<T> Clone for ConfigurableOrdering<T> {
+    fn clone(&self) -> Self {
+        Self { config: Arc::clone(&self.config), _tx: PhantomData }
+    }
+}
+
+/// Returns the fee bucket of the transaction's coinbase tip.
+fn fee_bucket<T: PoolTransaction>(
+    transaction: &T,
+    base_fee: u64,
+    bucket_size: u128,
+) -> Option<u128> {
+    transaction.effective_tip_per_gas(base_fee).map(|tip| tip / bucket_size)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::MockTransaction;
 
     #[test]
     fn test_priority_ordering() {
@@ -112,4 +335,34 @@ mod tests {
         assert!(p2 > p3); // Value(1) > None
         assert_eq!(p3, Priority::None);
     }
+
+    #[test]
+    fn fifo_fee_buckets() {
+        let ordering = FifoFeeBucketOrdering::new(10);
+        let mut tx = MockTransaction::eip1559();
+        tx.set_priority_fee(25).set_max_fee(100);
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(2));
+        tx.set_priority_fee(29);
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(2));
+        tx.set_priority_fee(30);
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(3));
+    }
+
+    #[test]
+    fn switch_configurable_ordering() {
+        let ordering = ConfigurableOrdering::default();
+        let handle = ordering.clone();
+        let mut tx = MockTransaction::eip1559();
+        tx.set_priority_fee(25).set_max_fee(100);
+
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(25));
+        assert_eq!(ordering.max_transactions_per_sender(), None);
+
+        handle.set_config(OrderingConfig {
+            strategy: OrderingStrategy::FifoFeeBuckets { bucket_size: 10 },
+            max_transactions_per_sender: Some(2),
+        });
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(2));
+        assert_eq!(ordering.max_transactions_per_sender(), Some(2));
+    }
 }
@@ -0,0 +1,314 @@
+//! Transaction inclusion policies.
+//!
+//! An [`InclusionPolicy`] decides whether a transaction may enter the pool and whether it may be
+//! included in a locally built block. Policies are local settings: a transaction that violates the
+//! policy is still valid, and blocks built by others that contain it are processed as usual.
+//!
+//! The policy is enforced at two points:
+//!  - pool admission, see
+//!    [`EthTransactionValidatorBuilder::with_inclusion_policy`](crate::validate::EthTransactionValidatorBuilder::with_inclusion_policy)
+//!  - payload building, see [`InclusionPolicyHandle::filter_best_transactions`]
+//!
+//! The [`InclusionPolicyHandle`] can be swapped at runtime, so transactions that were admitted
+//! before a policy change are still excluded from built blocks.
+
+use crate::{
+    error::PoolTransactionError, metrics::InclusionPolicyMetrics, pool::BestTransactionFilter,
+    BestTransactions, PoolTransaction, ValidPoolTransaction,
+};
+use alloy_consensus::Transaction;
+use alloy_primitives::{map::AddressSet, Address, Selector};
+use parking_lot::RwLock;
+use std::{any::Any, collections::HashSet, fmt, sync::Arc};
+
+/// Decides whether a transaction may be admitted to the pool and included in built blocks.
+pub trait InclusionPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns the rule the transaction violates, or `None` if it may be included.
+    fn check(&self, sender: Address, transaction: &dyn Transaction) -> Option<PolicyViolation>;
+}
+
+/// The rule of an [`InclusionPolicy`] that a transaction violates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyViolation {
+    /// The transaction was sent by an excluded address.
+    #[error("sender {0} is excluded by the inclusion policy")]
+    Sender(Address),
+    /// The transaction calls an excluded address.
+    #[error("recipient {0} is excluded by the inclusion policy")]
+    Recipient(Address),
+    /// The transaction declares access to an excluded address.
+    #[error("touched address {0} is excluded by the inclusion policy")]
+    TouchedAddress(Address),
+    /// The transaction calls an excluded function selector.
+    #[error("selector {0} is excluded by the inclusion policy")]
+    Selector(Selector),
+}
+
+impl PoolTransactionError for PolicyViolation {
+    fn is_bad_transaction(&self) -> bool {
+        // this is a local setting
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// An [`InclusionPolicy`] that accepts every transaction.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct AllowAllPolicy;
+
+impl InclusionPolicy for AllowAllPolicy {
+    fn check(&self, _sender: Address, _transaction: &dyn Transaction) -> Option<PolicyViolation> {
+        None
+    }
+}
+
+/// An [`InclusionPolicy`] that excludes transactions matching any of its lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenylistPolicy {
+    /// Excluded transaction senders.
+    pub senders: AddressSet,
+    /// Excluded call targets.
+    pub recipients: AddressSet,
+    /// Addresses that must not be touched, matched against the access list and the EIP-7702
+    /// delegation targets of a transaction.
+    ///
+    /// This is a pre-check on the declared accesses: a transaction may still touch an address
+    /// that it doesn't declare.
+    pub touched: AddressSet,
+    /// Excluded function selectors, matched against the first four bytes of the calldata.
+    pub selectors: HashSet<Selector>,
+}
+
+impl DenylistPolicy {
+    /// Returns `true` if the policy doesn't exclude anything.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty() &&
+            self.recipients.is_empty() &&
+            self.touched.is_empty() &&
+            self.selectors.is_empty()
+    }
+}
+
+impl InclusionPolicy for DenylistPolicy {
+    fn check(&self, sender: Address, transaction: &dyn Transaction) -> Option<PolicyViolation> {
+        if self.senders.contains(&sender) {
+            return Some(PolicyViolation::Sender(sender))
+        }
+
+        if let Some(to) = transaction.to() {
+            if self.recipients.contains(&to) {
+                return Some(PolicyViolation::Recipient(to))
+            }
+            if let Some(selector) = transaction.function_selector() &&
+                self.selectors.contains(selector)
+            {
+                return Some(PolicyViolation::Selector(*selector))
+            }
+        }
+
+        if !self.touched.is_empty() &&
+            let Some(address) = transaction
+                .access_list()
+                .into_iter()
+                .flat_map(|list| list.iter().map(|item| item.address))
+                .chain(
+                    transaction
+                        .authorization_list()
+                        .into_iter()
+                        .flatten()
+                        .map(|auth| *auth.address()),
+                )
+                .find(|address| self.touched.contains(address))
+        {
+            return Some(PolicyViolation::TouchedAddress(address))
+        }
+
+        None
+    }
+}
+
+/// A shared, hot-swappable [`InclusionPolicy`].
+///
+/// All clones observe the same policy. Violations are recorded in the
+/// [`InclusionPolicyMetrics`] of the stage that checked the transaction.
+#[derive(Clone)]
+pub struct InclusionPolicyHandle {
+    policy: Arc<RwLock<Arc<dyn InclusionPolicy>>>,
+    pool_metrics: InclusionPolicyMetrics,
+    payload_metrics: InclusionPolicyMetrics,
+}
+
+impl InclusionPolicyHandle {
+    /// Creates a new handle with the given initial policy.
+    pub fn new(policy: impl InclusionPolicy) -> Self {
+        Self {
+            policy: Arc::new(RwLock::new(Arc::new(policy))),
+            pool_metrics: InclusionPolicyMetrics::new_with_labels(&[("stage", "pool")]),
+            payload_metrics: InclusionPolicyMetrics::new_with_labels(&[("stage", "payload")]),
+        }
+    }
+
+    /// Replaces the current policy.
+    pub fn set(&self, policy: impl InclusionPolicy) {
+        *self.policy.write() = Arc::new(policy);
+    }
+
+    /// Returns the current policy.
+    pub fn current(&self) -> Arc<dyn InclusionPolicy> {
+        self.policy.read().clone()
+    }
+
+    /// Checks whether the transaction may be admitted to the pool.
+    pub fn check_admission<T: PoolTransaction>(&self, transaction: &T) -> Option<PolicyViolation> {
+        let violation = self.current().check(transaction.sender(), transaction);
+        if let Some(violation) = &violation {
+            self.pool_metrics.record(violation);
+        }
+        violation
+    }
+
+    /// Checks whether a transaction that doesn't come from the pool, e.g. one of an inclusion list,
+    /// may be included in a payload.
+    pub fn check_inclusion(
+        &self,
+        sender: Address,
+        transaction: &dyn Transaction,
+    ) -> Option<PolicyViolation> {
+        let violation = self.current().check(sender, transaction);
+        if let Some(violation) = &violation {
+            self.payload_metrics.record(violation);
+        }
+        violation
+    }
+
+    /// Wraps the given [`BestTransactions`] iterator so that transactions violating the current
+    /// policy are skipped, along with their descendants.
+    ///
+    /// The policy is captured when this is called, so it stays the same for a single payload.
+    pub fn filter_best_transactions<I, T>(
+        &self,
+        best: I,
+    ) -> BestTransactionFilter<
+        I,
+        impl FnMut(&Arc<ValidPoolTransaction<T>>) -> bool + Send + use<I, T>,
+    >
+    where
+        I: BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+        T: PoolTransaction,
+    {
+        let policy = self.current();
+        let metrics = self.payload_metrics.clone();
+        best.filter_transactions(move |tx| {
+            let Some(violation) = policy.check(tx.sender(), &tx.transaction) else { return true };
+            metrics.record(&violation);
+            false
+        })
+    }
+}
+
+impl Default for InclusionPolicyHandle {
+    fn default() -> Self {
+        Self::new(AllowAllPolicy)
+    }
+}
+
+impl PartialEq for InclusionPolicyHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.policy, &other.policy)
+    }
+}
+
+impl Eq for InclusionPolicyHandle {}
+
+impl fmt::Debug for InclusionPolicyHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InclusionPolicyHandle").field("policy", &self.current()).finish()
+    }
+}
+
+impl InclusionPolicyMetrics {
+    /// Increments the counter matching the violated rule.
+    fn record(&self, violation: &PolicyViolation) {
+        match violation {
+            PolicyViolation::Sender(_) => self.excluded_by_sender.increment(1),
+            PolicyViolation::Recipient(_) => self.excluded_by_recipient.increment(1),
+            PolicyViolation::TouchedAddress(_) => self.excluded_by_touched_address.increment(1),
+            PolicyViolation::Selector(_) => self.excluded_by_selector.increment(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::PendingPool,
+        test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
+    };
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{address, Bytes};
+
+    #[test]
+    fn denylist_matches_rules() {
+        let tx = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]));
+        let sender = tx.sender();
+        let to = tx.to().unwrap();
+
+        let policy = DenylistPolicy::default();
+        assert!(policy.is_empty());
+        assert_eq!(policy.check(sender, &tx), None);
+
+        let policy =
+            DenylistPolicy { senders: std::iter::once(sender).collect(), ..Default::default() };
+        assert_eq!(policy.check(sender, &tx), Some(PolicyViolation::Sender(sender)));
+
+        let policy =
+            DenylistPolicy { recipients: std::iter::once(to).collect(), ..Default::default() };
+        assert_eq!(policy.check(sender, &tx), Some(PolicyViolation::Recipient(to)));
+
+        let selector = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+        let policy =
+            DenylistPolicy { selectors: std::iter::once(selector).collect(), ..Default::default() };
+        assert_eq!(policy.check(sender, &tx), Some(PolicyViolation::Selector(selector)));
+
+        let touched = address!("0x00000000000000000000000000000000000000aa");
+        let mut tx = MockTransaction::eip2930();
+        tx.set_accesslist(AccessList(vec![AccessListItem {
+            address: touched,
+            storage_keys: vec![],
+        }]));
+        let policy =
+            DenylistPolicy { touched: std::iter::once(touched).collect(), ..Default::default() };
+        assert_eq!(policy.check(tx.sender(), &tx), Some(PolicyViolation::TouchedAddress(touched)));
+    }
+
+    #[test]
+    fn handle_filters_best_transactions() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let excluded = MockTransaction::eip1559();
+        let included = MockTransaction::eip1559();
+        pool.add_transaction(Arc::new(f.validated(excluded.clone())), 0);
+        pool.add_transaction(Arc::new(f.validated(included.clone())), 0);
+
+        let handle = InclusionPolicyHandle::default();
+        assert_eq!(handle.filter_best_transactions(pool.best()).count(), 2);
+
+        handle.set(DenylistPolicy {
+            senders: std::iter::once(excluded.sender()).collect(),
+            ..Default::default()
+        });
+        assert!(handle.check_admission(&excluded).is_some());
+        assert!(handle.check_admission(&included).is_none());
+
+        let best: Vec<_> = handle.filter_best_transactions(pool.best()).collect();
+        assert_eq!(best.len(), 1);
+        assert_eq!(best[0].sender(), included.sender());
+    }
+}
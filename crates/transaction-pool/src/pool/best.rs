@@ -9,7 +9,7 @@ use alloy_primitives::map::AddressSet;
 use core::fmt;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use std::{
-    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 use tokio::sync::broadcast::{error::TryRecvError, Receiver};
@@ -104,6 +104,11 @@ pub struct BestTransactions<T: TransactionOrdering> {
     pub(crate) last_priority: Option<Priority<T::PriorityValue>>,
     /// Flag to control whether to skip blob transactions (EIP4844).
     pub(crate) skip_blobs: bool,
+    /// Max number of transactions yielded per sender, see
+    /// [`TransactionOrdering::max_transactions_per_sender`].
+    pub(crate) max_transactions_per_sender: Option<usize>,
+    /// Number of transactions yielded per sender, if senders are capped.
+    pub(crate) yielded_per_sender: HashMap<SenderId, usize>,
 }
 
 impl<T: TransactionOrdering> BestTransactions<T> {
@@ -228,6 +233,14 @@ impl<T: TransactionOrdering> BestTransactions<T> {
                 if self.new_transaction_receiver.is_some() {
                     self.last_priority = Some(best.priority.clone())
                 }
+                if let Some(max) = self.max_transactions_per_sender {
+                    let yielded = self.yielded_per_sender.entry(sender_id).or_default();
+                    *yielded += 1;
+                    if *yielded >= max {
+                        // the sender got its fair share, skip its remaining transactions
+                        self.invalid.insert(sender_id);
+                    }
+                }
                 return Some((best.transaction, best.priority))
             }
         }
@@ -444,7 +457,7 @@ mod tests {
     use crate::{
         pool::pending::PendingPool,
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
-        BestTransactions, Priority,
+        BestTransactions, FifoFeeBucketOrdering, Priority, SenderCappedOrdering,
     };
 
     #[test]
@@ -473,6 +486,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_best_iter_sender_cap() {
+        let mut pool = PendingPool::new(SenderCappedOrdering::new(MockOrdering::default(), 2));
+        let mut f = MockTransactionFactory::default();
+
+        // sender a pays the higher tip, but only two of its transactions are yielded
+        let tx_a = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(100);
+        let tx_b = MockTransaction::eip1559().with_priority_fee(5).with_max_fee(100);
+        for nonce in 0..5 {
+            pool.add_transaction(
+                Arc::new(f.validated(tx_a.clone().rng_hash().with_nonce(nonce))),
+                0,
+            );
+        }
+        for nonce in 0..2 {
+            pool.add_transaction(
+                Arc::new(f.validated(tx_b.clone().rng_hash().with_nonce(nonce))),
+                0,
+            );
+        }
+
+        let best = pool.best().map(|tx| (tx.sender(), tx.nonce())).collect::<Vec<_>>();
+        assert_eq!(
+            best,
+            vec![(tx_a.sender(), 0), (tx_a.sender(), 1), (tx_b.sender(), 0), (tx_b.sender(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_best_iter_fifo_fee_buckets() {
+        let mut f = MockTransactionFactory::default();
+        let first = Arc::new(
+            f.validated(MockTransaction::eip1559().with_priority_fee(11).with_max_fee(100)),
+        );
+        let second = Arc::new(
+            f.validated(MockTransaction::eip1559().with_priority_fee(19).with_max_fee(100)),
+        );
+
+        // tips within the same bucket are served in arrival order
+        let mut pool = PendingPool::new(FifoFeeBucketOrdering::new(10));
+        pool.add_transaction(first.clone(), 0);
+        pool.add_transaction(second.clone(), 0);
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*first.hash(), *second.hash()]);
+
+        // while the tip ordering serves the higher tip first
+        let mut pool = PendingPool::new(MockOrdering::default());
+        pool.add_transaction(first.clone(), 0);
+        pool.add_transaction(second.clone(), 0);
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*second.hash(), *first.hash()]);
+    }
+
     #[test]
     fn test_best_iter_invalid() {
         let mut pool = PendingPool::new(MockOrdering::default());
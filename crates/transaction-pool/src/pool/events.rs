@@ -1,5 +1,5 @@
 use crate::{traits::PropagateKind, PoolTransaction, SubPool, ValidPoolTransaction};
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{Address, TxHash, B256};
 use std::sync::Arc;
 
 use crate::pool::QueuedReason;
@@ -31,10 +31,22 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
-    /// Transaction was dropped due to configured limits.
-    Discarded(TxHash),
+    /// Transaction was removed from the pool without being mined.
+    Discarded {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
     /// Transaction became invalid indefinitely.
-    Invalid(TxHash),
+    Invalid {
+        /// The hash of the invalid transaction.
+        tx_hash: TxHash,
+        /// The sender of the invalid transaction.
+        sender: Address,
+        /// The error the transaction failed validation with.
+        error: String,
+    },
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -50,8 +62,12 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
-            Self::Discarded(hash) => Self::Discarded(*hash),
-            Self::Invalid(hash) => Self::Invalid(*hash),
+            Self::Discarded { tx_hash, reason } => {
+                Self::Discarded { tx_hash: *tx_hash, reason: reason.clone() }
+            }
+            Self::Invalid { tx_hash, sender, error } => {
+                Self::Invalid { tx_hash: *tx_hash, sender: *sender, error: error.clone() }
+            }
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
@@ -87,6 +103,30 @@ impl TransactionEvent {
     }
 }
 
+/// The reason why a transaction was removed from the pool without being mined.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "camelCase"))]
+pub enum DiscardReason {
+    /// Evicted to keep the sub-pool within its configured size limits.
+    ///
+    /// The descendants of an evicted transaction are evicted for the same sub-pool.
+    PoolLimit {
+        /// The sub-pool whose limit was exceeded.
+        subpool: SubPool,
+    },
+    /// The nonce of the transaction is lower than the on-chain nonce of its sender.
+    NonceTooLow,
+    /// Removed on request, e.g. via
+    /// [`TransactionPool::remove_transactions`](crate::TransactionPool::remove_transactions).
+    Removed,
+    /// The transaction could not be validated.
+    ValidationError {
+        /// The error that occurred during validation.
+        error: String,
+    },
+}
+
 /// Represents a new transaction
 #[derive(Debug)]
 pub struct NewTransactionEvent<T: PoolTransaction> {
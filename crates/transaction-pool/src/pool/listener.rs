@@ -1,14 +1,15 @@
 //! Listeners for the transaction-pool
 
 use crate::{
+    error::InvalidPoolTransactionError,
     pool::{
-        events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+        events::{DiscardReason, FullTransactionEvent, NewTransactionEvent, TransactionEvent},
         QueuedReason,
     },
     traits::{NewBlobSidecar, PropagateKind},
-    PoolTransaction, ValidPoolTransaction,
+    PoolTransaction, SubPool, ValidPoolTransaction,
 };
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{Address, TxHash, B256};
 use futures_util::Stream;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -188,23 +189,47 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about all discarded transactions.
     #[inline]
-    pub fn discarded_many(&mut self, discarded: &[Arc<ValidPoolTransaction<T>>]) {
+    pub fn discarded_many(
+        &mut self,
+        discarded: &[Arc<ValidPoolTransaction<T>>],
+        reason: &DiscardReason,
+    ) {
         if self.is_empty() {
             return
         }
         for tx in discarded {
-            self.discarded(tx.hash());
+            self.discarded(tx.hash(), reason.clone());
+        }
+    }
+
+    /// Notify listeners about transactions that were evicted from the given sub-pools to enforce
+    /// their size limits.
+    #[inline]
+    pub fn evicted(&mut self, evicted: &[(Arc<ValidPoolTransaction<T>>, SubPool)]) {
+        if self.is_empty() {
+            return
+        }
+        for (tx, subpool) in evicted {
+            self.discarded(tx.hash(), DiscardReason::PoolLimit { subpool: *subpool });
         }
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded { tx_hash: *tx, reason },
+        );
     }
 
     /// Notify listeners about a transaction that was invalid.
-    pub fn invalid(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
+    pub fn invalid(&mut self, tx: &TxHash, sender: Address, error: &InvalidPoolTransactionError) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Invalid,
+            FullTransactionEvent::Invalid { tx_hash: *tx, sender, error: error.to_string() },
+        );
     }
 
     /// Notify listeners that the transaction was mined
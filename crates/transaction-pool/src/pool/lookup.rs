@@ -0,0 +1,109 @@
+//! Transaction lookups that don't acquire the pool lock.
+
+use crate::{identifier::SenderId, PoolTransaction, ValidPoolTransaction};
+use alloy_primitives::TxHash;
+use dashmap::DashMap;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A sharded index of all transactions in the pool, by hash and by sender.
+///
+/// The index mirrors the transactions of [`AllTransactions`](super::txpool::AllTransactions),
+/// which updates it together with its own maps while the pool write lock is held. Reads only lock
+/// a single shard of the index, so lookups by hash or sender don't contend with inserts, canonical
+/// state updates or `best_transactions` snapshots on the pool lock.
+pub(crate) struct TransactionLookup<T: PoolTransaction> {
+    /// All transactions by their hash.
+    by_hash: DashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+    /// All transactions by their sender, ordered by nonce.
+    by_sender: DashMap<SenderId, BTreeMap<u64, Arc<ValidPoolTransaction<T>>>>,
+}
+
+impl<T: PoolTransaction> TransactionLookup<T> {
+    /// Adds a transaction to the index.
+    pub(crate) fn insert(&self, tx: Arc<ValidPoolTransaction<T>>) {
+        self.by_sender.entry(tx.sender_id()).or_default().insert(tx.nonce(), tx.clone());
+        self.by_hash.insert(*tx.hash(), tx);
+    }
+
+    /// Removes a transaction from the index.
+    pub(crate) fn remove(&self, tx: &ValidPoolTransaction<T>) {
+        self.by_hash.remove(tx.hash());
+        self.by_sender.remove_if_mut(&tx.sender_id(), |_, txs| {
+            // a replacement with the same nonce may have been inserted already
+            if txs.get(&tx.nonce()).is_some_and(|existing| existing.hash() == tx.hash()) {
+                txs.remove(&tx.nonce());
+            }
+            txs.is_empty()
+        });
+    }
+
+    /// Returns the transaction with the given hash.
+    pub(crate) fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T>>> {
+        self.by_hash.get(tx_hash).map(|tx| tx.clone())
+    }
+
+    /// Returns `true` if the transaction with the given hash is in the pool.
+    pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
+        self.by_hash.contains_key(tx_hash)
+    }
+
+    /// Returns all transactions of the sender, ordered by nonce.
+    pub(crate) fn by_sender(&self, sender: SenderId) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        self.by_sender.get(&sender).map(|txs| txs.values().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Returns the number of transactions in the index.
+    pub(crate) fn len(&self) -> usize {
+        self.by_hash.len()
+    }
+}
+
+impl<T: PoolTransaction> Default for TransactionLookup<T> {
+    fn default() -> Self {
+        Self { by_hash: Default::default(), by_sender: Default::default() }
+    }
+}
+
+impl<T: PoolTransaction> std::fmt::Debug for TransactionLookup<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionLookup").field("len", &self.len()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+
+    #[test]
+    fn tracks_transactions_by_hash_and_sender() {
+        let mut f = MockTransactionFactory::default();
+        let lookup = TransactionLookup::default();
+
+        let tx = MockTransaction::eip1559();
+        let first = f.validated_arc(tx.clone());
+        let second = f.validated_arc(tx.next());
+        lookup.insert(first.clone());
+        lookup.insert(second.clone());
+
+        assert!(lookup.contains(first.hash()));
+        assert_eq!(lookup.get(second.hash()).unwrap().hash(), second.hash());
+        let by_sender = lookup.by_sender(first.sender_id());
+        assert_eq!(
+            by_sender.iter().map(|tx| tx.nonce()).collect::<Vec<_>>(),
+            vec![first.nonce(), second.nonce()]
+        );
+
+        // a replacement inserted before the removal of the replaced transaction is kept
+        let replacement = f.validated_arc(tx.rng_hash().inc_price());
+        lookup.insert(replacement.clone());
+        lookup.remove(&first);
+        assert!(!lookup.contains(first.hash()));
+        assert_eq!(lookup.by_sender(first.sender_id())[0].hash(), replacement.hash());
+
+        lookup.remove(&replacement);
+        lookup.remove(&second);
+        assert_eq!(lookup.len(), 0);
+        assert!(lookup.by_sender.is_empty());
+    }
+}
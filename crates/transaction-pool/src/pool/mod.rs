@@ -75,13 +75,15 @@ use crate::{
             BlobTransactionSidecarListener, PendingTransactionHashListener, PoolEventBroadcast,
             TransactionListener,
         },
+        lookup::TransactionLookup,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
         update::UpdateOutcome,
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, GetPooledTransactionLimit,
-        NewBlobSidecar, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        NewBlobSidecar, NonceGap, PoolSize, PoolTransaction, PropagatedTransactions,
+        TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction, ValidTransaction},
     CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
@@ -90,7 +92,7 @@ use crate::{
 
 use alloy_primitives::{
     map::{AddressSet, HashSet},
-    Address, TxHash, B256,
+    Address, TxHash, B256, U256,
 };
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
@@ -112,7 +114,7 @@ use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, NewTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -122,6 +124,7 @@ pub use best::BestTransactions;
 
 mod blob;
 pub mod listener;
+mod lookup;
 mod parked;
 pub mod pending;
 pub mod size;
@@ -136,6 +139,11 @@ pub const NEW_TX_LISTENER_BUFFER_SIZE: usize = 1024;
 
 const BLOB_SIDECAR_LISTENER_BUFFER_SIZE: usize = 512;
 
+const NONCE_GAP_LISTENER_BUFFER_SIZE: usize = 256;
+
+/// Max number of queued transaction hashes reported per [`NonceGap`].
+const MAX_QUEUED_HASHES_PER_NONCE_GAP: usize = 16;
+
 /// Transaction pool internals.
 pub struct PoolInner<V, T, S>
 where
@@ -149,6 +157,8 @@ where
     blob_store: S,
     /// The internal pool that manages all transactions.
     pool: RwLock<TxPool<T>>,
+    /// Index of all transactions in the pool that's read without acquiring the pool lock.
+    lookup: Arc<TransactionLookup<T::Transaction>>,
     /// Pool settings.
     config: PoolConfig,
     /// Manages listeners for transaction state change events.
@@ -161,6 +171,10 @@ where
     transaction_listener: RwLock<Vec<TransactionListener<T::Transaction>>>,
     /// Listener for new blob transaction sidecars added to the pool.
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
+    /// Listeners for nonce gaps of senders of newly queued transactions.
+    nonce_gap_listener: Mutex<Vec<mpsc::Sender<NonceGap>>>,
+    /// Tracks whether any nonce gap listeners have ever been installed.
+    has_nonce_gap_listeners: AtomicBool,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
 }
@@ -175,15 +189,19 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        let pool = TxPool::new(ordering, config.clone());
         Self {
             identifiers: Default::default(),
             validator,
             event_listener: Default::default(),
             has_event_listeners: AtomicBool::new(false),
-            pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            lookup: pool.all().lookup().clone(),
+            pool: RwLock::new(pool),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            nonce_gap_listener: Default::default(),
+            has_nonce_gap_listeners: AtomicBool::new(false),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -294,10 +312,19 @@ where
         rx
     }
 
+    /// Adds a new listener that gets notified about the nonce gap of the sender whenever a
+    /// transaction is queued because of it.
+    pub fn add_nonce_gap_listener(&self) -> mpsc::Receiver<NonceGap> {
+        let (sender, rx) = mpsc::channel(NONCE_GAP_LISTENER_BUFFER_SIZE);
+        self.nonce_gap_listener.lock().push(sender);
+        self.has_nonce_gap_listeners.store(true, Ordering::Relaxed);
+        rx
+    }
+
     /// If the pool contains the transaction, this adds a new listener that gets notified about
     /// transaction events.
     pub fn add_transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
-        if !self.get_pool_data().contains(&tx_hash) {
+        if !self.lookup.contains(&tx_hash) {
             return None
         }
         let mut listener = self.event_listener.write();
@@ -351,6 +378,17 @@ where
         self.pool.read()
     }
 
+    /// Applies `f` to the ordering of the pool and recomputes the priorities of all pending
+    /// transactions afterwards.
+    ///
+    /// The pool is locked meanwhile, so that no transaction is prioritized by the previous
+    /// ordering once this returns.
+    pub fn update_ordering(&self, f: impl FnOnce(&T)) {
+        let mut pool = self.pool.write();
+        f(pool.ordering());
+        pool.reprioritize_pending();
+    }
+
     /// Returns transactions in the pool that can be propagated
     pub fn pooled_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let mut out = Vec::new();
@@ -550,19 +588,18 @@ where
         self.notify_on_transaction_updates(promoted, discarded);
     }
 
-    /// Add a single validated transaction into the pool.
+    /// Prepares a validated transaction for the insertion into the pool.
     ///
-    /// Returns the outcome and optionally metadata to be processed after the pool lock is
-    /// released.
+    /// This does all the work that doesn't need the pool lock, so that the lock is only held for
+    /// the insertion itself. Invalid transactions are reported to the event listeners right away.
     ///
     /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
     /// come in through that function, either as a batch or `std::iter::once`.
-    fn add_transaction(
+    fn prepare_transaction(
         &self,
-        pool: &mut RwLockWriteGuard<'_, TxPool<T>>,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
-    ) -> (PoolResult<AddedTransactionOutcome>, Option<AddedTransactionMeta<T::Transaction>>) {
+    ) -> PoolResult<PreparedTransaction<T::Transaction>> {
         match tx {
             TransactionValidationOutcome::Valid {
                 balance,
@@ -587,7 +624,7 @@ where
                     }
                 };
 
-                let tx = ValidPoolTransaction {
+                let transaction = ValidPoolTransaction {
                     transaction,
                     transaction_id,
                     propagate,
@@ -596,28 +633,63 @@ where
                     authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
                 };
 
-                let added = match pool.add_transaction(tx, balance, state_nonce, bytecode_hash) {
-                    Ok(added) => added,
-                    Err(err) => return (Err(err), None),
-                };
-                let hash = *added.hash();
-                let state = added.transaction_state();
-
-                let meta = AddedTransactionMeta { added, blob_sidecar };
-
-                (Ok(AddedTransactionOutcome { hash, state }), Some(meta))
+                Ok(PreparedTransaction {
+                    transaction,
+                    balance,
+                    state_nonce,
+                    bytecode_hash,
+                    blob_sidecar,
+                })
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
-                self.with_event_listener(|listener| listener.invalid(tx.hash()));
-                (Err(PoolError::new(*tx.hash(), err)), None)
+                self.with_event_listener(|listener| listener.invalid(tx.hash(), tx.sender(), &err));
+                Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
-                self.with_event_listener(|listener| listener.discarded(&tx_hash));
-                (Err(PoolError::other(tx_hash, err)), None)
+                self.with_event_listener(|listener| {
+                    listener.discarded(
+                        &tx_hash,
+                        DiscardReason::ValidationError { error: err.to_string() },
+                    )
+                });
+                Err(PoolError::other(tx_hash, err))
             }
         }
     }
 
+    /// Add a single prepared transaction into the pool.
+    ///
+    /// Returns the outcome and optionally metadata to be processed after the pool lock is
+    /// released.
+    ///
+    /// If `track_nonce_gaps` is set and the transaction was queued because of a nonce gap, the gap
+    /// is looked up while the lock is still held.
+    fn add_transaction(
+        pool: &mut RwLockWriteGuard<'_, TxPool<T>>,
+        tx: PreparedTransaction<T::Transaction>,
+        track_nonce_gaps: bool,
+    ) -> (PoolResult<AddedTransactionOutcome>, Option<AddedTransactionMeta<T::Transaction>>) {
+        let PreparedTransaction { transaction, balance, state_nonce, bytecode_hash, blob_sidecar } =
+            tx;
+        let added = match pool.add_transaction(transaction, balance, state_nonce, bytecode_hash) {
+            Ok(added) => added,
+            Err(err) => return (Err(err), None),
+        };
+        let hash = *added.hash();
+        let state = added.transaction_state();
+
+        let nonce_gap =
+            if track_nonce_gaps && added.queued_reason() == Some(&QueuedReason::NonceGap) {
+                pool.nonce_gap(added.id().sender, MAX_QUEUED_HASHES_PER_NONCE_GAP)
+            } else {
+                None
+            };
+
+        let meta = AddedTransactionMeta { added, blob_sidecar, nonce_gap };
+
+        (Ok(AddedTransactionOutcome { hash, state }), Some(meta))
+    }
+
     /// Adds a transaction and returns the event stream.
     pub fn add_transaction_and_subscribe(
         &self,
@@ -655,15 +727,27 @@ where
             Item = (TransactionOrigin, TransactionValidationOutcome<T::Transaction>),
         >,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        // Prepare the transactions before acquiring the pool lock, so that concurrent inserts and
+        // readers are only blocked by the insertions themselves
+        let prepared = transactions
+            .into_iter()
+            .map(|(origin, tx)| self.prepare_transaction(origin, tx))
+            .collect::<Vec<_>>();
+
+        let track_nonce_gaps = self.has_nonce_gap_listeners.load(Ordering::Relaxed);
+
         // Collect results and metadata while holding the pool write lock
         let (mut results, added_metas, discarded) = {
             let mut pool = self.pool.write();
             let mut added_metas = Vec::new();
 
-            let results = transactions
+            let results = prepared
                 .into_iter()
-                .map(|(origin, tx)| {
-                    let (result, meta) = self.add_transaction(&mut pool, origin, tx);
+                .map(|tx| {
+                    let (result, meta) = match tx {
+                        Ok(tx) => Self::add_transaction(&mut pool, tx, track_nonce_gaps),
+                        Err(err) => (Err(err), None),
+                    };
 
                     // Only collect metadata for successful insertions
                     if result.is_ok() &&
@@ -692,11 +776,11 @@ where
 
         if !discarded.is_empty() {
             // Delete any blobs associated with discarded blob transactions
-            self.delete_discarded_blobs(discarded.iter());
-            self.with_event_listener(|listener| listener.discarded_many(&discarded));
+            self.delete_discarded_blobs(discarded.iter().map(|(tx, _)| tx));
+            self.with_event_listener(|listener| listener.evicted(&discarded));
 
             let discarded_hashes =
-                discarded.into_iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+                discarded.into_iter().map(|(tx, _)| *tx.hash()).collect::<HashSet<_>>();
 
             // A newly added transaction may be immediately discarded, so we need to
             // adjust the result here
@@ -740,6 +824,11 @@ where
             self.on_new_pending_transaction(pending);
         }
 
+        // Notify nonce gap listeners
+        if let Some(nonce_gap) = meta.nonce_gap {
+            self.on_nonce_gap(nonce_gap);
+        }
+
         // Notify event listeners
         self.notify_event_listeners(&meta.added);
 
@@ -836,6 +925,19 @@ where
         })
     }
 
+    /// Notify all listeners about the nonce gap of a sender of a newly queued transaction.
+    fn on_nonce_gap(&self, nonce_gap: NonceGap) {
+        let mut listeners = self.nonce_gap_listener.lock();
+        listeners.retain(|listener| match listener.try_send(nonce_gap.clone()) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                debug!(target: "txpool", sender=%nonce_gap.sender, "failed to send nonce gap; channel full");
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+    }
+
     /// Notifies transaction listeners about changes once a block was processed.
     fn notify_on_new_state(&self, outcome: OnNewCanonicalStateOutcome<T::Transaction>) {
         trace!(target: "txpool", promoted=outcome.promoted.len(), discarded= outcome.discarded.len() ,"notifying listeners on state change");
@@ -879,7 +981,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::NonceTooLow);
             }
         })
     }
@@ -947,7 +1049,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::NonceTooLow);
             }
         });
 
@@ -976,7 +1078,7 @@ where
                     listener.pending(tx.hash(), None);
                 }
                 for tx in discarded {
-                    listener.discarded(tx.hash());
+                    listener.discarded(tx.hash(), DiscardReason::NonceTooLow);
                 }
             }
             AddedTransaction::Parked { transaction, replaced, queued_reason, .. } => {
@@ -1048,7 +1150,9 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, &DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1066,7 +1170,7 @@ where
 
         self.with_event_listener(|listener| {
             for tx in &removed {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Removed);
             }
         });
 
@@ -1081,7 +1185,9 @@ where
         let sender_id = self.get_sender_id(sender);
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, &DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1109,13 +1215,12 @@ where
         if announcement.is_empty() {
             return
         }
-        let pool = self.get_pool_data();
-        announcement.retain_by_hash(|tx| !pool.contains(tx))
+        announcement.retain_by_hash(|tx| !self.lookup.contains(tx))
     }
 
     /// Returns the transaction by hash.
     pub fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().get(tx_hash)
+        self.lookup.get(tx_hash)
     }
 
     /// Returns all transactions of the address
@@ -1124,7 +1229,7 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let sender_id = self.get_sender_id(sender);
-        self.get_pool_data().get_transactions_by_sender(sender_id)
+        self.lookup.by_sender(sender_id)
     }
 
     /// Returns a pending transaction sent by the given sender with the given nonce.
@@ -1217,10 +1322,7 @@ where
     ///
     /// If no transaction exists, it is skipped.
     pub fn get_all(&self, txs: Vec<TxHash>) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        if txs.is_empty() {
-            return Vec::new()
-        }
-        self.get_pool_data().get_all(txs).collect()
+        txs.iter().filter_map(|tx| self.lookup.get(tx)).collect()
     }
 
     /// Returns all the transactions belonging to the hashes that are propagatable.
@@ -1230,11 +1332,7 @@ where
         &self,
         txs: &[TxHash],
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        if txs.is_empty() {
-            return Vec::new()
-        }
-        let pool = self.get_pool_data();
-        txs.iter().filter_map(|tx| pool.get(tx).filter(|tx| tx.propagate)).collect()
+        txs.iter().filter_map(|tx| self.lookup.get(tx).filter(|tx| tx.propagate)).collect()
     }
 
     /// Notify about propagated transactions.
@@ -1265,10 +1363,12 @@ where
     /// Inserts a blob transaction into the blob store
     fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecarVariant) {
         debug!(target: "txpool", "[{:?}] storing blob sidecar", hash);
+        let start = Instant::now();
         if let Err(err) = self.blob_store.insert(hash, blob) {
             warn!(target: "txpool", %err, "[{:?}] failed to insert blob", hash);
             self.blob_store_metrics.blobstore_failed_inserts.increment(1);
         }
+        self.blob_store_metrics.blobstore_insert_duration_seconds.record(start.elapsed());
         self.update_blob_store_metrics();
     }
 
@@ -1284,7 +1384,9 @@ where
 
     /// Cleans up the blob store
     pub fn cleanup_blobs(&self) {
+        let start = Instant::now();
         let stat = self.blob_store.cleanup();
+        self.blob_store_metrics.blobstore_cleanup_duration_seconds.record(start.elapsed());
         self.blob_store_metrics.blobstore_failed_deletes.increment(stat.delete_failed as u64);
         self.update_blob_store_metrics();
     }
@@ -1326,6 +1428,22 @@ struct AddedTransactionMeta<T: PoolTransaction> {
     added: AddedTransaction<T>,
     /// Optional blob sidecar for EIP-4844 transactions
     blob_sidecar: Option<BlobTransactionSidecarVariant>,
+    /// The nonce gap the transaction is queued behind, if tracked
+    nonce_gap: Option<NonceGap>,
+}
+
+/// A validated transaction that's ready to be inserted into the pool.
+struct PreparedTransaction<T: PoolTransaction> {
+    /// The transaction to insert.
+    transaction: ValidPoolTransaction<T>,
+    /// The balance of the sender.
+    balance: U256,
+    /// The on chain nonce of the sender.
+    state_nonce: u64,
+    /// The code hash of the sender, if it has code.
+    bytecode_hash: Option<B256>,
+    /// The blob sidecar of an EIP-4844 transaction, which is stored separately.
+    blob_sidecar: Option<BlobTransactionSidecarVariant>,
 }
 
 /// Tracks an added transaction and all graph changes caused by adding it.
@@ -1484,7 +1602,6 @@ impl<T: PoolTransaction> AddedTransaction<T> {
     }
 
     /// Returns the [`TransactionId`] of the added transaction
-    #[cfg(test)]
     pub(crate) fn id(&self) -> &TransactionId {
         match self {
             Self::Pending(added) => added.transaction.id(),
@@ -1520,6 +1637,8 @@ impl<T: PoolTransaction> AddedTransaction<T> {
 
 /// The specific reason why a transaction is queued (not ready for execution)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum QueuedReason {
     /// Transaction has a nonce gap - missing prior transactions
     NonceGap,
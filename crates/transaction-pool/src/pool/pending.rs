@@ -113,6 +113,8 @@ impl<T: TransactionOrdering> PendingPool<T> {
             new_transaction_receiver: Some(self.new_transaction_notifier.subscribe()),
             last_priority: None,
             skip_blobs: false,
+            max_transactions_per_sender: self.ordering.max_transactions_per_sender(),
+            yielded_per_sender: Default::default(),
         }
     }
 
@@ -253,6 +255,19 @@ impl<T: TransactionOrdering> PendingPool<T> {
         removed
     }
 
+    /// Recomputes the priorities of all transactions, e.g. after the ordering was reconfigured.
+    ///
+    /// This expects all transactions to satisfy the given base fee.
+    pub(crate) fn reprioritize(&mut self, base_fee: u64) {
+        let removed = self.update_base_fee(base_fee);
+        debug_assert!(removed.is_empty(), "pending transactions must satisfy the base fee");
+    }
+
+    /// Returns the ordering of the pool.
+    pub(crate) const fn ordering(&self) -> &T {
+        &self.ordering
+    }
+
     /// Updates the independent transaction and highest nonces set, assuming the given transaction
     /// is being _added_ to the pool.
     fn update_independents_and_highest_nonces(&mut self, tx: &PendingTransaction<T>) {
@@ -656,7 +671,7 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
-        PoolTransaction,
+        ConfigurableOrdering, OrderingConfig, OrderingStrategy, PoolTransaction,
     };
     use alloy_consensus::{Transaction, TxType};
     use alloy_primitives::address;
@@ -982,6 +997,33 @@ mod tests {
         assert!(!pool.contains(tx1.id()));
     }
 
+    #[test]
+    fn test_reorder_on_ordering_switch() {
+        let mut f = MockTransactionFactory::default();
+        let ordering = ConfigurableOrdering::default();
+        let mut pool = PendingPool::new(ordering.clone());
+
+        let tx1 =
+            f.validated_arc(MockTransaction::eip1559().with_priority_fee(11).with_max_fee(100));
+        let tx2 =
+            f.validated_arc(MockTransaction::eip1559().with_priority_fee(19).with_max_fee(100));
+        pool.add_transaction(tx1.clone(), 0);
+        pool.add_transaction(tx2.clone(), 0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*tx2.hash(), *tx1.hash()]);
+
+        ordering.set_config(OrderingConfig {
+            strategy: OrderingStrategy::FifoFeeBuckets { bucket_size: 10 },
+            max_transactions_per_sender: None,
+        });
+        pool.reprioritize(0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*tx1.hash(), *tx2.hash()]);
+        pool.assert_invariants();
+    }
+
     #[test]
     #[cfg(debug_assertions)]
     #[should_panic(expected = "transaction already included")]
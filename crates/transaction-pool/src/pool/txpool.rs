@@ -11,13 +11,14 @@ use crate::{
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
+        lookup::TransactionLookup,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate, UpdateOutcome},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
+    traits::{BestTransactionsAttributes, BlockInfo, NonceGap, PoolSize},
     PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -548,22 +549,6 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.config.is_exceeded(self.size())
     }
 
-    /// Returns the transaction for the given hash.
-    pub(crate) fn get(
-        &self,
-        tx_hash: &TxHash,
-    ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.all_transactions.by_hash.get(tx_hash).cloned()
-    }
-
-    /// Returns transactions for the multiple given hashes, if they exist.
-    pub(crate) fn get_all(
-        &self,
-        txs: Vec<TxHash>,
-    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
-        txs.into_iter().filter_map(|tx| self.get(&tx))
-    }
-
     /// Returns all transactions sent from the given sender.
     pub(crate) fn get_transactions_by_sender(
         &self,
@@ -572,6 +557,38 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
     }
 
+    /// Returns the ordering of the pending subpool.
+    pub(crate) const fn ordering(&self) -> &T {
+        self.pending_pool.ordering()
+    }
+
+    /// Recomputes the priorities of all pending transactions, e.g. after the ordering was
+    /// reconfigured.
+    pub(crate) fn reprioritize_pending(&mut self) {
+        self.pending_pool.reprioritize(self.all_transactions.pending_fees.base_fee);
+    }
+
+    /// Returns the first nonce gap of the given sender, together with the hashes of up to
+    /// `max_queued` of the sender's transactions that are stuck behind it, lowest nonce first.
+    ///
+    /// Returns `None` if the sender's transactions are gapless.
+    pub(crate) fn nonce_gap(&self, sender: SenderId, max_queued: usize) -> Option<NonceGap> {
+        let mut missing_nonce = self.all_transactions.sender_info.get(&sender)?.state_nonce;
+        let mut txs = self.all_transactions.txs_iter(sender).peekable();
+        while let Some((id, _)) = txs.next_if(|(id, _)| id.nonce <= missing_nonce) {
+            if id.nonce == missing_nonce {
+                missing_nonce += 1;
+            }
+        }
+
+        let (_, first_queued) = txs.peek()?;
+        Some(NonceGap {
+            sender: first_queued.transaction.sender(),
+            missing_nonce,
+            queued: txs.take(max_queued).map(|(_, tx)| *tx.transaction.hash()).collect(),
+        })
+    }
+
     /// Returns a pending transaction sent by the given sender with the given nonce.
     pub(crate) fn get_pending_transaction_by_sender_and_nonce(
         &self,
@@ -1243,13 +1260,16 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// If the current size exceeds the given bounds, the worst transactions are evicted from the
     /// pool and returned.
     ///
-    /// This returns all transactions that were removed from the entire pool.
-    pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+    /// This returns all transactions that were removed from the entire pool, together with the
+    /// sub-pool whose limit caused their removal.
+    pub(crate) fn discard_worst(
+        &mut self,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, SubPool)> {
         let mut removed = Vec::new();
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $metric:ident)),* $(,)*]) => {
+            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $subpool:ident, $metric:ident)),* $(,)*]) => {
                 $ (
                 while $this.$pool.exceeds(&$this.config.$limit)
                     {
@@ -1283,10 +1303,14 @@ impl<T: TransactionOrdering> TxPool<T> {
                             let id = *tx.id();
 
                             // keep track of removed transaction
-                            removed.push(tx);
+                            $removed.push((tx, SubPool::$subpool));
 
                             // 3. remove all its descendants from the entire pool
-                            $this.remove_descendants(&id, &mut $removed);
+                            let mut descendants = Vec::new();
+                            $this.remove_descendants(&id, &mut descendants);
+                            $removed.extend(
+                                descendants.into_iter().map(|tx| (tx, SubPool::$subpool)),
+                            );
                         }
                     }
 
@@ -1296,10 +1320,10 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit => (pending_pool, pending_transactions_evicted),
-                basefee_limit => (basefee_pool, basefee_transactions_evicted),
-                blob_limit    => (blob_pool, blob_transactions_evicted),
-                queued_limit  => (queued_pool, queued_transactions_evicted),
+                pending_limit => (pending_pool, Pending, pending_transactions_evicted),
+                basefee_limit => (basefee_pool, BaseFee, basefee_transactions_evicted),
+                blob_limit    => (blob_pool, Blob, blob_transactions_evicted),
+                queued_limit  => (queued_pool, Queued, queued_transactions_evicted),
             ]
         );
 
@@ -1393,6 +1417,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     max_account_slots: usize,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+    /// Mirror of `by_hash` that can be read without the pool lock.
+    lookup: Arc<TransactionLookup<T>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Contains the currently known information about the senders.
@@ -1428,6 +1454,18 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Returns the index of all transactions that can be read without the pool lock.
+    pub(crate) const fn lookup(&self) -> &Arc<TransactionLookup<T>> {
+        &self.lookup
+    }
+
+    /// Removes the transaction from `by_hash` and the lock-free lookup.
+    fn remove_hash(&mut self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T>>> {
+        let tx = self.by_hash.remove(tx_hash)?;
+        self.lookup.remove(&tx);
+        Some(tx)
+    }
+
     /// Returns an iterator over all _unique_ hashes in the pool
     #[expect(dead_code)]
     pub(crate) fn hashes_iter(&self) -> impl Iterator<Item = TxHash> + '_ {
@@ -1746,7 +1784,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         &mut self,
         tx_hash: &B256,
     ) -> Option<(Arc<ValidPoolTransaction<T>>, SubPool)> {
-        let tx = self.by_hash.remove(tx_hash)?;
+        let tx = self.remove_hash(tx_hash)?;
         let internal = self.txs.remove(&tx.transaction_id)?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
@@ -1762,7 +1800,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         tx_id: &TransactionId,
     ) -> Option<(Arc<ValidPoolTransaction<T>>, SubPool)> {
         let internal = self.txs.remove(tx_id)?;
-        let tx = self.by_hash.remove(internal.transaction.hash())?;
+        let tx = self.remove_hash(internal.transaction.hash())?;
         self.remove_auths(&internal);
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
@@ -1811,8 +1849,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
 
-        let result =
-            self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
+        let result = self.remove_hash(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
 
         self.remove_auths(&internal);
 
@@ -2055,6 +2092,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             Entry::Vacant(entry) => {
                 // Insert the transaction in both maps
                 self.by_hash.insert(*pool_tx.transaction.hash(), pool_tx.transaction.clone());
+                self.lookup.insert(pool_tx.transaction.clone());
                 entry.insert(pool_tx);
             }
             Entry::Occupied(mut entry) => {
@@ -2073,7 +2111,9 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
                 self.by_hash.remove(replaced.transaction.hash());
-                self.by_hash.insert(new_hash, new_transaction);
+                self.by_hash.insert(new_hash, new_transaction.clone());
+                self.lookup.remove(&replaced.transaction);
+                self.lookup.insert(new_transaction);
 
                 self.remove_auths(&replaced);
 
@@ -2180,6 +2220,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     #[cfg(any(test, feature = "test-utils"))]
     pub(crate) fn assert_invariants(&self) {
         assert_eq!(self.by_hash.len(), self.txs.len(), "by_hash.len() != txs.len()");
+        assert_eq!(self.lookup.len(), self.by_hash.len(), "lookup.len() != by_hash.len()");
         assert!(self.auths.len() <= self.txs.len(), "auths.len() > txs.len()");
     }
 }
@@ -2201,6 +2242,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
             by_hash: Default::default(),
+            lookup: Default::default(),
             txs: Default::default(),
             sender_info: Default::default(),
             tx_counter: Default::default(),
@@ -0,0 +1,633 @@
+//! Recording and replay of the transactions that arrive at the pool.
+//!
+//! A [`PoolRecorder`] attached to a [`Pool`](crate::Pool) with
+//! [`Pool::with_recorder`](crate::Pool::with_recorder) logs every transaction that's submitted to
+//! the pool, with its origin, arrival time, EIP-2718 encoded pooled bytes and validation outcome,
+//! together with the canonical state changes the pool is updated with.
+//!
+//! A [`PoolReplayer`] reads such a recording and drives another pool with the same traffic and
+//! timing, which makes it possible to benchmark pool changes against real network traffic.
+//!
+//! ## File format
+//!
+//! The file starts with [`RECORDING_MAGIC`], followed by records that each start with a kind byte
+//! and the microseconds elapsed since the recording started. All integers are little endian and
+//! variable length fields are prefixed with their `u32` length.
+
+use crate::{
+    error::PoolErrorKind, CanonicalStateUpdate, EthBlobTransactionSidecar, EthPoolTransaction,
+    PoolResult, PoolTransaction, PoolUpdateKind, TransactionOrigin, TransactionPoolExt,
+    TransactionValidationOutcome,
+};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use parking_lot::Mutex;
+use reth_execution_types::ChangedAccount;
+use reth_primitives_traits::{Block, SealedBlock, SignedTransaction};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// The magic bytes a pool recording starts with.
+pub const RECORDING_MAGIC: &[u8; 8] = b"rethpl01";
+
+/// Kind byte of a [`TransactionRecord`].
+const TRANSACTION_RECORD: u8 = 0;
+/// Kind byte of a [`CanonicalStateRecord`].
+const CANONICAL_STATE_RECORD: u8 = 1;
+
+/// Records the transaction arrivals and canonical state changes of a pool to a file.
+///
+/// The recorder is cheap to clone, all clones write to the same file.
+#[derive(Clone)]
+pub struct PoolRecorder {
+    inner: Arc<PoolRecorderInner>,
+}
+
+struct PoolRecorderInner {
+    /// When the recording started, the elapsed time of each record is relative to this.
+    started_at: Instant,
+    /// The buffered file the records are written to.
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl PoolRecorder {
+    /// Creates a new recording at the given path, truncating an existing file.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(RECORDING_MAGIC)?;
+        Ok(Self {
+            inner: Arc::new(PoolRecorderInner {
+                started_at: Instant::now(),
+                writer: Mutex::new(writer),
+            }),
+        })
+    }
+
+    /// Records a transaction that was submitted to the pool.
+    pub fn record_transaction(&self, record: TransactionRecord) {
+        self.write(|writer| record.encode(writer));
+    }
+
+    /// Records a canonical state change the pool is updated with.
+    ///
+    /// This also flushes the recording, so that the file is complete up to the last block.
+    pub fn record_canonical_state_change<B: Block>(&self, update: &CanonicalStateUpdate<'_, B>) {
+        let record = CanonicalStateRecord::new(self.elapsed(), update);
+        self.write(|writer| {
+            record.encode(writer)?;
+            writer.flush()
+        });
+    }
+
+    /// Flushes the buffered records to the file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.inner.writer.lock().flush()
+    }
+
+    /// Returns the time since the recording started.
+    pub fn elapsed(&self) -> Duration {
+        self.inner.started_at.elapsed()
+    }
+
+    fn write(&self, f: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>) {
+        if let Err(err) = f(&mut self.inner.writer.lock()) {
+            warn!(target: "txpool::recorder", %err, "Failed to write pool recording");
+        }
+    }
+}
+
+impl std::fmt::Debug for PoolRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolRecorder").field("started_at", &self.inner.started_at).finish()
+    }
+}
+
+/// A record of a pool recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolRecord {
+    /// A transaction was submitted to the pool.
+    Transaction(TransactionRecord),
+    /// The pool was updated with a canonical state change.
+    CanonicalState(CanonicalStateRecord),
+}
+
+impl PoolRecord {
+    /// Returns the time since the start of the recording at which this was recorded.
+    pub const fn elapsed(&self) -> Duration {
+        match self {
+            Self::Transaction(record) => record.elapsed,
+            Self::CanonicalState(record) => record.elapsed,
+        }
+    }
+
+    /// Reads the next record, returns `None` at the end of the recording.
+    fn decode(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut kind = [0u8];
+        if reader.read(&mut kind)? == 0 {
+            return Ok(None)
+        }
+        match kind[0] {
+            TRANSACTION_RECORD => Ok(Some(Self::Transaction(TransactionRecord::decode(reader)?))),
+            CANONICAL_STATE_RECORD => {
+                Ok(Some(Self::CanonicalState(CanonicalStateRecord::decode(reader)?)))
+            }
+            kind => Err(invalid_data(format!("unknown record kind {kind}"))),
+        }
+    }
+}
+
+/// The validation outcome of a recorded transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedOutcome {
+    /// The transaction passed validation.
+    Valid,
+    /// The transaction failed validation.
+    Invalid,
+    /// The transaction couldn't be validated.
+    Error,
+}
+
+impl RecordedOutcome {
+    /// Returns the recorded outcome of the validation outcome.
+    pub const fn of<T: PoolTransaction>(outcome: &TransactionValidationOutcome<T>) -> Self {
+        match outcome {
+            TransactionValidationOutcome::Valid { .. } => Self::Valid,
+            TransactionValidationOutcome::Invalid(..) => Self::Invalid,
+            TransactionValidationOutcome::Error(..) => Self::Error,
+        }
+    }
+
+    /// Returns the validation outcome that led to the result of adding a transaction.
+    ///
+    /// Errors of the pool itself, e.g. an underpriced replacement, happen after the validation
+    /// succeeded.
+    pub const fn of_result<T>(result: &PoolResult<T>) -> Self {
+        match result {
+            Ok(_) => Self::Valid,
+            Err(err) => match err.kind {
+                PoolErrorKind::InvalidTransaction(_) => Self::Invalid,
+                PoolErrorKind::Other(_) => Self::Error,
+                _ => Self::Valid,
+            },
+        }
+    }
+
+    const fn to_byte(self) -> u8 {
+        match self {
+            Self::Valid => 0,
+            Self::Invalid => 1,
+            Self::Error => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(Self::Valid),
+            1 => Ok(Self::Invalid),
+            2 => Ok(Self::Error),
+            byte => Err(invalid_data(format!("unknown validation outcome {byte}"))),
+        }
+    }
+}
+
+/// A transaction that was submitted to the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionRecord {
+    /// Time since the start of the recording.
+    pub elapsed: Duration,
+    /// Where the transaction came from.
+    pub origin: TransactionOrigin,
+    /// The EIP-2718 encoded pooled transaction, including the blob sidecar.
+    pub raw: Bytes,
+    /// The validation outcome of the transaction.
+    pub outcome: RecordedOutcome,
+}
+
+impl TransactionRecord {
+    fn encode(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&[TRANSACTION_RECORD])?;
+        write_u64(writer, self.elapsed.as_micros() as u64)?;
+        writer.write_all(&[origin_to_byte(self.origin), self.outcome.to_byte()])?;
+        write_bytes(writer, &self.raw)
+    }
+
+    fn decode(reader: &mut impl Read) -> io::Result<Self> {
+        let elapsed = Duration::from_micros(read_u64(reader)?);
+        let [origin, outcome] = read_array(reader)?;
+        Ok(Self {
+            elapsed,
+            origin: origin_from_byte(origin)?,
+            outcome: RecordedOutcome::from_byte(outcome)?,
+            raw: read_bytes(reader)?,
+        })
+    }
+}
+
+/// A canonical state change the pool was updated with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalStateRecord {
+    /// Time since the start of the recording.
+    pub elapsed: Duration,
+    /// The kind of the update.
+    pub update_kind: PoolUpdateKind,
+    /// The hash of the new tip block.
+    pub tip_hash: B256,
+    /// The RLP encoded header of the new tip block.
+    pub tip_header: Bytes,
+    /// EIP-1559 base fee of the pending block.
+    pub pending_block_base_fee: u64,
+    /// EIP-4844 blob fee of the pending block.
+    pub pending_block_blob_fee: Option<u128>,
+    /// The accounts that were changed by the update.
+    pub changed_accounts: Vec<ChangedAccount>,
+    /// The transactions that were mined by the update.
+    pub mined_transactions: Vec<B256>,
+}
+
+impl CanonicalStateRecord {
+    /// Creates the record of a canonical state update.
+    pub fn new<B: Block>(elapsed: Duration, update: &CanonicalStateUpdate<'_, B>) -> Self {
+        Self {
+            elapsed,
+            update_kind: update.update_kind,
+            tip_hash: update.new_tip.hash(),
+            tip_header: alloy_rlp::encode(update.new_tip.header()).into(),
+            pending_block_base_fee: update.pending_block_base_fee,
+            pending_block_blob_fee: update.pending_block_blob_fee,
+            changed_accounts: update.changed_accounts.clone(),
+            mined_transactions: update.mined_transactions.clone(),
+        }
+    }
+
+    /// Returns the tip block of the update, without a body.
+    pub fn tip_block<B: Block>(&self) -> alloy_rlp::Result<SealedBlock<B>> {
+        let header = alloy_rlp::Decodable::decode(&mut self.tip_header.as_ref())?;
+        Ok(SealedBlock::new_unchecked(B::new(header, Default::default()), self.tip_hash))
+    }
+
+    fn encode(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&[CANONICAL_STATE_RECORD])?;
+        write_u64(writer, self.elapsed.as_micros() as u64)?;
+        let update_kind = match self.update_kind {
+            PoolUpdateKind::Commit => 0,
+            PoolUpdateKind::Reorg => 1,
+        };
+        writer.write_all(&[update_kind])?;
+        writer.write_all(self.tip_hash.as_slice())?;
+        write_bytes(writer, &self.tip_header)?;
+        write_u64(writer, self.pending_block_base_fee)?;
+        match self.pending_block_blob_fee {
+            Some(fee) => {
+                writer.write_all(&[1])?;
+                writer.write_all(&fee.to_le_bytes())?;
+            }
+            None => writer.write_all(&[0])?,
+        }
+        write_u32(writer, self.changed_accounts.len())?;
+        for account in &self.changed_accounts {
+            writer.write_all(account.address.as_slice())?;
+            write_u64(writer, account.nonce)?;
+            writer.write_all(&account.balance.to_le_bytes::<32>())?;
+        }
+        write_u32(writer, self.mined_transactions.len())?;
+        for hash in &self.mined_transactions {
+            writer.write_all(hash.as_slice())?;
+        }
+        Ok(())
+    }
+
+    fn decode(reader: &mut impl Read) -> io::Result<Self> {
+        let elapsed = Duration::from_micros(read_u64(reader)?);
+        let update_kind = match read_array::<1>(reader)?[0] {
+            0 => PoolUpdateKind::Commit,
+            1 => PoolUpdateKind::Reorg,
+            kind => return Err(invalid_data(format!("unknown update kind {kind}"))),
+        };
+        let tip_hash = B256::from(read_array(reader)?);
+        let tip_header = read_bytes(reader)?;
+        let pending_block_base_fee = read_u64(reader)?;
+        let pending_block_blob_fee = match read_array::<1>(reader)?[0] {
+            0 => None,
+            _ => Some(u128::from_le_bytes(read_array(reader)?)),
+        };
+        let changed_accounts = (0..read_u32(reader)?)
+            .map(|_| {
+                Ok(ChangedAccount {
+                    address: Address::from(read_array(reader)?),
+                    nonce: read_u64(reader)?,
+                    balance: U256::from_le_bytes::<32>(read_array(reader)?),
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        let mined_transactions = (0..read_u32(reader)?)
+            .map(|_| read_array(reader).map(B256::from))
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            elapsed,
+            update_kind,
+            tip_hash,
+            tip_header,
+            pending_block_base_fee,
+            pending_block_blob_fee,
+            changed_accounts,
+            mined_transactions,
+        })
+    }
+}
+
+/// Reads all records of a recording.
+pub fn read_recording(path: impl AsRef<Path>) -> io::Result<Vec<PoolRecord>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    if &read_array::<8>(&mut reader)? != RECORDING_MAGIC {
+        return Err(invalid_data("not a pool recording"))
+    }
+
+    let mut records = Vec::new();
+    loop {
+        match PoolRecord::decode(&mut reader) {
+            Ok(Some(record)) => records.push(record),
+            Ok(None) => break,
+            // the recording ends with a partial record if the node wasn't shut down cleanly
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                debug!(target: "txpool::recorder", "Recording ends with a partial record");
+                break
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(records)
+}
+
+/// Returns the EIP-2718 encoded pooled form of the transaction, including its blob sidecar.
+///
+/// Returns `None` for blob transactions without a sidecar.
+pub(crate) fn encode_pooled<T: EthPoolTransaction>(transaction: &T) -> Option<Bytes> {
+    let pooled = if transaction.is_eip4844() {
+        let mut transaction = transaction.clone();
+        let EthBlobTransactionSidecar::Present(sidecar) = transaction.take_blob() else {
+            return None
+        };
+        transaction.try_into_pooled_eip4844(Arc::new(sidecar))?
+    } else {
+        transaction.clone_into_pooled().ok()?
+    };
+    Some(pooled.into_inner().encoded_2718().into())
+}
+
+/// Replays a pool recording.
+#[derive(Debug, Clone)]
+pub struct PoolReplayer {
+    /// The records to replay.
+    records: Vec<PoolRecord>,
+    /// How much faster than recorded to replay, `None` replays as fast as possible.
+    speed: Option<f64>,
+    /// Whether to apply the recorded canonical state changes.
+    canonical_state: bool,
+}
+
+impl PoolReplayer {
+    /// Creates a replayer of the records, with the original timing.
+    pub const fn new(records: Vec<PoolRecord>) -> Self {
+        Self { records, speed: Some(1.0), canonical_state: true }
+    }
+
+    /// Replays `speed` times faster than the original timing.
+    pub const fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Replays the records as fast as possible.
+    pub const fn without_delays(mut self) -> Self {
+        self.speed = None;
+        self
+    }
+
+    /// Only replays the transactions.
+    ///
+    /// This should be used for the pool of a running node, which is kept up to date with the
+    /// canonical state of the node.
+    pub const fn without_canonical_state(mut self) -> Self {
+        self.canonical_state = false;
+        self
+    }
+
+    /// Returns the records.
+    pub fn records(&self) -> &[PoolRecord] {
+        &self.records
+    }
+
+    /// Replays the records into the pool.
+    pub async fn replay<P>(&self, pool: &P) -> ReplayStats
+    where
+        P: TransactionPoolExt,
+    {
+        let started_at = Instant::now();
+        let mut stats = ReplayStats::default();
+
+        for record in &self.records {
+            if let Some(speed) = self.speed {
+                let at = started_at + record.elapsed().div_f64(speed);
+                tokio::time::sleep_until(at.into()).await;
+            }
+
+            match record {
+                PoolRecord::Transaction(record) => {
+                    let Some(transaction) = decode_pooled::<P::Transaction>(&record.raw) else {
+                        stats.undecodable += 1;
+                        continue
+                    };
+                    let result = pool.add_transaction(record.origin, transaction).await;
+                    stats.transactions += 1;
+                    if RecordedOutcome::of_result(&result) != record.outcome {
+                        stats.outcome_mismatches += 1;
+                    }
+                }
+                PoolRecord::CanonicalState(record) if self.canonical_state => {
+                    let Ok(tip) = record.tip_block::<P::Block>() else {
+                        stats.undecodable += 1;
+                        continue
+                    };
+                    pool.on_canonical_state_change(CanonicalStateUpdate {
+                        new_tip: &tip,
+                        pending_block_base_fee: record.pending_block_base_fee,
+                        pending_block_blob_fee: record.pending_block_blob_fee,
+                        changed_accounts: record.changed_accounts.clone(),
+                        mined_transactions: record.mined_transactions.clone(),
+                        update_kind: record.update_kind,
+                    });
+                    stats.canonical_state_changes += 1;
+                }
+                PoolRecord::CanonicalState(_) => {}
+            }
+        }
+
+        stats.duration = started_at.elapsed();
+        stats
+    }
+}
+
+/// Statistics of a replay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Number of replayed transactions.
+    pub transactions: usize,
+    /// Number of applied canonical state changes.
+    pub canonical_state_changes: usize,
+    /// Number of records that couldn't be decoded.
+    pub undecodable: usize,
+    /// Number of transactions whose validation outcome differs from the recorded outcome.
+    pub outcome_mismatches: usize,
+    /// How long the replay took.
+    pub duration: Duration,
+}
+
+/// Decodes a recorded pooled transaction.
+fn decode_pooled<T: PoolTransaction>(raw: &[u8]) -> Option<T> {
+    let pooled = T::Pooled::decode_2718(&mut &raw[..]).ok()?;
+    Some(T::from_pooled(pooled.try_into_recovered().ok()?))
+}
+
+const fn origin_to_byte(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+fn origin_from_byte(byte: u8) -> io::Result<TransactionOrigin> {
+    match byte {
+        0 => Ok(TransactionOrigin::Local),
+        1 => Ok(TransactionOrigin::External),
+        2 => Ok(TransactionOrigin::Private),
+        byte => Err(invalid_data(format!("unknown transaction origin {byte}"))),
+    }
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn write_u32(writer: &mut impl Write, value: usize) -> io::Result<()> {
+    let value = u32::try_from(value).map_err(|_| invalid_data("record field too large"))?;
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_u64(writer: &mut impl Write, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    write_u32(writer, bytes.len())?;
+    writer.write_all(bytes)
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    read_array(reader).map(u32::from_le_bytes)
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    read_array(reader).map(u64::from_le_bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<Bytes> {
+    let mut buf = vec![0u8; read_u32(reader)? as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(buf.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionPool,
+    };
+    use alloy_consensus::Header;
+    use reth_ethereum_primitives::Block as EthBlock;
+
+    #[test]
+    fn records_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pool.rec");
+        let recorder = PoolRecorder::create(&path).unwrap();
+
+        let transaction = TransactionRecord {
+            elapsed: Duration::from_micros(42),
+            origin: TransactionOrigin::External,
+            raw: Bytes::from_static(&[1, 2, 3]),
+            outcome: RecordedOutcome::Invalid,
+        };
+        recorder.record_transaction(transaction.clone());
+
+        let tip = SealedBlock::<EthBlock>::seal_parts(
+            Header { number: 7, ..Default::default() },
+            Default::default(),
+        );
+        recorder.record_canonical_state_change(&CanonicalStateUpdate {
+            new_tip: &tip,
+            pending_block_base_fee: 10,
+            pending_block_blob_fee: Some(3),
+            changed_accounts: vec![ChangedAccount {
+                address: Address::random(),
+                nonce: 1,
+                balance: U256::from(100),
+            }],
+            mined_transactions: vec![B256::random()],
+            update_kind: PoolUpdateKind::Reorg,
+        });
+
+        let records = read_recording(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], PoolRecord::Transaction(transaction));
+        let PoolRecord::CanonicalState(canonical) = &records[1] else { panic!("{records:?}") };
+        assert_eq!(canonical.update_kind, PoolUpdateKind::Reorg);
+        assert_eq!(canonical.pending_block_blob_fee, Some(3));
+        assert_eq!(canonical.tip_block::<EthBlock>().unwrap(), tip);
+
+        // a partial trailing record is ignored
+        let mut contents = std::fs::read(&path).unwrap();
+        contents.truncate(contents.len() - 1);
+        std::fs::write(&path, contents).unwrap();
+        assert_eq!(read_recording(&path).unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replays_recorded_pool() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pool.rec");
+
+        let pool = testing_pool().with_recorder(PoolRecorder::create(&path).unwrap());
+        pool.add_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await
+            .unwrap();
+        pool.add_transaction(TransactionOrigin::Local, MockTransaction::eip1559()).await.unwrap();
+        drop(pool);
+
+        let records = read_recording(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        let PoolRecord::Transaction(record) = &records[0] else { panic!("{records:?}") };
+        assert_eq!(record.origin, TransactionOrigin::External);
+        assert_eq!(record.outcome, RecordedOutcome::Valid);
+
+        let replayed = testing_pool();
+        let stats = PoolReplayer::new(records).without_delays().replay(&replayed).await;
+        assert_eq!(stats.transactions, 2);
+        assert_eq!(stats.outcome_mismatches, 0);
+        assert_eq!(replayed.len(), 2);
+    }
+}
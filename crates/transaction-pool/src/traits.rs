@@ -257,6 +257,12 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// commitments/proofs) for eip-4844 transactions inserted into the pool
     fn blob_transaction_sidecars_listener(&self) -> Receiver<NewBlobSidecar>;
 
+    /// Returns a new [Receiver] that yields a [`NonceGap`] whenever a transaction is parked in the
+    /// queued subpool because a transaction with a lower nonce of the same sender is missing.
+    ///
+    /// This allows the network to actively fetch the missing transaction.
+    fn nonce_gap_listener(&self) -> Receiver<NonceGap>;
+
     /// Returns a new stream that yields new valid transactions added to the pool
     /// depending on the given [`TransactionListenerKind`] argument.
     fn new_transactions_listener_for(
@@ -903,6 +909,18 @@ pub struct NewBlobSidecar {
     pub sidecar: Arc<BlobTransactionSidecarVariant>,
 }
 
+/// A gap in the nonces of a sender's transactions, which keeps the sender's transactions with
+/// higher nonces from becoming executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceGap {
+    /// The sender of the transactions.
+    pub sender: Address,
+    /// The lowest nonce of the sender that is missing from the pool.
+    pub missing_nonce: u64,
+    /// Hashes of the sender's transactions that are queued behind the gap, lowest nonce first.
+    pub queued: Vec<TxHash>,
+}
+
 /// Where the transaction originates from.
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
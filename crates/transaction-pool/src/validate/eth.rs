@@ -7,6 +7,7 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     metrics::TxPoolValidationMetrics,
+    policy::InclusionPolicyHandle,
     traits::TransactionOrigin,
     validate::{ValidTransaction, ValidationTask},
     Address, BlobTransactionSidecarVariant, EthBlobTransactionSidecar, EthPoolTransaction,
@@ -85,6 +86,8 @@ pub struct EthTransactionValidator<Client, T, Evm> {
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Policy deciding which transactions may be admitted to the pool.
+    inclusion_policy: InclusionPolicyHandle,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
@@ -172,6 +175,11 @@ impl<Client, Tx, Evm> EthTransactionValidator<Client, Tx, Evm> {
         &self.local_transactions_config
     }
 
+    /// Returns the policy deciding which transactions may be admitted to the pool.
+    pub const fn inclusion_policy(&self) -> &InclusionPolicyHandle {
+        &self.inclusion_policy
+    }
+
     /// Returns the maximum size in bytes a single transaction can have in order to be accepted into
     /// the pool.
     pub const fn max_tx_input_bytes(&self) -> usize {
@@ -464,6 +472,14 @@ where
             ))
         }
 
+        // Drop transactions excluded by the local inclusion policy
+        if let Some(violation) = self.inclusion_policy.check_admission(&transaction) {
+            return Err(TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::other(violation),
+            ))
+        }
+
         if transaction.is_eip7702() {
             // Prague fork is required for 7702 txs
             if !self.fork_tracker.is_prague_activated() {
@@ -919,6 +935,8 @@ pub struct EthTransactionValidatorBuilder<Client, Evm> {
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
+    /// Policy deciding which transactions may be admitted to the pool.
+    inclusion_policy: InclusionPolicyHandle,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
@@ -969,6 +987,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             additional_tasks: 1,
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
+            inclusion_policy: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             tx_fee_cap: Some(1e18 as u128),
             max_tx_gas_limit: None,
@@ -1018,6 +1037,14 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
         self
     }
 
+    /// Sets the policy deciding which transactions may be admitted to the pool.
+    ///
+    /// The handle is shared, so policy updates apply to all transactions validated afterwards.
+    pub fn with_inclusion_policy(mut self, inclusion_policy: InclusionPolicyHandle) -> Self {
+        self.inclusion_policy = inclusion_policy;
+        self
+    }
+
     /// Set the Cancun fork.
     pub const fn set_cancun(mut self, cancun: bool) -> Self {
         self.cancun = cancun;
@@ -1200,6 +1227,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             minimum_priority_fee,
             kzg_settings,
             local_transactions_config,
+            inclusion_policy,
             max_tx_input_bytes,
             max_tx_gas_limit,
             disable_balance_check,
@@ -1235,6 +1263,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
+            inclusion_policy,
             max_tx_input_bytes,
             max_tx_gas_limit,
             disable_balance_check,
@@ -1378,7 +1407,10 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
+        blobstore::InMemoryBlobStore,
+        error::PoolErrorKind,
+        policy::{DenylistPolicy, PolicyViolation},
+        traits::PoolTransaction,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use alloy_consensus::Transaction;
@@ -1792,6 +1824,31 @@ mod tests {
         assert!(outcome.is_invalid()); // Still invalid because sender not in whitelist
     }
 
+    #[tokio::test]
+    async fn invalid_on_inclusion_policy_violation() {
+        let (transaction, provider) = setup_priority_fee_test();
+
+        let inclusion_policy = InclusionPolicyHandle::default();
+        let validator = EthTransactionValidatorBuilder::new(provider, test_evm_config())
+            .with_inclusion_policy(inclusion_policy.clone())
+            .build(InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        inclusion_policy.set(DenylistPolicy {
+            senders: std::iter::once(transaction.sender()).collect(),
+            ..Default::default()
+        });
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        let err = outcome.as_invalid().and_then(|err| err.as_other()).unwrap();
+        assert_eq!(
+            err.as_any().downcast_ref::<PolicyViolation>(),
+            Some(&PolicyViolation::Sender(transaction.sender()))
+        );
+        assert!(!err.is_bad_transaction());
+    }
+
     #[test]
     fn reject_oversized_tx() {
         let mut transaction = get_transaction();
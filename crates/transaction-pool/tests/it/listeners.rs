@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    DiscardReason, FullTransactionEvent, PoolConfig, PoolTransaction, SubPool, SubPoolLimit,
+    TransactionEvent, TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -131,6 +131,33 @@ async fn txpool_listener_queued_event() {
     assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Queued(hash,_ )) if hash == *transaction.get_hash());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_nonce_gap() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let tx0 = mock_tx_factory.create_eip1559().transaction;
+    let tx2 = tx0.skip(1);
+    let tx3 = tx2.next();
+
+    let mut nonce_gaps = txpool.nonce_gap_listener();
+
+    txpool.add_transaction(TransactionOrigin::External, tx2.clone()).await.unwrap();
+    txpool.add_transaction(TransactionOrigin::External, tx3.clone()).await.unwrap();
+
+    let gap = nonce_gaps.recv().await.unwrap();
+    assert_eq!(gap.sender, *tx0.get_sender());
+    assert_eq!(gap.missing_nonce, 0);
+    assert_eq!(gap.queued, vec![*tx2.get_hash()]);
+
+    let gap = nonce_gaps.recv().await.unwrap();
+    assert_eq!(gap.missing_nonce, 0);
+    assert_eq!(gap.queued, vec![*tx2.get_hash(), *tx3.get_hash()]);
+
+    // filling the first nonce leaves a gap at nonce 1, but no transaction is queued because of it
+    txpool.add_transaction(TransactionOrigin::External, tx0).await.unwrap();
+    assert!(nonce_gaps.try_recv().is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_invalid_event() {
     let txpool =
@@ -146,7 +173,7 @@ async fn txpool_listener_invalid_event() {
     assert_matches!(result, Err(_));
 
     // The listener of all should receive invalid event.
-    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Invalid(hash)) if hash == *transaction.get_hash());
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Invalid { tx_hash, sender, .. }) if tx_hash == *transaction.get_hash() && sender == transaction.sender());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -170,7 +197,35 @@ async fn txpool_listener_all() {
 
     assert_eq!(transaction.transaction.hash(), removed_txs[0].transaction.hash());
 
-    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded(hash)) if hash == *transaction.transaction.get_hash());
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded { tx_hash, reason: DiscardReason::Removed }) if tx_hash == *transaction.transaction.get_hash());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_evicted() {
+    let config = PoolConfig {
+        pending_limit: SubPoolLimit { max_txs: 1, max_size: usize::MAX },
+        ..Default::default()
+    };
+    let txpool = TestPoolBuilder::default().with_config(config);
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let first = mock_tx_factory.create_eip1559().transaction;
+    let second = mock_tx_factory.create_eip1559().transaction;
+
+    let mut all_tx_events = txpool.all_transactions_event_listener();
+
+    txpool.add_transaction(TransactionOrigin::External, first.clone()).await.unwrap();
+    let _ = txpool.add_transaction(TransactionOrigin::External, second.clone()).await;
+
+    // one of the transactions is evicted to enforce the pending limit
+    let (tx_hash, reason) = loop {
+        match all_tx_events.next().await {
+            Some(FullTransactionEvent::Discarded { tx_hash, reason }) => break (tx_hash, reason),
+            Some(_) => {}
+            None => panic!("event stream ended"),
+        }
+    };
+    assert!(tx_hash == *first.get_hash() || tx_hash == *second.get_hash());
+    assert_eq!(reason, DiscardReason::PoolLimit { subpool: SubPool::Pending });
 }
 
 #[tokio::test(flavor = "multi_thread")]
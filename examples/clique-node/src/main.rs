@@ -0,0 +1,240 @@
+//! Example of running reth on a Clique proof-of-authority network.
+//!
+//! The chain is configured from the `clique` section of the genesis JSON. If a signer key is
+//! configured, the node seals blocks whenever it is allowed to, otherwise it only follows the
+//! chain, which is propagated over devp2p as there is no consensus layer.
+//!
+//! Run with
+//!
+//! ```sh
+//! cargo run -p example-clique-node -- node --chain genesis.json --http --clique.signer <KEY>
+//! ```
+//!
+//! Signers can vote on authorizing or dropping other signers via the `clique` RPC namespace:
+//!
+//! ```sh
+//! cast rpc clique_propose 0x... true
+//! cast rpc clique_getSigners
+//! ```
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_consensus::Header;
+use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
+use clap::Parser;
+use eyre::OptionExt;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use reth_clique_consensus::{
+    CliqueBlockImport, CliqueConsensus, CliqueEvmConfig, CliqueSealer, CliqueSealerHandle,
+};
+use reth_ethereum::{
+    chainspec::ChainSpec,
+    cli::{chainspec::EthereumChainSpecParser, interface::Cli},
+    network::{primitives::BasicNetworkPrimitives, NetworkHandle, NetworkManager, PeersInfo},
+    node::{
+        api::{FullNodeComponents, FullNodeTypes, NodeTypes, PrimitivesTy, TxTy},
+        builder::{
+            components::{ConsensusBuilder, ExecutorBuilder, NetworkBuilder},
+            BuilderContext,
+        },
+        node::EthereumAddOns,
+        EthereumNode,
+    },
+    pool::{PoolPooledTx, PoolTransaction, TransactionPool},
+    storage::{BlockNumReader, HeaderProvider},
+    Block, EthPrimitives,
+};
+use reth_tracing::tracing::info;
+use std::{collections::BTreeMap, fmt::Debug};
+
+fn main() {
+    Cli::<EthereumChainSpecParser, CliqueArgs>::parse()
+        .run(|builder, args| async move {
+            let (import, import_service) = CliqueBlockImport::<Block>::new();
+            let proposals = CliqueSealerHandle::default();
+            let rpc_proposals = proposals.clone();
+
+            let handle = builder
+                .with_types::<EthereumNode>()
+                .with_components(
+                    EthereumNode::components()
+                        .executor(CliqueExecutorBuilder)
+                        .network(CliqueNetworkBuilder { import })
+                        .consensus(CliqueConsensusBuilder),
+                )
+                .with_add_ons(EthereumAddOns::default())
+                .extend_rpc_modules(move |ctx| {
+                    let clique = CliqueRpc {
+                        consensus: ctx.node().consensus().clone(),
+                        proposals: rpc_proposals,
+                    };
+                    ctx.modules.merge_configured(clique.into_rpc())?;
+                    Ok(())
+                })
+                .launch()
+                .await?;
+
+            let node = &handle.node;
+            let engine = node.add_ons_handle.beacon_engine_handle.clone();
+
+            let consensus = CliqueConsensus::new(node.config.chain.clone(), node.provider.clone())
+                .ok_or_eyre("genesis has no clique config")?;
+            node.task_executor.spawn_critical_task(
+                "clique block import",
+                import_service.run(engine.clone(), consensus.clone()),
+            );
+
+            if let Some(signer) = args.signer {
+                let sealer = CliqueSealer::new(
+                    consensus,
+                    signer,
+                    engine,
+                    node.payload_builder_handle.clone(),
+                    node.pool.clone(),
+                    node.network.clone(),
+                )
+                .with_handle(proposals);
+                node.task_executor.spawn_critical_task("clique sealer", sealer.run());
+            }
+
+            handle.wait_for_node_exit().await
+        })
+        .unwrap();
+}
+
+/// Clique specific CLI arguments.
+#[derive(Debug, Clone, Default, clap::Args)]
+struct CliqueArgs {
+    /// Private key of the signer to seal blocks with.
+    ///
+    /// If not set, the node only follows the chain.
+    #[arg(long = "clique.signer", value_name = "KEY")]
+    signer: Option<PrivateKeySigner>,
+}
+
+/// Builds [`CliqueConsensus`] from the `clique` section of the genesis.
+#[derive(Debug, Clone, Copy, Default)]
+struct CliqueConsensusBuilder;
+
+impl<Node> ConsensusBuilder<Node> for CliqueConsensusBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+{
+    type Consensus = CliqueConsensus<ChainSpec, Node::Provider>;
+
+    async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
+        CliqueConsensus::new(ctx.chain_spec(), ctx.provider().clone())
+            .ok_or_eyre("genesis has no clique config")
+    }
+}
+
+/// Builds the [`CliqueEvmConfig`], which executes blocks with the pre-merge rules of the
+/// configured forks.
+#[derive(Debug, Clone, Copy, Default)]
+struct CliqueExecutorBuilder;
+
+impl<Node> ExecutorBuilder<Node> for CliqueExecutorBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+{
+    type EVM = CliqueEvmConfig<ChainSpec>;
+
+    async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
+        Ok(CliqueEvmConfig::new(ctx.chain_spec()))
+    }
+}
+
+/// Builds a proof-of-work style network that propagates and imports sealed blocks.
+#[derive(Debug)]
+struct CliqueNetworkBuilder {
+    import: CliqueBlockImport<Block>,
+}
+
+impl<Node, Pool> NetworkBuilder<Node, Pool> for CliqueNetworkBuilder
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+{
+    type Network =
+        NetworkHandle<BasicNetworkPrimitives<PrimitivesTy<Node::Types>, PoolPooledTx<Pool>>>;
+
+    async fn build_network(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<Self::Network> {
+        let config = ctx.network_config_builder()?.with_pow().block_import(Box::new(self.import));
+        let network = NetworkManager::builder(ctx.build_network_config(config)).await?;
+        let handle = ctx.start_network(network, pool);
+        info!(target: "reth::cli", enode=%handle.local_node_record(), "P2P networking initialized");
+        Ok(handle)
+    }
+}
+
+/// The `clique` RPC namespace.
+#[rpc(server, namespace = "clique")]
+trait CliqueApi {
+    /// Returns the authorized signers at the canonical head.
+    #[method(name = "getSigners")]
+    fn get_signers(&self) -> RpcResult<Vec<Address>>;
+
+    /// Returns the pending proposals of the local signer.
+    #[method(name = "proposals")]
+    fn proposals(&self) -> RpcResult<BTreeMap<Address, bool>>;
+
+    /// Proposes to authorize or drop the given signer.
+    #[method(name = "propose")]
+    fn propose(&self, address: Address, authorize: bool) -> RpcResult<()>;
+
+    /// Drops a pending proposal.
+    #[method(name = "discard")]
+    fn discard(&self, address: Address) -> RpcResult<()>;
+}
+
+/// Implements the `clique` RPC namespace.
+struct CliqueRpc<Provider> {
+    consensus: CliqueConsensus<ChainSpec, Provider>,
+    proposals: CliqueSealerHandle,
+}
+
+impl<Provider> CliqueApiServer for CliqueRpc<Provider>
+where
+    Provider:
+        HeaderProvider<Header = Header> + BlockNumReader + Debug + Clone + Send + Sync + 'static,
+{
+    fn get_signers(&self) -> RpcResult<Vec<Address>> {
+        let provider = self.consensus.provider();
+        let head = provider
+            .best_block_number()
+            .and_then(|number| provider.sealed_header(number))
+            .map_err(internal_error)?
+            .ok_or_else(|| internal_error("canonical head not found"))?;
+        let snapshot = self.consensus.snapshot(head.number, head.hash()).map_err(internal_error)?;
+        Ok(snapshot.signers.into_iter().collect())
+    }
+
+    fn proposals(&self) -> RpcResult<BTreeMap<Address, bool>> {
+        Ok(self.proposals.proposals().into_iter().collect())
+    }
+
+    fn propose(&self, address: Address, authorize: bool) -> RpcResult<()> {
+        self.proposals.propose(address, authorize);
+        Ok(())
+    }
+
+    fn discard(&self, address: Address) -> RpcResult<()> {
+        self.proposals.discard(&address);
+        Ok(())
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+        err.to_string(),
+        None::<()>,
+    )
+}